@@ -14,4 +14,9 @@ pub enum Error {
     /// stored wasm hash so the contract decides to panic rather than silently
     /// skip the batch.
     NotInitialized = 2,
+    /// `deploy_pool` was asked for zero accounts or more than
+    /// `MAX_POOL_DEPLOY` in one call.
+    InvalidPoolSize = 3,
+    /// `assign_from_pool` found no unexpired account in the creator's pool.
+    PoolEmpty = 4,
 }
@@ -1,19 +1,18 @@
 #![no_std]
 
-mod ephemeral_account_contract {
-    soroban_sdk::contractimport!(
-        file = "../../target/wasm32v1-none/release/ephemeral_account.wasm"
-    );
-}
-use ephemeral_account_contract::Client as EphemeralAccountClient;
-
 mod errors;
+mod pool;
+mod registry;
+mod types;
 pub use errors::Error;
+pub use pool::MAX_POOL_DEPLOY;
+pub use registry::{AccountRecord, CreatorStats};
+pub use types::{AccountInitRequest, AccountInitResult, InitConfig};
 
 #[cfg(test)]
 mod test;
 
-use bridgelet_shared::{AccountInitRequest, AccountInitResult};
+use bridgelet_shared::EphemeralAccountClient;
 use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
 
 #[contract]
@@ -91,45 +90,14 @@ impl AccountFactory {
     ) -> Vec<AccountInitResult> {
         creator.require_auth();
 
-        let wasm_hash = env
-            .storage()
-            .instance()
-            .get::<_, BytesN<32>>(&DataKey::EphemeralAccountWasmHash)
-            .expect("factory not initialized; call initialize() first");
-
-        // Bump the per-factory-call nonce exactly once per invocation.
-        // The combined `nonce || index` salt ensures no two deployments from
-        // separate calls ever produce the same address, while still being
-        // deterministic within a single call.
-        let prev_nonce: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::BatchNonce)
-            .unwrap_or(0);
-        // u64 + 1 cannot overflow for any realistic call count. The workspace
-        // enables `overflow-checks = true` in release, so any overflow would
-        // surface as a panic rather than a silent wraparound to a colliding
-        // salt.
-        let nonce = prev_nonce + 1u64;
-        env.storage().instance().set(&DataKey::BatchNonce, &nonce);
+        let wasm_hash =
+            Self::wasm_hash(&env).expect("factory not initialized; call initialize() first");
+        let nonce = Self::next_batch_nonce(&env);
 
         let mut results = Vec::new(&env);
 
         for (index, request) in requests.iter().enumerate() {
-            // Salt layout (32 bytes, big-endian):
-            //   [0..8]  nonce   — monotonically increases each call to
-            //                     batch_initialize
-            //   [8..28] zeros  — reserved (kept zero to leave room for future
-            //                    fields such as a creator-tag)
-            //   [28..32] index — per-request position inside the call
-            let mut salt_bytes = [0u8; 32];
-            salt_bytes[0..8].copy_from_slice(&nonce.to_be_bytes());
-            salt_bytes[28..32].copy_from_slice(&(index as u32).to_be_bytes());
-            let salt = BytesN::from_array(&env, &salt_bytes);
-            let account_address = env
-                .deployer()
-                .with_current_contract(salt)
-                .deploy_v2(wasm_hash.clone(), ());
+            let account_address = Self::deploy_account(&env, &wasm_hash, nonce, index as u32);
 
             // Initialize it
             let client = EphemeralAccountClient::new(&env, &account_address);
@@ -141,11 +109,14 @@ impl AccountFactory {
                 &creator,
                 &creator,
             ) {
-                Ok(_) => AccountInitResult {
-                    account_address: account_address.clone(),
-                    success: true,
-                    error: None,
-                },
+                Ok(_) => {
+                    registry::record_created(&env, &creator, &account_address);
+                    AccountInitResult {
+                        account_address: account_address.clone(),
+                        success: true,
+                        error: None,
+                    }
+                }
                 Err(_) => AccountInitResult {
                     account_address: account_address.clone(),
                     success: false,
@@ -158,13 +129,156 @@ impl AccountFactory {
 
         results
     }
+
+    /// Deploy `count` ready-to-use accounts for `creator` into its pool,
+    /// all initialized from `template`, so checkouts can take one with
+    /// `assign_from_pool` instead of deploying on demand.
+    ///
+    /// Unlike `batch_initialize`, an account that fails to initialize fails
+    /// the whole call: the pool only ever holds ready accounts.
+    ///
+    /// # Returns
+    /// The deployed accounts, in the order they join the pool.
+    ///
+    /// # Errors
+    /// * `Error::NotInitialized` - the factory has not been initialized.
+    /// * `Error::InvalidPoolSize` - `count` is zero or above [`MAX_POOL_DEPLOY`].
+    pub fn deploy_pool(
+        env: Env,
+        creator: Address,
+        count: u32,
+        template: InitConfig,
+    ) -> Result<Vec<Address>, Error> {
+        creator.require_auth();
+
+        if count == 0 || count > MAX_POOL_DEPLOY {
+            return Err(Error::InvalidPoolSize);
+        }
+        let wasm_hash = Self::wasm_hash(&env).ok_or(Error::NotInitialized)?;
+        let nonce = Self::next_batch_nonce(&env);
+
+        let mut accounts = Vec::new(&env);
+        for index in 0..count {
+            let account = Self::deploy_account(&env, &wasm_hash, nonce, index);
+            EphemeralAccountClient::new(&env, &account).initialize_with_options(
+                &creator,
+                &template.expiry_ledger,
+                &template.recovery_address,
+                &template.authorized_controller,
+                &creator,
+                &template.options,
+            );
+            registry::record_created(&env, &creator, &account);
+            accounts.push_back(account);
+        }
+
+        pool::add_to_pool(&env, &creator, &accounts);
+        Ok(accounts)
+    }
+
+    /// Bind the next free account of `creator`'s pool to `order_id`.
+    ///
+    /// Retrying with an order that is already bound returns the same
+    /// account. Accounts that expired or were cancelled while waiting in the
+    /// pool are skipped and dropped from it.
+    ///
+    /// # Errors
+    /// * `Error::PoolEmpty` - no unexpired account is left in the pool.
+    pub fn assign_from_pool(
+        env: Env,
+        creator: Address,
+        order_id: BytesN<32>,
+    ) -> Result<Address, Error> {
+        creator.require_auth();
+
+        pool::assign(&env, &creator, &order_id)
+    }
+
+    /// Number of unassigned accounts in `creator`'s pool, including any
+    /// that expired and will be skipped.
+    pub fn get_pool_size(env: Env, creator: Address) -> u32 {
+        pool::get_pool(&env, &creator).len()
+    }
+
+    /// Account bound to `order_id` by `assign_from_pool`, if any.
+    pub fn get_order_account(env: Env, creator: Address, order_id: BytesN<32>) -> Option<Address> {
+        pool::get_order_account(&env, &creator, &order_id)
+    }
+
+    /// Count a deployed account's terminal status (swept or expired) and its
+    /// swept volume towards its creator's statistics.
+    ///
+    /// Permissionless: the numbers are read from the account itself, and each
+    /// account is counted at most once. The sweep controller calls this as a
+    /// post-settlement hook when configured with this factory as its registry.
+    ///
+    /// # Returns
+    /// `true` if the account was counted by this call.
+    pub fn sync_account(env: Env, account: Address) -> bool {
+        registry::sync_account(&env, &account)
+    }
+
+    /// Aggregated statistics for every account `creator` deployed through
+    /// this factory.
+    pub fn get_creator_stats(env: Env, creator: Address) -> CreatorStats {
+        registry::get_creator_stats(&env, &creator)
+    }
+
+    /// Registry entry for `account`, or `None` if this factory did not deploy it.
+    pub fn get_account_record(env: Env, account: Address) -> Option<AccountRecord> {
+        registry::get_account_record(&env, &account)
+    }
+}
+
+impl AccountFactory {
+    fn wasm_hash(env: &Env) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::EphemeralAccountWasmHash)
+    }
+
+    /// Bump the per-factory-call nonce. Called exactly once per deploying
+    /// invocation: the combined `nonce || index` salt ensures no two
+    /// deployments from separate calls ever produce the same address, while
+    /// still being deterministic within a single call.
+    fn next_batch_nonce(env: &Env) -> u64 {
+        let prev_nonce: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::BatchNonce)
+            .unwrap_or(0);
+        // u64 + 1 cannot overflow for any realistic call count. The workspace
+        // enables `overflow-checks = true` in release, so any overflow would
+        // surface as a panic rather than a silent wraparound to a colliding
+        // salt.
+        let nonce = prev_nonce + 1u64;
+        env.storage().instance().set(&DataKey::BatchNonce, &nonce);
+        nonce
+    }
+
+    /// Deploy an uninitialized account at the address derived from
+    /// `nonce` and `index`.
+    fn deploy_account(env: &Env, wasm_hash: &BytesN<32>, nonce: u64, index: u32) -> Address {
+        // Salt layout (32 bytes, big-endian):
+        //   [0..8]  nonce   — monotonically increases each deploying call
+        //   [8..28] zeros  — reserved (kept zero to leave room for future
+        //                    fields such as a creator-tag)
+        //   [28..32] index — per-account position inside the call
+        let mut salt_bytes = [0u8; 32];
+        salt_bytes[0..8].copy_from_slice(&nonce.to_be_bytes());
+        salt_bytes[28..32].copy_from_slice(&index.to_be_bytes());
+        let salt = BytesN::from_array(env, &salt_bytes);
+        env.deployer()
+            .with_current_contract(salt)
+            .deploy_v2(wasm_hash.clone(), ())
+    }
 }
 
 #[contracttype]
 enum DataKey {
     EphemeralAccountWasmHash,
     /// Monotonically increasing counter incremented once per call to
-    /// `batch_initialize`. Mixed into the deployment salt to keep addresses
+    /// `batch_initialize` or `deploy_pool`. Mixed into the deployment salt to keep addresses
     /// disjoint across separate invocations (issue #241).
     BatchNonce,
 }
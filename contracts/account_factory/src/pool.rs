@@ -0,0 +1,87 @@
+//! Pre-provisioned account pools.
+//!
+//! `deploy_pool` deploys and initializes accounts ahead of demand, so a
+//! checkout only has to bind one of them to its order with
+//! `assign_from_pool` instead of paying for a deploy and an initialization
+//! per request. Each creator has its own pool, handed out oldest first.
+
+use crate::Error;
+use bridgelet_shared::{AccountStatus, EphemeralAccountClient};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+const PERSISTENT_TTL_THRESHOLD: u32 = 100;
+const PERSISTENT_TTL_EXTEND_TO: u32 = 518_400;
+
+/// Most accounts one `deploy_pool` call may create. Each account costs a
+/// deployment and an initialization, so larger pools are built over
+/// several transactions to stay within per-transaction resource limits.
+pub const MAX_POOL_DEPLOY: u32 = 10;
+
+#[contracttype]
+enum PoolKey {
+    /// Unassigned accounts of a creator, oldest first.
+    Pool(Address),
+    /// Account bound to a creator's order.
+    Order(Address, BytesN<32>),
+}
+
+pub fn get_pool(env: &Env, creator: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&PoolKey::Pool(creator.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn set_pool(env: &Env, creator: &Address, pool: &Vec<Address>) {
+    let key = PoolKey::Pool(creator.clone());
+    env.storage().persistent().set(&key, pool);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+/// Append freshly initialized accounts to `creator`'s pool.
+pub fn add_to_pool(env: &Env, creator: &Address, accounts: &Vec<Address>) {
+    let mut pool = get_pool(env, creator);
+    pool.append(accounts);
+    set_pool(env, creator, &pool);
+}
+
+pub fn get_order_account(env: &Env, creator: &Address, order_id: &BytesN<32>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&PoolKey::Order(creator.clone(), order_id.clone()))
+}
+
+/// Bind the oldest unexpired account of `creator`'s pool to `order_id`.
+///
+/// Retrying an order that is already bound returns the same account.
+/// Accounts that expired, or that the creator cancelled, while waiting in
+/// the pool are dropped.
+///
+/// # Errors
+/// Returns Error::PoolEmpty if no unexpired account is left
+pub fn assign(env: &Env, creator: &Address, order_id: &BytesN<32>) -> Result<Address, Error> {
+    if let Some(account) = get_order_account(env, creator, order_id) {
+        return Ok(account);
+    }
+
+    let mut pool = get_pool(env, creator);
+    while let Some(account) = pool.pop_front() {
+        let client = EphemeralAccountClient::new(env, &account);
+        if client.is_expired() || client.get_status() == AccountStatus::Cancelled {
+            continue;
+        }
+
+        set_pool(env, creator, &pool);
+        let key = PoolKey::Order(creator.clone(), order_id.clone());
+        env.storage().persistent().set(&key, &account);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_TTL_THRESHOLD,
+            PERSISTENT_TTL_EXTEND_TO,
+        );
+        return Ok(account);
+    }
+    Err(Error::PoolEmpty)
+}
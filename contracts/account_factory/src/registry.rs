@@ -0,0 +1,142 @@
+//! Per-creator statistics for accounts deployed by this factory.
+//!
+//! Counters are derived from the accounts' own on-chain state, so
+//! `sync_account` is permissionless: anyone (typically the sweep controller,
+//! as a post-settlement hook) can ask the registry to record an account's
+//! terminal status, and each account is counted at most once.
+
+use bridgelet_shared::{AccountStatus, EphemeralAccountClient};
+use soroban_sdk::{contracttype, Address, Env, Map};
+
+const PERSISTENT_TTL_THRESHOLD: u32 = 100;
+const PERSISTENT_TTL_EXTEND_TO: u32 = 518_400;
+
+/// Aggregates for all accounts a creator deployed through this factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreatorStats {
+    pub accounts_created: u32,
+    pub accounts_swept: u32,
+    pub accounts_expired: u32,
+    /// Swept volume per asset.
+    pub volume: Map<Address, i128>,
+}
+
+/// Registry entry for a deployed account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountRecord {
+    pub creator: Address,
+    /// Whether the account's terminal status has been counted.
+    pub settled: bool,
+}
+
+#[contracttype]
+enum RegistryKey {
+    Stats(Address),
+    Account(Address),
+}
+
+fn empty_stats(env: &Env) -> CreatorStats {
+    CreatorStats {
+        accounts_created: 0,
+        accounts_swept: 0,
+        accounts_expired: 0,
+        volume: Map::new(env),
+    }
+}
+
+pub fn get_creator_stats(env: &Env, creator: &Address) -> CreatorStats {
+    env.storage()
+        .persistent()
+        .get(&RegistryKey::Stats(creator.clone()))
+        .unwrap_or_else(|| empty_stats(env))
+}
+
+fn set_creator_stats(env: &Env, creator: &Address, stats: &CreatorStats) {
+    let key = RegistryKey::Stats(creator.clone());
+    env.storage().persistent().set(&key, stats);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+pub fn get_account_record(env: &Env, account: &Address) -> Option<AccountRecord> {
+    env.storage()
+        .persistent()
+        .get(&RegistryKey::Account(account.clone()))
+}
+
+fn set_account_record(env: &Env, account: &Address, record: &AccountRecord) {
+    let key = RegistryKey::Account(account.clone());
+    env.storage().persistent().set(&key, record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_TTL_THRESHOLD, PERSISTENT_TTL_EXTEND_TO);
+}
+
+/// Register a freshly deployed and initialized account under `creator`.
+pub fn record_created(env: &Env, creator: &Address, account: &Address) {
+    set_account_record(
+        env,
+        account,
+        &AccountRecord {
+            creator: creator.clone(),
+            settled: false,
+        },
+    );
+
+    let mut stats = get_creator_stats(env, creator);
+    stats.accounts_created += 1;
+    set_creator_stats(env, creator, &stats);
+}
+
+/// Count `account`'s terminal status towards its creator's stats.
+///
+/// A swept account adds the amounts it settled, not the amounts it
+/// recorded, to the creator's volume. A `PartiallySwept` account still owes
+/// part of its payments, so it is only counted once it reaches `Swept`.
+///
+/// Returns `false` if the account is unknown to this factory, already
+/// counted, or not yet settled.
+pub fn sync_account(env: &Env, account: &Address) -> bool {
+    let Some(mut record) = get_account_record(env, account) else {
+        return false;
+    };
+    if record.settled {
+        return false;
+    }
+
+    let client = EphemeralAccountClient::new(env, account);
+    let status = client.get_status();
+    let mut stats = get_creator_stats(env, &record.creator);
+
+    if status == AccountStatus::Swept {
+        stats.accounts_swept += 1;
+        let mut offset = 0;
+        loop {
+            let page = client.get_payments_page(&offset, &u32::MAX);
+            for settled in page.settled.iter() {
+                let volume = stats.volume.get(settled.asset.clone()).unwrap_or(0);
+                stats
+                    .volume
+                    .set(settled.asset, volume.saturating_add(settled.amount));
+            }
+            offset += page.payments.len();
+            if page.payments.is_empty() || offset >= page.total {
+                break;
+            }
+        }
+    } else if status == AccountStatus::Expired {
+        stats.accounts_expired += 1;
+    } else if status == AccountStatus::Cancelled {
+        // Settled without ever being used: nothing to count.
+    } else {
+        return false;
+    }
+
+    record.settled = true;
+    set_account_record(env, account, &record);
+    set_creator_stats(env, &record.creator, &stats);
+    true
+}
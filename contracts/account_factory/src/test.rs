@@ -1,9 +1,11 @@
 extern crate std;
 
 use super::*;
-use bridgelet_shared::AccountInitRequest;
 use ephemeral_account::EphemeralAccountContract;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, InvokeError};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, InvokeError,
+};
 
 // Include the compiled ephemeral account WASM so the factory can deploy it
 // during tests without depending on `stellar contract build` having run.
@@ -14,7 +16,12 @@ const EPHEMERAL_ACCOUNT_WASM: &[u8] =
 /// Upload the ephemeral account WASM into the test env, returning both the
 /// WASM hash (which the factory will forward to `deploy_v2`) and the
 /// template contract id (used for direct SDK calls when convenient).
+///
+/// Lifts the test budget: it is sized for one transaction, and parsing the
+/// account WASM once per deployed account exhausts it on multi-account
+/// deploys. These tests cover deployment logic, not transaction cost.
 fn register_template(env: &Env) -> (BytesN<32>, Address) {
+    env.cost_estimate().budget().reset_unlimited();
     let wasm_hash = env.deployer().upload_contract_wasm(EPHEMERAL_ACCOUNT_WASM);
     let template_id = env.register(EphemeralAccountContract, ());
     (wasm_hash, template_id)
@@ -225,3 +232,249 @@ fn test_batch_initialize_keeps_nonce_monotonic_across_more_invocations() {
     }
     assert_unique_addresses(&addresses);
 }
+
+// ── Creator statistics ───────────────────────────────────────────────────────
+
+fn setup_factory_with_accounts(
+    env: &Env,
+    count: u32,
+) -> (AccountFactoryClient<'_>, Address, std::vec::Vec<Address>) {
+    let (wasm_hash, _template) = register_template(env);
+    let factory_id = env.register(AccountFactory, ());
+    let client = AccountFactoryClient::new(env, &factory_id);
+
+    let creator = Address::generate(env);
+    client.initialize(&creator, &wasm_hash);
+
+    let (_expiry, requests) = build_requests(env, count);
+    let accounts = client
+        .batch_initialize(&creator, &requests)
+        .iter()
+        .map(|r| r.account_address)
+        .collect();
+    (client, creator, accounts)
+}
+
+#[test]
+fn test_batch_initialize_counts_created_accounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, accounts) = setup_factory_with_accounts(&env, 3);
+
+    let stats = client.get_creator_stats(&creator);
+    assert_eq!(stats.accounts_created, 3);
+    assert_eq!(stats.accounts_swept, 0);
+    assert_eq!(stats.accounts_expired, 0);
+    assert!(stats.volume.is_empty());
+
+    let record = client.get_account_record(&accounts[0]).unwrap();
+    assert_eq!(record.creator, creator);
+    assert!(!record.settled);
+}
+
+#[test]
+fn test_sync_account_ignores_unknown_and_unsettled_accounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, accounts) = setup_factory_with_accounts(&env, 1);
+
+    assert!(!client.sync_account(&Address::generate(&env)));
+    assert!(!client.sync_account(&accounts[0]));
+    assert_eq!(client.get_creator_stats(&creator).accounts_swept, 0);
+}
+
+#[test]
+fn test_sync_account_counts_swept_account_and_volume_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, accounts) = setup_factory_with_accounts(&env, 1);
+    let account = ephemeral_account::EphemeralAccountContractClient::new(&env, &accounts[0]);
+    // The sweep transfers the payment, so the account must hold it.
+    let asset = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &asset).mint(&accounts[0], &250);
+    account.record_payment(&250, &asset);
+    account.sweep_claim(&Address::generate(&env));
+
+    assert!(client.sync_account(&accounts[0]));
+    assert!(!client.sync_account(&accounts[0]));
+
+    let stats = client.get_creator_stats(&creator);
+    assert_eq!(stats.accounts_swept, 1);
+    assert_eq!(stats.accounts_expired, 0);
+    assert_eq!(stats.volume.get(asset), Some(250));
+    assert!(client.get_account_record(&accounts[0]).unwrap().settled);
+}
+
+#[test]
+fn test_sync_account_counts_expired_account() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator, accounts) = setup_factory_with_accounts(&env, 1);
+    let account = ephemeral_account::EphemeralAccountContractClient::new(&env, &accounts[0]);
+    env.ledger().with_mut(|l| l.sequence_number += 2000);
+    account.expire();
+
+    assert!(client.sync_account(&accounts[0]));
+
+    let stats = client.get_creator_stats(&creator);
+    assert_eq!(stats.accounts_swept, 0);
+    assert_eq!(stats.accounts_expired, 1);
+    assert!(stats.volume.is_empty());
+}
+
+// ── Account pools ────────────────────────────────────────────────────────────
+
+fn setup_factory(env: &Env) -> (AccountFactoryClient<'_>, Address) {
+    let (wasm_hash, _template) = register_template(env);
+    let factory_id = env.register(AccountFactory, ());
+    let client = AccountFactoryClient::new(env, &factory_id);
+
+    let creator = Address::generate(env);
+    client.initialize(&creator, &wasm_hash);
+    (client, creator)
+}
+
+fn pool_template(env: &Env, expiry_ledger: u32) -> InitConfig {
+    InitConfig {
+        expiry_ledger,
+        recovery_address: Address::generate(env),
+        authorized_controller: Address::generate(env),
+        options: bridgelet_shared::InitOptions::default(),
+    }
+}
+
+#[test]
+fn test_deploy_pool_creates_ready_accounts_assigned_in_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator) = setup_factory(&env);
+    let template = pool_template(&env, env.ledger().sequence() + 1000);
+    let accounts = client.deploy_pool(&creator, &3, &template);
+
+    assert_eq!(accounts.len(), 3);
+    assert_eq!(client.get_pool_size(&creator), 3);
+    assert_eq!(client.get_creator_stats(&creator).accounts_created, 3);
+    let account =
+        ephemeral_account::EphemeralAccountContractClient::new(&env, &accounts.get_unchecked(0));
+    assert_eq!(
+        account.get_init_state(),
+        ephemeral_account::InitState::Ready
+    );
+    assert_eq!(
+        account.get_info().recovery_address,
+        template.recovery_address
+    );
+
+    let order = BytesN::from_array(&env, &[1u8; 32]);
+    let assigned = client.assign_from_pool(&creator, &order);
+    assert_eq!(assigned, accounts.get_unchecked(0));
+    assert_eq!(
+        client.get_order_account(&creator, &order),
+        Some(assigned.clone())
+    );
+
+    // Retrying the same order does not take another account.
+    assert_eq!(client.assign_from_pool(&creator, &order), assigned);
+    assert_eq!(client.get_pool_size(&creator), 2);
+
+    let next = client.assign_from_pool(&creator, &BytesN::from_array(&env, &[2u8; 32]));
+    assert_eq!(next, accounts.get_unchecked(1));
+}
+
+#[test]
+fn test_deploy_pool_applies_the_template_options() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator) = setup_factory(&env);
+    let attestation = BytesN::from_array(&env, &[7u8; 32]);
+    let settlement = Address::generate(&env);
+    let template = InitConfig {
+        options: bridgelet_shared::InitOptions {
+            attestation: Some(attestation.clone()),
+            preferred_settlement_asset: Some(settlement.clone()),
+            max_assets: Some(3),
+            ..Default::default()
+        },
+        ..pool_template(&env, env.ledger().sequence() + 1000)
+    };
+    let accounts = client.deploy_pool(&creator, &1, &template);
+
+    let info =
+        ephemeral_account::EphemeralAccountContractClient::new(&env, &accounts.get_unchecked(0))
+            .get_info();
+    assert_eq!(info.attestation, Some(attestation));
+    assert_eq!(info.preferred_settlement_asset, Some(settlement));
+    assert_eq!(info.max_assets, 3);
+}
+
+#[test]
+fn test_assign_from_pool_skips_expired_accounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator) = setup_factory(&env);
+    let now = env.ledger().sequence();
+    client.deploy_pool(&creator, &1, &pool_template(&env, now + 10));
+    let fresh = client.deploy_pool(&creator, &1, &pool_template(&env, now + 1000));
+
+    env.ledger().with_mut(|l| l.sequence_number += 100);
+    let order = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.assign_from_pool(&creator, &order),
+        fresh.get_unchecked(0)
+    );
+    assert_eq!(client.get_pool_size(&creator), 0);
+
+    assert!(matches!(
+        client.try_assign_from_pool(&creator, &BytesN::from_array(&env, &[2u8; 32])),
+        Err(Ok(Error::PoolEmpty))
+    ));
+}
+
+#[test]
+fn test_assign_from_pool_skips_cancelled_accounts() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator) = setup_factory(&env);
+    let template = pool_template(&env, env.ledger().sequence() + 1000);
+    let accounts = client.deploy_pool(&creator, &2, &template);
+    ephemeral_account::EphemeralAccountContractClient::new(&env, &accounts.get_unchecked(0))
+        .cancel();
+
+    let order = BytesN::from_array(&env, &[1u8; 32]);
+    assert_eq!(
+        client.assign_from_pool(&creator, &order),
+        accounts.get_unchecked(1)
+    );
+    assert_eq!(client.get_pool_size(&creator), 0);
+}
+
+#[test]
+fn test_deploy_pool_rejects_invalid_sizes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (client, creator) = setup_factory(&env);
+    let template = pool_template(&env, env.ledger().sequence() + 1000);
+    for count in [0, MAX_POOL_DEPLOY + 1] {
+        assert!(matches!(
+            client.try_deploy_pool(&creator, &count, &template),
+            Err(Ok(Error::InvalidPoolSize))
+        ));
+    }
+
+    let uninitialized = AccountFactoryClient::new(&env, &env.register(AccountFactory, ()));
+    assert!(matches!(
+        uninitialized.try_deploy_pool(&creator, &1, &template),
+        Err(Ok(Error::NotInitialized))
+    ));
+}
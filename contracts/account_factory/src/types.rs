@@ -0,0 +1,31 @@
+//! Argument and result types of the factory's entrypoints.
+
+use bridgelet_shared::InitOptions;
+use soroban_sdk::{contracttype, Address, Bytes};
+
+/// Request to initialize a single ephemeral account
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AccountInitRequest {
+    pub expiry_ledger: u32,
+    pub recovery_address: Address,
+}
+
+/// Settings shared by every account of a pre-provisioned pool
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InitConfig {
+    pub expiry_ledger: u32,
+    pub recovery_address: Address,
+    pub authorized_controller: Address,
+    pub options: InitOptions,
+}
+
+/// Result of initializing an ephemeral account
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AccountInitResult {
+    pub account_address: Address,
+    pub success: bool,
+    pub error: Option<Bytes>,
+}
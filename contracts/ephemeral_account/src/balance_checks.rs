@@ -0,0 +1,66 @@
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, contracttype, token::TokenClient, Address, Env, Vec};
+
+/// A recorded payment the account's token balance does not match, as
+/// reported by `verify_balances`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceDiscrepancy {
+    pub asset: Address,
+    /// Amount recorded for `asset` and not settled yet
+    pub recorded: i128,
+    /// The account's balance of `asset`; `0` if the token could not be read
+    pub balance: i128,
+}
+
+/// Checks of the recorded payments against the account's token balances.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Compare every recorded payment with the account's actual balance.
+    pub fn verify_balances(env: Env) -> Vec<BalanceDiscrepancy> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Vec::new(&env);
+        }
+        Self::balance_discrepancies(&env)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Recorded payments whose asset balance differs from the recorded
+    /// amount, while the account still holds its payments.
+    pub(crate) fn balance_discrepancies(env: &Env) -> Vec<BalanceDiscrepancy> {
+        let mut discrepancies = Vec::new(env);
+        match storage::get_status(env) {
+            AccountStatus::Active
+            | AccountStatus::PaymentReceived
+            | AccountStatus::PendingSweep => {}
+            _ => return discrepancies,
+        }
+
+        for payment in storage::get_payments_ordered(env).iter() {
+            let balance = Self::balance_of(env, &payment.asset);
+            if balance != payment.amount {
+                discrepancies.push_back(BalanceDiscrepancy {
+                    asset: payment.asset,
+                    recorded: payment.amount,
+                    balance,
+                });
+            }
+        }
+        discrepancies
+    }
+
+    /// The account's balance of `asset`; `0` if the token cannot be read.
+    pub(crate) fn balance_of(env: &Env, asset: &Address) -> i128 {
+        match TokenClient::new(env, asset).try_balance(&env.current_contract_address()) {
+            Ok(Ok(balance)) => balance,
+            _ => 0,
+        }
+    }
+}
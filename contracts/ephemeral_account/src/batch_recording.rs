@@ -0,0 +1,52 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+    MAX_PAYMENT_ASSETS,
+};
+use bridgelet_shared::AssetAmount;
+use soroban_sdk::{contractimpl, Env, Vec};
+
+/// Several payments recorded in one call.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Record several payments at once, all or none, with one `BatchPaymentReceived`.
+    pub fn record_payments(env: Env, payments: Vec<AssetAmount>) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+        Self::require_recorder(&env, None);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        if payments.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        if payments.len() > MAX_PAYMENT_ASSETS {
+            return Err(Error::TooManyPayments);
+        }
+
+        // Enforce the asset cap once for the whole batch.
+        let new_assets = payments
+            .iter()
+            .enumerate()
+            .filter(|(i, payment)| {
+                storage::get_payment(&env, &payment.asset).is_none()
+                    && payments
+                        .iter()
+                        .take(*i)
+                        .all(|earlier| earlier.asset != payment.asset)
+            })
+            .count() as u32;
+        if storage::get_total_payments(&env) + new_assets > Self::max_assets(&env) {
+            return Err(Error::TooManyPayments);
+        }
+
+        for payment in payments.iter() {
+            Self::store_payment(&env, &None, payment.amount, &payment.asset, &None)?;
+        }
+
+        events::emit_batch_payment_received(&env, payments);
+        Ok(())
+    }
+}
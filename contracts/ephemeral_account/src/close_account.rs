@@ -0,0 +1,56 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage::{self, InitState};
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, token::TokenClient, Env};
+
+/// Deletion of a completed account's storage.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Delete a completed account's storage. Creator only.
+    pub fn close_account(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+
+        let status = storage::get_status(&env);
+        if !matches!(
+            status,
+            AccountStatus::Swept | AccountStatus::Expired | AccountStatus::Cancelled
+        ) {
+            return Err(Error::InvalidStatus);
+        }
+        if storage::has_unclaimed_vesting(&env) {
+            return Err(Error::InvalidStatus);
+        }
+        if !storage::is_reserve_reclaimed(&env) {
+            return Err(Error::ReserveNotReclaimed);
+        }
+
+        // Nothing is left once the reserve is reclaimed; never strand XLM
+        Self::repay_reserve_deposit(&env, i128::MAX)?;
+        events::emit_account_archived(&env, status);
+        let recovery_address = storage::get_recovery_address(&env);
+        let assets = storage::get_known_assets(&env);
+        storage::purge(&env);
+        storage::set_init_state(&env, InitState::Closed);
+
+        // Never strand a balance in an account nobody can operate any more
+        let account = env.current_contract_address();
+        for asset in assets.iter() {
+            let token = TokenClient::new(&env, &asset);
+            let balance = token.balance(&account);
+            if balance > 0 {
+                token.transfer(&account, &recovery_address, &balance);
+            }
+        }
+        events::emit_account_closed(&env, status);
+        Ok(())
+    }
+}
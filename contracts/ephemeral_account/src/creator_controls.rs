@@ -0,0 +1,77 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, Address, Env};
+
+/// Creator controls over an account's settings and lifecycle.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Cancel an account nobody paid. Creator only.
+    pub fn cancel(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+
+        if storage::get_status(&env) != AccountStatus::Active
+            || storage::get_total_payments(&env) > 0
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        let recovery_address = storage::get_recovery_address(&env);
+        storage::set_status(&env, AccountStatus::Cancelled);
+        storage::set_swept_to(&env, &recovery_address);
+        storage::set_settled_ledger(&env, env.ledger().sequence());
+
+        let sweep_id = env.ledger().sequence() as u64;
+        storage::set_last_sweep_id(&env, sweep_id);
+
+        events::emit_account_cancelled(&env, recovery_address.clone());
+        Self::reclaim_reserve_to(&env, &recovery_address, sweep_id)?;
+        Ok(())
+    }
+
+    /// Replace the recovery address before the first payment. Creator only.
+    pub fn update_recovery_address(env: Env, new_address: Address) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_config_unlocked(&env)?;
+        storage::get_creator(&env).require_auth();
+
+        if storage::get_status(&env) != AccountStatus::Active
+            || storage::get_total_payments(&env) > 0
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        let old_address = storage::get_recovery_address(&env);
+        storage::set_recovery_address(&env, &new_address);
+        events::emit_recovery_address_updated(&env, old_address, new_address);
+        Ok(())
+    }
+
+    /// Permanently freeze the account's settings. Creator only.
+    pub fn lock_config(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        Self::ensure_config_unlocked(&env)?;
+
+        storage::set_config_locked(&env);
+        events::emit_config_locked(&env);
+        Ok(())
+    }
+}
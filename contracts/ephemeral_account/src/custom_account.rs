@@ -0,0 +1,141 @@
+use soroban_sdk::{
+    auth::{Context, CustomAccountInterface},
+    contractimpl,
+    crypto::Hash,
+    Address, BytesN, Env, Map, Symbol, TryFromVal, Vec,
+};
+
+use crate::{
+    storage, AccountStatus, EphemeralAccountContract, EphemeralAccountContractArgs,
+    EphemeralAccountContractClient, Error,
+};
+
+/// The account as a Soroban custom account: it authorizes only the
+/// controller's `execute_sweep_as_account`, signed by its `sweep_signer`,
+/// before expiry, to its locked destination (when one is set), together
+/// with the token transfers that sweep makes out of the account. This lets
+/// the account address act directly as `from` in token transfers under the
+/// standard auth framework.
+///
+/// The policy follows the account's own sweep rules: nothing is authorized
+/// unless the account is paid and awaiting its sweep, while paused, on a
+/// vesting account, or when a signer set requires more than one signature;
+/// destinations outside `allowed_destinations` are refused, and the
+/// creator must cosign under `require_creator_cosign`.
+#[contractimpl]
+impl CustomAccountInterface for EphemeralAccountContract {
+    type Signature = BytesN<64>;
+    type Error = Error;
+
+    fn __check_auth(
+        env: Env,
+        signature_payload: Hash<32>,
+        signature: BytesN<64>,
+        auth_contexts: Vec<Context>,
+    ) -> Result<(), Error> {
+        let signer = storage::get_sweep_signer(&env).ok_or(Error::Unauthorized)?;
+        if Self::is_expired(env.clone()) {
+            return Err(Error::AccountExpired);
+        }
+        if storage::is_paused(&env) {
+            return Err(Error::Paused);
+        }
+        // As for `sweep`, one key never suffices once a signer set is registered
+        if storage::get_sweep_signers(&env).is_some() {
+            return Err(Error::InsufficientSignatures);
+        }
+        // Once swept (or being swept) nothing is left to authorize, and a
+        // vesting account releases its funds itself through `claim_vested`.
+        if storage::get_status(&env) != AccountStatus::PaymentReceived {
+            return Err(Error::InvalidStatus);
+        }
+        if storage::has_vesting_schedule(&env) {
+            return Err(Error::SweepPolicyViolation);
+        }
+
+        Self::check_sweep_policy(&env, &auth_contexts)?;
+        Self::require_creator_cosign(&env);
+
+        env.crypto()
+            .ed25519_verify(&signer, &signature_payload.into(), &signature);
+        Ok(())
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Allow only the controller's account-authorized sweep, as the root
+    /// context, and the token transfers it makes out of recorded payment
+    /// assets to its destination. Transfers of an asset together may not
+    /// exceed what is still unswept of its payment. The destination must be
+    /// the locked one when set, and an allowed one.
+    fn check_sweep_policy(env: &Env, contexts: &Vec<Context>) -> Result<(), Error> {
+        let sweep = match contexts.first() {
+            Some(Context::Contract(call))
+                if Some(&call.contract) == storage::get_authorized_controller(env).as_ref()
+                    && call.fn_name == Symbol::new(env, "execute_sweep_as_account") =>
+            {
+                call
+            }
+            _ => return Err(Error::SweepPolicyViolation),
+        };
+        // execute_sweep_as_account(destination, destination_memo)
+        let destination = sweep
+            .args
+            .get(0)
+            .and_then(|arg| Address::try_from_val(env, &arg).ok())
+            .ok_or(Error::SweepPolicyViolation)?;
+        if !storage::is_destination_allowed(env, &destination) {
+            return Err(Error::SweepPolicyViolation);
+        }
+        if let Some(locked) = storage::get_locked_destination(env) {
+            if locked != destination {
+                return Err(Error::SweepPolicyViolation);
+            }
+        }
+
+        let account = env.current_contract_address();
+        let mut transferred = Map::<Address, i128>::new(env);
+        for context in contexts.iter().skip(1) {
+            let Context::Contract(call) = context else {
+                return Err(Error::SweepPolicyViolation);
+            };
+            if call.fn_name != Symbol::new(env, "transfer") {
+                return Err(Error::SweepPolicyViolation);
+            }
+            // transfer(from, to, amount)
+            let payment =
+                storage::get_payment(env, &call.contract).ok_or(Error::SweepPolicyViolation)?;
+            let from = call
+                .args
+                .get(0)
+                .and_then(|arg| Address::try_from_val(env, &arg).ok());
+            let to = call
+                .args
+                .get(1)
+                .and_then(|arg| Address::try_from_val(env, &arg).ok());
+            let amount = call
+                .args
+                .get(2)
+                .and_then(|arg| i128::try_from_val(env, &arg).ok())
+                .ok_or(Error::SweepPolicyViolation)?;
+            if from.as_ref() != Some(&account) || to.as_ref() != Some(&destination) || amount < 0 {
+                return Err(Error::SweepPolicyViolation);
+            }
+
+            let total = transferred
+                .get(call.contract.clone())
+                .unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::SweepPolicyViolation)?;
+            let unswept = payment
+                .amount
+                .checked_sub(storage::get_settled_amount(env, &call.contract))
+                .ok_or(Error::Overflow)?;
+            if total > unswept {
+                return Err(Error::SweepPolicyViolation);
+            }
+            transferred.set(call.contract, total);
+        }
+        Ok(())
+    }
+}
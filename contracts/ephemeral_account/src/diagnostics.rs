@@ -0,0 +1,117 @@
+use crate::errors::Error;
+use crate::events::ReserveReclaimed;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountLifecycle, AssetAmount};
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// Whether `record_payment` would accept a payment in an asset right now.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordCheck {
+    pub allowed: bool,
+    /// Code of the `Error` `record_payment` would fail with; `None` when
+    /// allowed.
+    pub reason: Option<u32>,
+}
+
+/// Read-only checks and estimates for wallets and payment watchers:
+/// whether a payment or sweep would go through, and when the account
+/// expires.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Check whether a payment in `asset` could be recorded now.
+    pub fn can_record_payment(env: Env, asset: Address) -> RecordCheck {
+        storage::extend_instance_ttl(&env);
+
+        match Self::check_recordable(&env, &asset) {
+            Ok(_) => RecordCheck {
+                allowed: true,
+                reason: None,
+            },
+            Err(reason) => RecordCheck {
+                allowed: false,
+                reason: Some(reason as u32),
+            },
+        }
+    }
+
+    /// Ledgers left before the account reaches its expiry ledger, `0` once
+    /// it has.
+    pub fn ledgers_until_expiry(env: Env) -> Result<u32, Error> {
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        Ok(storage::get_expiry_ledger(&env).saturating_sub(env.ledger().sequence()))
+    }
+
+    /// Approximate seconds before the account expires.
+    pub fn estimated_seconds_until_expiry(env: Env) -> Result<u64, Error> {
+        let ledgers = Self::ledgers_until_expiry(env.clone())?;
+        Ok(ledgers as u64 * storage::get_ledger_seconds(&env) as u64)
+    }
+
+    /// Ledgers at which the account was created, first paid and settled.
+    pub fn get_lifecycle(env: Env) -> Result<AccountLifecycle, Error> {
+        storage::extend_instance_ttl(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        Ok(storage::get_lifecycle(&env))
+    }
+
+    /// Last reserve reclaim event payload emitted by this contract.
+    pub fn get_last_reserve_event(env: Env) -> Option<ReserveReclaimed> {
+        if !storage::is_initialized(&env) {
+            return None;
+        }
+
+        storage::get_last_reserve_event(&env)
+    }
+
+    /// Number of reserve reclaim events emitted by this contract.
+    pub fn get_reserve_reclaim_event_count(env: Env) -> u32 {
+        if !storage::is_initialized(&env) {
+            return 0;
+        }
+
+        storage::get_reserve_event_count(&env)
+    }
+
+    /// Dry run of a sweep to `destination`: what it would move, or why it would fail.
+    pub fn simulate_sweep(env: Env, destination: Address) -> (Vec<AssetAmount>, i128, u32) {
+        storage::extend_instance_ttl(&env);
+
+        if let Err(error) = Self::check_sweepable(&env, &destination) {
+            return (Vec::new(&env), 0, error as u32);
+        }
+
+        let payments = match Self::net_of_fees(&env, storage::get_payments_ordered(&env)) {
+            Ok(payments) => payments,
+            Err(error) => return (Vec::new(&env), 0, error as u32),
+        };
+        let mut transfers = Vec::new(&env);
+        for payment in payments.iter() {
+            transfers.push_back(AssetAmount {
+                asset: payment.asset,
+                amount: payment.amount,
+            });
+        }
+
+        let refund = Self::reserve_overcollection(&env).unwrap_or(0);
+        let (Some(remaining), Some(available)) = (
+            storage::get_base_reserve_remaining(&env).checked_sub(refund),
+            storage::get_available_reserve(&env).checked_sub(refund),
+        ) else {
+            return (Vec::new(&env), 0, Error::Overflow as u32);
+        };
+        let reserve = remaining.min(available).max(0);
+
+        (transfers, reserve, 0)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, Env};
+
+/// Sweep disputes (`dispute_window_ledgers`, `arbiter`), which keep the
+/// base reserve locked after a sweep.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Dispute the sweep, freezing the base reserve until `resolve_dispute`.
+    pub fn dispute(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        let (window_ledgers, _) =
+            storage::get_dispute_window(&env).ok_or(Error::InvalidDisputeWindow)?;
+
+        if !storage::is_swept(&env)
+            || storage::is_disputed(&env)
+            || env.ledger().sequence() >= Self::swept_ledger(&env).saturating_add(window_ledgers)
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        storage::set_disputed(&env, true);
+        events::emit_sweep_disputed(&env);
+        Ok(())
+    }
+
+    /// Lift a `dispute` once the dispute window has passed. Arbiter only.
+    pub fn resolve_dispute(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        let (_, arbiter) = storage::get_dispute_window(&env).ok_or(Error::InvalidDisputeWindow)?;
+        arbiter.require_auth();
+        if !storage::is_disputed(&env) {
+            return Err(Error::InvalidStatus);
+        }
+
+        storage::set_disputed(&env, false);
+        events::emit_dispute_resolved(&env, arbiter);
+        Ok(())
+    }
+
+    /// Whether the creator disputed the sweep and the arbiter has not
+    /// resolved it yet.
+    pub fn is_disputed(env: Env) -> bool {
+        storage::extend_instance_ttl(&env);
+        storage::is_disputed(&env)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Whether a dispute window or an unresolved dispute keeps a swept
+    /// account's reserve from being reclaimed.
+    pub(crate) fn is_reserve_locked(env: &Env) -> bool {
+        let Some((window_ledgers, _)) = storage::get_dispute_window(env) else {
+            return false;
+        };
+        storage::is_disputed(env)
+            || env.ledger().sequence() < Self::swept_ledger(env).saturating_add(window_ledgers)
+    }
+
+    /// Ledger of the account's last sweep.
+    fn swept_ledger(env: &Env) -> u32 {
+        storage::get_last_sweep_id(env) as u32
+    }
+}
@@ -1,3 +1,4 @@
+use bridgelet_shared::AccountErrorCode;
 use soroban_sdk::contracterror;
 
 #[contracterror]
@@ -17,5 +18,44 @@ pub enum Error {
     InvalidStatus = 12,
     DuplicateAsset = 13,
     TooManyPayments = 14,
-    NotUpgradeAdmin = 15,
+    SweepPolicyViolation = 16,
+    NotRefundable = 17,
+    HoldNotElapsed = 18,
+    ConfigLocked = 19,
+    InvalidReminders = 20,
+    InvalidExpectations = 21,
+    InvalidAssetSelection = 22,
+    AssetNotAllowed = 23,
+    InvalidMaxAssets = 24,
+    DustAmount = 25,
+    SweepPending = 26,
+    Paused = 27,
+    DestinationNotAllowed = 28,
+    ClawbackWindowClosed = 29,
+    InvalidTtl = 30,
+    ReserveNotReclaimed = 31,
+    InvalidLedgerTime = 32,
+    InvalidFee = 33,
+    Overflow = 34,
+    InvalidHook = 35,
+    InvalidSigners = 36,
+    InsufficientSignatures = 37,
+    GuardianDelayActive = 38,
+    ReserveLocked = 39,
+    InvalidDisputeWindow = 40,
+    InvalidMetadata = 41,
+    PayerBlocked = 42,
+    InvalidBlocklist = 43,
+    InvalidVestingSchedule = 44,
+    NothingToClaim = 45,
+    NoSoftExpiry = 46,
+    InvalidRecorder = 47,
+    BalanceShortfall = 48,
+    SweepContention = 50,
+    MigrationRequired = 51,
+    TransferFailed = 52,
 }
+
+// Other contracts report these codes through `AccountErrorCode`.
+const _: () = assert!(Error::AlreadySwept as u32 == AccountErrorCode::AlreadySwept as u32);
+const _: () = assert!(Error::AccountExpired as u32 == AccountErrorCode::AccountExpired as u32);
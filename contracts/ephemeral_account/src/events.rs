@@ -1,40 +1,91 @@
-use bridgelet_shared::Payment;
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use crate::storage;
+use bridgelet_shared::events as envelope;
+use bridgelet_shared::AccountStatus;
+use bridgelet_shared::AssetAmount;
+use bridgelet_shared::{AccountLifecycle, ArbitrationResult, Payment, SettlementPath};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{
+    contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec,
+};
 
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AccountCreated {
     pub creator: Address,
     pub expiry_ledger: u32,
+    pub attestation: Option<BytesN<32>>,
 }
 
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PaymentReceived {
     pub amount: i128,
     pub asset: Address,
+    /// Off-chain reference given to `record_payment_with_reference`.
+    pub reference: Option<BytesN<32>>,
+    /// Payer given to `record_payment_from`.
+    pub payer: Option<Address>,
 }
 
-#[contracttype]
+/// Emitted once by `record_payments` for the whole batch, instead of one
+/// payment event per entry.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentReceived {
+    pub payments: Vec<AssetAmount>,
+    /// Distinct assets recorded on the account after the batch.
+    pub payment_count: u32,
+}
+
+/// Most payments carried inline by `SweepExecutedMulti`. Larger sweeps are
+/// summarized so the payload stays far below Soroban's per-transaction event
+/// size limit; the full list remains readable through `get_payment_assets()`
+/// and `get_payment(asset)`.
+pub const MAX_EVENT_PAYMENTS: u32 = 3;
+
+#[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SweepExecutedMulti {
     pub destination: Address,
+    /// The first `MAX_EVENT_PAYMENTS` swept payments.
     pub payments: Vec<Payment>,
+    /// Number of swept payments, including any not carried inline.
+    pub payment_count: u32,
+    pub truncated: bool,
+    /// Sum of the recorded payment amounts.
+    pub total_amount: i128,
+    /// Sum of the settled amounts (see `PaymentsPage::settled`).
+    pub total_settled: i128,
+    /// Protocol fee taken out of the swept payments, sent to the fee
+    /// recipient.
+    pub total_fee: i128,
+    /// What the destination receives: `total_amount` less `total_fee`.
+    pub total_net: i128,
 }
 
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MultiPaymentReceived {
     pub asset: Address,
     pub amount: i128,
+    /// Off-chain reference given to `record_payment_with_reference`.
+    pub reference: Option<BytesN<32>>,
+    /// Payer given to `record_payment_from`.
+    pub payer: Option<Address>,
 }
 
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AccountExpired {
     pub recovery_address: Address,
+    /// Settled amount returned to the recovery address.
     pub amount_returned: i128,
+    /// Sum of the recorded payment amounts.
+    pub amount_recorded: i128,
     pub reserve_amount: i128,
+    pub created_ledger: u32,
+    pub first_payment_ledger: Option<u32>,
+    pub settled_ledger: u32,
 }
 
 #[contracttype]
@@ -47,44 +98,405 @@ pub struct ReserveReclaimed {
     pub remaining_reserve: i128,
 }
 
-pub fn emit_account_created(env: &Env, creator: Address, expiry_ledger: u32) {
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveRefunded {
+    pub creator: Address,
+    pub amount: i128,
+    pub trustlines_used: u32,
+}
+
+/// Emitted when `refund_stale` returned a payment to its payer after its
+/// hold elapsed.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StalePaymentRefunded {
+    pub payer: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `refund_payment` returns a payment to its payer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRefunded {
+    pub payer: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Emitted when the creator reversed a payment with `clawback`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentClawedBack {
+    pub payer: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Emitted when `refund_excess` returned the surplus over an expected
+/// payment to its payer.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExcessRefunded {
+    pub payer: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+/// Emitted when a payment in an expected asset falls short of the
+/// expected amount by more than the tolerance.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Underpaid {
+    pub asset: Address,
+    pub expected: i128,
+    pub received: i128,
+}
+
+/// Emitted when a payment in an expected asset exceeds the expected amount
+/// by more than the tolerance.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Overpaid {
+    pub asset: Address,
+    pub expected: i128,
+    pub received: i128,
+}
+
+/// Emitted when a payment is added to an asset already recorded, on an
+/// account initialized with `accumulate_payments`. `total` is the asset's
+/// recorded amount after the top-up.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentToppedUp {
+    pub asset: Address,
+    pub amount: i128,
+    pub total: i128,
+}
+
+/// Emitted when refunds removed every recorded payment and the account
+/// returned from `PaymentReceived` to `Active`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountDrained {
+    pub ledger: u32,
+}
+
+/// Emitted when the creator cancelled an unused account. The base reserve
+/// is reclaimed to `recovery_address`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountCancelled {
+    pub recovery_address: Address,
+    pub ledger: u32,
+}
+
+/// Emitted by `close_account` just before it purges the account, so
+/// indexers keep a durable copy of the state removed from live storage.
+/// `state_digest` is the SHA-256 of the XDR of `(status, creator, swept_to,
+/// payments, settled, sweep_nonce)`, against which an archived copy of that
+/// state can be checked. `audit_head` is the head of the audit hash chain
+/// over every event the account emitted before this one.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountArchived {
+    pub status: AccountStatus,
+    pub creator: Address,
+    pub swept_to: Option<Address>,
+    pub payments: Vec<Payment>,
+    pub settled: Vec<AssetAmount>,
+    pub sweep_nonce: u64,
+    pub state_digest: BytesN<32>,
+    pub audit_head: BytesN<32>,
+    pub ledger: u32,
+}
+
+/// Emitted when `close_account` purged a completed account's storage.
+/// `status` is the account's final status.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountClosed {
+    pub status: AccountStatus,
+    pub ledger: u32,
+}
+
+/// Emitted after the account called its sweep hook's `on_sweep`.
+/// `succeeded` is `false` if the hook failed; the settlement stands either
+/// way.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepHookCalled {
+    pub hook: Address,
+    pub succeeded: bool,
+}
+
+/// Emitted when `migrate` brought the account's storage from one schema
+/// version to another.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// Emitted when the creator rotated the recovery address before funding.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryAddressUpdated {
+    pub old_address: Address,
+    pub new_address: Address,
+}
+
+/// Emitted when the guardian redirected the account's expired funds to a
+/// new recovery address with `recover_to`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRedirected {
+    pub guardian: Address,
+    pub old_address: Address,
+    pub new_address: Address,
+}
+
+/// Emitted when the creator added native XLM to the base reserve with
+/// `top_up_reserve`, so the account can take more assets.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveToppedUp {
+    pub creator: Address,
+    pub amount: i128,
+    pub reserve_remaining: i128,
+}
+
+/// Emitted when the creator disputed a sweep with `dispute`, freezing the
+/// base reserve until the arbiter resolves it.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepDisputed {
+    pub ledger: u32,
+}
+
+/// Emitted when the arbiter lifted a dispute with `resolve_dispute`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeResolved {
+    pub arbiter: Address,
+    pub ledger: u32,
+}
+
+/// Emitted when the creator added a payer to the blocklist with
+/// `block_payer` (`blocked` is `true`) or removed one with `unblock_payer`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayerBlocklistUpdated {
+    pub payer: Address,
+    pub blocked: bool,
+}
+
+/// Emitted when the creator set or removed a metadata entry with
+/// `set_metadata`. `value` is empty for a removal.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataUpdated {
+    pub key: Symbol,
+    pub value: Bytes,
+}
+
+/// Emitted when the destination collected vested funds with
+/// `claim_vested`. `claimed_total` includes earlier claims of the asset.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestedClaimed {
+    pub destination: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub claimed_total: i128,
+}
+
+/// Emitted per asset when `claim_expired_portion` returned the share of a
+/// payment released since the soft expiry to the recovery address.
+/// `released_total` includes earlier claims of the asset.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiredPortionClaimed {
+    pub recovery_address: Address,
+    pub asset: Address,
+    pub amount: i128,
+    pub released_total: i128,
+}
+
+/// Emitted when `lock_config` permanently froze the account's settings.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigLocked {
+    pub ledger: u32,
+}
+
+/// Emitted when the creator paused payments and sweeps with `pause`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountPaused {
+    pub ledger: u32,
+}
+
+/// Emitted when the creator lifted a pause with `unpause`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountUnpaused {
+    pub ledger: u32,
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryReminder {
+    pub offset: u32,
+    pub expiry_ledger: u32,
+}
+
+/// Publish under `topic`, wrapped in an `EventEnvelope` and stamped with
+/// the trace id of the sweep being handled, if the controller set one.
+fn publish<D: IntoVal<Env, Val>>(env: &Env, topic: Symbol, data: D) {
+    let topics: Vec<Val> = (topic,).into_val(env);
+    let data: Val = data.into_val(env);
+    chain_audit_head(env, &topics, data);
+    envelope::publish(env, topics, data, storage::get_trace_id(env));
+}
+
+/// Publish under `(topic, index)`, where `index` is the asset, destination
+/// or party the event is about, so indexers can filter on it without
+/// decoding the body. Enveloped and stamped as for `publish`.
+fn publish_indexed<I, D>(env: &Env, topic: Symbol, index: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    let topics: Vec<Val> = (topic, index).into_val(env);
+    let data: Val = data.into_val(env);
+    chain_audit_head(env, &topics, data);
+    envelope::publish(env, topics, data, storage::get_trace_id(env));
+}
+
+/// Fold an event into the account's audit hash chain: the new head is the
+/// SHA-256 of the previous head followed by the XDR of `(topics, body)`,
+/// topics without the trace id. The chain starts from 32 zero bytes.
+fn chain_audit_head(env: &Env, topics: &Vec<Val>, body: Val) {
+    let mut preimage = Bytes::from(storage::get_audit_head(env));
+    preimage.append(&(topics.clone(), body).to_xdr(env));
+    storage::set_audit_head(env, &env.crypto().sha256(&preimage).into());
+}
+
+pub fn emit_account_created(
+    env: &Env,
+    creator: Address,
+    expiry_ledger: u32,
+    attestation: Option<BytesN<32>>,
+) {
     let event = AccountCreated {
         creator,
         expiry_ledger,
+        attestation,
     };
-    env.events().publish((symbol_short!("created"),), event);
+    publish_indexed(env, symbol_short!("created"), event.creator.clone(), event);
 }
 
-pub fn emit_payment_received(env: &Env, amount: i128, asset: Address) {
-    let event = PaymentReceived { amount, asset };
-    env.events().publish((symbol_short!("payment"),), event);
+pub fn emit_payment_received(
+    env: &Env,
+    amount: i128,
+    asset: Address,
+    reference: Option<BytesN<32>>,
+    payer: Option<Address>,
+) {
+    let event = PaymentReceived {
+        amount,
+        asset,
+        reference,
+        payer,
+    };
+    publish_indexed(env, symbol_short!("payment"), event.asset.clone(), event);
+}
+
+pub fn emit_batch_payment_received(env: &Env, payments: Vec<AssetAmount>) {
+    let event = BatchPaymentReceived {
+        payments,
+        payment_count: storage::get_total_payments(env),
+    };
+    publish(env, symbol_short!("batch_pay"), event);
 }
 
-pub fn emit_sweep_executed_multi(env: &Env, destination: Address, payments: &Vec<Payment>) {
+pub fn emit_sweep_executed_multi(
+    env: &Env,
+    destination: Address,
+    payments: &Vec<Payment>,
+    total_amount: i128,
+    total_settled: i128,
+    total_fee: i128,
+    total_net: i128,
+) {
+    let payment_count = payments.len();
+    let truncated = payment_count > MAX_EVENT_PAYMENTS;
     let event = SweepExecutedMulti {
         destination,
-        payments: payments.clone(),
+        payments: if truncated {
+            payments.slice(0..MAX_EVENT_PAYMENTS)
+        } else {
+            payments.clone()
+        },
+        payment_count,
+        truncated,
+        total_amount,
+        total_settled,
+        total_fee,
+        total_net,
     };
-    env.events().publish((symbol_short!("swept_mul"),), event);
+    publish_indexed(
+        env,
+        symbol_short!("swept_mul"),
+        event.destination.clone(),
+        event,
+    );
 }
 
-pub fn emit_multi_payment_received(env: &Env, asset: Address, amount: i128) {
-    let event = MultiPaymentReceived { asset, amount };
-    env.events().publish((symbol_short!("multi_pay"),), event);
+pub fn emit_multi_payment_received(
+    env: &Env,
+    asset: Address,
+    amount: i128,
+    reference: Option<BytesN<32>>,
+    payer: Option<Address>,
+) {
+    let event = MultiPaymentReceived {
+        asset,
+        amount,
+        reference,
+        payer,
+    };
+    publish_indexed(env, symbol_short!("multi_pay"), event.asset.clone(), event);
 }
 
 pub fn emit_account_expired(
     env: &Env,
     recovery_address: Address,
     amount_returned: i128,
+    amount_recorded: i128,
     reserve_amount: i128,
+    lifecycle: AccountLifecycle,
 ) {
     let event = AccountExpired {
         recovery_address,
         amount_returned,
+        amount_recorded,
         reserve_amount,
+        created_ledger: lifecycle.created_ledger,
+        first_payment_ledger: lifecycle.first_payment_ledger,
+        settled_ledger: lifecycle
+            .settled_ledger
+            .unwrap_or_else(|| env.ledger().sequence()),
     };
-    env.events().publish((symbol_short!("expired"),), event);
+    publish_indexed(
+        env,
+        symbol_short!("expired"),
+        event.recovery_address.clone(),
+        event,
+    );
 }
 
 pub fn emit_reserve_reclaimed(
@@ -102,5 +514,291 @@ pub fn emit_reserve_reclaimed(
         fully_reclaimed,
         remaining_reserve,
     };
-    env.events().publish((symbol_short!("reserve"),), event);
+    publish_indexed(
+        env,
+        symbol_short!("reserve"),
+        event.destination.clone(),
+        event,
+    );
+}
+
+pub fn emit_reserve_refunded(env: &Env, creator: Address, amount: i128, trustlines_used: u32) {
+    let event = ReserveRefunded {
+        creator,
+        amount,
+        trustlines_used,
+    };
+    publish_indexed(env, symbol_short!("res_rfnd"), event.creator.clone(), event);
+}
+
+pub fn emit_stale_payment_refunded(env: &Env, payer: Address, asset: Address, amount: i128) {
+    let event = StalePaymentRefunded {
+        payer,
+        asset,
+        amount,
+    };
+    publish_indexed(env, symbol_short!("stale_rfd"), event.asset.clone(), event);
+}
+
+pub fn emit_payment_refunded(env: &Env, payer: Address, asset: Address, amount: i128) {
+    let event = PaymentRefunded {
+        payer,
+        asset,
+        amount,
+    };
+    publish_indexed(env, symbol_short!("refunded"), event.asset.clone(), event);
+}
+
+pub fn emit_excess_refunded(env: &Env, payer: Address, asset: Address, amount: i128) {
+    let event = ExcessRefunded {
+        payer,
+        asset,
+        amount,
+    };
+    publish_indexed(env, symbol_short!("excess"), event.asset.clone(), event);
+}
+
+pub fn emit_payment_clawed_back(env: &Env, payer: Address, asset: Address, amount: i128) {
+    let event = PaymentClawedBack {
+        payer,
+        asset,
+        amount,
+    };
+    publish_indexed(env, symbol_short!("clawback"), event.asset.clone(), event);
+}
+
+pub fn emit_arbitration_result(env: &Env, winner: SettlementPath, loser_reason: u32) {
+    let event = ArbitrationResult {
+        winner,
+        loser_reason,
+    };
+    publish(env, symbol_short!("arbitrate"), event);
+}
+
+pub fn emit_account_cancelled(env: &Env, recovery_address: Address) {
+    let event = AccountCancelled {
+        recovery_address,
+        ledger: env.ledger().sequence(),
+    };
+    publish_indexed(
+        env,
+        symbol_short!("cancelled"),
+        event.recovery_address.clone(),
+        event,
+    );
+}
+
+pub fn emit_account_archived(env: &Env, status: AccountStatus) {
+    let creator = storage::get_creator(env);
+    let swept_to = storage::get_swept_to(env);
+    let payments = storage::get_payments_ordered(env);
+    let settled = storage::get_settled_ordered(env);
+    let sweep_nonce = storage::get_sweep_nonce(env);
+    let state = (
+        status,
+        creator.clone(),
+        swept_to.clone(),
+        payments.clone(),
+        settled.clone(),
+        sweep_nonce,
+    );
+    let event = AccountArchived {
+        status,
+        creator,
+        swept_to,
+        payments,
+        settled,
+        sweep_nonce,
+        state_digest: env.crypto().sha256(&state.to_xdr(env)).into(),
+        audit_head: storage::get_audit_head(env),
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("archived"), event);
+}
+
+pub fn emit_account_closed(env: &Env, status: AccountStatus) {
+    let event = AccountClosed {
+        status,
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("closed"), event);
+}
+
+pub fn emit_sweep_hook_called(env: &Env, hook: Address, succeeded: bool) {
+    let event = SweepHookCalled { hook, succeeded };
+    publish_indexed(env, symbol_short!("hook"), event.hook.clone(), event);
+}
+
+pub fn emit_account_migrated(env: &Env, from_version: u32, to_version: u32) {
+    let event = AccountMigrated {
+        from_version,
+        to_version,
+    };
+    publish(env, symbol_short!("migrated"), event);
+}
+
+pub fn emit_recovery_address_updated(env: &Env, old_address: Address, new_address: Address) {
+    let event = RecoveryAddressUpdated {
+        old_address,
+        new_address,
+    };
+    publish_indexed(
+        env,
+        symbol_short!("recovery"),
+        event.new_address.clone(),
+        event,
+    );
+}
+
+pub fn emit_recovery_redirected(
+    env: &Env,
+    guardian: Address,
+    old_address: Address,
+    new_address: Address,
+) {
+    let event = RecoveryRedirected {
+        guardian,
+        old_address,
+        new_address,
+    };
+    publish_indexed(
+        env,
+        symbol_short!("redirect"),
+        event.new_address.clone(),
+        event,
+    );
+}
+
+pub fn emit_reserve_topped_up(env: &Env, creator: Address, amount: i128, reserve_remaining: i128) {
+    let event = ReserveToppedUp {
+        creator,
+        amount,
+        reserve_remaining,
+    };
+    publish_indexed(
+        env,
+        symbol_short!("res_topup"),
+        event.creator.clone(),
+        event,
+    );
+}
+
+pub fn emit_sweep_disputed(env: &Env) {
+    let event = SweepDisputed {
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("disputed"), event);
+}
+
+pub fn emit_dispute_resolved(env: &Env, arbiter: Address) {
+    let event = DisputeResolved {
+        arbiter,
+        ledger: env.ledger().sequence(),
+    };
+    publish_indexed(env, symbol_short!("resolved"), event.arbiter.clone(), event);
+}
+
+pub fn emit_account_drained(env: &Env) {
+    let event = AccountDrained {
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("drained"), event);
+}
+
+pub fn emit_payer_blocklist_updated(env: &Env, payer: Address, blocked: bool) {
+    let event = PayerBlocklistUpdated { payer, blocked };
+    publish_indexed(env, symbol_short!("blocklist"), event.payer.clone(), event);
+}
+
+pub fn emit_metadata_updated(env: &Env, key: Symbol, value: Bytes) {
+    let event = MetadataUpdated { key, value };
+    publish_indexed(env, symbol_short!("metadata"), event.key.clone(), event);
+}
+
+pub fn emit_vested_claimed(
+    env: &Env,
+    destination: Address,
+    asset: Address,
+    amount: i128,
+    claimed_total: i128,
+) {
+    let event = VestedClaimed {
+        destination,
+        asset,
+        amount,
+        claimed_total,
+    };
+    publish_indexed(env, symbol_short!("vested"), event.asset.clone(), event);
+}
+
+pub fn emit_expired_portion_claimed(
+    env: &Env,
+    recovery_address: Address,
+    asset: Address,
+    amount: i128,
+    released_total: i128,
+) {
+    let event = ExpiredPortionClaimed {
+        recovery_address,
+        asset,
+        amount,
+        released_total,
+    };
+    publish_indexed(env, symbol_short!("exp_part"), event.asset.clone(), event);
+}
+
+pub fn emit_config_locked(env: &Env) {
+    let event = ConfigLocked {
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("cfg_lock"), event);
+}
+
+pub fn emit_account_paused(env: &Env) {
+    let event = AccountPaused {
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("paused"), event);
+}
+
+pub fn emit_account_unpaused(env: &Env) {
+    let event = AccountUnpaused {
+        ledger: env.ledger().sequence(),
+    };
+    publish(env, symbol_short!("unpaused"), event);
+}
+
+pub fn emit_expiry_reminder(env: &Env, offset: u32, expiry_ledger: u32) {
+    let event = ExpiryReminder {
+        offset,
+        expiry_ledger,
+    };
+    publish(env, symbol_short!("reminder"), event);
+}
+
+pub fn emit_underpaid(env: &Env, asset: Address, expected: i128, received: i128) {
+    let event = Underpaid {
+        asset,
+        expected,
+        received,
+    };
+    publish_indexed(env, symbol_short!("underpaid"), event.asset.clone(), event);
+}
+
+pub fn emit_overpaid(env: &Env, asset: Address, expected: i128, received: i128) {
+    let event = Overpaid {
+        asset,
+        expected,
+        received,
+    };
+    publish_indexed(env, symbol_short!("overpaid"), event.asset.clone(), event);
+}
+
+pub fn emit_payment_topped_up(env: &Env, asset: Address, amount: i128, total: i128) {
+    let event = PaymentToppedUp {
+        asset,
+        amount,
+        total,
+    };
+    publish_indexed(env, symbol_short!("topped_up"), event.asset.clone(), event);
 }
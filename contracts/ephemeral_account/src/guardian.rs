@@ -0,0 +1,49 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, Address, Env};
+
+/// Redirection of an expired account's funds by its `guardian`.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Redirect the expired account's funds to `new_address`. Guardian only, after its delay.
+    pub fn recover_to(env: Env, new_address: Address) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let (guardian, delay_ledgers) = storage::get_guardian(&env).ok_or(Error::Unauthorized)?;
+        guardian.require_auth();
+        Self::ensure_no_pending_sweep(&env)?;
+
+        if !matches!(
+            storage::get_status(&env),
+            AccountStatus::Active | AccountStatus::PaymentReceived
+        ) {
+            return Err(Error::InvalidStatus);
+        }
+
+        let available_at = storage::get_expiry_ledger(&env).saturating_add(delay_ledgers);
+        if env.ledger().sequence() < available_at {
+            return Err(Error::GuardianDelayActive);
+        }
+
+        let old_address = storage::get_recovery_address(&env);
+        storage::set_recovery_address(&env, &new_address);
+        events::emit_recovery_redirected(&env, guardian, old_address, new_address);
+
+        Self::finalize_expiry(&env)
+    }
+
+    /// Guardian allowed to call `recover_to` and its delay, if any.
+    pub fn get_guardian(env: Env) -> Option<(Address, u32)> {
+        storage::extend_instance_ttl(&env);
+        storage::get_guardian(&env)
+    }
+}
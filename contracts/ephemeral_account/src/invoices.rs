@@ -0,0 +1,117 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountStatus, ExpectedPayment, Payment};
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env};
+
+/// Expected payments (`expected_payments`): per-asset expectations, their
+/// progress and the refund of overpayments.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Amount recorded in `asset` above its expected amount; `0` if the
+    /// asset has no expectation or was not overpaid.
+    pub fn get_overpayment(env: Env, asset: Address) -> i128 {
+        storage::extend_instance_ttl(&env);
+
+        Self::overpayment(&env, &asset)
+    }
+
+    /// Return the surplus recorded in `asset` above its expected amount to the payer.
+    pub fn refund_excess(env: Env, asset: Address) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_no_pending_sweep(&env)?;
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+
+        let excess = Self::overpayment(&env, &asset);
+        let (Some(payment), Some(expected)) = (
+            storage::get_payment(&env, &asset),
+            storage::get_expected_payment(&env, &asset),
+        ) else {
+            return Err(Error::NotRefundable);
+        };
+        let payer = payment.payer.clone().ok_or(Error::NotRefundable)?;
+        if excess == 0 {
+            return Err(Error::NotRefundable);
+        }
+
+        // Update state before the transfer to prevent reentrancy
+        storage::add_payment(
+            &env,
+            Payment {
+                amount: expected.amount,
+                ..payment
+            },
+        );
+        Self::check_expectation(&env, &expected, expected.amount);
+        if storage::get_soft_expiry_ledger(&env).is_some() {
+            let mut paid_out = storage::get_paid_out(&env, &asset);
+            paid_out.refunded = paid_out
+                .refunded
+                .checked_add(excess)
+                .ok_or(Error::Overflow)?;
+            storage::set_paid_out(&env, &asset, &paid_out);
+        }
+
+        TokenClient::new(&env, &asset).transfer(&env.current_contract_address(), &payer, &excess);
+
+        events::emit_excess_refunded(&env, payer, asset, excess);
+        Ok(excess)
+    }
+
+    /// Amount the account expects in `asset`, if it was initialized with
+    /// one.
+    pub fn get_expected_payment(env: Env, asset: Address) -> Option<ExpectedPayment> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_expected_payment(&env, &asset)
+    }
+
+    /// Whether a recorded payment in `asset` matched its expected amount
+    /// within the tolerance.
+    pub fn is_expectation_met(env: Env, asset: Address) -> bool {
+        storage::extend_instance_ttl(&env);
+
+        storage::is_expectation_met(&env, &asset)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Amount recorded in `asset` above its expected amount.
+    fn overpayment(env: &Env, asset: &Address) -> i128 {
+        match (
+            storage::get_payment(env, asset),
+            storage::get_expected_payment(env, asset),
+        ) {
+            (Some(payment), Some(expected)) => {
+                payment.amount.saturating_sub(expected.amount).max(0)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Mark `expected` met by a recorded amount of `amount`, or flag it as
+    /// underpaid or overpaid when it falls outside the tolerance.
+    pub(crate) fn check_expectation(env: &Env, expected: &ExpectedPayment, amount: i128) {
+        let asset = expected.asset.clone();
+        if amount < expected.amount.saturating_sub(expected.tolerance) {
+            events::emit_underpaid(env, asset, expected.amount, amount);
+        } else if amount > expected.amount.saturating_add(expected.tolerance) {
+            events::emit_overpaid(env, asset, expected.amount, amount);
+        } else {
+            storage::set_expectation_met(env, &asset);
+            return;
+        }
+        // A top-up can push a met expectation over the tolerance.
+        storage::clear_expectation_met(env, &expected.asset);
+    }
+}
@@ -1,37 +1,133 @@
 #![no_std]
 
+mod balance_checks;
+mod batch_recording;
+mod close_account;
+mod creator_controls;
+mod custom_account;
+mod diagnostics;
+mod disputes;
 mod errors;
 mod events;
+mod guardian;
+mod invoices;
+mod metadata;
+mod migration;
+mod multisig;
+mod partial_settlement;
+mod partial_sweeps;
+mod pause;
+mod payer_controls;
+mod payment_references;
+mod protocol_fee;
+mod refunds;
+mod reminders;
+mod reserve_top_up;
+mod soft_expiry;
 mod storage;
 #[cfg(test)]
 mod test;
-
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
-
-pub use bridgelet_shared::{AccountInfo, AccountStatus, EphemeralAccountInterface, Payment};
+mod tracing;
+mod vesting;
+
+use soroban_sdk::token::TokenClient;
+use soroban_sdk::IntoVal;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Symbol, Vec};
+
+pub use balance_checks::BalanceDiscrepancy;
+use bridgelet_shared::AccountErrorCode;
+pub use bridgelet_shared::{
+    AccountInfo, AccountLifecycle, AccountStatus, ArbitrationResult, AssetAmount, ContractVersion,
+    EphemeralAccountInterface, ExpectationProgress, ExpectedPayment, InitOptions, Payment,
+    PaymentsPage, SettlementPath, SweepReadiness,
+};
+pub use diagnostics::RecordCheck;
 pub use errors::Error;
 pub use events::{
-    AccountCreated, AccountExpired, MultiPaymentReceived, PaymentReceived, ReserveReclaimed,
-    SweepExecutedMulti,
+    AccountArchived, AccountCancelled, AccountClosed, AccountCreated, AccountDrained,
+    AccountExpired, AccountMigrated, AccountPaused, AccountUnpaused, BatchPaymentReceived,
+    ConfigLocked, DisputeResolved, ExcessRefunded, ExpiredPortionClaimed, ExpiryReminder,
+    MetadataUpdated, MultiPaymentReceived, Overpaid, PayerBlocklistUpdated, PaymentClawedBack,
+    PaymentReceived, PaymentRefunded, PaymentToppedUp, RecoveryAddressUpdated, RecoveryRedirected,
+    ReserveReclaimed, ReserveRefunded, ReserveToppedUp, StalePaymentRefunded, SweepDisputed,
+    SweepExecutedMulti, SweepHookCalled, Underpaid, VestedClaimed,
 };
-pub use storage::DataKey;
+pub use protocol_fee::ProtocolFee;
+pub use storage::{DataKey, DataKeyExt, InitState, PaymentHold, VestingGrant, VestingSchedule};
 
 const BASE_RESERVE_STROOPS: i128 = 1_000_000_000;
 
+/// Protocol maximum of distinct assets (and therefore trustlines) per
+/// account. Each account may set a lower cap with `max_assets`.
+const MAX_PAYMENT_ASSETS: u32 = 10;
+
+// Sweep events carry at most MAX_EVENT_PAYMENTS payments inline; keep that
+// within the asset cap so small sweeps are never summarized.
+const _: () = assert!(events::MAX_EVENT_PAYMENTS <= MAX_PAYMENT_ASSETS);
+
+/// Share of `BASE_RESERVE_STROOPS` backing the account entry itself.
+const ACCOUNT_RESERVE_STROOPS: i128 = 500_000_000;
+
+/// Share of `BASE_RESERVE_STROOPS` provisioned per trustline. The base
+/// reserve covers the account entry plus one trustline per asset slot.
+const TRUSTLINE_RESERVE_STROOPS: i128 = 50_000_000;
+
+// An account using every asset slot provisions the full base reserve.
+const _: () = assert!(
+    ACCOUNT_RESERVE_STROOPS + TRUSTLINE_RESERVE_STROOPS * MAX_PAYMENT_ASSETS as i128
+        == BASE_RESERVE_STROOPS
+);
+
+/// Protocol fees are expressed in basis points of each swept amount. A fee
+/// stays below the whole amount, so a sweep always delivers something.
+const FULL_BPS: u32 = 10_000;
+
+/// Largest signer set an account may register for multisig sweeps.
+const MAX_SWEEP_SIGNERS: u32 = 10;
+
+/// Most payers an account's blocklist may hold.
+const MAX_BLOCKED_PAYERS: u32 = 20;
+
+/// Most payments `get_payments_page` returns in one call.
+const MAX_PAYMENTS_PAGE: u32 = 10;
+
+/// Maximum number of expiry reminder windows per account.
+const MAX_EXPIRY_REMINDERS: u32 = 5;
+
+/// Cross-contract call shapes exposed by this build. See `interface_version`.
+/// Version 5 adds `deliver`, and sweeps that transfer their own funds.
+const INTERFACE_VERSION: u32 = 5;
+
+/// Version of this build, reported by `get_version`.
+const BUILD_VERSION: ContractVersion = ContractVersion::parse(env!("CARGO_PKG_VERSION"));
+
+/// Storage layout written by this build; `migrate` brings accounts written
+/// by earlier builds up to it.
+///
+/// 1: payments in a single instance map. 2: one persistent entry per asset.
+/// 3: per-asset state in persistent entries too. 4: status, first payment
+/// ledger, payment index and audit head packed into one `AccountCore`.
+const SCHEMA_VERSION: u32 = 4;
+
+/// How `store_payment` recorded a payment, which decides the event to emit.
+enum Recorded {
+    /// The account's first payment
+    First,
+    /// A payment in an asset not recorded yet
+    Another,
+    /// Added to the payment already recorded in its asset; the new total
+    ToppedUp(i128),
+}
+
+/// Entry point docs are compiled into the wasm's contract spec, which counts
+/// toward the network's contract size limit, so they stay to one line; the
+/// full contract API is documented in `docs/api-reference.md`.
 #[contract]
 pub struct EphemeralAccountContract;
 
 #[contractimpl]
 impl EphemeralAccountContract {
     /// Initialize the ephemeral account with restrictions
-    ///
-    /// # Arguments
-    /// * `creator` - Address that created this account
-    /// * `expiry_ledger` - Ledger number when account expires
-    /// * `recovery_address` - Address to return funds if expired
-    ///
-    /// # Errors
-    /// Returns Error::AlreadyInitialized if called more than once
     pub fn initialize(
         env: Env,
         creator: Address,
@@ -39,11 +135,35 @@ impl EphemeralAccountContract {
         recovery_address: Address,
         authorized_controller: Address,
         admin: Address,
+    ) -> Result<(), Error> {
+        Self::initialize_with_options(
+            env,
+            creator,
+            expiry_ledger,
+            recovery_address,
+            authorized_controller,
+            admin,
+            InitOptions::default(),
+        )
+    }
+
+    /// Same as [`initialize`], with optional settings.
+    pub fn initialize_with_options(
+        env: Env,
+        creator: Address,
+        expiry_ledger: u32,
+        recovery_address: Address,
+        authorized_controller: Address,
+        admin: Address,
+        options: InitOptions,
     ) -> Result<(), Error> {
         storage::extend_instance_ttl(&env);
+        // Deprecated and ignored: upgrades are authorized by the creator.
+        // Kept in the signature until 0.2.0.
+        let _ = admin;
 
-        // Check if already initialized
-        if storage::is_initialized(&env) {
+        // Check if already initialized, even partially
+        if storage::get_init_state(&env) != InitState::Uninitialized {
             return Err(Error::AlreadyInitialized);
         }
 
@@ -56,54 +176,324 @@ impl EphemeralAccountContract {
             return Err(Error::InvalidExpiry);
         }
 
-        // Store initialization data
-        storage::set_initialized(&env, true);
+        if let Some(reminders) = &options.expiry_reminders {
+            if reminders.len() > MAX_EXPIRY_REMINDERS {
+                return Err(Error::InvalidReminders);
+            }
+            for (i, offset) in reminders.iter().enumerate() {
+                if offset == 0 || reminders.first_index_of(offset) != Some(i as u32) {
+                    return Err(Error::InvalidReminders);
+                }
+            }
+        }
+
+        let max_assets = options.max_assets.unwrap_or(MAX_PAYMENT_ASSETS);
+        if max_assets == 0 || max_assets > MAX_PAYMENT_ASSETS {
+            return Err(Error::InvalidMaxAssets);
+        }
+
+        let min_payments = options.min_payment_per_asset.unwrap_or(Vec::new(&env));
+        if options.min_payment.is_some_and(|amount| amount <= 0)
+            || min_payments.iter().enumerate().any(|(i, minimum)| {
+                minimum.amount <= 0
+                    || min_payments
+                        .iter()
+                        .take(i)
+                        .any(|earlier| earlier.asset == minimum.asset)
+            })
+        {
+            return Err(Error::InvalidAmount);
+        }
+
+        if let Some(expected_payments) = &options.expected_payments {
+            if expected_payments.len() > max_assets {
+                return Err(Error::InvalidExpectations);
+            }
+            for (i, expected) in expected_payments.iter().enumerate() {
+                let duplicate = expected_payments
+                    .iter()
+                    .take(i)
+                    .any(|earlier| earlier.asset == expected.asset);
+                if expected.amount <= 0 || expected.tolerance < 0 || duplicate {
+                    return Err(Error::InvalidExpectations);
+                }
+            }
+        }
+
+        if let Some(ledgers) = options.ttl_extend_to {
+            if ledgers <= storage::INSTANCE_TTL_THRESHOLD || ledgers > env.storage().max_ttl() {
+                return Err(Error::InvalidTtl);
+            }
+        }
+
+        if options.ledger_seconds == Some(0) {
+            return Err(Error::InvalidLedgerTime);
+        }
+
+        if let Some(hook) = &options.sweep_hook {
+            if !bridgelet_shared::is_contract_address(&env, hook) {
+                return Err(Error::InvalidHook);
+            }
+        }
+
+        match &options.sweep_signers {
+            Some(signers) => {
+                let duplicate = signers
+                    .iter()
+                    .enumerate()
+                    .any(|(i, signer)| signers.first_index_of(&signer) != Some(i as u32));
+                if signers.is_empty()
+                    || signers.len() > MAX_SWEEP_SIGNERS
+                    || duplicate
+                    || options.sweep_threshold == 0
+                    || options.sweep_threshold > signers.len()
+                {
+                    return Err(Error::InvalidSigners);
+                }
+            }
+            None if options.sweep_threshold != 0 => return Err(Error::InvalidSigners),
+            None => {}
+        }
+
+        if (options.dispute_window_ledgers > 0) != options.arbiter.is_some() {
+            return Err(Error::InvalidDisputeWindow);
+        }
+
+        if let Some(payers) = &options.blocked_payers {
+            if payers.len() > MAX_BLOCKED_PAYERS {
+                return Err(Error::InvalidBlocklist);
+            }
+        }
+
+        if options.recorder.is_some() && !options.restrict_recording {
+            return Err(Error::InvalidRecorder);
+        }
+
+        if let Some(ledger) = options.soft_expiry_ledger {
+            if ledger < current_ledger || ledger >= expiry_ledger {
+                return Err(Error::InvalidExpiry);
+            }
+        }
+
+        let vesting = match options.vesting_cliff_ledger {
+            Some(cliff_ledger) => {
+                if cliff_ledger
+                    .checked_add(options.vesting_duration_ledgers)
+                    .is_none()
+                {
+                    return Err(Error::InvalidVestingSchedule);
+                }
+                Some(VestingSchedule {
+                    cliff_ledger,
+                    duration_ledgers: options.vesting_duration_ledgers,
+                })
+            }
+            None if options.vesting_duration_ledgers != 0 => {
+                return Err(Error::InvalidVestingSchedule)
+            }
+            None => None,
+        };
+
+        if options.protocol_fee_bps >= FULL_BPS
+            || (options.protocol_fee_bps > 0 && options.protocol_fee_recipient.is_none())
+        {
+            return Err(Error::InvalidFee);
+        }
+
+        // Store initialization data, marking the account ready last
+        storage::set_init_state(&env, InitState::Initializing);
         storage::set_creator(&env, &creator);
         storage::set_expiry_ledger(&env, expiry_ledger);
         storage::set_recovery_address(&env, &recovery_address);
         storage::set_status(&env, AccountStatus::Active);
         storage::set_authorized_controller(&env, &authorized_controller);
-        storage::set_admin(&env, &admin);
-        storage::init_reserve_tracking(&env, BASE_RESERVE_STROOPS);
+        storage::init_reserve_tracking(&env, Self::base_reserve(max_assets));
+        if max_assets != MAX_PAYMENT_ASSETS {
+            storage::set_max_assets(&env, max_assets);
+        }
+        storage::set_created_ledger(&env, current_ledger);
+        storage::set_schema_version(&env, SCHEMA_VERSION);
+        storage::set_version(&env, &BUILD_VERSION);
+        if let Some(attestation) = &options.attestation {
+            storage::set_attestation(&env, attestation);
+        }
+        if let Some(signer) = &options.sweep_signer {
+            storage::set_sweep_signer(&env, signer);
+        }
+        if let Some(destination) = &options.locked_destination {
+            storage::set_locked_destination(&env, destination);
+        }
+        if let Some(ledgers) = options.payment_hold_ledgers {
+            storage::set_payment_hold_ledgers(&env, ledgers);
+        }
+        if options.require_creator_cosign {
+            storage::set_creator_cosign(&env);
+        }
+        if options.accumulate_payments {
+            storage::set_accumulate_payments(&env);
+        }
+        if let Some(amount) = options.min_payment {
+            storage::set_min_payment(&env, amount);
+        }
+        for minimum in min_payments.iter() {
+            storage::set_asset_min_payment(&env, &minimum.asset, minimum.amount);
+        }
+        if let Some(assets) = options.allowed_assets.filter(|assets| !assets.is_empty()) {
+            storage::set_allowed_assets(&env, &assets);
+        }
+        if let Some(destinations) = options
+            .allowed_destinations
+            .filter(|destinations| !destinations.is_empty())
+        {
+            storage::set_allowed_destinations(&env, &destinations);
+        }
+        if let Some(ledgers) = options.clawback_window_ledgers {
+            storage::set_clawback_window_ledgers(&env, ledgers);
+        }
+        if let Some(ledgers) = options.ttl_extend_to {
+            storage::set_ttl_extend_to(&env, ledgers);
+        }
+        if options.sweep_contention_ledgers > 0 {
+            storage::set_sweep_contention_ledgers(&env, options.sweep_contention_ledgers);
+        }
+        if let Some(seconds) = options.ledger_seconds {
+            storage::set_ledger_seconds(&env, seconds);
+        }
+        if let Some(hook) = &options.sweep_hook {
+            storage::set_sweep_hook(&env, hook);
+        }
+        if let Some(signers) = &options.sweep_signers {
+            storage::set_sweep_signers(&env, signers, options.sweep_threshold);
+        }
+        if let Some(guardian) = &options.guardian {
+            storage::set_guardian(&env, guardian, options.guardian_delay_ledgers);
+        }
+        if let Some(arbiter) = &options.arbiter {
+            storage::set_dispute_window(&env, options.dispute_window_ledgers, arbiter);
+        }
+        if let Some(payers) = &options.blocked_payers {
+            let mut blocked = Vec::new(&env);
+            for payer in payers.iter() {
+                if !blocked.contains(&payer) {
+                    blocked.push_back(payer);
+                }
+            }
+            storage::set_blocked_payers(&env, &blocked);
+        }
+        if let Some(vesting) = &vesting {
+            storage::set_vesting_schedule(&env, vesting);
+        }
+        if let Some(ledger) = options.soft_expiry_ledger {
+            storage::set_soft_expiry_ledger(&env, ledger);
+        }
+        if options.verify_balances_on_sweep {
+            storage::set_verify_balances_on_sweep(&env);
+        }
+        if options.restrict_recording {
+            storage::set_recorder(&env, options.recorder.as_ref().unwrap_or(&creator));
+        }
+        if let Some(recipient) = &options.protocol_fee_recipient {
+            if options.protocol_fee_bps > 0 {
+                storage::set_protocol_fee(
+                    &env,
+                    &ProtocolFee {
+                        bps: options.protocol_fee_bps,
+                        recipient: recipient.clone(),
+                    },
+                );
+            }
+        }
+        if let Some(reminders) = options.expiry_reminders.filter(|r| !r.is_empty()) {
+            storage::set_expiry_reminders(&env, &reminders);
+        }
+        if let Some(asset) = &options.preferred_settlement_asset {
+            storage::set_preferred_settlement_asset(&env, asset);
+        }
+        if let Some(expected) = options.expected_payments.filter(|e| !e.is_empty()) {
+            storage::set_expected_payments(&env, &expected);
+        }
+        storage::set_init_state(&env, InitState::Ready);
+        storage::extend_ttls(&env);
 
         // Emit event
-        events::emit_account_created(&env, creator, expiry_ledger);
+        events::emit_account_created(&env, creator, expiry_ledger, options.attestation);
 
         Ok(())
     }
 
     /// Record an inbound payment to this ephemeral account
     /// Multiple payments with different assets are supported
-    ///
-    /// # Arguments
-    /// * `amount` - Payment amount
-    /// * `asset` - Asset address
-    ///
-    /// # Errors
-    /// Returns Error::InvalidAmount if amount is not positive
-    /// Returns Error::DuplicateAsset if asset already has a payment
     pub fn record_payment(env: Env, amount: i128, asset: Address) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
+        Self::record(env, None, amount, asset, None)
+    }
 
-        // Check initialized
-        if !storage::is_initialized(&env) {
-            return Err(Error::NotInitialized);
+    /// Deposit `amount` of `asset` from `payer` and record it as `payer`'s payment.
+    pub fn record_payment_from(
+        env: Env,
+        payer: Address,
+        amount: i128,
+        asset: Address,
+    ) -> Result<(), Error> {
+        payer.require_auth();
+        Self::record(
+            env.clone(),
+            Some(payer.clone()),
+            amount,
+            asset.clone(),
+            None,
+        )?;
+        TokenClient::new(&env, &asset).transfer(&payer, &env.current_contract_address(), &amount);
+        Ok(())
+    }
+
+    fn record(
+        env: Env,
+        payer: Option<Address>,
+        amount: i128,
+        asset: Address,
+        reference: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+        Self::require_recorder(&env, payer.as_ref());
+
+        match Self::store_payment(&env, &payer, amount, &asset, &reference)? {
+            Recorded::First => events::emit_payment_received(&env, amount, asset, reference, payer),
+            Recorded::Another => {
+                events::emit_multi_payment_received(&env, asset, amount, reference, payer)
+            }
+            Recorded::ToppedUp(total) => events::emit_payment_topped_up(&env, asset, amount, total),
+        }
+
+        Ok(())
+    }
+
+    /// Validate and store one payment, without emitting its event.
+    fn store_payment(
+        env: &Env,
+        payer: &Option<Address>,
+        amount: i128,
+        asset: &Address,
+        reference: &Option<BytesN<32>>,
+    ) -> Result<Recorded, Error> {
+        let payment_count = Self::check_recordable(env, asset)?;
+
+        if let Some(payer) = payer {
+            if storage::is_payer_blocked(env, payer) {
+                return Err(Error::PayerBlocked);
+            }
         }
 
         // Validate amount
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
-
-        // Check for duplicate asset
-        if storage::get_payment(&env, &asset).is_some() {
-            return Err(Error::DuplicateAsset);
+        if amount < storage::get_min_payment(env, asset) {
+            return Err(Error::DustAmount);
         }
 
-        // Check payment limit to prevent gas issues (max 10 assets)
-        let payment_count = storage::get_total_payments(&env);
-        if payment_count >= 10 {
-            return Err(Error::TooManyPayments);
+        if let Some(recorded) = storage::get_payment(env, asset) {
+            let total = Self::top_up(env, payer.clone(), recorded, amount)?;
+            return Ok(Recorded::ToppedUp(total));
         }
 
         // Create payment with current timestamp
@@ -111,178 +501,215 @@ impl EphemeralAccountContract {
             asset: asset.clone(),
             amount,
             timestamp: env.ledger().timestamp(),
+            payer: payer.clone(),
         };
 
         // Add payment
-        storage::add_payment(&env, payment);
-
-        // Update status only on first payment
-        if payment_count == 0 {
-            storage::set_status(&env, AccountStatus::PaymentReceived);
+        storage::add_payment(env, payment);
+        storage::set_first_payment_ledger(env, env.ledger().sequence());
+        if let Some(reference) = reference {
+            storage::set_payment_reference(env, asset, reference);
+        }
+        if let Some(ledgers) = storage::get_clawback_window_ledgers(env) {
+            storage::set_clawback_until(
+                env,
+                asset,
+                env.ledger().sequence().saturating_add(ledgers),
+            );
+        }
+        if let (Some(payer), Some(ledgers)) = (payer, storage::get_payment_hold_ledgers(env)) {
+            storage::set_payment_hold(
+                env,
+                asset,
+                &PaymentHold {
+                    payer: payer.clone(),
+                    hold_until_ledger: env.ledger().sequence().saturating_add(ledgers),
+                },
+            );
         }
 
-        // Emit appropriate event
-        if payment_count == 0 {
-            events::emit_payment_received(&env, amount, asset);
-        } else {
-            events::emit_multi_payment_received(&env, asset, amount);
+        if let Some(expected) = storage::get_expected_payment(env, asset) {
+            Self::check_expectation(env, &expected, amount);
         }
 
-        Ok(())
+        Self::receive_if_fulfilled(env);
+
+        Ok(if payment_count == 0 {
+            Recorded::First
+        } else {
+            Recorded::Another
+        })
     }
 
-    /// Execute sweep to destination wallet via Ed25519 signature path.
-    ///
-    /// This is the **off-chain signer** sweep path: the caller passes an
-    /// `auth_signature` that was produced off-chain by the `authorized_signer`.
-    /// Authorization is verified against the stored Ed25519 public key.
+    /// Add `amount` to the payment already recorded in its asset. Only
+    /// reached when payments accumulate.
     ///
-    /// **Do not call directly** — always route through
-    /// `SweepController::execute_sweep`, which handles signature verification,
-    /// nonce management, and token transfers.
-    ///
-    /// # Arguments
-    /// * `destination` - Recipient wallet address
-    /// * `auth_signature` - Ed25519 signature from the authorized off-chain signer
-    ///
-    /// # Errors
-    /// * `Error::NotInitialized` — contract not yet initialized
-    /// * `Error::AlreadySwept` — account already swept
-    /// * `Error::NoPaymentReceived` — no payment recorded yet
-    /// * `Error::AccountExpired` — past expiry ledger
-    /// * `Error::Unauthorized` — caller is not the authorized controller
-    ///
-    /// # Authorization Flow
-    /// 1. Off-chain: signer signs `hash(destination + nonce + contract_id)`
-    /// 2. Caller invokes `SweepController.execute_sweep(destination, signature)`
-    /// 3. `SweepController` verifies the Ed25519 signature and increments nonce
-    /// 4. `SweepController` calls this function via `authorize_ephemeral_sweep`
-    /// 5. This function validates state, transitions to `Swept`, and reclaims reserve
-    ///
-    /// See also: [`sweep_claim`] for the Soroban-auth claim path used by
-    /// `SweepController::claim`.
-    pub fn sweep(env: Env, destination: Address, auth_signature: BytesN<64>) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
-
-        // Check initialized
-        if !storage::is_initialized(&env) {
-            return Err(Error::NotInitialized);
-        }
-
-        // Check not already swept
-        if storage::get_status(&env) == AccountStatus::Swept {
-            return Err(Error::AlreadySwept);
-        }
+    /// The hold placed by `record_payment_from` is renewed for a top-up by
+    /// the same payer. A top-up by anyone else drops it and the recorded
+    /// payer: the payment no longer belongs to a single payer who could be
+    /// refunded. Returns the new total.
+    fn top_up(
+        env: &Env,
+        payer: Option<Address>,
+        recorded: Payment,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let total = recorded.amount.checked_add(amount).ok_or(Error::Overflow)?;
+        let asset = recorded.asset;
+        let mixed_payers = recorded.payer.is_some() && payer != recorded.payer;
+        storage::add_payment(
+            env,
+            Payment {
+                asset: asset.clone(),
+                amount: total,
+                timestamp: env.ledger().timestamp(),
+                payer: if mixed_payers { None } else { recorded.payer },
+            },
+        );
 
-        // Check payment received
-        if !storage::has_payment_received(&env) {
-            return Err(Error::NoPaymentReceived);
+        if mixed_payers {
+            storage::remove_payment_hold(env, &asset);
+        } else if let (Some(payer), Some(_), Some(ledgers)) = (
+            payer,
+            storage::get_payment_hold(env, &asset),
+            storage::get_payment_hold_ledgers(env),
+        ) {
+            storage::set_payment_hold(
+                env,
+                &asset,
+                &PaymentHold {
+                    payer,
+                    hold_until_ledger: env.ledger().sequence().saturating_add(ledgers),
+                },
+            );
         }
 
-        // Check not expired
-        if Self::is_expired(env.clone()) {
-            return Err(Error::AccountExpired);
+        if let Some(expected) = storage::get_expected_payment(env, &asset) {
+            Self::check_expectation(env, &expected, total);
         }
 
-        // Verify authorization signature
-        // Note: In production, implement proper signature verification
-        // For MVP, we trust the SDK to only call with valid signatures
-        Self::verify_sweep_authorization(&env, &destination, &auth_signature)?;
+        Self::receive_if_fulfilled(env);
 
-        // Get all payments
-        let payments = storage::get_all_payments(&env);
-        let mut payments_vec = Vec::new(&env);
-        for payment in payments.values() {
-            payments_vec.push_back(payment);
-        }
+        Ok(total)
+    }
 
-        // Update status before transfer to prevent reentrancy
-        storage::set_status(&env, AccountStatus::Swept);
-        storage::set_swept_to(&env, &destination);
+    /// Sweep to `destination` for `SweepController::execute_sweep`. Do not call directly.
+    pub fn sweep(env: Env, destination: Address, auth_signature: BytesN<64>) -> Result<(), Error> {
+        // Verified by the controller, whose authorization is required here
+        let _ = auth_signature;
+        Self::signed_sweep(&env, &destination).map(|_| ())
+    }
 
-        // Note: Actual token transfers happen in the SDK via Stellar SDK.
-        // This contract enforces authorization/state transitions and reserve lifecycle.
-        let sweep_id = env.ledger().sequence() as u64;
-        storage::set_last_sweep_id(&env, sweep_id);
+    /// Same as [`sweep`], but returns the payments that were swept.
+    pub fn sweep_and_report(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, Error> {
+        let _ = auth_signature;
+        Self::signed_sweep(&env, &destination)
+    }
 
-        // Emit sweep event once transfer authorization/state update succeeds.
-        events::emit_sweep_executed_multi(&env, destination.clone(), &payments_vec);
+    /// First phase of a two-phase sweep: lock the account in `PendingSweep`.
+    pub fn prepare_sweep(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, Error> {
+        let _ = auth_signature;
+        storage::extend_ttls(&env);
+
+        Self::check_sweepable(&env, &destination)?;
+        Self::authorize_sweep(&env)?;
+        Self::claim_sweep(&env, &destination)?;
+
+        storage::set_status(&env, AccountStatus::PendingSweep);
+        storage::set_pending_sweep_destination(&env, &destination);
+        Self::net_of_fees(&env, storage::get_payments_ordered(&env))
+    }
 
-        // Reclaim base reserve only after successful sweep state transition.
-        Self::reclaim_reserve_to(&env, &destination, sweep_id)?;
+    /// Settle the sweep [`prepare_sweep`] staged.
+    pub fn commit_sweep(env: Env) -> Result<(), Error> {
+        Self::transition_sweep_status(&env, AccountStatus::PendingSweep, AccountStatus::Swept)?;
+        let destination =
+            storage::take_pending_sweep_destination(&env).ok_or(Error::InvalidStatus)?;
+        Self::settle_sweep(&env, &destination, false).map(|_| ())
+    }
 
+    /// Release the sweep [`prepare_sweep`] staged without settling it.
+    pub fn abort_sweep(env: Env) -> Result<(), Error> {
+        Self::transition_sweep_status(
+            &env,
+            AccountStatus::PendingSweep,
+            AccountStatus::PaymentReceived,
+        )?;
+        storage::take_pending_sweep_destination(&env);
         Ok(())
     }
 
-    /// Sweep initiated by a direct claim — **no off-chain signature required**.
-    ///
-    /// This is the **Soroban-auth claim** path: authorization is enforced
-    /// entirely by requiring the sweep controller as the invoker via Soroban
-    /// auth. Used by `SweepController::claim()` where the recipient has already
-    /// proven ownership via the Soroban auth entry on the outer transaction.
-    ///
-    /// **Do not call directly** — always route through
-    /// `SweepController::claim`, which handles destination validation, nonce
-    /// management, and event emission.
-    ///
-    /// # Arguments
-    /// * `destination` - Recipient wallet address (must match locked destination if set)
-    ///
-    /// # Errors
-    /// * `Error::NotInitialized` — contract not yet initialized
-    /// * `Error::AlreadySwept` — account already swept
-    /// * `Error::NoPaymentReceived` — no payment recorded yet
-    /// * `Error::AccountExpired` — past expiry ledger
-    /// * `Error::Unauthorized` — caller is not the authorized controller
-    ///
-    /// # Authorization Flow
-    /// 1. Recipient signs a Soroban auth entry for `SweepController::claim`
-    /// 2. Caller (or relayer) invokes `SweepController::claim(recipient, ephemeral_account)`
-    /// 3. `SweepController` validates destination, authorizes as invoker of `sweep_claim`
-    /// 4. This function validates state, transitions to `Swept`, and reclaims reserve
-    ///
-    /// See also: [`sweep`] for the Ed25519 signature path used by
-    /// `SweepController::execute_sweep`.
-    pub fn sweep_claim(env: Env, destination: Address) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
+    /// Transfer `amount` of `asset` to `recipient` for the controller settling a sweep.
+    pub fn deliver(
+        env: Env,
+        asset: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        storage::extend_ttls(&env);
 
         if !storage::is_initialized(&env) {
             return Err(Error::NotInitialized);
         }
+        let controller = storage::get_authorized_controller(&env).ok_or(Error::Unauthorized)?;
+        controller.require_auth();
 
-        if storage::get_status(&env) == AccountStatus::Swept {
-            return Err(Error::AlreadySwept);
+        match storage::get_status(&env) {
+            AccountStatus::PendingSweep | AccountStatus::PartiallySwept => {}
+            _ => return Err(Error::InvalidStatus),
         }
-
-        if !storage::has_payment_received(&env) {
-            return Err(Error::NoPaymentReceived);
+        let payment = storage::get_payment(&env, &asset).ok_or(Error::InvalidAmount)?;
+        if amount < 0 || amount > payment.amount {
+            return Err(Error::InvalidAmount);
         }
 
-        if Self::is_expired(env.clone()) {
-            return Err(Error::AccountExpired);
-        }
+        Self::transfer(&env, &asset, &recipient, amount)
+    }
 
-        // Only the authorized controller may invoke this path
-        let controller = storage::get_authorized_controller(&env).ok_or(Error::Unauthorized)?;
-        controller.require_auth();
+    /// Number of sweeps this account has settled, which account-nonce
+    /// sweep signatures bind.
+    pub fn get_sweep_nonce(env: Env) -> u64 {
+        storage::extend_instance_ttl(&env);
 
-        let payments = storage::get_all_payments(&env);
-        let mut payments_vec = Vec::new(&env);
-        for payment in payments.values() {
-            payments_vec.push_back(payment);
-        }
+        storage::get_sweep_nonce(&env)
+    }
 
-        storage::set_status(&env, AccountStatus::Swept);
-        storage::set_swept_to(&env, &destination);
+    /// Version of the cross-contract call shapes this account exposes.
+    pub fn interface_version(_env: Env) -> u32 {
+        INTERFACE_VERSION
+    }
 
-        let sweep_id = env.ledger().sequence() as u64;
-        storage::set_last_sweep_id(&env, sweep_id);
+    /// Sweep to `destination` for `SweepController::claim`. Do not call directly.
+    pub fn sweep_claim(env: Env, destination: Address) -> Result<Vec<Payment>, Error> {
+        storage::extend_ttls(&env);
+
+        Self::check_sweepable(&env, &destination)?;
 
-        events::emit_sweep_executed_multi(&env, destination.clone(), &payments_vec);
+        // Only the authorized controller may invoke this path, and only
+        // without a signer set: a claim carries no signatures.
+        if storage::get_sweep_signers(&env).is_some() {
+            return Err(Error::InsufficientSignatures);
+        }
+        Self::require_controller(&env)?;
 
-        Self::reclaim_reserve_to(&env, &destination, sweep_id)?;
+        storage::set_status(&env, AccountStatus::Swept);
+        Self::settle_sweep(&env, &destination, true)
+    }
 
-        Ok(())
+    /// Whether the account can be swept right now and, if not, the first
+    /// obstacle.
+    pub fn can_sweep_detailed(env: Env) -> SweepReadiness {
+        storage::extend_instance_ttl(&env);
+
+        Self::sweep_readiness(&env)
     }
 
     /// Check if account has expired
@@ -299,38 +726,50 @@ impl EphemeralAccountContract {
         current_ledger >= expiry_ledger
     }
 
+    /// Initialization progress; anything but `Ready` counts as not initialized.
+    pub fn get_init_state(env: Env) -> InitState {
+        storage::extend_instance_ttl(&env);
+        storage::get_init_state(&env)
+    }
+
     /// Get current account status
     pub fn get_status(env: Env) -> AccountStatus {
         storage::extend_instance_ttl(&env);
 
-        if !storage::is_initialized(&env) {
-            return AccountStatus::Active;
+        match storage::get_init_state(&env) {
+            InitState::Ready | InitState::Closed => storage::get_status(&env),
+            _ => AccountStatus::Active,
         }
-
-        storage::get_status(&env)
     }
 
     /// Expire the account and return funds to recovery address
     /// Can only be called after expiry ledger is reached
-    ///
-    /// # Errors
-    /// Returns Error::NotExpired if called before expiry ledger
     pub fn expire(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
         // Check initialized
         if !storage::is_initialized(&env) {
             return Err(Error::NotInitialized);
         }
 
-        // Check not already swept or expired
-        let status = storage::get_status(&env);
-        if status == AccountStatus::Swept || status == AccountStatus::Expired {
-            return Err(Error::InvalidStatus);
-        }
-
         // Check if expired
         if !Self::is_expired(env.clone()) {
             return Err(Error::NotExpired);
         }
+        Self::ensure_no_pending_sweep(&env)?;
+
+        // Lost the race against a sweep
+        if Self::report_lost_to_sweep(&env) {
+            return Ok(());
+        }
+
+        // Check not already expired or cancelled
+        if matches!(
+            storage::get_status(&env),
+            AccountStatus::Expired | AccountStatus::Cancelled
+        ) {
+            return Err(Error::InvalidStatus);
+        }
 
         // expire() is intentionally permissionless (see docs/security.md threat
         // model #3): anyone may trigger cleanup once the account has expired.
@@ -338,17 +777,26 @@ impl EphemeralAccountContract {
         Self::finalize_expiry(&env)
     }
 
-    /// Reclaim remaining base reserve for a previously swept/expired account.
+    /// Reclaim remaining base reserve for a previously swept/expired/cancelled account.
     /// This is safe to call repeatedly: once fully reclaimed, subsequent calls transfer 0.
     pub fn reclaim_reserve(env: Env) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
         if !storage::is_initialized(&env) {
             return Err(Error::NotInitialized);
         }
 
-        let status = storage::get_status(&env);
-        if status != AccountStatus::Swept && status != AccountStatus::Expired {
+        if !storage::is_swept(&env)
+            && !matches!(
+                storage::get_status(&env),
+                AccountStatus::Expired | AccountStatus::Cancelled
+            )
+        {
             return Err(Error::InvalidStatus);
         }
+        if storage::is_swept(&env) && Self::is_reserve_locked(&env) {
+            return Err(Error::ReserveLocked);
+        }
 
         let destination = storage::get_swept_to(&env).ok_or(Error::InvalidStatus)?;
         let sweep_id = storage::get_last_sweep_id(&env);
@@ -378,36 +826,30 @@ impl EphemeralAccountContract {
         storage::get_available_reserve(&env)
     }
 
-    /// Whether reserve has been fully reclaimed.
-    pub fn is_reserve_reclaimed(env: Env) -> bool {
+    /// Reserve (stroops) refunded to the creator for unused trustlines.
+    pub fn get_reserve_refunded(env: Env) -> i128 {
         storage::extend_instance_ttl(&env);
 
         if !storage::is_initialized(&env) {
-            return false;
+            return 0;
         }
 
-        storage::is_reserve_reclaimed(&env)
+        storage::get_reserve_refunded(&env)
     }
 
-    /// Last reserve reclaim event payload emitted by this contract.
-    pub fn get_last_reserve_event(env: Env) -> Option<ReserveReclaimed> {
-        if !storage::is_initialized(&env) {
-            return None;
-        }
-
-        storage::get_last_reserve_event(&env)
-    }
+    /// Whether reserve has been fully reclaimed.
+    pub fn is_reserve_reclaimed(env: Env) -> bool {
+        storage::extend_instance_ttl(&env);
 
-    /// Number of reserve reclaim events emitted by this contract.
-    pub fn get_reserve_reclaim_event_count(env: Env) -> u32 {
         if !storage::is_initialized(&env) {
-            return 0;
+            return false;
         }
 
-        storage::get_reserve_event_count(&env)
+        storage::is_reserve_reclaimed(&env)
     }
 
-    /// Get account information
+    /// Get account information. Payments are counted, not listed; read them
+    /// with [`Self::get_payments_page`] or [`Self::get_payment`].
     pub fn get_info(env: Env) -> Result<AccountInfo, Error> {
         storage::extend_instance_ttl(&env);
 
@@ -415,8 +857,7 @@ impl EphemeralAccountContract {
             return Err(Error::NotInitialized);
         }
 
-        let payments = storage::get_all_payments(&env);
-        let payment_count = payments.len();
+        let payment_count = storage::get_total_payments(&env);
 
         Ok(AccountInfo {
             creator: storage::get_creator(&env),
@@ -425,109 +866,381 @@ impl EphemeralAccountContract {
             recovery_address: storage::get_recovery_address(&env),
             payment_received: payment_count > 0,
             payment_count,
-            payments: {
-                let mut payments_vec = Vec::new(&env);
-                for payment in payments.values() {
-                    payments_vec.push_back(payment);
-                }
-                payments_vec
-            },
             swept_to: storage::get_swept_to(&env),
+            attestation: storage::get_attestation(&env),
+            config_locked: storage::is_config_locked(&env),
+            preferred_settlement_asset: storage::get_preferred_settlement_asset(&env),
+            expectations: storage::get_expectation_progress(&env),
+            max_assets: Self::max_assets(&env),
+            paused: storage::is_paused(&env),
+            metadata: storage::get_metadata(&env),
+            vesting: storage::has_vesting_schedule(&env),
         })
     }
 
-    /// Recover funds for an expired account.
-    /// Only callable by the original creator or recovery_address after expiry.
-    ///
-    /// # Errors
-    /// Returns Error::NotExpired if the account has not expired yet
-    /// Returns Error::Unauthorized if caller is neither creator nor recovery_address
-    /// Returns Error::InvalidStatus if already swept or recovered
-    pub fn recover(env: Env, caller: Address) -> Result<(), Error> {
+    /// Up to `limit` recorded payments starting at `offset`, in canonical
+    /// asset order. `limit` is capped at `MAX_PAYMENTS_PAGE`.
+    pub fn get_payments_page(env: Env, offset: u32, limit: u32) -> PaymentsPage {
         storage::extend_instance_ttl(&env);
 
         if !storage::is_initialized(&env) {
-            return Err(Error::NotInitialized);
+            return PaymentsPage {
+                payments: Vec::new(&env),
+                settled: Vec::new(&env),
+                total: 0,
+            };
         }
 
-        let status = storage::get_status(&env);
-        if status == AccountStatus::Swept || status == AccountStatus::Expired {
-            return Err(Error::InvalidStatus);
-        }
+        storage::get_payments_page(&env, offset, limit.min(MAX_PAYMENTS_PAGE))
+    }
 
-        if !Self::is_expired(env.clone()) {
-            return Err(Error::NotExpired);
+    /// Assets that have a recorded payment on this account.
+    pub fn get_payment_assets(env: Env) -> Vec<Address> {
+        storage::extend_instance_ttl(&env);
+
+        if !storage::is_initialized(&env) {
+            return Vec::new(&env);
         }
 
-        let creator = storage::get_creator(&env);
-        let recovery_address = storage::get_recovery_address(&env);
+        storage::get_payment_assets(&env)
+    }
+
+    /// Recorded payment for `asset`, if any.
+    pub fn get_payment(env: Env, asset: Address) -> Option<Payment> {
+        storage::extend_instance_ttl(&env);
+
+        if !storage::is_initialized(&env) {
+            return None;
+        }
+
+        storage::get_payment(&env, &asset)
+    }
+
+    /// Recover funds for an expired account.
+    /// Only callable by the original creator or recovery_address after expiry.
+    pub fn recover(env: Env, caller: Address) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        if !Self::is_expired(env.clone()) {
+            return Err(Error::NotExpired);
+        }
+
+        let creator = storage::get_creator(&env);
+        let recovery_address = storage::get_recovery_address(&env);
 
         if caller != creator && caller != recovery_address {
             return Err(Error::Unauthorized);
         }
         caller.require_auth();
+        Self::ensure_no_pending_sweep(&env)?;
+
+        if Self::report_lost_to_sweep(&env) {
+            return Ok(());
+        }
+
+        if matches!(
+            storage::get_status(&env),
+            AccountStatus::Expired | AccountStatus::Cancelled
+        ) {
+            return Err(Error::InvalidStatus);
+        }
 
         // Same fund-routing state transition as expire(); the only difference
         // between the two entry points is recover()'s narrower access check.
         Self::finalize_expiry(&env)
     }
 
-    /// Upgrade the contract WASM. Restricted to the admin set at deploy time.
-    ///
-    /// # Arguments
-    /// * `new_wasm_hash` - Hash of the new WASM blob already uploaded to the ledger
-    ///
-    /// # Errors
-    /// Returns Error::NotUpgradeAdmin if caller is not the stored admin
+    /// Upgrade the contract WASM to the uploaded `new_wasm_hash`.
+    /// Restricted to the creator.
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
+        storage::extend_ttls(&env);
 
         if !storage::is_initialized(&env) {
             return Err(Error::NotInitialized);
         }
 
-        let admin = storage::get_admin(&env).ok_or(Error::NotUpgradeAdmin)?;
-        admin.require_auth();
+        storage::get_creator(&env).require_auth();
+        Self::ensure_no_pending_sweep(&env)?;
+        if storage::get_schema_version(&env) < SCHEMA_VERSION {
+            return Err(Error::MigrationRequired);
+        }
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
 
-    /// Dry-run sweep simulation: returns the payments that would be swept and
-    /// any error that would prevent a real sweep, without executing on-chain.
-    ///
-    /// Returns `(payments, error_code)` where `error_code` is 0 on success.
-    pub fn simulate_sweep(env: Env, destination: Address) -> (Vec<Payment>, u32) {
+    /// Version of the build that initialized or last migrated the account.
+    pub fn get_version(env: Env) -> ContractVersion {
         storage::extend_instance_ttl(&env);
+        storage::get_version(&env).unwrap_or(BUILD_VERSION)
+    }
 
-        if !storage::is_initialized(&env) {
-            return (Vec::new(&env), Error::NotInitialized as u32);
-        }
+    /// Storage schema version the account is at. See `migrate`.
+    pub fn get_schema_version(env: Env) -> u32 {
+        storage::extend_instance_ttl(&env);
+        storage::get_schema_version(&env)
+    }
+
+    // Private helper functions
+
+    /// Shared body of `sweep` and `sweep_and_report`. Returns the swept
+    /// payments.
+    fn signed_sweep(env: &Env, destination: &Address) -> Result<Vec<Payment>, Error> {
+        storage::extend_ttls(env);
+
+        Self::check_sweepable(env, destination)?;
+        Self::authorize_sweep(env)?;
+
+        // Update status before transfer to prevent reentrancy
+        storage::set_status(env, AccountStatus::Swept);
+        Self::settle_sweep(env, destination, true)
+    }
 
-        if storage::get_status(&env) == AccountStatus::Swept {
-            return (Vec::new(&env), Error::AlreadySwept as u32);
+    /// Sum of `amounts`, or `Error::Overflow` instead of wrapping.
+    fn checked_total(mut amounts: impl Iterator<Item = i128>) -> Result<i128, Error> {
+        amounts.try_fold(0i128, |total, amount| {
+            total.checked_add(amount).ok_or(Error::Overflow)
+        })
+    }
+
+    /// Emit `SweepExecutedMulti` for `payments` swept to `destination`,
+    /// with gross, settled, fee and net totals.
+    fn emit_sweep_executed(
+        env: &Env,
+        destination: &Address,
+        payments: &Vec<Payment>,
+        total_fee: i128,
+    ) -> Result<(), Error> {
+        let total_amount = Self::checked_total(payments.iter().map(|p| p.amount))?;
+        let total_settled =
+            Self::checked_total(storage::get_settled_ordered(env).iter().map(|s| s.amount))?;
+        let total_net = total_amount.checked_sub(total_fee).ok_or(Error::Overflow)?;
+        events::emit_sweep_executed_multi(
+            env,
+            destination.clone(),
+            payments,
+            total_amount,
+            total_settled,
+            total_fee,
+            total_net,
+        );
+        Ok(())
+    }
+
+    /// Reject a sweep of an account that is not initialized, already
+    /// settled or being swept, not paid yet, or expired, a sweep to a
+    /// destination outside the account's allowlist, or, with
+    /// `verify_balances_on_sweep`, a sweep of an account holding less of an
+    /// asset than recorded.
+    fn check_sweepable(env: &Env, destination: &Address) -> Result<(), Error> {
+        match Self::sweep_readiness(env) {
+            SweepReadiness::Ready => {}
+            SweepReadiness::NotInitialized => return Err(Error::NotInitialized),
+            SweepReadiness::AlreadySwept => return Err(Error::AlreadySwept),
+            SweepReadiness::SweepPending => return Err(Error::SweepPending),
+            SweepReadiness::Paused => return Err(Error::Paused),
+            SweepReadiness::NoPayments => return Err(Error::NoPaymentReceived),
+            SweepReadiness::Expired => return Err(Error::AccountExpired),
         }
 
-        if !storage::has_payment_received(&env) {
-            return (Vec::new(&env), Error::NoPaymentReceived as u32);
+        if !storage::is_destination_allowed(env, destination) {
+            return Err(Error::DestinationNotAllowed);
+        }
+        if storage::verifies_balances_on_sweep(env)
+            && Self::balance_discrepancies(env)
+                .iter()
+                .any(|discrepancy| discrepancy.balance < discrepancy.recorded)
+        {
+            return Err(Error::BalanceShortfall);
         }
+        Ok(())
+    }
 
+    /// First obstacle to sweeping the account, whatever the destination.
+    fn sweep_readiness(env: &Env) -> SweepReadiness {
+        if !storage::is_initialized(env) {
+            return SweepReadiness::NotInitialized;
+        }
+        if storage::is_swept(env) {
+            return SweepReadiness::AlreadySwept;
+        }
+        if storage::get_status(env) == AccountStatus::PendingSweep {
+            return SweepReadiness::SweepPending;
+        }
+        if storage::is_paused(env) {
+            return SweepReadiness::Paused;
+        }
+        if Self::awaits_payment(env) {
+            return SweepReadiness::NoPayments;
+        }
         if Self::is_expired(env.clone()) {
-            return (Vec::new(&env), Error::AccountExpired as u32);
+            return SweepReadiness::Expired;
         }
+        SweepReadiness::Ready
+    }
+
+    /// Record the sweep of every payment to `destination`, emit
+    /// `SweepExecutedMulti`, reclaim the reserve, transfer the payments
+    /// unless the controller already delivered them (`transfer` is false
+    /// for a committed two-phase sweep) and call the sweep hook. The caller
+    /// has already moved the account to `Swept`. Returns the payments net
+    /// of protocol fees.
+    fn settle_sweep(
+        env: &Env,
+        destination: &Address,
+        transfer: bool,
+    ) -> Result<Vec<Payment>, Error> {
+        let payments_vec = storage::get_payments_ordered(env);
+
+        storage::set_swept_to(env, destination);
+        storage::increment_sweep_nonce(env);
+        storage::set_settled_ledger(env, env.ledger().sequence());
+        // The controller delivers everything unless it reports otherwise
+        // through `record_undelivered`.
+        storage::set_settled_amounts(env, |payment| payment.amount);
+
+        // State is recorded before the funds move, to prevent reentrancy; a
+        // failed transfer reverts the whole sweep.
+        let sweep_id = env.ledger().sequence() as u64;
+        storage::set_last_sweep_id(env, sweep_id);
 
-        let _ = destination; // destination accepted for future fee simulation
+        let total_fee = Self::collect_protocol_fees(env, &payments_vec)?;
 
-        let payments = storage::get_all_payments(&env);
-        let mut payments_vec = Vec::new(&env);
-        for payment in payments.values() {
-            payments_vec.push_back(payment);
+        // Emit sweep event once transfer authorization/state update succeeds.
+        Self::emit_sweep_executed(env, destination, &payments_vec, total_fee)?;
+
+        // Return reserve provisioned for unused trustlines to the creator,
+        // then reclaim the rest only after successful sweep state transition.
+        Self::refund_reserve_overcollection(env)?;
+        Self::reclaim_swept_reserve(env, destination, sweep_id)?;
+
+        let delivered = Self::net_of_fees(env, payments_vec)?;
+        if transfer {
+            Self::transfer_out(env, destination, &delivered)?;
         }
+        Self::start_vesting(env, destination, &delivered);
+        Self::call_sweep_hook(env, destination, &delivered);
+        Ok(delivered)
+    }
 
-        (payments_vec, 0)
+    /// Transfer each of `payments` from the account to `destination`. A
+    /// vesting account keeps them and releases them through
+    /// `claim_vested` instead.
+    ///
+    /// # Errors
+    /// Returns Error::TransferFailed if a token transfer fails
+    fn transfer_out(
+        env: &Env,
+        destination: &Address,
+        payments: &Vec<Payment>,
+    ) -> Result<(), Error> {
+        if storage::get_vesting_schedule(env).is_some() {
+            return Ok(());
+        }
+        for payment in payments.iter() {
+            Self::transfer(env, &payment.asset, destination, payment.amount)?;
+        }
+        Ok(())
     }
 
-    // Private helper functions
+    /// Transfer `amount` of `asset` from the account through
+    /// `try_transfer`, so a token contract that errors or panics yields
+    /// `Error::TransferFailed` instead of a host error.
+    fn transfer(env: &Env, asset: &Address, to: &Address, amount: i128) -> Result<(), Error> {
+        match TokenClient::new(env, asset).try_transfer(
+            &env.current_contract_address(),
+            to,
+            &amount,
+        ) {
+            Ok(Ok(())) => Ok(()),
+            _ => Err(Error::TransferFailed),
+        }
+    }
+
+    /// Best-effort `on_sweep(account, destination, assets)` call to the
+    /// sweep hook, if one is registered. Runs last, once the settlement is
+    /// recorded; a failing hook is reported by `SweepHookCalled` and does
+    /// not revert it.
+    fn call_sweep_hook(env: &Env, destination: &Address, payments: &Vec<Payment>) {
+        let Some(hook) = storage::get_sweep_hook(env) else {
+            return;
+        };
+        let mut assets = Vec::new(env);
+        for payment in payments.iter() {
+            assets.push_back(AssetAmount {
+                asset: payment.asset,
+                amount: payment.amount,
+            });
+        }
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &hook,
+            &Symbol::new(env, "on_sweep"),
+            (env.current_contract_address(), destination.clone(), assets).into_val(env),
+        );
+        events::emit_sweep_hook_called(env, hook, matches!(result, Ok(Ok(()))));
+    }
+
+    /// Controller-gated move between sweep statuses.
+    fn transition_sweep_status(
+        env: &Env,
+        from: AccountStatus,
+        to: AccountStatus,
+    ) -> Result<(), Error> {
+        storage::extend_ttls(env);
+
+        if !storage::is_initialized(env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let controller = storage::get_authorized_controller(env).ok_or(Error::Unauthorized)?;
+        controller.require_auth();
+
+        if storage::get_status(env) != from {
+            return Err(Error::InvalidStatus);
+        }
+
+        storage::set_status(env, to);
+        Ok(())
+    }
+
+    /// Payments `sweep_assets` has not swept yet, while it is sweeping the
+    /// account asset by asset. `None` once every asset is swept, or when the
+    /// account was not partially swept through `sweep_assets`.
+    fn unswept_payments(env: &Env) -> Option<Vec<Payment>> {
+        let payments = storage::get_payments_ordered(env);
+        let mut unswept = Vec::new(env);
+        for payment in payments.iter() {
+            if !storage::is_asset_swept(env, &payment.asset) {
+                unswept.push_back(payment);
+            }
+        }
+        if unswept.is_empty() || unswept.len() == payments.len() {
+            return None;
+        }
+        Some(unswept)
+    }
+
+    /// Emit the arbitration event for an expiry that arrived after a sweep
+    /// already settled the account. Returns `true` if the race was lost.
+    ///
+    /// An account `sweep_assets` left partially swept has not been settled:
+    /// expiry still returns its unswept assets.
+    fn report_lost_to_sweep(env: &Env) -> bool {
+        if !storage::is_swept(env) || Self::unswept_payments(env).is_some() {
+            return false;
+        }
+
+        events::emit_arbitration_result(
+            env,
+            SettlementPath::Sweep,
+            AccountErrorCode::AlreadySwept as u32,
+        );
+        true
+    }
 
     /// Shared fund-routing state transition used by both `expire` and
     /// `recover`. Marks the account `Expired`, routes funds to the recovery
@@ -540,40 +1253,156 @@ impl EphemeralAccountContract {
 
         storage::set_status(env, AccountStatus::Expired);
         storage::set_swept_to(env, &recovery_address);
-
-        let total_amount = if storage::has_payment_received(env) {
-            let payments = storage::get_all_payments(env);
-            let mut total = 0i128;
-            for (_, payment) in payments.iter() {
-                total = total
-                    .checked_add(payment.amount)
-                    .ok_or(Error::InvalidAmount)?;
+        storage::set_settled_ledger(env, env.ledger().sequence());
+
+        // Assets `sweep_assets` already delivered are not returned.
+        let mut returned = Vec::new(env);
+        if storage::has_payment_received(env) {
+            for payment in storage::get_payments_ordered(env).iter() {
+                if !storage::is_asset_swept(env, &payment.asset) {
+                    returned.push_back(payment);
+                }
             }
-            total
-        } else {
-            0
-        };
+        }
+        let total_amount = Self::checked_total(returned.iter().map(|payment| payment.amount))?;
+
+        // Every payment goes back to the recovery address in full.
+        storage::set_settled_amounts(env, |payment| payment.amount);
 
         let sweep_id = env.ledger().sequence() as u64;
         storage::set_last_sweep_id(env, sweep_id);
 
         let reclaimed_reserve = Self::reclaim_reserve_to(env, &recovery_address, sweep_id)?;
-        events::emit_account_expired(env, recovery_address, total_amount, reclaimed_reserve);
 
+        // State is final before the transfers, to prevent reentrancy.
+        for payment in returned.iter() {
+            TokenClient::new(env, &payment.asset).transfer(
+                &env.current_contract_address(),
+                &recovery_address,
+                &payment.amount,
+            );
+        }
+        events::emit_account_expired(
+            env,
+            recovery_address.clone(),
+            total_amount,
+            total_amount,
+            reclaimed_reserve,
+            storage::get_lifecycle(env),
+        );
+
+        Self::call_sweep_hook(env, &recovery_address, &returned);
         Ok(())
     }
 
-    fn verify_sweep_authorization(
-        env: &Env,
-        _destination: &Address,
-        _signature: &BytesN<64>,
-    ) -> Result<(), Error> {
+    /// Require the controller's authorization for a single-signature sweep.
+    /// The off-chain signer's signature is verified by the controller, not
+    /// here.
+    fn authorize_sweep(env: &Env) -> Result<(), Error> {
+        // A single signature never suffices once a signer set is registered
+        if storage::get_sweep_signers(env).is_some() {
+            return Err(Error::InsufficientSignatures);
+        }
+        Self::require_controller(env)
+    }
+
+    /// Require the authorized controller's authorization, and the
+    /// creator's under `require_creator_cosign`.
+    fn require_controller(env: &Env) -> Result<(), Error> {
         let controller = storage::get_authorized_controller(env).ok_or(Error::Unauthorized)?;
         controller.require_auth();
+        Self::require_creator_cosign(env);
         Ok(())
     }
 
-    fn reclaim_reserve_to(env: &Env, destination: &Address, sweep_id: u64) -> Result<i128, Error> {
+    /// Require the creator's authorization if the account was initialized
+    /// with `require_creator_cosign`.
+    fn require_creator_cosign(env: &Env) {
+        if storage::requires_creator_cosign(env) {
+            storage::get_creator(env).require_auth();
+        }
+    }
+
+    /// Refund the reserve that was provisioned for unused trustlines to the
+    /// creator, who over-funded it. Only the share backing the account entry
+    /// and the trustlines actually used stays with the reserve recipient.
+    ///
+    /// Bounded by the reserve currently available; returns the refunded amount.
+    fn refund_reserve_overcollection(env: &Env) -> Result<i128, Error> {
+        let refund = Self::reserve_overcollection(env)?;
+        if refund == 0 {
+            return Ok(0);
+        }
+
+        let trustlines = storage::get_total_payments(env);
+        let reserve_remaining = storage::get_base_reserve_remaining(env);
+        let reserve_available = storage::get_available_reserve(env);
+        storage::set_available_reserve(
+            env,
+            reserve_available
+                .checked_sub(refund)
+                .ok_or(Error::Overflow)?,
+        );
+        storage::set_base_reserve_remaining(
+            env,
+            reserve_remaining
+                .checked_sub(refund)
+                .ok_or(Error::Overflow)?,
+        );
+        let refunded = storage::get_reserve_refunded(env)
+            .checked_add(refund)
+            .ok_or(Error::Overflow)?;
+        storage::set_reserve_refunded(env, refunded);
+        Self::repay_reserve_deposit(env, refund)?;
+
+        events::emit_reserve_refunded(env, storage::get_creator(env), refund, trustlines);
+
+        Ok(refund)
+    }
+
+    /// Reserve provisioned for trustlines the account never used, which a
+    /// sweep returns to the creator.
+    fn reserve_overcollection(env: &Env) -> Result<i128, Error> {
+        let trustlines = storage::get_total_payments(env);
+        let reclaimable = TRUSTLINE_RESERVE_STROOPS
+            .checked_mul(trustlines as i128)
+            .and_then(|t| t.checked_add(ACCOUNT_RESERVE_STROOPS))
+            .ok_or(Error::Overflow)?;
+
+        let reserve_remaining = storage::get_base_reserve_remaining(env);
+        let reserve_available = storage::get_available_reserve(env);
+        if reserve_remaining <= reclaimable || reserve_available <= 0 {
+            return Ok(0);
+        }
+
+        let excess = reserve_remaining
+            .checked_sub(reclaimable)
+            .ok_or(Error::Overflow)?;
+        Ok(if reserve_available < excess {
+            reserve_available
+        } else {
+            excess
+        })
+    }
+
+    /// Reclaim the reserve at the end of a sweep, unless a dispute window
+    /// keeps it locked; `reclaim_reserve` releases it afterwards.
+    fn reclaim_swept_reserve(
+        env: &Env,
+        destination: &Address,
+        sweep_id: u64,
+    ) -> Result<i128, Error> {
+        if storage::get_dispute_window(env).is_some() {
+            return Ok(0);
+        }
+        Self::reclaim_reserve_to(env, destination, sweep_id)
+    }
+
+    pub(crate) fn reclaim_reserve_to(
+        env: &Env,
+        destination: &Address,
+        sweep_id: u64,
+    ) -> Result<i128, Error> {
         let reserve_remaining = storage::get_base_reserve_remaining(env);
         let reserve_available = storage::get_available_reserve(env);
 
@@ -602,14 +1431,15 @@ impl EphemeralAccountContract {
 
         let new_available = reserve_available
             .checked_sub(reclaim_amount)
-            .ok_or(Error::InvalidAmount)?;
+            .ok_or(Error::Overflow)?;
         let new_remaining = reserve_remaining
             .checked_sub(reclaim_amount)
-            .ok_or(Error::InvalidAmount)?;
+            .ok_or(Error::Overflow)?;
 
         storage::set_available_reserve(env, new_available);
         storage::set_base_reserve_remaining(env, new_remaining);
         storage::set_reserve_reclaimed(env, new_remaining == 0);
+        Self::repay_reserve_deposit(env, reclaim_amount)?;
 
         let event = ReserveReclaimed {
             destination: destination.clone(),
@@ -623,6 +1453,25 @@ impl EphemeralAccountContract {
         Ok(reclaim_amount)
     }
 
+    /// Pay back to the creator up to `released` stroops of the XLM
+    /// `top_up_reserve` deposited, as the reserve it funded is released.
+    fn repay_reserve_deposit(env: &Env, released: i128) -> Result<(), Error> {
+        let deposit = storage::get_reserve_deposit(env);
+        let repaid = deposit.min(released);
+        if repaid <= 0 {
+            return Ok(());
+        }
+
+        // Update state before the transfer to prevent reentrancy
+        storage::set_reserve_deposit(env, deposit.checked_sub(repaid).ok_or(Error::Overflow)?);
+        TokenClient::new(env, &bridgelet_shared::native_asset_address(env)).transfer(
+            &env.current_contract_address(),
+            &storage::get_creator(env),
+            &repaid,
+        );
+        Ok(())
+    }
+
     fn emit_and_store_reserve_event(env: &Env, event: ReserveReclaimed) -> Result<(), Error> {
         events::emit_reserve_reclaimed(
             env,
@@ -634,7 +1483,7 @@ impl EphemeralAccountContract {
         );
 
         let event_count = storage::get_reserve_event_count(env);
-        let next_count = event_count.checked_add(1).ok_or(Error::InvalidAmount)?;
+        let next_count = event_count.checked_add(1).ok_or(Error::Overflow)?;
         storage::set_last_reserve_event(env, &event);
         storage::set_reserve_event_count(env, next_count);
 
@@ -642,11 +1491,164 @@ impl EphemeralAccountContract {
     }
 }
 
+/// Legacy views kept for integrators that have not yet migrated to
+/// `get_payments_page` and the per-asset getters. Only compiled with the
+/// `compat-get-payments` feature.
+#[cfg(feature = "compat-get-payments")]
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Deprecated: every recorded payment in one map. Use [`Self::get_payments_page`].
+    pub fn get_payments(env: Env) -> soroban_sdk::Map<Address, Payment> {
+        storage::extend_instance_ttl(&env);
+
+        let mut payments = soroban_sdk::Map::new(&env);
+        if !storage::is_initialized(&env) {
+            return payments;
+        }
+
+        for payment in storage::get_payments_ordered(&env).iter() {
+            payments.set(payment.asset.clone(), payment);
+        }
+        payments
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Checks `record_payment` makes before looking at the amount. Returns
+    /// the number of payments already recorded.
+    fn check_recordable(env: &Env, asset: &Address) -> Result<u32, Error> {
+        // Check initialized
+        if !storage::is_initialized(env) {
+            return Err(Error::NotInitialized);
+        }
+
+        // Settled accounts can no longer take payments
+        match storage::get_status(env) {
+            AccountStatus::Swept | AccountStatus::PartiallySwept => {
+                return Err(Error::AlreadySwept)
+            }
+            AccountStatus::Expired => return Err(Error::AccountExpired),
+            AccountStatus::Cancelled => return Err(Error::InvalidStatus),
+            AccountStatus::PendingSweep => return Err(Error::SweepPending),
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+        }
+
+        // Past expiry the account can only be expired; a payment recorded
+        // now could never be swept.
+        if Self::is_expired(env.clone()) {
+            return Err(Error::AccountExpired);
+        }
+
+        if storage::is_paused(env) {
+            return Err(Error::Paused);
+        }
+
+        if !storage::is_asset_allowed(env, asset) {
+            return Err(Error::AssetNotAllowed);
+        }
+
+        // Check for duplicate asset, unless payments accumulate
+        let payment_count = storage::get_total_payments(env);
+        if storage::get_payment(env, asset).is_some() {
+            if !storage::accumulates_payments(env) {
+                return Err(Error::DuplicateAsset);
+            }
+            return Ok(payment_count);
+        }
+
+        // Check payment limit to prevent gas issues (at most 10 assets).
+        // Past `max_assets`, a reserve topped up for another trustline
+        // still admits one more asset.
+        if payment_count >= Self::max_assets(env)
+            && (payment_count >= MAX_PAYMENT_ASSETS
+                || storage::get_base_reserve_remaining(env) < Self::base_reserve(payment_count + 1))
+        {
+            return Err(Error::TooManyPayments);
+        }
+        Ok(payment_count)
+    }
+
+    /// Most distinct assets the account accepts.
+    fn max_assets(env: &Env) -> u32 {
+        storage::get_max_assets(env).unwrap_or(MAX_PAYMENT_ASSETS)
+    }
+
+    /// Base reserve provisioned for an account with `max_assets` asset
+    /// slots: the account entry plus one trustline per slot.
+    fn base_reserve(max_assets: u32) -> i128 {
+        ACCOUNT_RESERVE_STROOPS + TRUSTLINE_RESERVE_STROOPS * max_assets as i128
+    }
+
+    /// Whether every expected payment has reached its amount, less the
+    /// tolerance. Always `true` for an account that expects no payments.
+    fn invoice_fulfilled(env: &Env) -> bool {
+        storage::get_expectation_progress(env)
+            .iter()
+            .zip(storage::get_expected_payments(env).iter())
+            .all(|(progress, expected)| progress.received >= expected.amount - expected.tolerance)
+    }
+
+    /// Move an `Active` account with recorded payments to
+    /// `PaymentReceived`, making it sweepable, once its invoice (if any) is
+    /// fulfilled.
+    fn receive_if_fulfilled(env: &Env) {
+        if storage::get_status(env) == AccountStatus::Active
+            && storage::get_total_payments(env) > 0
+            && Self::invoice_fulfilled(env)
+        {
+            storage::set_status(env, AccountStatus::PaymentReceived);
+        }
+    }
+
+    /// Whether the account has nothing to sweep yet: no payment recorded,
+    /// or an invoice not fulfilled.
+    fn awaits_payment(env: &Env) -> bool {
+        !storage::has_payment_received(env) || storage::get_status(env) == AccountStatus::Active
+    }
+
+    /// Reject setting changes once `lock_config` froze the account.
+    fn ensure_config_unlocked(env: &Env) -> Result<(), Error> {
+        if storage::is_config_locked(env) {
+            return Err(Error::ConfigLocked);
+        }
+        Ok(())
+    }
+
+    /// Claim the account for a prepared sweep to `destination` for
+    /// `sweep_contention_ledgers`, so two operators racing to sweep it to
+    /// different destinations cannot keep aborting each other's sweeps.
+    ///
+    /// # Errors
+    /// Returns Error::SweepContention if a claim for another destination
+    /// has not run out yet
+    fn claim_sweep(env: &Env, destination: &Address) -> Result<(), Error> {
+        let ledgers = storage::get_sweep_contention_ledgers(env);
+        if ledgers == 0 {
+            return Ok(());
+        }
+        let current_ledger = env.ledger().sequence();
+        if let Some((claimed_for, until_ledger)) = storage::get_sweep_claim(env) {
+            if claimed_for != *destination && current_ledger <= until_ledger {
+                return Err(Error::SweepContention);
+            }
+        }
+        storage::set_sweep_claim(env, destination, current_ledger.saturating_add(ledgers));
+        Ok(())
+    }
+
+    /// Refuse anything that could move the funds of a sweep `prepare_sweep`
+    /// staged until the controller commits or aborts it.
+    fn ensure_no_pending_sweep(env: &Env) -> Result<(), Error> {
+        if storage::get_status(env) == AccountStatus::PendingSweep {
+            return Err(Error::SweepPending);
+        }
+        Ok(())
+    }
+}
+
 /// Issue #43: conform to the shared interface for type-safe SDK integration.
 /// Each method delegates to the inherent contract implementation above.
 impl EphemeralAccountInterface for EphemeralAccountContract {
-    type Error = Error;
-
     fn initialize(
         env: Env,
         creator: Address,
@@ -654,30 +1656,141 @@ impl EphemeralAccountInterface for EphemeralAccountContract {
         recovery_address: Address,
         authorized_controller: Address,
         admin: Address,
-    ) -> Result<(), Error> {
-        Self::initialize(
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::initialize(
             env,
             creator,
             expiry_ledger,
             recovery_address,
             authorized_controller,
             admin,
-        )
+        )?)
+    }
+
+    fn initialize_with_options(
+        env: Env,
+        creator: Address,
+        expiry_ledger: u32,
+        recovery_address: Address,
+        authorized_controller: Address,
+        admin: Address,
+        options: InitOptions,
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::initialize_with_options(
+            env,
+            creator,
+            expiry_ledger,
+            recovery_address,
+            authorized_controller,
+            admin,
+            options,
+        )?)
     }
 
-    fn record_payment(env: Env, amount: i128, asset: Address) -> Result<(), Error> {
-        Self::record_payment(env, amount, asset)
+    fn record_payment(env: Env, amount: i128, asset: Address) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::record_payment(env, amount, asset)?)
     }
 
-    fn sweep(env: Env, destination: Address, auth_signature: BytesN<64>) -> Result<(), Error> {
-        Self::sweep(env, destination, auth_signature)
+    fn sweep(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::sweep(env, destination, auth_signature)?)
     }
 
-    fn sweep_claim(env: Env, destination: Address) -> Result<(), Error> {
-        Self::sweep_claim(env, destination)
+    fn sweep_and_report(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, soroban_sdk::Error> {
+        Ok(Self::sweep_and_report(env, destination, auth_signature)?)
+    }
+
+    fn prepare_sweep(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, soroban_sdk::Error> {
+        Ok(Self::prepare_sweep(env, destination, auth_signature)?)
+    }
+
+    fn prepare_multisig_sweep(
+        env: Env,
+        destination: Address,
+        destination_memo: Option<BytesN<32>>,
+        signatures: Vec<(BytesN<32>, BytesN<64>)>,
+    ) -> Result<Vec<Payment>, soroban_sdk::Error> {
+        Ok(Self::prepare_multisig_sweep(
+            env,
+            destination,
+            destination_memo,
+            signatures,
+        )?)
+    }
+
+    fn commit_sweep(env: Env) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::commit_sweep(env)?)
+    }
+
+    fn abort_sweep(env: Env) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::abort_sweep(env)?)
+    }
+
+    fn deliver(
+        env: Env,
+        asset: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::deliver(env, asset, recipient, amount)?)
+    }
+
+    fn get_sweep_nonce(env: Env) -> u64 {
+        Self::get_sweep_nonce(env)
+    }
+
+    fn interface_version(env: Env) -> u32 {
+        Self::interface_version(env)
+    }
+
+    fn get_version(env: Env) -> ContractVersion {
+        Self::get_version(env)
+    }
+
+    fn sweep_claim(env: Env, destination: Address) -> Result<Vec<Payment>, soroban_sdk::Error> {
+        Ok(Self::sweep_claim(env, destination)?)
     }
 
     fn is_expired(env: Env) -> bool {
         Self::is_expired(env)
     }
+
+    fn can_sweep_detailed(env: Env) -> SweepReadiness {
+        Self::can_sweep_detailed(env)
+    }
+
+    fn get_status(env: Env) -> AccountStatus {
+        Self::get_status(env)
+    }
+
+    fn get_info(env: Env) -> Result<AccountInfo, soroban_sdk::Error> {
+        Ok(Self::get_info(env)?)
+    }
+
+    fn get_payments_page(env: Env, offset: u32, limit: u32) -> PaymentsPage {
+        Self::get_payments_page(env, offset, limit)
+    }
+
+    fn get_lifecycle(env: Env) -> Result<AccountLifecycle, soroban_sdk::Error> {
+        Ok(Self::get_lifecycle(env)?)
+    }
+
+    fn mark_partially_swept(env: Env) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::mark_partially_swept(env)?)
+    }
+
+    fn complete_partial_sweep(env: Env) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::complete_partial_sweep(env)?)
+    }
 }
@@ -0,0 +1,50 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, Bytes, Env, Symbol};
+
+/// Most metadata entries an account may carry.
+const MAX_METADATA_ENTRIES: u32 = 10;
+
+/// Longest metadata value, in bytes.
+const MAX_METADATA_VALUE_LEN: u32 = 64;
+
+/// Labels the creator attaches to the account.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Attach a label to the account under `key`; an empty `value` removes it. Creator only.
+    pub fn set_metadata(env: Env, key: Symbol, value: Bytes) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+
+        let mut metadata = storage::get_metadata(&env);
+        if value.is_empty() {
+            metadata.remove(key.clone());
+        } else {
+            if value.len() > MAX_METADATA_VALUE_LEN
+                || (!metadata.contains_key(key.clone()) && metadata.len() >= MAX_METADATA_ENTRIES)
+            {
+                return Err(Error::InvalidMetadata);
+            }
+            metadata.set(key.clone(), value.clone());
+        }
+
+        storage::set_metadata(&env, &metadata);
+        events::emit_metadata_updated(&env, key, value);
+        Ok(())
+    }
+
+    /// Value the creator attached under `key`, if any.
+    pub fn get_metadata(env: Env, key: Symbol) -> Option<Bytes> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_metadata(&env).get(key)
+    }
+}
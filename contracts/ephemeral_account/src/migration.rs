@@ -0,0 +1,41 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+    BUILD_VERSION, SCHEMA_VERSION,
+};
+use soroban_sdk::{contractimpl, Env};
+
+/// Migration of accounts created by older builds.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Bring the account's storage up to this build's schema after an `upgrade`. Creator only.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+
+        storage::set_version(&env, &BUILD_VERSION);
+        let from_version = storage::get_schema_version(&env);
+        if from_version >= SCHEMA_VERSION {
+            return Ok(from_version);
+        }
+        if from_version < 2 {
+            storage::migrate_legacy_payments(&env);
+        }
+        if from_version < 3 {
+            storage::migrate_asset_entries(&env);
+        }
+        if from_version < 4 {
+            storage::migrate_account_core(&env);
+        }
+
+        storage::set_schema_version(&env, SCHEMA_VERSION);
+        events::emit_account_migrated(&env, from_version, SCHEMA_VERSION);
+        Ok(SCHEMA_VERSION)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountStatus, Payment};
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Vec};
+
+/// Sweeps authorized by a signer set (`sweep_signers`, `sweep_threshold`)
+/// instead of a single signature.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Same as [`prepare_sweep`], authorized by the account's signer set.
+    pub fn prepare_multisig_sweep(
+        env: Env,
+        destination: Address,
+        destination_memo: Option<BytesN<32>>,
+        signatures: Vec<(BytesN<32>, BytesN<64>)>,
+    ) -> Result<Vec<Payment>, Error> {
+        storage::extend_ttls(&env);
+
+        Self::check_sweepable(&env, &destination)?;
+        Self::verify_sweep_signatures(&env, &destination, &destination_memo, &signatures)?;
+        Self::claim_sweep(&env, &destination)?;
+
+        storage::set_status(&env, AccountStatus::PendingSweep);
+        storage::set_pending_sweep_destination(&env, &destination);
+        Self::net_of_fees(&env, storage::get_payments_ordered(&env))
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Check `signatures` meet the signer set's threshold over the account
+    /// sweep message, then require the controller as for any sweep.
+    fn verify_sweep_signatures(
+        env: &Env,
+        destination: &Address,
+        destination_memo: &Option<BytesN<32>>,
+        signatures: &Vec<(BytesN<32>, BytesN<64>)>,
+    ) -> Result<(), Error> {
+        let (signers, threshold) = storage::get_sweep_signers(env).ok_or(Error::Unauthorized)?;
+        if signatures.len() < threshold {
+            return Err(Error::InsufficientSignatures);
+        }
+        let controller = storage::get_authorized_controller(env).ok_or(Error::Unauthorized)?;
+
+        let message = bridgelet_shared::bytes::account_sweep_message_digest(
+            env,
+            &env.ledger().network_id(),
+            &env.current_contract_address(),
+            storage::get_sweep_nonce(env),
+            destination,
+            &controller,
+            destination_memo.as_ref(),
+        );
+        for (i, (signer, signature)) in signatures.iter().enumerate() {
+            let repeated = signatures
+                .iter()
+                .take(i)
+                .any(|(earlier, _)| earlier == signer);
+            if !signers.contains(&signer) || repeated {
+                return Err(Error::Unauthorized);
+            }
+            env.crypto()
+                .ed25519_verify(&signer, &message.clone().into(), &signature);
+        }
+
+        Self::require_controller(env)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountStatus, Payment};
+use soroban_sdk::{contractimpl, Env, Vec};
+
+/// Reports by the controller when it could deliver only part of a sweep.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Record that the controller delivered only part of the swept assets.
+    pub fn mark_partially_swept(env: Env) -> Result<(), Error> {
+        Self::transition_sweep_status(&env, AccountStatus::Swept, AccountStatus::PartiallySwept)
+    }
+
+    /// Record that the controller delivered the rest of a partial sweep.
+    pub fn complete_partial_sweep(env: Env) -> Result<(), Error> {
+        Self::transition_sweep_status(&env, AccountStatus::PartiallySwept, AccountStatus::Swept)?;
+        storage::set_settled_amounts(&env, |payment| payment.amount);
+        Ok(())
+    }
+
+    /// Record the part of each payment the destination has not received yet.
+    pub fn record_undelivered(env: Env, undelivered: Vec<Payment>) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let controller = storage::get_authorized_controller(&env).ok_or(Error::Unauthorized)?;
+        controller.require_auth();
+
+        if !storage::is_swept(&env) {
+            return Err(Error::InvalidStatus);
+        }
+        if undelivered.iter().any(|u| u.amount < 0) {
+            return Err(Error::InvalidAmount);
+        }
+
+        storage::set_settled_amounts(&env, |payment| {
+            let owed = undelivered
+                .iter()
+                .find(|u| u.asset == payment.asset)
+                .map_or(0, |u| u.amount);
+            (payment.amount - owed).max(0)
+        });
+        Ok(())
+    }
+}
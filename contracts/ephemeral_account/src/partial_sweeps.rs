@@ -0,0 +1,143 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountStatus, Payment};
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Vec};
+
+/// Sweeps of selected assets (`sweep_assets`), leaving the others for a
+/// later call.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Sweep only `assets`, leaving the other payments for a later call.
+    pub fn sweep_assets(
+        env: Env,
+        destination: Address,
+        assets: Vec<Address>,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        match storage::get_status(&env) {
+            AccountStatus::Active => return Err(Error::NoPaymentReceived),
+            AccountStatus::Expired => return Err(Error::AccountExpired),
+            AccountStatus::Cancelled => return Err(Error::InvalidStatus),
+            AccountStatus::PendingSweep => return Err(Error::SweepPending),
+            AccountStatus::PaymentReceived => {}
+            AccountStatus::PartiallySwept if Self::unswept_payments(&env).is_some() => {}
+            AccountStatus::Swept | AccountStatus::PartiallySwept => {
+                return Err(Error::AlreadySwept)
+            }
+        }
+
+        if Self::is_expired(env.clone()) {
+            return Err(Error::AccountExpired);
+        }
+
+        if storage::is_paused(&env) {
+            return Err(Error::Paused);
+        }
+
+        if !storage::is_destination_allowed(&env, &destination) {
+            return Err(Error::DestinationNotAllowed);
+        }
+
+        if assets.is_empty() {
+            return Err(Error::InvalidAssetSelection);
+        }
+        let mut swept = Vec::new(&env);
+        for (index, asset) in assets.iter().enumerate() {
+            if assets.first_index_of(&asset) != Some(index as u32) {
+                return Err(Error::InvalidAssetSelection);
+            }
+            let payment = storage::get_payment(&env, &asset).ok_or(Error::InvalidAssetSelection)?;
+            if storage::is_asset_swept(&env, &asset) {
+                return Err(Error::AlreadySwept);
+            }
+            if storage::verifies_balances_on_sweep(&env)
+                && Self::balance_of(&env, &asset) < payment.amount
+            {
+                return Err(Error::BalanceShortfall);
+            }
+            swept.push_back(payment);
+        }
+
+        Self::verify_sweep_assets_signature(&env, &destination, &assets, &auth_signature)?;
+
+        for asset in assets.iter() {
+            storage::set_asset_swept(&env, &asset);
+        }
+        storage::set_swept_to(&env, &destination);
+        storage::increment_sweep_nonce(&env);
+
+        let sweep_id = env.ledger().sequence() as u64;
+        storage::set_last_sweep_id(&env, sweep_id);
+
+        let fully_swept = Self::unswept_payments(&env).is_none();
+        if fully_swept {
+            storage::set_status(&env, AccountStatus::Swept);
+            storage::set_settled_ledger(&env, env.ledger().sequence());
+            storage::set_settled_amounts(&env, |payment| payment.amount);
+        } else {
+            storage::set_status(&env, AccountStatus::PartiallySwept);
+            storage::set_settled_amounts(&env, |payment| {
+                if storage::is_asset_swept(&env, &payment.asset) {
+                    payment.amount
+                } else {
+                    0
+                }
+            });
+        }
+
+        let total_fee = Self::collect_protocol_fees(&env, &swept)?;
+        Self::emit_sweep_executed(&env, &destination, &swept, total_fee)?;
+
+        if fully_swept {
+            Self::refund_reserve_overcollection(&env)?;
+            Self::reclaim_swept_reserve(&env, &destination, sweep_id)?;
+        }
+
+        let delivered = Self::net_of_fees(&env, swept)?;
+        Self::transfer_out(&env, &destination, &delivered)?;
+        Self::start_vesting(&env, &destination, &delivered);
+        Self::call_sweep_hook(&env, &destination, &delivered);
+        Ok(delivered)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Check `signature` is the sweep signer's over the per-asset sweep
+    /// message, which binds `assets` in order, then require the controller
+    /// as for any sweep.
+    fn verify_sweep_assets_signature(
+        env: &Env,
+        destination: &Address,
+        assets: &Vec<Address>,
+        signature: &BytesN<64>,
+    ) -> Result<(), Error> {
+        if storage::get_sweep_signers(env).is_some() {
+            return Err(Error::InsufficientSignatures);
+        }
+        let signer = storage::get_sweep_signer(env).ok_or(Error::Unauthorized)?;
+        let controller = storage::get_authorized_controller(env).ok_or(Error::Unauthorized)?;
+
+        let message = bridgelet_shared::bytes::account_sweep_assets_message_digest(
+            env,
+            &env.ledger().network_id(),
+            &env.current_contract_address(),
+            storage::get_sweep_nonce(env),
+            destination,
+            &controller,
+            assets,
+        );
+        env.crypto()
+            .ed25519_verify(&signer, &message.into(), signature);
+
+        Self::require_controller(env)
+    }
+}
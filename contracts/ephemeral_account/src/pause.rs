@@ -0,0 +1,45 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, Env};
+
+/// The creator's emergency brake.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Reject payments and sweeps until `unpause`. Creator only.
+    pub fn pause(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        if storage::is_paused(&env) {
+            return Err(Error::Paused);
+        }
+
+        storage::set_paused(&env, true);
+        events::emit_account_paused(&env);
+        Ok(())
+    }
+
+    /// Lift a `pause`. Restricted to the creator.
+    pub fn unpause(env: Env) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        if !storage::is_paused(&env) {
+            return Err(Error::InvalidStatus);
+        }
+
+        storage::set_paused(&env, false);
+        events::emit_account_unpaused(&env);
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::MAX_BLOCKED_PAYERS;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, Address, Env};
+
+/// Controls over who may pay the account: the payer blocklist
+/// (`blocked_payers`) and the recorder of a `restrict_recording` account.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Refuse further deposits from `payer`. Creator only.
+    pub fn block_payer(env: Env, payer: Address) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+
+        let mut blocked = storage::get_blocked_payers(&env);
+        if blocked.contains(&payer) {
+            return Ok(());
+        }
+        if blocked.len() >= MAX_BLOCKED_PAYERS {
+            return Err(Error::InvalidBlocklist);
+        }
+        blocked.push_back(payer.clone());
+        storage::set_blocked_payers(&env, &blocked);
+        events::emit_payer_blocklist_updated(&env, payer, true);
+        Ok(())
+    }
+
+    /// Accept deposits from `payer` again. Restricted to the creator.
+    pub fn unblock_payer(env: Env, payer: Address) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_config_unlocked(&env)?;
+        storage::get_creator(&env).require_auth();
+
+        let mut blocked = storage::get_blocked_payers(&env);
+        let Some(index) = blocked.first_index_of(&payer) else {
+            return Ok(());
+        };
+        blocked.remove(index);
+        storage::set_blocked_payers(&env, &blocked);
+        events::emit_payer_blocklist_updated(&env, payer, false);
+        Ok(())
+    }
+
+    /// Whether deposits from `payer` are refused.
+    pub fn is_payer_blocked(env: Env, payer: Address) -> bool {
+        storage::extend_instance_ttl(&env);
+
+        storage::is_payer_blocked(&env, &payer)
+    }
+
+    /// Address that must authorize `record_payment*` calls, if the account
+    /// restricts recording.
+    pub fn get_recorder(env: Env) -> Option<Address> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_recorder(&env)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// On a restricted account, require the recorder's authorization unless
+    /// it already authorized the call as `payer`.
+    pub(crate) fn require_recorder(env: &Env, payer: Option<&Address>) {
+        if let Some(recorder) = storage::get_recorder(env) {
+            if payer != Some(&recorder) {
+                recorder.require_auth();
+            }
+        }
+    }
+}
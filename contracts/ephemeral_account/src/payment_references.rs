@@ -0,0 +1,27 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, Address, BytesN, Env};
+
+/// Payments tagged with an off-chain reference.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Same as `record_payment`, tagged with an off-chain reference.
+    pub fn record_payment_with_reference(
+        env: Env,
+        amount: i128,
+        asset: Address,
+        reference: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::record(env, None, amount, asset, Some(reference))
+    }
+
+    /// Reference the payment in `asset` was recorded with, if any.
+    pub fn get_payment_reference(env: Env, asset: Address) -> Option<BytesN<32>> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_payment_reference(&env, &asset)
+    }
+}
@@ -0,0 +1,78 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+    FULL_BPS,
+};
+use bridgelet_shared::Payment;
+use soroban_sdk::{contractimpl, contracttype, token::TokenClient, Address, Env, Vec};
+
+/// Fee an account deducts from each asset it sweeps, as configured by
+/// `InitOptions::protocol_fee_bps` and `protocol_fee_recipient`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProtocolFee {
+    /// Share of each swept amount, in basis points (1 to 9,999)
+    pub bps: u32,
+    pub recipient: Address,
+}
+
+/// Protocol fee deducted from each swept asset and paid to the fee
+/// recipient (`protocol_fee_bps`, `protocol_fee_recipient`).
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Fee the account deducts from each asset it sweeps, if any.
+    pub fn get_protocol_fee(env: Env) -> Option<ProtocolFee> {
+        storage::extend_instance_ttl(&env);
+        storage::get_protocol_fee(&env)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Protocol fee on `amount` under `fee`, rounded down so dust goes to
+    /// the destination.
+    fn protocol_fee_on(fee: &ProtocolFee, amount: i128) -> Result<i128, Error> {
+        amount
+            .checked_mul(fee.bps as i128)
+            .map(|scaled| scaled / FULL_BPS as i128)
+            .ok_or(Error::Overflow)
+    }
+
+    /// `payments` less the protocol fee on each: what the destination
+    /// receives.
+    pub(crate) fn net_of_fees(env: &Env, payments: Vec<Payment>) -> Result<Vec<Payment>, Error> {
+        let Some(fee) = storage::get_protocol_fee(env) else {
+            return Ok(payments);
+        };
+        let mut net = Vec::new(env);
+        for payment in payments.iter() {
+            let amount = payment
+                .amount
+                .checked_sub(Self::protocol_fee_on(&fee, payment.amount)?)
+                .ok_or(Error::Overflow)?;
+            net.push_back(Payment { amount, ..payment });
+        }
+        Ok(net)
+    }
+
+    /// Transfer the protocol fee on each of `payments` to the fee
+    /// recipient. Returns the total fee taken.
+    pub(crate) fn collect_protocol_fees(env: &Env, payments: &Vec<Payment>) -> Result<i128, Error> {
+        let Some(fee) = storage::get_protocol_fee(env) else {
+            return Ok(0);
+        };
+        let mut total: i128 = 0;
+        for payment in payments.iter() {
+            let amount = Self::protocol_fee_on(&fee, payment.amount)?;
+            if amount > 0 {
+                TokenClient::new(env, &payment.asset).transfer(
+                    &env.current_contract_address(),
+                    &fee.recipient,
+                    &amount,
+                );
+                total = total.checked_add(amount).ok_or(Error::Overflow)?;
+            }
+        }
+        Ok(total)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::storage::PaymentHold;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env};
+
+/// Refunds of recorded payments to the payer that funded them: after a
+/// payment hold (`payment_hold_ledgers`), on the creator's request, or as a
+/// clawback within `clawback_window_ledgers`.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Return a payment to its payer after its hold elapsed without a sweep.
+    pub fn refund_stale(env: Env, asset: Address) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_no_pending_sweep(&env)?;
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+
+        let hold = storage::get_payment_hold(&env, &asset).ok_or(Error::NotRefundable)?;
+        if env.ledger().sequence() <= hold.hold_until_ledger {
+            return Err(Error::HoldNotElapsed);
+        }
+        let amount = Self::refund_to_payer(&env, &asset, &hold.payer)?;
+
+        events::emit_stale_payment_refunded(&env, hold.payer, asset, amount);
+        Ok(amount)
+    }
+
+    /// Return the payment in `asset` to its payer before any sweep. Creator only.
+    pub fn refund_payment(env: Env, asset: Address) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        Self::ensure_no_pending_sweep(&env)?;
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+
+        let payer = storage::get_payment(&env, &asset)
+            .and_then(|payment| payment.payer)
+            .ok_or(Error::NotRefundable)?;
+        let amount = Self::refund_to_payer(&env, &asset, &payer)?;
+
+        events::emit_payment_refunded(&env, payer, asset, amount);
+        Ok(amount)
+    }
+
+    /// Reverse the payment in `asset` within the clawback window. Creator only.
+    pub fn clawback(env: Env, asset: Address) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        storage::get_creator(&env).require_auth();
+        Self::ensure_no_pending_sweep(&env)?;
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+
+        let payer = storage::get_payment(&env, &asset)
+            .and_then(|payment| payment.payer)
+            .ok_or(Error::NotRefundable)?;
+        match storage::get_clawback_until(&env, &asset) {
+            Some(until) if env.ledger().sequence() <= until => {}
+            _ => return Err(Error::ClawbackWindowClosed),
+        }
+        let amount = Self::refund_to_payer(&env, &asset, &payer)?;
+
+        events::emit_payment_clawed_back(&env, payer, asset, amount);
+        Ok(amount)
+    }
+
+    /// Hold placed on the payment in `asset`, if it is refundable to a
+    /// known payer.
+    pub fn get_payment_hold(env: Env, asset: Address) -> Option<PaymentHold> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_payment_hold(&env, &asset)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Remove the payment in `asset` and transfer it back to `payer`.
+    /// Returns the refunded amount.
+    fn refund_to_payer(env: &Env, asset: &Address, payer: &Address) -> Result<i128, Error> {
+        let payment = storage::get_payment(env, asset).ok_or(Error::NotRefundable)?;
+
+        // Update state before the transfer to prevent reentrancy
+        storage::remove_payment(env, asset);
+        storage::remove_payment_hold(env, asset);
+        storage::remove_paid_out(env, asset);
+        Self::downgrade_if_drained(env);
+
+        TokenClient::new(env, asset).transfer(
+            &env.current_contract_address(),
+            payer,
+            &payment.amount,
+        );
+        Ok(payment.amount)
+    }
+
+    /// Return a `PaymentReceived` account whose payments were all refunded
+    /// to `Active`, so it no longer reads as sweepable, and emit
+    /// `AccountDrained`. An invoice a refund left unfulfilled also returns
+    /// to `Active`, without the event.
+    pub(crate) fn downgrade_if_drained(env: &Env) {
+        if storage::get_status(env) != AccountStatus::PaymentReceived {
+            return;
+        }
+        if storage::get_total_payments(env) == 0 {
+            storage::set_status(env, AccountStatus::Active);
+            events::emit_account_drained(env);
+        } else if !Self::invoice_fulfilled(env) {
+            storage::set_status(env, AccountStatus::Active);
+        }
+    }
+}
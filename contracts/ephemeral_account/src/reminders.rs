@@ -0,0 +1,45 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, Env, Vec};
+
+/// Expiry reminders (`expiry_reminders`) driven by keepers.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Emit an `ExpiryReminder` for each reminder window entered since the last ping.
+    pub fn ping(env: Env) -> Result<u32, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        let status = storage::get_status(&env);
+        if status != AccountStatus::Active && status != AccountStatus::PaymentReceived {
+            return Ok(0);
+        }
+        let expiry_ledger = storage::get_expiry_ledger(&env);
+        let current_ledger = env.ledger().sequence();
+        if current_ledger >= expiry_ledger {
+            return Ok(0);
+        }
+
+        let mut pending = Vec::new(&env);
+        let mut emitted = 0;
+        for offset in storage::get_expiry_reminders(&env).iter() {
+            if expiry_ledger - current_ledger <= offset {
+                events::emit_expiry_reminder(&env, offset, expiry_ledger);
+                emitted += 1;
+            } else {
+                pending.push_back(offset);
+            }
+        }
+        if emitted > 0 {
+            storage::set_expiry_reminders(&env, &pending);
+        }
+        Ok(emitted)
+    }
+}
@@ -0,0 +1,77 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::AccountStatus;
+use soroban_sdk::{contractimpl, token::TokenClient, Env};
+
+/// Resizing the base reserve after initialization.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Refund the reserve of unused asset slots to the creator now. Creator only.
+    pub fn recalculate_reserve(env: Env) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        Self::ensure_config_unlocked(&env)?;
+        storage::get_creator(&env).require_auth();
+
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+        let trustlines = storage::get_total_payments(&env);
+        if trustlines == 0 {
+            return Err(Error::NoPaymentReceived);
+        }
+
+        storage::set_max_assets(&env, trustlines);
+        Self::refund_reserve_overcollection(&env)
+    }
+
+    /// Add `amount` stroops of XLM to the base reserve for more assets. Creator only.
+    pub fn top_up_reserve(env: Env, amount: i128) -> Result<(), Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        let creator = storage::get_creator(&env);
+        creator.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        match storage::get_status(&env) {
+            AccountStatus::Active | AccountStatus::PaymentReceived => {}
+            _ => return Err(Error::InvalidStatus),
+        }
+
+        let reserve_remaining = storage::get_base_reserve_remaining(&env)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        let reserve_available = storage::get_available_reserve(&env)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        let deposit = storage::get_reserve_deposit(&env)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        storage::set_base_reserve_remaining(&env, reserve_remaining);
+        storage::set_available_reserve(&env, reserve_available);
+        storage::set_reserve_reclaimed(&env, false);
+        storage::set_reserve_deposit(&env, deposit);
+
+        TokenClient::new(&env, &bridgelet_shared::native_asset_address(&env)).transfer(
+            &creator,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        events::emit_reserve_topped_up(&env, creator, amount, reserve_remaining);
+        Ok(())
+    }
+}
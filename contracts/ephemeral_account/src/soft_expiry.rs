@@ -0,0 +1,129 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::{AccountStatus, AssetAmount, Payment};
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, Vec};
+
+/// Streaming expiry: from `soft_expiry_ledger` on, payments are released
+/// to the recovery address bit by bit.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Return to the recovery address the share released since the soft expiry.
+    pub fn claim_expired_portion(env: Env) -> Result<Vec<AssetAmount>, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+        let soft_expiry = storage::get_soft_expiry_ledger(&env).ok_or(Error::NoSoftExpiry)?;
+        if env.ledger().sequence() < soft_expiry {
+            return Err(Error::NotExpired);
+        }
+        if Self::is_expired(env.clone()) {
+            return Err(Error::AccountExpired);
+        }
+        if storage::get_status(&env) != AccountStatus::PaymentReceived {
+            return Err(Error::InvalidStatus);
+        }
+
+        let recovery_address = storage::get_recovery_address(&env);
+        let mut claimed = Vec::new(&env);
+        let mut drained = false;
+        for payment in storage::get_payments_ordered(&env).iter() {
+            let mut paid_out = storage::get_paid_out(&env, &payment.asset);
+            let due = Self::expired_portion(&env, soft_expiry, &payment, &paid_out)?;
+            if due == 0 {
+                continue;
+            }
+
+            // Update state before the transfer to prevent reentrancy
+            paid_out.released = paid_out.released.checked_add(due).ok_or(Error::Overflow)?;
+            if due == payment.amount {
+                storage::remove_payment(&env, &payment.asset);
+                storage::remove_payment_hold(&env, &payment.asset);
+                storage::remove_paid_out(&env, &payment.asset);
+                drained = true;
+            } else {
+                storage::add_payment(
+                    &env,
+                    Payment {
+                        amount: payment.amount - due,
+                        ..payment.clone()
+                    },
+                );
+                storage::set_paid_out(&env, &payment.asset, &paid_out);
+            }
+            TokenClient::new(&env, &payment.asset).transfer(
+                &env.current_contract_address(),
+                &recovery_address,
+                &due,
+            );
+
+            events::emit_expired_portion_claimed(
+                &env,
+                recovery_address.clone(),
+                payment.asset.clone(),
+                due,
+                paid_out.released,
+            );
+            claimed.push_back(AssetAmount {
+                asset: payment.asset,
+                amount: due,
+            });
+        }
+
+        if claimed.is_empty() {
+            return Err(Error::NothingToClaim);
+        }
+        if drained {
+            Self::downgrade_if_drained(&env);
+        }
+        Ok(claimed)
+    }
+
+    /// Soft expiry ledger of the account, if it streams its expiry.
+    pub fn get_soft_expiry_ledger(env: Env) -> Option<u32> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_soft_expiry_ledger(&env)
+    }
+
+    /// Amount of `asset` `claim_expired_portion` has returned so far.
+    pub fn get_expired_portion(env: Env, asset: Address) -> i128 {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_paid_out(&env, &asset).released
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Share of `payment` released to the recovery address since the soft
+    /// expiry and not returned yet, at most the payment left. The share is
+    /// of the payment as recorded, i.e. with everything in `paid_out` added
+    /// back, so a refunded surplus does not slow the release down.
+    fn expired_portion(
+        env: &Env,
+        soft_expiry: u32,
+        payment: &Payment,
+        paid_out: &storage::PaidOut,
+    ) -> Result<i128, Error> {
+        let recorded = payment
+            .amount
+            .checked_add(paid_out.released)
+            .and_then(|amount| amount.checked_add(paid_out.refunded))
+            .ok_or(Error::Overflow)?;
+        let window = storage::get_expiry_ledger(env).saturating_sub(soft_expiry) as i128;
+        let elapsed = env.ledger().sequence().saturating_sub(soft_expiry) as i128;
+        let share = recorded
+            .checked_mul(elapsed)
+            .map(|scaled| scaled / window)
+            .ok_or(Error::Overflow)?;
+        let due = share
+            .checked_sub(paid_out.released)
+            .ok_or(Error::Overflow)?;
+        Ok(due.clamp(0, payment.amount))
+    }
+}
@@ -1,33 +1,269 @@
 use crate::events::ReserveReclaimed;
-use bridgelet_shared::{AccountStatus, Payment};
-use soroban_sdk::{contracttype, Address, Env, Map};
+use crate::protocol_fee::ProtocolFee;
+use bridgelet_shared::{
+    AccountLifecycle, AccountStatus, AssetAmount, ContractVersion, ExpectationProgress,
+    ExpectedPayment, Payment, PaymentsPage,
+};
+use soroban_sdk::{
+    contracttype, Address, Bytes, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
 
-#[contracttype]
+#[contracttype(export = false)]
 pub enum DataKey {
+    /// Written by accounts initialized before `InitState`; read as `Ready`.
     Initialized,
+    InitState,
     Creator,
     ExpiryLedger,
     RecoveryAddress,
-    Payments,
+    /// Written by schema version 3 and earlier builds; now in `Core`
     Status,
     SweptTo,
     BaseReserveRemaining,
     AvailableReserve,
     ReserveReclaimed,
     LastSweepId,
-    ReserveEventCount,
-    LastReserveEvent,
     AuthorizedController,
+    /// Written by builds that authorized upgrades by an admin
     Admin,
+    ReserveRefunded,
+    CreatedLedger,
+    /// Written by schema version 3 and earlier builds; now in `Core`
+    FirstPaymentLedger,
+    SettledLedger,
+    Attestation,
+    /// Index of the assets with a recorded payment, written by schema
+    /// version 3 and earlier builds; now in `Core`
+    PaymentOrder,
+    PreferredSettlementAsset,
+    MaxAssets,
+    PendingSweepDestination,
+    SweepNonce,
+    /// Persistent entry holding the payment recorded in one asset
+    Payment(Address),
+    TtlExtendTo,
+    SchemaVersion,
+    Version,
+    SettledAmount(Address),
+    SweepContentionLedgers,
+    /// Destination the last `prepare_sweep` claimed the account for, and
+    /// the last ledger of the claim
+    SweepClaim,
+    /// Written by schema version 3 and earlier builds; now in `Core`
+    AuditHead,
+    /// The packed [`AccountCore`]
+    Core,
+}
+
+/// Storage keys of the optional subsystems and of legacy storage layouts.
+/// Keys serialize as their variant name plus fields, so no variant may
+/// share a name with one of `DataKey`'s.
+#[contracttype(export = false)]
+pub enum DataKeyExt {
+    /// Map of every payment, written by accounts recorded before per-asset
+    /// `Payment` entries. Still read, never written.
+    Payments,
+    /// Map of settled amounts, written by accounts settled before
+    /// per-asset `SettledAmount` entries. Still read, never written.
+    SettledAmounts,
+    ReserveEventCount,
+    LastReserveEvent,
+    SweepSigner,
+    LockedDestination,
+    TraceId,
+    PaymentHoldLedgers,
+    PaymentHold(Address),
+    ConfigLocked,
+    CreatorCosign,
+    ExpiryReminders,
+    ExpectedPayments,
+    ExpectationMet(Address),
+    AssetSwept(Address),
+    AccumulatePayments,
+    AllowedAssets,
+    MinPayment,
+    AssetMinPayment(Address),
+    /// Assets with an `AssetMinPayment`, so `purge` can find them.
+    MinPaymentAssets,
+    PaymentReference(Address),
+    Paused,
+    AllowedDestinations,
+    ClawbackWindowLedgers,
+    ClawbackUntil(Address),
+    LedgerSeconds,
+    ProtocolFee,
+    SweepHook,
+    SweepSigners,
+    SweepThreshold,
+    Guardian,
+    DisputeWindow,
+    Disputed,
+    Metadata,
+    BlockedPayers,
+    VestingSchedule,
+    VestingGrant(Address),
+    SoftExpiryLedger,
+    PaidOut(Address),
+    Recorder,
+    VerifyBalancesOnSweep,
+    ReserveDeposit,
+}
+
+/// Initialization progress. `initialize` writes `Initializing` before any
+/// other key and `Ready` after all of them, so an account whose
+/// initialization was interrupted can neither be used nor initialized
+/// again over its partial state.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InitState {
+    Uninitialized,
+    Initializing,
+    Ready,
+    /// Purged by `close_account`.
+    Closed,
 }
 
 // Initialization
+pub fn get_init_state(env: &Env) -> InitState {
+    if let Some(state) = env.storage().instance().get(&DataKey::InitState) {
+        return state;
+    }
+    if env.storage().instance().has(&DataKey::Initialized) {
+        InitState::Ready
+    } else {
+        InitState::Uninitialized
+    }
+}
+
+pub fn set_init_state(env: &Env, state: InitState) {
+    env.storage().instance().set(&DataKey::InitState, &state);
+}
+
 pub fn is_initialized(env: &Env) -> bool {
-    env.storage().instance().has(&DataKey::Initialized)
+    get_init_state(env) == InitState::Ready
 }
 
-pub fn set_initialized(env: &Env, value: bool) {
-    env.storage().instance().set(&DataKey::Initialized, &value);
+/// Assets the account was paid in, expected, allowed or given a minimum
+/// payment for. May repeat an asset.
+pub fn get_known_assets(env: &Env) -> Vec<Address> {
+    let mut assets = get_payment_assets(env);
+    for expected in get_expected_payments(env).iter() {
+        assets.push_back(expected.asset);
+    }
+    if let Some(allowed) = env
+        .storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKeyExt::AllowedAssets)
+    {
+        assets.append(&allowed);
+    }
+    if let Some(minimums) = env
+        .storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKeyExt::MinPaymentAssets)
+    {
+        assets.append(&minimums);
+    }
+    assets
+}
+
+/// Remove every entry of a completed account but its `InitState` and
+/// status, which stay behind as a tombstone so the account can be neither
+/// reused nor initialized again.
+///
+/// Per-asset entries are found through `get_known_assets`.
+pub fn purge(env: &Env) {
+    for asset in get_known_assets(env).iter() {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Payment(asset.clone()));
+        for key in asset_entry_keys(env, &asset) {
+            remove_asset_entry(env, &key);
+        }
+    }
+    remove_asset_entry(env, &DataKey::PaymentOrder);
+
+    let status = get_status(env);
+    let instance = env.storage().instance();
+
+    for key in [
+        DataKey::Initialized,
+        DataKey::Status,
+        DataKey::Creator,
+        DataKey::ExpiryLedger,
+        DataKey::RecoveryAddress,
+        DataKey::SweptTo,
+        DataKey::BaseReserveRemaining,
+        DataKey::AvailableReserve,
+        DataKey::ReserveReclaimed,
+        DataKey::LastSweepId,
+        DataKey::AuthorizedController,
+        DataKey::Admin,
+        DataKey::ReserveRefunded,
+        DataKey::CreatedLedger,
+        DataKey::FirstPaymentLedger,
+        DataKey::SettledLedger,
+        DataKey::Attestation,
+        DataKey::PreferredSettlementAsset,
+        DataKey::MaxAssets,
+        DataKey::PendingSweepDestination,
+        DataKey::SweepNonce,
+        DataKey::TtlExtendTo,
+        DataKey::SchemaVersion,
+        DataKey::Version,
+        DataKey::SweepContentionLedgers,
+        DataKey::SweepClaim,
+        DataKey::AuditHead,
+    ] {
+        instance.remove(&key);
+    }
+    for key in [
+        DataKeyExt::Payments,
+        DataKeyExt::ReserveEventCount,
+        DataKeyExt::LastReserveEvent,
+        DataKeyExt::SweepSigner,
+        DataKeyExt::LockedDestination,
+        DataKeyExt::PaymentHoldLedgers,
+        DataKeyExt::SettledAmounts,
+        DataKeyExt::ConfigLocked,
+        DataKeyExt::CreatorCosign,
+        DataKeyExt::ExpiryReminders,
+        DataKeyExt::ExpectedPayments,
+        DataKeyExt::AccumulatePayments,
+        DataKeyExt::AllowedAssets,
+        DataKeyExt::MinPayment,
+        DataKeyExt::MinPaymentAssets,
+        DataKeyExt::Paused,
+        DataKeyExt::AllowedDestinations,
+        DataKeyExt::ClawbackWindowLedgers,
+        DataKeyExt::LedgerSeconds,
+        DataKeyExt::ProtocolFee,
+        DataKeyExt::SweepHook,
+        DataKeyExt::SweepSigners,
+        DataKeyExt::SweepThreshold,
+        DataKeyExt::Guardian,
+        DataKeyExt::DisputeWindow,
+        DataKeyExt::Disputed,
+        DataKeyExt::Metadata,
+        DataKeyExt::BlockedPayers,
+        DataKeyExt::VestingSchedule,
+        DataKeyExt::SoftExpiryLedger,
+        DataKeyExt::Recorder,
+        DataKeyExt::VerifyBalancesOnSweep,
+        DataKeyExt::ReserveDeposit,
+    ] {
+        instance.remove(&key);
+    }
+    env.storage().temporary().remove(&DataKeyExt::TraceId);
+    set_core(
+        env,
+        &AccountCore {
+            status,
+            first_payment_ledger: None,
+            payment_assets: Vec::new(env),
+            audit_head: BytesN::from_array(env, &[0; 32]),
+        },
+    );
 }
 
 // Creator
@@ -67,51 +303,372 @@ pub fn get_recovery_address(env: &Env) -> Address {
         .unwrap()
 }
 
-// Payments
-pub fn has_payments(env: &Env) -> bool {
-    env.storage().instance().has(&DataKey::Payments)
+// Per-asset state (holds, flags, references, settled amounts...) lives in
+// persistent entries of its own, so the instance entry does not grow with
+// the number of assets. Accounts at schema version 2 kept it in the
+// instance: reads fall back to it until `migrate_asset_entries` moves it.
+
+/// Every per-asset entry but the asset's `Payment`.
+fn asset_entry_keys(env: &Env, asset: &Address) -> Vec<Val> {
+    Vec::from_array(
+        env,
+        [
+            DataKey::SettledAmount(asset.clone()).into_val(env),
+            DataKeyExt::PaymentHold(asset.clone()).into_val(env),
+            DataKeyExt::ClawbackUntil(asset.clone()).into_val(env),
+            DataKeyExt::ExpectationMet(asset.clone()).into_val(env),
+            DataKeyExt::AssetSwept(asset.clone()).into_val(env),
+            DataKeyExt::AssetMinPayment(asset.clone()).into_val(env),
+            DataKeyExt::PaymentReference(asset.clone()).into_val(env),
+            DataKeyExt::VestingGrant(asset.clone()).into_val(env),
+            DataKeyExt::PaidOut(asset.clone()).into_val(env),
+        ],
+    )
 }
 
-pub fn get_all_payments(env: &Env) -> Map<Address, Payment> {
+fn set_asset_entry<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let persistent = env.storage().persistent();
+    persistent.set(key, value);
+    persistent.extend_ttl(key, INSTANCE_TTL_THRESHOLD, get_ttl_extend_to(env));
+}
+
+fn get_asset_entry<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    value.or_else(|| env.storage().instance().get(key))
+}
+
+fn remove_asset_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage().persistent().remove(key);
+    env.storage().instance().remove(key);
+}
+
+/// Move the per-asset entries of a schema version 2 account out of the
+/// instance.
+pub fn migrate_asset_entries(env: &Env) {
+    let instance = env.storage().instance();
+    let mut assets = get_payment_assets(env);
+    for expected in get_expected_payments(env).iter() {
+        assets.push_back(expected.asset);
+    }
+    if let Some(allowed) = instance.get::<_, Vec<Address>>(&DataKeyExt::AllowedAssets) {
+        assets.append(&allowed);
+    }
+
+    let mut keys: Vec<Val> = Vec::new(env);
+    for asset in assets.iter() {
+        for key in asset_entry_keys(env, &asset) {
+            keys.push_back(key);
+        }
+    }
+    for key in keys.iter() {
+        if let Some(value) = instance.get::<_, Val>(&key) {
+            instance.remove(&key);
+            set_asset_entry(env, &key, &value);
+        }
+    }
+
+    let settled = get_legacy_settled_amounts(env);
+    instance.remove(&DataKeyExt::SettledAmounts);
+    for (asset, amount) in settled.iter() {
+        set_asset_entry(env, &DataKey::SettledAmount(asset), &amount);
+    }
+}
+
+// Payments: one persistent entry per asset, so recording a payment writes
+// only that entry and the instance instead of rewriting every payment. The
+// index of paid assets is part of the `AccountCore`, which a payment writes
+// anyway; it holds at most `MAX_PAYMENT_ASSETS` addresses. Payments
+// recorded before live in the legacy instance map.
+fn get_legacy_payments(env: &Env) -> Map<Address, Payment> {
     env.storage()
         .instance()
-        .get(&DataKey::Payments)
+        .get(&DataKeyExt::Payments)
         .unwrap_or_else(|| Map::new(env))
 }
 
-pub fn set_all_payments(env: &Env, payments: &Map<Address, Payment>) {
-    env.storage().instance().set(&DataKey::Payments, payments);
+pub fn has_payments(env: &Env) -> bool {
+    !get_payment_assets(env).is_empty()
 }
 
 pub fn add_payment(env: &Env, payment: Payment) {
-    let mut payments = get_all_payments(env);
-    payments.set(payment.asset.clone(), payment);
-    set_all_payments(env, &payments);
+    let asset = payment.asset.clone();
+    let key = DataKey::Payment(asset.clone());
+    env.storage().persistent().set(&key, &payment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, get_ttl_extend_to(env));
+
+    let mut order = get_payment_assets(env);
+    if let Err(index) = order.binary_search(&asset) {
+        order.insert(index, asset);
+    }
+    update_core(env, |core| core.payment_assets = order);
+}
+
+pub fn remove_payment(env: &Env, asset: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Payment(asset.clone()));
+    {
+        let mut legacy = get_legacy_payments(env);
+        if legacy.remove(asset.clone()).is_some() {
+            env.storage().instance().set(&DataKeyExt::Payments, &legacy);
+        }
+
+        let mut settled = get_legacy_settled_amounts(env);
+        if settled.remove(asset.clone()).is_some() {
+            env.storage()
+                .instance()
+                .set(&DataKeyExt::SettledAmounts, &settled);
+        }
+    }
+    remove_asset_entry(env, &DataKey::SettledAmount(asset.clone()));
+
+    clear_expectation_met(env, asset);
+    remove_asset_entry(env, &DataKeyExt::PaymentReference(asset.clone()));
+    remove_asset_entry(env, &DataKeyExt::ClawbackUntil(asset.clone()));
+
+    let mut order = get_payment_assets(env);
+    if let Ok(index) = order.binary_search(asset) {
+        order.remove(index);
+    }
+    update_core(env, |core| core.payment_assets = order);
 }
 
 pub fn get_payment(env: &Env, asset: &Address) -> Option<Payment> {
-    let payments = get_all_payments(env);
-    payments.get(asset.clone())
+    let payment = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Payment(asset.clone()));
+    payment.or_else(|| get_legacy_payments(env).get(asset.clone()))
+}
+
+/// Assets with a recorded payment, in canonical order: ascending by
+/// `Address` as compared by the Soroban host.
+///
+/// Every iteration over payments (events, sweeps, views) goes through this
+/// index, so the order is part of the contract's interface rather than an
+/// implementation detail of `Map`. Accounts whose payments predate the
+/// index fall back to the payment map's keys, which the host also keeps in
+/// ascending order.
+pub fn get_payment_assets(env: &Env) -> Vec<Address> {
+    get_core(env).payment_assets
+}
+
+/// All recorded payments in canonical asset order (see
+/// [`get_payment_assets`]).
+pub fn get_payments_ordered(env: &Env) -> Vec<Payment> {
+    let mut ordered = Vec::new(env);
+    for asset in get_payment_assets(env).iter() {
+        if let Some(payment) = get_payment(env, &asset) {
+            ordered.push_back(payment);
+        }
+    }
+    ordered
 }
 
 pub fn get_total_payments(env: &Env) -> u32 {
-    get_all_payments(env).len()
+    get_payment_assets(env).len()
 }
 
 pub fn has_payment_received(env: &Env) -> bool {
     has_payments(env)
 }
 
+// Settled amounts: how much of each recorded payment has left the account
+// for its final recipient. Unsettled assets have no entry.
+fn get_legacy_settled_amounts(env: &Env) -> Map<Address, i128> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::SettledAmounts)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Set the settled amount of each recorded payment to the result of
+/// `settled(payment)`.
+pub fn set_settled_amounts(env: &Env, settled: impl Fn(&Payment) -> i128) {
+    env.storage().instance().remove(&DataKeyExt::SettledAmounts);
+    for payment in get_payments_ordered(env).iter() {
+        let key = DataKey::SettledAmount(payment.asset.clone());
+        set_asset_entry(env, &key, &settled(&payment));
+    }
+}
+
+/// How much of the payment in `asset` has been settled; `0` if none.
+pub fn get_settled_amount(env: &Env, asset: &Address) -> i128 {
+    let settled = get_asset_entry(env, &DataKey::SettledAmount(asset.clone()));
+    let settled = settled.or_else(|| get_legacy_settled_amounts(env).get(asset.clone()));
+    settled.unwrap_or(0)
+}
+
+/// Settled amount of each recorded payment, in canonical asset order.
+pub fn get_settled_ordered(env: &Env) -> Vec<AssetAmount> {
+    let mut ordered = Vec::new(env);
+    for asset in get_payment_assets(env).iter() {
+        let amount = get_settled_amount(env, &asset);
+        ordered.push_back(AssetAmount { asset, amount });
+    }
+    ordered
+}
+
+/// Up to `limit` payments starting at `offset` in canonical asset order,
+/// with their settled amounts.
+pub fn get_payments_page(env: &Env, offset: u32, limit: u32) -> PaymentsPage {
+    let assets = get_payment_assets(env);
+    let mut payments = Vec::new(env);
+    let mut settled = Vec::new(env);
+    let end = offset.saturating_add(limit).min(assets.len());
+    for index in offset..end {
+        let asset = assets.get_unchecked(index);
+        if let Some(payment) = get_payment(env, &asset) {
+            payments.push_back(payment);
+            let amount = get_settled_amount(env, &asset);
+            settled.push_back(AssetAmount { asset, amount });
+        }
+    }
+    PaymentsPage {
+        payments,
+        settled,
+        total: assets.len(),
+    }
+}
+
+// Account core: the per-account state a payment updates, packed into one
+// instance value so a call rewrites it as a whole instead of one key per
+// field. Accounts at schema version 3 and earlier kept each field under a
+// key of its own: reads fall back to them until `migrate_account_core`
+// folds them in.
+
+/// Status, first payment ledger, paid-asset index and audit chain head of
+/// the account, stored together under `DataKey::Core`.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountCore {
+    pub status: AccountStatus,
+    pub first_payment_ledger: Option<u32>,
+    /// Assets with a recorded payment, in canonical order (see
+    /// [`get_payment_assets`])
+    pub payment_assets: Vec<Address>,
+    /// Head of the hash chain over the account's events
+    pub audit_head: BytesN<32>,
+}
+
+pub fn get_core(env: &Env) -> AccountCore {
+    env.storage()
+        .instance()
+        .get(&DataKey::Core)
+        .unwrap_or_else(|| get_legacy_core(env))
+}
+
+fn set_core(env: &Env, core: &AccountCore) {
+    env.storage().instance().set(&DataKey::Core, core);
+}
+
+fn update_core(env: &Env, update: impl FnOnce(&mut AccountCore)) {
+    let mut core = get_core(env);
+    update(&mut core);
+    set_core(env, &core);
+}
+
+/// The core of an account written before it was packed. Accounts whose
+/// payments predate the asset index fall back to the payment map's keys,
+/// which the host also keeps in ascending order.
+fn get_legacy_core(env: &Env) -> AccountCore {
+    let instance = env.storage().instance();
+    AccountCore {
+        status: instance
+            .get(&DataKey::Status)
+            .unwrap_or(AccountStatus::Active),
+        first_payment_ledger: instance.get(&DataKey::FirstPaymentLedger),
+        payment_assets: instance
+            .get(&DataKey::PaymentOrder)
+            .or_else(|| env.storage().persistent().get(&DataKey::PaymentOrder))
+            .unwrap_or_else(|| get_legacy_payments(env).keys()),
+        audit_head: instance
+            .get(&DataKey::AuditHead)
+            .unwrap_or_else(|| BytesN::from_array(env, &[0; 32])),
+    }
+}
+
+/// Fold the separately stored core fields of a schema version 3 account
+/// into its `AccountCore`.
+pub fn migrate_account_core(env: &Env) {
+    let core = get_core(env);
+    let instance = env.storage().instance();
+    for key in [
+        DataKey::Status,
+        DataKey::FirstPaymentLedger,
+        DataKey::PaymentOrder,
+        DataKey::AuditHead,
+    ] {
+        instance.remove(&key);
+    }
+    env.storage().persistent().remove(&DataKey::PaymentOrder);
+    set_core(env, &core);
+}
+
 // Status
 pub fn set_status(env: &Env, status: AccountStatus) {
-    env.storage().instance().set(&DataKey::Status, &status);
+    update_core(env, |core| core.status = status);
 }
 
 pub fn get_status(env: &Env) -> AccountStatus {
+    get_core(env).status
+}
+
+/// Whether a sweep has settled the account, fully or partially.
+pub fn is_swept(env: &Env) -> bool {
+    matches!(
+        get_status(env),
+        AccountStatus::Swept | AccountStatus::PartiallySwept
+    )
+}
+
+// Destination of the sweep `prepare_sweep` locked the account for
+pub fn set_pending_sweep_destination(env: &Env, destination: &Address) {
     env.storage()
         .instance()
-        .get(&DataKey::Status)
-        .unwrap_or(AccountStatus::Active)
+        .set(&DataKey::PendingSweepDestination, destination);
+}
+
+pub fn take_pending_sweep_destination(env: &Env) -> Option<Address> {
+    let storage = env.storage().instance();
+    let destination = storage.get(&DataKey::PendingSweepDestination);
+    storage.remove(&DataKey::PendingSweepDestination);
+    destination
+}
+
+// Ledgers a prepared sweep keeps other destinations out for
+pub fn set_sweep_contention_ledgers(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SweepContentionLedgers, &ledgers);
+}
+
+pub fn get_sweep_contention_ledgers(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SweepContentionLedgers)
+        .unwrap_or(0)
+}
+
+// Destination a prepared sweep claimed the account for, until a ledger
+pub fn set_sweep_claim(env: &Env, destination: &Address, until_ledger: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SweepClaim, &(destination.clone(), until_ledger));
+}
+
+pub fn get_sweep_claim(env: &Env) -> Option<(Address, u32)> {
+    env.storage().instance().get(&DataKey::SweepClaim)
 }
 
 // Swept to
@@ -187,24 +744,51 @@ pub fn get_last_sweep_id(env: &Env) -> u64 {
 pub fn set_reserve_event_count(env: &Env, count: u32) {
     env.storage()
         .instance()
-        .set(&DataKey::ReserveEventCount, &count);
+        .set(&DataKeyExt::ReserveEventCount, &count);
 }
 
 pub fn get_reserve_event_count(env: &Env) -> u32 {
     env.storage()
         .instance()
-        .get(&DataKey::ReserveEventCount)
+        .get(&DataKeyExt::ReserveEventCount)
+        .unwrap_or(0)
+}
+
+/// Native XLM `top_up_reserve` deposited that has not been paid back yet.
+pub fn set_reserve_deposit(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::ReserveDeposit, &amount);
+}
+
+pub fn get_reserve_deposit(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::ReserveDeposit)
+        .unwrap_or(0)
+}
+
+pub fn set_reserve_refunded(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ReserveRefunded, &amount);
+}
+
+pub fn get_reserve_refunded(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReserveRefunded)
         .unwrap_or(0)
 }
 
 pub fn set_last_reserve_event(env: &Env, event: &ReserveReclaimed) {
     env.storage()
         .instance()
-        .set(&DataKey::LastReserveEvent, event);
+        .set(&DataKeyExt::LastReserveEvent, event);
 }
 
 pub fn get_last_reserve_event(env: &Env) -> Option<ReserveReclaimed> {
-    env.storage().instance().get(&DataKey::LastReserveEvent)
+    env.storage().instance().get(&DataKeyExt::LastReserveEvent)
 }
 
 // Authorized controller
@@ -218,22 +802,703 @@ pub fn get_authorized_controller(env: &Env) -> Option<Address> {
     env.storage().instance().get(&DataKey::AuthorizedController)
 }
 
-// Admin (upgrade authority)
-pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&DataKey::Admin, admin);
+// Attestation
+pub fn set_attestation(env: &Env, attestation: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::Attestation, attestation);
+}
+
+pub fn get_attestation(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKey::Attestation)
+}
+
+// Custom account sweep policy
+pub fn set_sweep_signer(env: &Env, signer: &BytesN<32>) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::SweepSigner, signer);
+}
+
+pub fn get_sweep_signer(env: &Env) -> Option<BytesN<32>> {
+    env.storage().instance().get(&DataKeyExt::SweepSigner)
+}
+
+pub fn set_locked_destination(env: &Env, destination: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::LockedDestination, destination);
+}
+
+pub fn get_locked_destination(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKeyExt::LockedDestination)
+}
+
+// Trace id of the sweep being handled. Temporary: it only needs to live
+// for the transaction that sets it.
+pub fn set_trace_id(env: &Env, trace_id: Option<&BytesN<16>>) {
+    match trace_id {
+        Some(trace_id) => env
+            .storage()
+            .temporary()
+            .set(&DataKeyExt::TraceId, trace_id),
+        None => env.storage().temporary().remove(&DataKeyExt::TraceId),
+    }
+}
+
+pub fn get_trace_id(env: &Env) -> Option<BytesN<16>> {
+    env.storage().temporary().get(&DataKeyExt::TraceId)
+}
+
+// Audit hash chain over the account's events. Accounts start from zeros,
+// as do accounts upgraded from builds without the chain at their next event.
+pub fn set_audit_head(env: &Env, head: &BytesN<32>) {
+    update_core(env, |core| core.audit_head = head.clone());
+}
+
+pub fn get_audit_head(env: &Env) -> BytesN<32> {
+    get_core(env).audit_head
+}
+
+// Payment holds
+pub fn set_payment_hold_ledgers(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::PaymentHoldLedgers, &ledgers);
+}
+
+pub fn get_payment_hold_ledgers(env: &Env) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::PaymentHoldLedgers)
+}
+
+// Creator co-signature on sweeps
+pub fn set_creator_cosign(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::CreatorCosign, &true);
+}
+
+pub fn requires_creator_cosign(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::CreatorCosign)
+        .unwrap_or(false)
+}
+
+// Accumulation of repeated payments per asset
+pub fn set_accumulate_payments(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::AccumulatePayments, &true);
+}
+
+pub fn accumulates_payments(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::AccumulatePayments)
+        .unwrap_or(false)
+}
+
+// Per-account asset cap; unset means the protocol maximum
+pub fn set_max_assets(env: &Env, max_assets: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::MaxAssets, &max_assets);
+}
+
+pub fn get_max_assets(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::MaxAssets)
+}
+
+// Dust thresholds: a per-asset minimum overrides the global one
+pub fn set_min_payment(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::MinPayment, &amount);
+}
+
+pub fn set_asset_min_payment(env: &Env, asset: &Address, amount: i128) {
+    set_asset_entry(env, &DataKeyExt::AssetMinPayment(asset.clone()), &amount);
+
+    let instance = env.storage().instance();
+    let mut assets: Vec<Address> = instance
+        .get(&DataKeyExt::MinPaymentAssets)
+        .unwrap_or_else(|| Vec::new(env));
+    if !assets.contains(asset) {
+        assets.push_back(asset.clone());
+        instance.set(&DataKeyExt::MinPaymentAssets, &assets);
+    }
+}
+
+/// Smallest payment accepted in `asset`; `0` without a threshold.
+pub fn get_min_payment(env: &Env, asset: &Address) -> i128 {
+    get_asset_entry(env, &DataKeyExt::AssetMinPayment(asset.clone()))
+        .or_else(|| env.storage().instance().get(&DataKeyExt::MinPayment))
+        .unwrap_or(0)
+}
+
+// Asset allowlist; without one every asset is accepted
+pub fn set_allowed_assets(env: &Env, assets: &Vec<Address>) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::AllowedAssets, assets);
+}
+
+pub fn is_asset_allowed(env: &Env, asset: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKeyExt::AllowedAssets)
+        .is_none_or(|allowed| allowed.contains(asset))
+}
+
+// Destination allowlist; without one every destination is accepted
+pub fn set_allowed_destinations(env: &Env, destinations: &Vec<Address>) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::AllowedDestinations, destinations);
+}
+
+pub fn is_destination_allowed(env: &Env, destination: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, Vec<Address>>(&DataKeyExt::AllowedDestinations)
+        .is_none_or(|allowed| allowed.contains(destination))
+}
+
+// Expected payments, and whether a recorded payment met each one
+pub fn set_expected_payments(env: &Env, expected: &Vec<ExpectedPayment>) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::ExpectedPayments, expected);
+}
+
+pub fn get_expected_payments(env: &Env) -> Vec<ExpectedPayment> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::ExpectedPayments)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn get_expected_payment(env: &Env, asset: &Address) -> Option<ExpectedPayment> {
+    get_expected_payments(env)
+        .iter()
+        .find(|expected| expected.asset == *asset)
+}
+
+/// Amount recorded against each expected payment, in the order given.
+pub fn get_expectation_progress(env: &Env) -> Vec<ExpectationProgress> {
+    let mut progress = Vec::new(env);
+    for expected in get_expected_payments(env).iter() {
+        let received = get_payment(env, &expected.asset).map_or(0, |payment| payment.amount);
+        progress.push_back(ExpectationProgress {
+            asset: expected.asset,
+            expected: expected.amount,
+            received,
+        });
+    }
+    progress
+}
+
+pub fn set_expectation_met(env: &Env, asset: &Address) {
+    set_asset_entry(env, &DataKeyExt::ExpectationMet(asset.clone()), &true);
 }
 
-pub fn get_admin(env: &Env) -> Option<Address> {
-    env.storage().instance().get(&DataKey::Admin)
+pub fn clear_expectation_met(env: &Env, asset: &Address) {
+    remove_asset_entry(env, &DataKeyExt::ExpectationMet(asset.clone()));
+}
+
+pub fn is_expectation_met(env: &Env, asset: &Address) -> bool {
+    get_asset_entry(env, &DataKeyExt::ExpectationMet(asset.clone())).unwrap_or(false)
+}
+
+// Settlement currency preference
+pub fn set_preferred_settlement_asset(env: &Env, asset: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::PreferredSettlementAsset, asset);
+}
+
+pub fn get_preferred_settlement_asset(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::PreferredSettlementAsset)
+}
+
+// Expiry reminder windows not yet announced, in ledgers before expiry
+pub fn set_expiry_reminders(env: &Env, offsets: &Vec<u32>) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::ExpiryReminders, offsets);
+}
+
+pub fn get_expiry_reminders(env: &Env) -> Vec<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::ExpiryReminders)
+        .unwrap_or(Vec::new(env))
+}
+
+/// A payment that may be returned to its payer once it has been held for
+/// the account's hold period without being swept.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentHold {
+    pub payer: Address,
+    /// Last ledger of the hold; `refund_stale` is allowed after it.
+    pub hold_until_ledger: u32,
+}
+
+pub fn set_payment_hold(env: &Env, asset: &Address, hold: &PaymentHold) {
+    set_asset_entry(env, &DataKeyExt::PaymentHold(asset.clone()), hold);
+}
+
+pub fn get_payment_hold(env: &Env, asset: &Address) -> Option<PaymentHold> {
+    get_asset_entry(env, &DataKeyExt::PaymentHold(asset.clone()))
+}
+
+pub fn remove_payment_hold(env: &Env, asset: &Address) {
+    remove_asset_entry(env, &DataKeyExt::PaymentHold(asset.clone()));
+}
+
+// Off-chain reference (e.g. an order id) each payment was recorded with
+pub fn set_payment_reference(env: &Env, asset: &Address, reference: &BytesN<32>) {
+    set_asset_entry(env, &DataKeyExt::PaymentReference(asset.clone()), reference);
+}
+
+pub fn get_payment_reference(env: &Env, asset: &Address) -> Option<BytesN<32>> {
+    get_asset_entry(env, &DataKeyExt::PaymentReference(asset.clone()))
+}
+
+// Clawback window, and the last ledger each payment can be clawed back in
+pub fn set_clawback_window_ledgers(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::ClawbackWindowLedgers, &ledgers);
+}
+
+pub fn get_clawback_window_ledgers(env: &Env) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::ClawbackWindowLedgers)
+}
+
+pub fn set_clawback_until(env: &Env, asset: &Address, ledger: u32) {
+    set_asset_entry(env, &DataKeyExt::ClawbackUntil(asset.clone()), &ledger);
+}
+
+pub fn get_clawback_until(env: &Env, asset: &Address) -> Option<u32> {
+    get_asset_entry(env, &DataKeyExt::ClawbackUntil(asset.clone()))
+}
+
+// Configuration lock: once set, settings can no longer change
+pub fn set_config_locked(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::ConfigLocked, &true);
+}
+
+pub fn is_config_locked(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::ConfigLocked)
+        .unwrap_or(false)
+}
+
+// Per-account sweep nonce, bumped by every successful sweep
+pub fn get_sweep_nonce(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SweepNonce)
+        .unwrap_or(0)
+}
+
+pub fn increment_sweep_nonce(env: &Env) {
+    let nonce = get_sweep_nonce(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::SweepNonce, &(nonce + 1));
+}
+
+// Emergency brake: payments and sweeps are rejected while set
+pub fn set_paused(env: &Env, paused: bool) {
+    if paused {
+        env.storage().instance().set(&DataKeyExt::Paused, &true);
+    } else {
+        env.storage().instance().remove(&DataKeyExt::Paused);
+    }
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::Paused)
+        .unwrap_or(false)
+}
+
+// Lifecycle ledgers
+pub fn set_created_ledger(env: &Env, ledger: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CreatedLedger, &ledger);
+}
+
+/// Record the first payment's ledger; later payments keep the original.
+pub fn set_first_payment_ledger(env: &Env, ledger: u32) {
+    update_core(env, |core| {
+        core.first_payment_ledger.get_or_insert(ledger);
+    });
+}
+
+pub fn set_settled_ledger(env: &Env, ledger: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SettledLedger, &ledger);
+}
+
+pub fn get_lifecycle(env: &Env) -> AccountLifecycle {
+    AccountLifecycle {
+        created_ledger: env
+            .storage()
+            .instance()
+            .get(&DataKey::CreatedLedger)
+            .unwrap_or(0),
+        first_payment_ledger: get_core(env).first_payment_ledger,
+        settled_ledger: env.storage().instance().get(&DataKey::SettledLedger),
+    }
+}
+
+// Per-asset sweep flags, set by `sweep_assets`
+pub fn set_asset_swept(env: &Env, asset: &Address) {
+    set_asset_entry(env, &DataKeyExt::AssetSwept(asset.clone()), &true);
+}
+
+pub fn is_asset_swept(env: &Env, asset: &Address) -> bool {
+    get_asset_entry(env, &DataKeyExt::AssetSwept(asset.clone())).unwrap_or(false)
+}
+
+// Storage layout version. Accounts initialized before it was stored read
+// as 1.
+pub fn set_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SchemaVersion, &version);
+}
+
+pub fn get_schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(1)
+}
+
+// Version of the build that initialized or last migrated the account
+pub fn set_version(env: &Env, version: &ContractVersion) {
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+pub fn get_version(env: &Env) -> Option<ContractVersion> {
+    env.storage().instance().get(&DataKey::Version)
+}
+
+// Average ledger close time for expiry countdowns
+const DEFAULT_LEDGER_SECONDS: u32 = 5;
+
+pub fn set_ledger_seconds(env: &Env, seconds: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::LedgerSeconds, &seconds);
+}
+
+pub fn get_ledger_seconds(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::LedgerSeconds)
+        .unwrap_or(DEFAULT_LEDGER_SECONDS)
+}
+
+// Fee deducted from each swept asset
+pub fn set_protocol_fee(env: &Env, fee: &ProtocolFee) {
+    env.storage().instance().set(&DataKeyExt::ProtocolFee, fee);
+}
+
+pub fn get_protocol_fee(env: &Env) -> Option<ProtocolFee> {
+    env.storage().instance().get(&DataKeyExt::ProtocolFee)
+}
+
+// Contract notified after each sweep or expiry
+pub fn set_sweep_hook(env: &Env, hook: &Address) {
+    env.storage().instance().set(&DataKeyExt::SweepHook, hook);
+}
+
+pub fn get_sweep_hook(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKeyExt::SweepHook)
+}
+
+// Signer set whose threshold of signatures authorizes sweeps
+pub fn set_sweep_signers(env: &Env, signers: &Vec<BytesN<32>>, threshold: u32) {
+    let instance = env.storage().instance();
+    instance.set(&DataKeyExt::SweepSigners, signers);
+    instance.set(&DataKeyExt::SweepThreshold, &threshold);
+}
+
+pub fn get_sweep_signers(env: &Env) -> Option<(Vec<BytesN<32>>, u32)> {
+    let instance = env.storage().instance();
+    let signers = instance.get(&DataKeyExt::SweepSigners)?;
+    let threshold = instance.get(&DataKeyExt::SweepThreshold)?;
+    Some((signers, threshold))
+}
+
+// Guardian allowed to redirect expired funds, with its delay after expiry
+pub fn set_guardian(env: &Env, guardian: &Address, delay_ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::Guardian, &(guardian.clone(), delay_ledgers));
+}
+
+pub fn get_guardian(env: &Env) -> Option<(Address, u32)> {
+    env.storage().instance().get(&DataKeyExt::Guardian)
+}
+
+// Ledgers after a sweep that the reserve stays locked, with the arbiter of disputes
+pub fn set_dispute_window(env: &Env, window_ledgers: u32, arbiter: &Address) {
+    env.storage().instance().set(
+        &DataKeyExt::DisputeWindow,
+        &(window_ledgers, arbiter.clone()),
+    );
+}
+
+pub fn get_dispute_window(env: &Env) -> Option<(u32, Address)> {
+    env.storage().instance().get(&DataKeyExt::DisputeWindow)
+}
+
+pub fn set_disputed(env: &Env, disputed: bool) {
+    if disputed {
+        env.storage().instance().set(&DataKeyExt::Disputed, &true);
+    } else {
+        env.storage().instance().remove(&DataKeyExt::Disputed);
+    }
+}
+
+pub fn is_disputed(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::Disputed)
+        .unwrap_or(false)
+}
+
+// Payers whose deposits are refused
+pub fn set_blocked_payers(env: &Env, payers: &Vec<Address>) {
+    if payers.is_empty() {
+        env.storage().instance().remove(&DataKeyExt::BlockedPayers);
+    } else {
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::BlockedPayers, payers);
+    }
+}
+
+pub fn get_blocked_payers(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::BlockedPayers)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn is_payer_blocked(env: &Env, payer: &Address) -> bool {
+    get_blocked_payers(env).contains(payer)
+}
+
+// Address that must authorize recording, on a restricted account
+pub fn set_recorder(env: &Env, recorder: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::Recorder, recorder);
+}
+
+pub fn get_recorder(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKeyExt::Recorder)
+}
+
+// Whether sweeps check recorded payments against token balances
+pub fn set_verify_balances_on_sweep(env: &Env) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::VerifyBalancesOnSweep, &true);
+}
+
+pub fn verifies_balances_on_sweep(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::VerifyBalancesOnSweep)
+        .unwrap_or(false)
+}
+
+// Integrator labels attached by the creator
+pub fn set_metadata(env: &Env, metadata: &Map<Symbol, Bytes>) {
+    if metadata.is_empty() {
+        env.storage().instance().remove(&DataKeyExt::Metadata);
+    } else {
+        env.storage()
+            .instance()
+            .set(&DataKeyExt::Metadata, metadata);
+    }
+}
+
+pub fn get_metadata(env: &Env) -> Map<Symbol, Bytes> {
+    env.storage()
+        .instance()
+        .get(&DataKeyExt::Metadata)
+        .unwrap_or(Map::new(env))
+}
+
+// Gradual release of swept funds
+/// Timed release of swept funds, as configured by `vesting_cliff_ledger`
+/// and `vesting_duration_ledgers`.
+///
+/// Each swept asset vests linearly from the ledger it was swept at over
+/// `duration_ledgers`, but nothing can be claimed before `cliff_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub cliff_ledger: u32,
+    pub duration_ledgers: u32,
+}
+
+pub fn set_vesting_schedule(env: &Env, schedule: &VestingSchedule) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::VestingSchedule, schedule);
+}
+
+pub fn get_vesting_schedule(env: &Env) -> Option<VestingSchedule> {
+    env.storage().instance().get(&DataKeyExt::VestingSchedule)
+}
+
+pub fn has_vesting_schedule(env: &Env) -> bool {
+    env.storage().instance().has(&DataKeyExt::VestingSchedule)
+}
+
+/// Swept funds of one asset vesting to the sweep's destination.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingGrant {
+    pub destination: Address,
+    /// Amount vesting, net of the protocol fee.
+    pub total: i128,
+    /// Amount `claim_vested` has paid out so far.
+    pub claimed: i128,
+    /// Ledger the asset was swept at, from which it vests.
+    pub start_ledger: u32,
+}
+
+pub fn set_vesting_grant(env: &Env, asset: &Address, grant: &VestingGrant) {
+    set_asset_entry(env, &DataKeyExt::VestingGrant(asset.clone()), grant);
+}
+
+pub fn get_vesting_grant(env: &Env, asset: &Address) -> Option<VestingGrant> {
+    get_asset_entry(env, &DataKeyExt::VestingGrant(asset.clone()))
+}
+
+/// Whether any swept asset still has funds the destination has not claimed.
+pub fn has_unclaimed_vesting(env: &Env) -> bool {
+    get_payment_assets(env).iter().any(|asset| {
+        get_vesting_grant(env, &asset).is_some_and(|grant| grant.claimed < grant.total)
+    })
+}
+
+// Streaming expiry
+pub fn set_soft_expiry_ledger(env: &Env, ledger: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKeyExt::SoftExpiryLedger, &ledger);
+}
+
+pub fn get_soft_expiry_ledger(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKeyExt::SoftExpiryLedger)
+}
+
+/// What was taken out of the payment in one asset before settlement, on an
+/// account with a soft expiry. Added back to the payment, it gives the
+/// amount recorded, which `claim_expired_portion` measures its share
+/// against.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PaidOut {
+    /// Returned to the recovery address by `claim_expired_portion`.
+    pub released: i128,
+    /// Surplus returned to the payer by `refund_excess`.
+    pub refunded: i128,
+}
+
+pub fn set_paid_out(env: &Env, asset: &Address, paid_out: &PaidOut) {
+    set_asset_entry(env, &DataKeyExt::PaidOut(asset.clone()), paid_out);
+}
+
+pub fn get_paid_out(env: &Env, asset: &Address) -> PaidOut {
+    get_asset_entry(env, &DataKeyExt::PaidOut(asset.clone())).unwrap_or_default()
+}
+
+pub fn remove_paid_out(env: &Env, asset: &Address) {
+    remove_asset_entry(env, &DataKeyExt::PaidOut(asset.clone()));
+}
+
+/// Move payments out of the legacy instance map into per-asset entries.
+pub fn migrate_legacy_payments(env: &Env) {
+    let legacy = get_legacy_payments(env);
+    if legacy.is_empty() {
+        return;
+    }
+    env.storage().instance().remove(&DataKeyExt::Payments);
+    for payment in legacy.values() {
+        add_payment(env, payment);
+    }
 }
 
 // TTL management
 
-const INSTANCE_TTL_THRESHOLD: u32 = 100;
+pub const INSTANCE_TTL_THRESHOLD: u32 = 100;
 const INSTANCE_TTL_EXTEND_TO: u32 = 518_400;
 
+pub fn set_ttl_extend_to(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::TtlExtendTo, &ledgers);
+}
+
+/// Ledgers an extension keeps entries alive for: the `ttl_extend_to`
+/// chosen at initialization, else the default.
+pub fn get_ttl_extend_to(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::TtlExtendTo)
+        .unwrap_or(INSTANCE_TTL_EXTEND_TO)
+}
+
 pub fn extend_instance_ttl(env: &Env) {
     env.storage()
         .instance()
-        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, get_ttl_extend_to(env));
+}
+
+/// Extend the instance, every persistent payment entry and the per-asset
+/// entries of each paid asset, so an account holding funds cannot have part
+/// of its state archived while the rest stays live.
+pub fn extend_ttls(env: &Env) {
+    extend_instance_ttl(env);
+    let persistent = env.storage().persistent();
+    let extend_to = get_ttl_extend_to(env);
+    if persistent.has(&DataKey::PaymentOrder) {
+        persistent.extend_ttl(&DataKey::PaymentOrder, INSTANCE_TTL_THRESHOLD, extend_to);
+    }
+    for asset in get_payment_assets(env).iter() {
+        let mut keys = asset_entry_keys(env, &asset);
+        keys.push_back(DataKey::Payment(asset.clone()).into_val(env));
+        for key in keys.iter() {
+            if persistent.has(&key) {
+                persistent.extend_ttl(&key, INSTANCE_TTL_THRESHOLD, extend_to);
+            }
+        }
+    }
 }
@@ -1,16 +1,18 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod test {
     extern crate std;
 
     use std::println;
 
+    use crate::ReserveReclaimed;
     use crate::{
         storage, AccountStatus, EphemeralAccountContract, EphemeralAccountContractClient, Error,
-        ReserveReclaimed,
     };
+    use bridgelet_shared::EventHeader;
     use soroban_sdk::{
         testutils::{Address as _, Events as _, Ledger as _},
-        Address, BytesN, Env, InvokeError, TryFromVal,
+        Address, BytesN, Env, InvokeError, TryFromVal, Val,
     };
 
     const BASE_RESERVE_STROOPS: i128 = 1_000_000_000;
@@ -21,6 +23,37 @@ mod test {
             .expect("reserve event was not emitted")
     }
 
+    /// Decode the body of an account event's `(EventHeader, body)` data.
+    fn event_body<T: TryFromVal<Env, Val>>(env: &Env, data: &Val) -> T {
+        let (_, body) = <(EventHeader, Val)>::try_from_val(env, data).unwrap();
+        T::try_from_val(env, &body).unwrap()
+    }
+
+    #[test]
+    fn test_events_extend_the_audit_hash_chain() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let head = || env.as_contract(&contract_id, || storage::get_audit_head(&env));
+        let before = head();
+        assert_ne!(before, BytesN::from_array(&env, &[0; 32]));
+
+        client.record_payment(&100, &Address::generate(&env));
+        let mut expected = before;
+        for (contract, topics, data) in env.events().all().iter() {
+            if contract != contract_id {
+                continue;
+            }
+            let (_, body) = <(EventHeader, Val)>::try_from_val(&env, &data).unwrap();
+            let mut preimage = soroban_sdk::Bytes::from(expected);
+            preimage.append(&(topics, body).to_xdr(&env));
+            expected = env.crypto().sha256(&preimage).into();
+        }
+        assert_eq!(head(), expected);
+    }
+
     #[test]
     fn test_initialize() {
         let env = Env::default();
@@ -120,7 +153,7 @@ mod test {
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
         let controller = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let destination = Address::generate(&env);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
@@ -140,9 +173,12 @@ mod test {
         assert_eq!(client.get_reserve_remaining(), 0);
         assert!(client.is_reserve_reclaimed());
 
+        // One trustline used: the other nine slots are refunded to the creator.
+        assert_eq!(client.get_reserve_refunded(), 450_000_000);
+
         let reserve_event = latest_reserve_event(&client);
         assert_eq!(reserve_event.destination, destination);
-        assert_eq!(reserve_event.amount, BASE_RESERVE_STROOPS);
+        assert_eq!(reserve_event.amount, 550_000_000);
         assert_eq!(reserve_event.remaining_reserve, 0);
         assert!(reserve_event.fully_reclaimed);
         assert_eq!(reserve_event.sweep_id, env.ledger().sequence() as u64);
@@ -350,7 +386,7 @@ mod test {
 
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let destination = Address::generate(&env);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
@@ -379,7 +415,7 @@ mod test {
 
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let destination = Address::generate(&env);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
@@ -414,7 +450,6 @@ mod test {
         assert_eq!(Error::InvalidStatus as u32, 12);
         assert_eq!(Error::DuplicateAsset as u32, 13);
         assert_eq!(Error::TooManyPayments as u32, 14);
-        assert_eq!(Error::NotUpgradeAdmin as u32, 15);
     }
 
     #[test]
@@ -439,8 +474,8 @@ mod test {
             &Address::generate(&env),
         );
 
-        let asset1 = Address::generate(&env);
-        let asset2 = Address::generate(&env);
+        let asset1 = token_held_by(&env, &contract_id, 100);
+        let asset2 = token_held_by(&env, &contract_id, 200);
         client.record_payment(&100, &asset1);
         client.record_payment(&200, &asset2);
 
@@ -451,9 +486,11 @@ mod test {
         assert_eq!(client.get_reserve_remaining(), 0);
         assert!(client.is_reserve_reclaimed());
 
+        assert_eq!(client.get_reserve_refunded(), 400_000_000);
+
         let reserve_event = latest_reserve_event(&client);
         assert_eq!(reserve_event.destination, destination);
-        assert_eq!(reserve_event.amount, BASE_RESERVE_STROOPS);
+        assert_eq!(reserve_event.amount, 600_000_000);
         assert_eq!(reserve_event.remaining_reserve, 0);
         assert!(reserve_event.fully_reclaimed);
         assert_eq!(client.get_reserve_reclaim_event_count(), 1);
@@ -471,7 +508,7 @@ mod test {
         let recovery = Address::generate(&env);
         let controller = Address::generate(&env);
         let destination = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
         client.initialize(
@@ -513,7 +550,7 @@ mod test {
         let recovery = Address::generate(&env);
         let controller = Address::generate(&env);
         let destination = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
         client.initialize(
@@ -525,7 +562,8 @@ mod test {
         );
         client.record_payment(&100, &asset);
 
-        let initial_available = 250_000_000i128;
+        // Covers the 450M unused-trustline refund, leaving 250M to reclaim.
+        let initial_available = 700_000_000i128;
         env.as_contract(&contract_id, || {
             storage::set_available_reserve(&env, initial_available);
         });
@@ -541,7 +579,7 @@ mod test {
 
         let partial_event = latest_reserve_event(&client);
         assert_eq!(partial_event.destination, destination);
-        assert_eq!(partial_event.amount, initial_available);
+        assert_eq!(partial_event.amount, 250_000_000);
         assert_eq!(partial_event.remaining_reserve, expected_remaining);
         assert!(!partial_event.fully_reclaimed);
 
@@ -576,7 +614,7 @@ mod test {
         let recovery = Address::generate(&env);
         let controller = Address::generate(&env);
         let destination = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let expiry_ledger = env.ledger().sequence() + 1000;
 
         client.initialize(
@@ -700,7 +738,7 @@ mod test {
 
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let expiry_ledger = env.ledger().sequence() + 1;
 
         client.initialize(
@@ -717,7 +755,10 @@ mod test {
 
         let info = client.get_info();
         assert_eq!(info.status, AccountStatus::Expired);
-        assert_eq!(info.swept_to, Some(recovery));
+        assert_eq!(info.swept_to, Some(recovery.clone()));
+        let token = soroban_sdk::token::TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&recovery), 100);
+        assert_eq!(token.balance(&contract_id), 0);
         assert_eq!(client.get_reserve_remaining(), 0);
         assert!(client.is_reserve_reclaimed());
         assert_eq!(client.get_reserve_reclaim_event_count(), 1);
@@ -758,7 +799,7 @@ mod test {
 
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
         let expiry_ledger = env.ledger().sequence() + 1;
 
         client.initialize(
@@ -857,34 +898,260 @@ mod test {
     // ── Issue #109: upgrade() ────────────────────────────────────────────────
 
     #[test]
-    fn test_upgrade_returns_not_upgrade_admin_for_non_admin() {
-        let env = Env::default();
-        env.mock_all_auths();
+    fn test_upgrade_and_migrate_require_the_creator_not_the_admin() {
+        use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+        use soroban_sdk::IntoVal;
 
+        let env = Env::default();
         let contract_id = env.register(EphemeralAccountContract, ());
         let client = EphemeralAccountContractClient::new(&env, &contract_id);
-
         let creator = Address::generate(&env);
-        let recovery = Address::generate(&env);
         let admin = Address::generate(&env);
-        let expiry_ledger = env.ledger().sequence() + 1000;
-
-        // Initialize with a specific admin
+        env.mock_all_auths();
         client.initialize(
             &creator,
-            &expiry_ledger,
-            &recovery,
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
             &Address::generate(&env),
             &admin,
         );
+        // Stop before the WASM swap, which needs an uploaded build.
+        env.as_contract(&contract_id, || {
+            storage::set_status(&env, AccountStatus::PendingSweep);
+        });
+
+        let hash = BytesN::from_array(&env, &[0; 32]);
+        let as_signer = |signer: &Address, fn_name: &'static str, args: soroban_sdk::Vec<Val>| {
+            env.mock_auths(&[MockAuth {
+                address: signer,
+                invoke: &MockAuthInvoke {
+                    contract: &contract_id,
+                    fn_name,
+                    args,
+                    sub_invokes: &[],
+                },
+            }]);
+        };
+
+        as_signer(&admin, "upgrade", (hash.clone(),).into_val(&env));
+        assert!(client.try_upgrade(&hash).is_err());
+        as_signer(&creator, "upgrade", (hash.clone(),).into_val(&env));
+        assert_eq!(client.try_upgrade(&hash), Err(Ok(Error::SweepPending)));
+
+        as_signer(&admin, "migrate", ().into_val(&env));
+        assert!(client.try_migrate().is_err());
+        as_signer(&creator, "migrate", ().into_val(&env));
+        assert_eq!(client.migrate(), 4);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_a_non_creator() {
+        use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+        use soroban_sdk::IntoVal;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let stranger = Address::generate(&env);
+        let hash = BytesN::from_array(&env, &[0; 32]);
+
+        env.mock_auths(&[MockAuth {
+            address: &stranger,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "upgrade",
+                args: (hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(matches!(client.try_upgrade(&hash), Err(Err(_))));
+    }
+
+    #[test]
+    fn test_upgrade_requires_migrating_an_older_schema_first() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        env.as_contract(&contract_id, || {
+            storage::set_schema_version(&env, 2);
+        });
+
+        let hash = BytesN::from_array(&env, &[0; 32]);
+        assert_eq!(client.try_upgrade(&hash), Err(Ok(Error::MigrationRequired)));
+
+        // Stop before the WASM swap, which needs an uploaded build.
+        client.migrate();
+        env.as_contract(&contract_id, || {
+            storage::set_status(&env, AccountStatus::PendingSweep);
+        });
+        assert_eq!(client.try_upgrade(&hash), Err(Ok(Error::SweepPending)));
+    }
+
+    #[test]
+    fn test_upgrade_is_rejected_while_a_sweep_is_pending() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+
+        env.as_contract(&contract_id, || {
+            storage::set_status(&env, AccountStatus::PendingSweep);
+        });
+        assert_eq!(
+            client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+            Err(Ok(Error::SweepPending))
+        );
+    }
+
+    #[test]
+    fn test_migrate_moves_legacy_payments_into_per_asset_entries() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        assert_eq!(client.get_schema_version(), 4);
+        assert_eq!(client.migrate(), 4);
+        assert!(env.events().all().is_empty());
+
+        // An account written by a build that kept payments in one map.
+        let asset = Address::generate(&env);
+        let legacy = crate::Payment {
+            asset: asset.clone(),
+            amount: 70,
+            timestamp: 0,
+            payer: None,
+        };
+        env.as_contract(&contract_id, || {
+            let mut payments = soroban_sdk::Map::new(&env);
+            payments.set(asset.clone(), legacy.clone());
+            env.storage()
+                .instance()
+                .set(&storage::DataKeyExt::Payments, &payments);
+            env.storage()
+                .instance()
+                .remove(&storage::DataKey::SchemaVersion);
+            storage::set_status(&env, AccountStatus::PaymentReceived);
+        });
+        unpack_core(&env, &contract_id);
+        assert_eq!(client.get_schema_version(), 1);
+
+        assert_eq!(client.migrate(), 4);
+        let migrated = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("migrated"))
+            })
+            .expect("AccountMigrated should be emitted");
+        assert_eq!(
+            event_body::<crate::AccountMigrated>(&env, &migrated.2),
+            crate::AccountMigrated {
+                from_version: 1,
+                to_version: 4,
+            }
+        );
+
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().instance().has(&storage::DataKeyExt::Payments));
+            assert_eq!(
+                env.storage()
+                    .persistent()
+                    .get::<_, crate::Payment>(&storage::DataKey::Payment(asset.clone())),
+                Some(legacy.clone())
+            );
+        });
+        assert_eq!(client.get_payment(&asset), Some(legacy));
+        assert_eq!(client.get_schema_version(), 4);
+    }
+
+    #[test]
+    fn test_migrate_moves_per_asset_state_out_of_the_instance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let asset = Address::generate(&env);
+        let reference = BytesN::from_array(&env, &[7; 32]);
+
+        // An account written by a build that kept per-asset state, the
+        // payment index and settled amounts in the instance.
+        env.as_contract(&contract_id, || {
+            storage::add_payment(
+                &env,
+                crate::Payment {
+                    asset: asset.clone(),
+                    amount: 70,
+                    timestamp: 0,
+                    payer: None,
+                },
+            );
+            let instance = env.storage().instance();
+            instance.set(
+                &storage::DataKeyExt::PaymentReference(asset.clone()),
+                &reference,
+            );
+            let mut settled = soroban_sdk::Map::<Address, i128>::new(&env);
+            settled.set(asset.clone(), 30);
+            instance.set(&storage::DataKeyExt::SettledAmounts, &settled);
+            storage::set_schema_version(&env, 2);
+            storage::set_status(&env, AccountStatus::PaymentReceived);
+        });
+        unpack_core(&env, &contract_id);
+        assert_eq!(
+            client.get_payment_reference(&asset),
+            Some(reference.clone())
+        );
+
+        assert_eq!(client.migrate(), 4);
+        env.as_contract(&contract_id, || {
+            let instance = env.storage().instance();
+            let persistent = env.storage().persistent();
+            assert!(!instance.has(&storage::DataKeyExt::PaymentReference(asset.clone())));
+            assert!(!instance.has(&storage::DataKeyExt::SettledAmounts));
+            // The payment index is packed into the account core
+            assert!(!instance.has(&storage::DataKey::PaymentOrder));
+            assert_eq!(
+                storage::get_core(&env).payment_assets,
+                soroban_sdk::vec![&env, asset.clone()]
+            );
+            assert_eq!(
+                persistent
+                    .get::<_, BytesN<32>>(&storage::DataKeyExt::PaymentReference(asset.clone())),
+                Some(reference.clone())
+            );
+            assert_eq!(
+                persistent.get::<_, i128>(&storage::DataKey::SettledAmount(asset.clone())),
+                Some(30)
+            );
+        });
+        assert_eq!(client.get_payment_reference(&asset), Some(reference));
+        let page = client.get_payments_page(&0, &10);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.settled.get_unchecked(0).amount, 30);
+    }
+
+    #[test]
+    fn test_migrate_records_the_running_build_version() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let build = crate::ContractVersion::parse(env!("CARGO_PKG_VERSION"));
+        assert_eq!(client.get_version(), build);
 
-        // Attempt upgrade with a non-admin — should be rejected
-        // We can't call upgrade directly without a valid wasm hash, but we can verify
-        // the admin is stored by checking the storage directly via as_contract
+        // Written by an older build, then upgraded to this one.
         env.as_contract(&contract_id, || {
-            let stored = storage::get_admin(&env);
-            assert_eq!(stored, Some(admin));
+            storage::set_version(
+                &env,
+                &crate::ContractVersion {
+                    major: 0,
+                    minor: 0,
+                    patch: 9,
+                },
+            );
         });
+        assert_eq!(client.get_version().patch, 9);
+
+        client.migrate();
+        assert_eq!(client.get_version(), build);
     }
 
     // ── Issue #122: simulate_sweep() ────────────────────────────────────────
@@ -912,12 +1179,42 @@ mod test {
         );
         client.record_payment(&500, &asset);
 
-        let (payments, error_code) = client.simulate_sweep(&destination);
+        let (payments, _, error_code) = client.simulate_sweep(&destination);
         assert_eq!(error_code, 0);
         assert_eq!(payments.len(), 1);
         assert_eq!(payments.get(0).unwrap().amount, 500);
     }
 
+    #[test]
+    fn test_simulate_sweep_plans_the_transfers_and_reserve_of_a_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let usdc = token_held_by(&env, &client.address, 500);
+        let eurc = token_held_by(&env, &client.address, 200);
+        client.record_payment(&500, &usdc);
+        client.record_payment(&200, &eurc);
+        let destination = Address::generate(&env);
+
+        let (transfers, reserve, error_code) = client.simulate_sweep(&destination);
+        assert_eq!(error_code, 0);
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.get_last_reserve_event(), None);
+
+        let page = client.get_payments_page(&0, &10);
+        assert_eq!(transfers.len(), page.payments.len());
+        for (transfer, payment) in transfers.iter().zip(page.payments.iter()) {
+            assert_eq!(transfer.asset, payment.asset);
+            assert_eq!(transfer.amount, payment.amount);
+        }
+
+        client.sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        let reclaimed = latest_reserve_event(&client);
+        assert_eq!(reclaimed.destination, destination);
+        assert_eq!(reserve, reclaimed.amount);
+        assert!(reserve > 0);
+    }
+
     #[test]
     fn test_simulate_sweep_returns_error_when_no_payment() {
         let env = Env::default();
@@ -939,7 +1236,7 @@ mod test {
             &Address::generate(&env),
         );
 
-        let (payments, error_code) = client.simulate_sweep(&destination);
+        let (payments, _, error_code) = client.simulate_sweep(&destination);
         assert_eq!(error_code, Error::NoPaymentReceived as u32);
         assert_eq!(payments.len(), 0);
     }
@@ -968,7 +1265,7 @@ mod test {
         client.record_payment(&100, &asset);
         env.ledger().set_sequence_number(expiry_ledger);
 
-        let (payments, error_code) = client.simulate_sweep(&destination);
+        let (payments, _, error_code) = client.simulate_sweep(&destination);
         assert_eq!(error_code, Error::AccountExpired as u32);
         assert_eq!(payments.len(), 0);
     }
@@ -986,7 +1283,8 @@ mod test {
 
         let creator = Address::generate(&env);
         let recovery = Address::generate(&env);
-        let asset = Address::generate(&env);
+        let recorded_amount: i128 = 42_000_000;
+        let asset = token_held_by(&env, &contract_id, recorded_amount);
         let expiry_ledger = env.ledger().sequence() + 1;
 
         client.initialize(
@@ -997,7 +1295,6 @@ mod test {
             &Address::generate(&env),
         );
 
-        let recorded_amount: i128 = 42_000_000;
         client.record_payment(&recorded_amount, &asset);
 
         assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
@@ -1014,14 +1311,13 @@ mod test {
         for i in 0..events.len() {
             let (contract, topics, data) = events.get_unchecked(i);
             let _ = contract;
-            if topics.len() == 0 {
+            if topics.is_empty() {
                 continue;
             }
             if let Ok(topic_sym) = soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
             {
                 if topic_sym == symbol_short!("expired") {
-                    let expired_event: AccountExpired =
-                        AccountExpired::try_from_val(&env, &data).unwrap();
+                    let expired_event: AccountExpired = event_body::<AccountExpired>(&env, &data);
                     assert_eq!(expired_event.recovery_address, recovery);
                     assert_eq!(expired_event.amount_returned, recorded_amount);
                     found_expired = true;
@@ -1035,8 +1331,5477 @@ mod test {
         assert_eq!(info_after.status, AccountStatus::Expired);
         assert_eq!(info_after.swept_to, Some(recovery.clone()));
         assert_eq!(info_after.payment_count, 1);
-        assert_eq!(info_after.payments.get(0).unwrap().amount, recorded_amount);
+        assert_eq!(
+            client
+                .get_payments_page(&0, &1)
+                .payments
+                .get(0)
+                .unwrap()
+                .amount,
+            recorded_amount
+        );
         assert_eq!(client.get_reserve_remaining(), 0);
         assert!(client.is_reserve_reclaimed());
     }
+
+    // ── Sweep readiness ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_can_sweep_detailed_reports_the_first_obstacle() {
+        use crate::SweepReadiness;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let uninitialized =
+            EphemeralAccountContractClient::new(&env, &env.register(EphemeralAccountContract, ()));
+        assert_eq!(
+            uninitialized.can_sweep_detailed(),
+            SweepReadiness::NotInitialized
+        );
+
+        let (_, client) = setup_initialized(&env);
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::NoPayments);
+
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::Ready);
+
+        client.pause();
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::Paused);
+        client.unpause();
+
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        client.prepare_sweep(&destination, &signature);
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::SweepPending);
+        client.abort_sweep();
+
+        let expiry_ledger = client.get_info().expiry_ledger;
+        env.ledger().set_sequence_number(expiry_ledger);
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::Expired);
+
+        env.ledger().set_sequence_number(expiry_ledger - 1);
+        client.sweep(&destination, &signature);
+        assert_eq!(client.can_sweep_detailed(), SweepReadiness::AlreadySwept);
+    }
+
+    // ── Per-asset payment getters ───────────────────────────────────────────
+
+    #[test]
+    fn test_get_payment_assets_and_get_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let asset1 = Address::generate(&env);
+        let asset2 = Address::generate(&env);
+        let unknown = Address::generate(&env);
+        let expiry_ledger = env.ledger().sequence() + 1000;
+
+        client.initialize(
+            &creator,
+            &expiry_ledger,
+            &recovery,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        assert_eq!(client.get_payment_assets().len(), 0);
+
+        client.record_payment(&100, &asset1);
+        client.record_payment(&250, &asset2);
+
+        let assets = client.get_payment_assets();
+        assert_eq!(assets.len(), 2);
+        assert!(assets.contains(&asset1));
+        assert!(assets.contains(&asset2));
+
+        assert_eq!(client.get_payment(&asset1).unwrap().amount, 100);
+        assert_eq!(client.get_payment(&asset2).unwrap().amount, 250);
+        assert_eq!(client.get_payment(&unknown), None);
+    }
+
+    #[test]
+    fn test_payment_getters_before_initialize_are_empty() {
+        let env = Env::default();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        assert_eq!(client.get_payment_assets().len(), 0);
+        assert_eq!(client.get_payment(&Address::generate(&env)), None);
+    }
+
+    #[cfg(feature = "compat-get-payments")]
+    #[test]
+    fn test_compat_get_payments_returns_every_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let asset1 = Address::generate(&env);
+        let asset2 = Address::generate(&env);
+        client.record_payment(&100, &asset1);
+        client.record_payment(&250, &asset2);
+
+        let payments = client.get_payments();
+        assert_eq!(payments.len(), 2);
+        assert_eq!(payments.get(asset1.clone()), client.get_payment(&asset1));
+        assert_eq!(payments.get(asset2.clone()).unwrap().amount, 250);
+    }
+
+    // ── Sweep/expiry race arbitration ───────────────────────────────────────
+
+    #[test]
+    fn test_expire_after_sweep_emits_arbitration_result() {
+        use crate::{ArbitrationResult, SettlementPath};
+        use soroban_sdk::symbol_short;
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
+        let destination = Address::generate(&env);
+        let expiry_ledger = env.ledger().sequence() + 1;
+
+        client.initialize(
+            &creator,
+            &expiry_ledger,
+            &recovery,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        client.record_payment(&100, &asset);
+
+        let auth_sig = BytesN::from_array(&env, &[0u8; 64]);
+        client.sweep(&destination, &auth_sig);
+
+        env.ledger().set_sequence_number(expiry_ledger);
+        client.expire();
+
+        let events = env.events().all();
+        let (_, topics, data) = events.get_unchecked(events.len() - 1);
+        let topic = soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap();
+        assert_eq!(topic, symbol_short!("arbitrate"));
+        let arbitration = event_body::<ArbitrationResult>(&env, &data);
+        assert_eq!(arbitration.winner, SettlementPath::Sweep);
+        assert_eq!(arbitration.loser_reason, Error::AlreadySwept as u32);
+
+        let info = client.get_info();
+        assert_eq!(info.status, AccountStatus::Swept);
+        assert_eq!(info.swept_to, Some(destination));
+        assert_eq!(client.get_reserve_reclaim_event_count(), 1);
+    }
+
+    #[test]
+    fn test_early_or_unauthorized_expiry_after_sweep_reports_nothing() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        assert_eq!(client.try_expire(), Err(Ok(Error::NotExpired)));
+        assert_eq!(
+            client.try_recover(&Address::generate(&env)),
+            Err(Ok(Error::NotExpired))
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number += 1000);
+        assert_eq!(
+            client.try_recover(&Address::generate(&env)),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_expire_twice_returns_invalid_status() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let recovery = Address::generate(&env);
+        let expiry_ledger = env.ledger().sequence() + 1;
+
+        client.initialize(
+            &creator,
+            &expiry_ledger,
+            &recovery,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        env.ledger().set_sequence_number(expiry_ledger);
+        client.expire();
+
+        let result = client.try_expire();
+        assert!(matches!(result, Err(Ok(Error::InvalidStatus))));
+    }
+
+    // ── Reserve over-collection refund ──────────────────────────────────────
+
+    #[test]
+    fn test_sweep_refunds_unused_trustline_reserve_to_creator() {
+        use crate::ReserveRefunded;
+        use soroban_sdk::symbol_short;
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let destination = Address::generate(&env);
+        let expiry_ledger = env.ledger().sequence() + 1000;
+
+        client.initialize(
+            &creator,
+            &expiry_ledger,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        for amount in 1..=3 {
+            client.record_payment(&amount, &token_held_by(&env, &contract_id, amount));
+        }
+
+        let auth_sig = BytesN::from_array(&env, &[0u8; 64]);
+        client.sweep(&destination, &auth_sig);
+
+        let refund_event = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    .map(|topic| topic == symbol_short!("res_rfnd"))
+                    .unwrap_or(false)
+            })
+            .map(|(_, _, data)| event_body::<ReserveRefunded>(&env, &data))
+            .expect("refund event was not emitted");
+        assert_eq!(refund_event.creator, creator);
+        assert_eq!(refund_event.amount, 350_000_000);
+        assert_eq!(refund_event.trustlines_used, 3);
+
+        assert_eq!(client.get_reserve_refunded(), 350_000_000);
+        assert_eq!(latest_reserve_event(&client).amount, 650_000_000);
+        assert_eq!(client.get_reserve_remaining(), 0);
+        assert!(client.is_reserve_reclaimed());
+    }
+
+    #[test]
+    fn test_expiry_does_not_refund_reserve_overcollection() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let recovery = Address::generate(&env);
+        let expiry_ledger = env.ledger().sequence() + 1;
+
+        client.initialize(
+            &Address::generate(&env),
+            &expiry_ledger,
+            &recovery,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+
+        env.ledger().set_sequence_number(expiry_ledger);
+        client.expire();
+
+        assert_eq!(client.get_reserve_refunded(), 0);
+        let reserve_event = latest_reserve_event(&client);
+        assert_eq!(reserve_event.destination, recovery);
+        assert_eq!(reserve_event.amount, BASE_RESERVE_STROOPS);
+    }
+
+    #[test]
+    fn test_recalculate_reserve_refunds_unused_slots_before_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let creator = client.get_info().creator;
+        for amount in 1..=2 {
+            client.record_payment(&amount, &token_held_by(&env, &client.address, amount));
+        }
+
+        assert_eq!(client.recalculate_reserve(), 400_000_000);
+        assert_eq!(env.auths()[0].0, creator);
+        assert_eq!(client.get_reserve_refunded(), 400_000_000);
+        assert_eq!(client.get_reserve_remaining(), 600_000_000);
+        assert_eq!(client.get_info().max_assets, 2);
+        assert_eq!(
+            client.try_record_payment(&3, &Address::generate(&env)),
+            Err(Ok(Error::TooManyPayments))
+        );
+        assert_eq!(client.recalculate_reserve(), 0);
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        assert_eq!(client.get_reserve_refunded(), 400_000_000);
+        assert_eq!(latest_reserve_event(&client).amount, 600_000_000);
+        assert!(client.is_reserve_reclaimed());
+    }
+
+    #[test]
+    fn test_recalculate_reserve_needs_a_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        assert_eq!(
+            client.try_recalculate_reserve(),
+            Err(Ok(Error::NoPaymentReceived))
+        );
+        client.record_payment(&100, &Address::generate(&env));
+        client.lock_config();
+        assert_eq!(
+            client.try_recalculate_reserve(),
+            Err(Ok(Error::ConfigLocked))
+        );
+    }
+
+    // ── Reserve top-up ──────────────────────────────────────────────────────
+
+    /// Balance entry of the native SAC, written directly because native XLM
+    /// cannot be minted in the test environment.
+    #[soroban_sdk::contracttype]
+    enum NativeBalanceKey {
+        Balance(Address),
+    }
+
+    #[soroban_sdk::contracttype]
+    struct NativeBalanceValue {
+        amount: i128,
+        authorized: bool,
+        clawback: bool,
+    }
+
+    fn fund_with_native(env: &Env, holder: &Address, amount: i128) -> Address {
+        let native = env
+            .deployer()
+            .with_stellar_asset(soroban_sdk::Bytes::from_array(env, &[0u8; 4]))
+            .deploy();
+        env.as_contract(&native, || {
+            env.storage().persistent().set(
+                &NativeBalanceKey::Balance(holder.clone()),
+                &NativeBalanceValue {
+                    amount,
+                    authorized: true,
+                    clawback: false,
+                },
+            )
+        });
+        native
+    }
+
+    #[test]
+    fn test_top_up_reserve_admits_an_asset_beyond_max_assets() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let creator = Address::generate(&env);
+        client.initialize_with_options(
+            &creator,
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                max_assets: Some(1),
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &Address::generate(&env));
+        let extra = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&100, &extra),
+            Err(Ok(Error::TooManyPayments))
+        );
+
+        let native = fund_with_native(&env, &creator, 50_000_000);
+        client.top_up_reserve(&50_000_000);
+        assert_eq!(env.auths()[0].0, creator);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("res_topup"))
+            })
+            .expect("ReserveToppedUp should be emitted");
+        assert_eq!(
+            event_body::<crate::ReserveToppedUp>(&env, &data),
+            crate::ReserveToppedUp {
+                creator,
+                amount: 50_000_000,
+                reserve_remaining: 600_000_000,
+            }
+        );
+        assert_eq!(
+            TokenClient::new(&env, &native).balance(&contract_id),
+            50_000_000
+        );
+        assert_eq!(client.get_reserve_remaining(), 600_000_000);
+
+        client.record_payment(&100, &extra);
+        assert_eq!(client.get_info().payment_count, 2);
+        assert_eq!(
+            client.try_record_payment(&100, &Address::generate(&env)),
+            Err(Ok(Error::TooManyPayments))
+        );
+    }
+
+    #[test]
+    fn test_top_up_reserve_rejects_non_positive_amounts() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        assert_eq!(client.try_top_up_reserve(&0), Err(Ok(Error::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_top_up_reserve_is_paid_back_to_the_creator() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let creator = Address::generate(&env);
+        client.initialize_with_options(
+            &creator,
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                max_assets: Some(1),
+                ..Default::default()
+            },
+        );
+        let native = TokenClient::new(&env, &fund_with_native(&env, &creator, 100_000_000));
+
+        // Two trustlines' worth, only one of them used
+        client.top_up_reserve(&100_000_000);
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        assert_eq!(native.balance(&creator), 0);
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        assert_eq!(client.get_reserve_refunded(), 50_000_000);
+        assert_eq!(native.balance(&creator), 100_000_000);
+        assert_eq!(native.balance(&contract_id), 0);
+        client.close_account();
+    }
+
+    // ── record_payment error paths ──────────────────────────────────────────
+
+    fn setup_initialized(env: &Env) -> (Address, EphemeralAccountContractClient<'_>) {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+        );
+        (contract_id, client)
+    }
+
+    /// A fresh SAC token with `amount` minted to `holder`.
+    fn token_held_by(env: &Env, holder: &Address, amount: i128) -> Address {
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        soroban_sdk::token::StellarAssetClient::new(env, &token).mint(holder, &amount);
+        token
+    }
+
+    /// Key [`setup_asset_sweeper`] registers as the account's sweep signer.
+    fn asset_signing_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[9u8; 32])
+    }
+
+    /// Like [`setup_initialized`], with `asset_signing_key()` as the sweep
+    /// signer, so `sweep_assets` calls can be signed with [`sign_assets`].
+    fn setup_asset_sweeper(env: &Env) -> (Address, EphemeralAccountContractClient<'_>) {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                sweep_signer: Some(BytesN::from_array(
+                    env,
+                    &asset_signing_key().verifying_key().to_bytes(),
+                )),
+                ..Default::default()
+            },
+        );
+        (contract_id, client)
+    }
+
+    /// The sweep signer's signature approving `sweep_assets(destination,
+    /// assets)` on `account` at its current sweep nonce.
+    fn sign_assets(
+        env: &Env,
+        account: &Address,
+        destination: &Address,
+        assets: &soroban_sdk::Vec<Address>,
+    ) -> BytesN<64> {
+        use ed25519_dalek::Signer;
+        let digest = env.as_contract(account, || {
+            bridgelet_shared::bytes::account_sweep_assets_message_digest(
+                env,
+                &env.ledger().network_id(),
+                account,
+                storage::get_sweep_nonce(env),
+                destination,
+                &storage::get_authorized_controller(env).unwrap(),
+                assets,
+            )
+        });
+        BytesN::from_array(
+            env,
+            &asset_signing_key().sign(&digest.to_array()).to_bytes(),
+        )
+    }
+
+    #[test]
+    fn test_record_payment_cap_rejection_leaves_state_unchanged() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        for amount in 1..=10 {
+            client.record_payment(&amount, &Address::generate(&env));
+        }
+
+        let rejected = Address::generate(&env);
+        let result = client.try_record_payment(&500, &rejected);
+
+        assert_eq!(result, Err(Ok(Error::TooManyPayments)));
+        assert_eq!(Error::TooManyPayments as u32, 14);
+        assert_eq!(client.get_info().payment_count, 10);
+        assert_eq!(client.get_payment(&rejected), None);
+    }
+
+    #[test]
+    fn test_record_payment_duplicate_is_reported_before_cap() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let first = Address::generate(&env);
+        client.record_payment(&1, &first);
+        for amount in 2..=10 {
+            client.record_payment(&amount, &Address::generate(&env));
+        }
+
+        // A duplicate of a recorded asset is reported as such even at the cap.
+        assert_eq!(
+            client.try_record_payment(&5, &first),
+            Err(Ok(Error::DuplicateAsset))
+        );
+    }
+
+    #[test]
+    fn test_record_payment_rejects_negative_amount() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let asset = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&-1, &asset),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_record_payment(&i128::MIN, &asset),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(Error::InvalidAmount as u32, 4);
+        assert_eq!(client.get_status(), AccountStatus::Active);
+        assert_eq!(client.get_info().payment_count, 0);
+    }
+
+    #[test]
+    fn test_record_payment_duplicate_keeps_original_amount() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let asset = Address::generate(&env);
+        client.record_payment(&100, &asset);
+
+        assert_eq!(
+            client.try_record_payment(&900, &asset),
+            Err(Ok(Error::DuplicateAsset))
+        );
+        assert_eq!(Error::DuplicateAsset as u32, 13);
+        assert_eq!(client.get_payment(&asset).unwrap().amount, 100);
+        assert_eq!(client.get_info().payment_count, 1);
+    }
+
+    #[test]
+    fn test_record_payment_after_sweep_returns_already_swept() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        let late_asset = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&50, &late_asset),
+            Err(Ok(Error::AlreadySwept))
+        );
+        assert_eq!(client.get_payment(&late_asset), None);
+        assert_eq!(client.get_info().payment_count, 1);
+    }
+
+    #[test]
+    fn test_record_payment_after_expire_returns_account_expired() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+
+        let late_asset = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&50, &late_asset),
+            Err(Ok(Error::AccountExpired))
+        );
+        assert_eq!(client.get_status(), AccountStatus::Expired);
+        assert_eq!(client.get_payment(&late_asset), None);
+    }
+
+    #[test]
+    fn test_record_payment_past_expiry_ledger_is_rejected_before_expire() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&100, &Address::generate(&env));
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        let late_asset = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&50, &late_asset),
+            Err(Ok(Error::AccountExpired))
+        );
+        assert_eq!(
+            client.can_record_payment(&late_asset).reason,
+            Some(Error::AccountExpired as u32)
+        );
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.get_payment(&late_asset), None);
+    }
+
+    #[test]
+    fn test_can_record_payment_predicts_record_outcome() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let first = Address::generate(&env);
+        assert_eq!(
+            client.can_record_payment(&first),
+            crate::RecordCheck {
+                allowed: true,
+                reason: None,
+            }
+        );
+
+        client.record_payment(&1, &first);
+        assert_eq!(
+            client.can_record_payment(&first).reason,
+            Some(Error::DuplicateAsset as u32)
+        );
+
+        for amount in 2..=10 {
+            client.record_payment(&amount, &Address::generate(&env));
+        }
+        let next = Address::generate(&env);
+        let check = client.can_record_payment(&next);
+        assert!(!check.allowed);
+        assert_eq!(check.reason, Some(Error::TooManyPayments as u32));
+        assert_eq!(
+            client.try_record_payment(&5, &next),
+            Err(Ok(Error::TooManyPayments))
+        );
+
+        let uninitialized =
+            EphemeralAccountContractClient::new(&env, &env.register(EphemeralAccountContract, ()));
+        assert_eq!(
+            uninitialized.can_record_payment(&next).reason,
+            Some(Error::NotInitialized as u32)
+        );
+    }
+
+    // ── Batch recording ─────────────────────────────────────────────────────
+
+    fn asset_amount(asset: &Address, amount: i128) -> crate::AssetAmount {
+        crate::AssetAmount {
+            asset: asset.clone(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn test_record_payments_records_a_batch_with_one_event() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let usdc = Address::generate(&env);
+        let eurc = Address::generate(&env);
+        let batch = soroban_sdk::vec![&env, asset_amount(&usdc, 100), asset_amount(&eurc, 50)];
+
+        client.record_payments(&batch);
+        let payment_events = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                let topic = soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0));
+                topic == Ok(soroban_sdk::symbol_short!("payment"))
+                    || topic == Ok(soroban_sdk::symbol_short!("multi_pay"))
+            })
+            .count();
+        assert_eq!(payment_events, 0);
+        let event = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("batch_pay"))
+            })
+            .expect("BatchPaymentReceived should be emitted");
+        assert_eq!(
+            event_body::<crate::BatchPaymentReceived>(&env, &event.2),
+            crate::BatchPaymentReceived {
+                payments: batch,
+                payment_count: 2,
+            }
+        );
+
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.get_payment(&usdc).unwrap().amount, 100);
+        assert_eq!(client.get_payment(&eurc).unwrap().amount, 50);
+    }
+
+    #[test]
+    fn test_record_payments_is_all_or_nothing() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let usdc = Address::generate(&env);
+
+        assert_eq!(
+            client.try_record_payments(&soroban_sdk::vec![
+                &env,
+                asset_amount(&usdc, 100),
+                asset_amount(&Address::generate(&env), 0),
+            ]),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(client.get_payment(&usdc), None);
+
+        let mut too_many = soroban_sdk::Vec::new(&env);
+        for _ in 0..9 {
+            too_many.push_back(asset_amount(&Address::generate(&env), 10));
+        }
+        client.record_payment(&10, &usdc);
+        client.record_payment(&10, &Address::generate(&env));
+        assert_eq!(
+            client.try_record_payments(&too_many),
+            Err(Ok(Error::TooManyPayments))
+        );
+        assert_eq!(client.get_info().payment_count, 2);
+        assert_eq!(
+            client.try_record_payments(&soroban_sdk::Vec::new(&env)),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    // ── Event topics ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_events_are_indexed_by_asset_and_destination() {
+        use soroban_sdk::{symbol_short, IntoVal, Val};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
+        let destination = Address::generate(&env);
+
+        client.record_payment(&100, &asset);
+        let topics = env.events().all().last().unwrap().1;
+        let expected: soroban_sdk::Vec<Val> = (symbol_short!("payment"), asset).into_val(&env);
+        assert_eq!(topics, expected);
+
+        client.sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        let swept = env
+            .events()
+            .all()
+            .iter()
+            .find(|(contract, topics, _)| {
+                *contract == contract_id
+                    && soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(symbol_short!("swept_mul"))
+            })
+            .expect("SweepExecutedMulti should be emitted");
+        let expected: soroban_sdk::Vec<Val> =
+            (symbol_short!("swept_mul"), destination).into_val(&env);
+        assert_eq!(swept.1, expected);
+    }
+
+    #[test]
+    fn test_events_carry_a_versioned_header_naming_the_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let asset = Address::generate(&env);
+        env.ledger().with_mut(|li| li.sequence_number += 5);
+
+        client.record_payment(&100, &asset);
+        let (_, _, data) = env.events().all().last().unwrap();
+        let (header, body) = <(EventHeader, Val)>::try_from_val(&env, &data).unwrap();
+        assert_eq!(
+            header,
+            EventHeader {
+                schema_version: bridgelet_shared::EVENT_SCHEMA_VERSION,
+                account: contract_id,
+                ledger: env.ledger().sequence(),
+            }
+        );
+        let received = crate::PaymentReceived::try_from_val(&env, &body).unwrap();
+        assert_eq!(received.asset, asset);
+    }
+
+    // ── Event payload size ──────────────────────────────────────────────────
+
+    /// Budget for any single event payload, well under Soroban's
+    /// per-transaction contract event size limit.
+    const MAX_EVENT_PAYLOAD_BYTES: u32 = 1_024;
+
+    fn sweep_with_assets(env: &Env, asset_count: u32) -> EphemeralAccountContractClient<'_> {
+        let (_, client) = setup_initialized(env);
+        for amount in 1..=asset_count {
+            let amount = amount as i128;
+            client.record_payment(&amount, &token_held_by(env, &client.address, amount));
+        }
+        client.sweep(
+            &Address::generate(env),
+            &BytesN::from_array(env, &[0u8; 64]),
+        );
+        client
+    }
+
+    fn sweep_event(env: &Env) -> crate::SweepExecutedMulti {
+        use soroban_sdk::symbol_short;
+
+        env.events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(env, &topics.get_unchecked(0))
+                    .map(|topic| topic == symbol_short!("swept_mul"))
+                    .unwrap_or(false)
+            })
+            .map(|(_, _, data)| event_body::<crate::SweepExecutedMulti>(env, &data))
+            .expect("sweep event was not emitted")
+    }
+
+    #[test]
+    fn test_max_asset_sweep_event_is_summarized_within_size_budget() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = sweep_with_assets(&env, 10);
+
+        for (_, _, data) in env.events().all().iter() {
+            let size = soroban_sdk::Val::to_xdr(data, &env).len();
+            assert!(
+                size <= MAX_EVENT_PAYLOAD_BYTES,
+                "event payload is {size} bytes"
+            );
+        }
+
+        let event = sweep_event(&env);
+        assert!(event.truncated);
+        assert_eq!(event.payment_count, 10);
+        assert_eq!(event.payments.len(), 3);
+
+        // Full detail stays available through the views.
+        let assets = client.get_payment_assets();
+        assert_eq!(assets.len(), 10);
+        for payment in event.payments.iter() {
+            assert_eq!(client.get_payment(&payment.asset), Some(payment.clone()));
+        }
+    }
+
+    #[test]
+    fn test_small_sweep_event_carries_every_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        sweep_with_assets(&env, 3);
+
+        let event = sweep_event(&env);
+        assert!(!event.truncated);
+        assert_eq!(event.payment_count, 3);
+        assert_eq!(event.payments.len(), 3);
+    }
+
+    // ── Partial sweep status ────────────────────────────────────────────────
+
+    #[test]
+    fn test_settled_amounts_track_partial_delivery() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let asset = token_held_by(&env, &client.address, 100);
+        client.record_payment(&100, &asset);
+        let settled = |amount| {
+            soroban_sdk::vec![
+                &env,
+                crate::AssetAmount {
+                    asset: asset.clone(),
+                    amount,
+                }
+            ]
+        };
+        assert_eq!(client.get_payments_page(&0, &10).settled, settled(0));
+        assert_eq!(
+            client.try_record_undelivered(&soroban_sdk::Vec::new(&env)),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        let event = sweep_event(&env);
+        assert_eq!((event.total_amount, event.total_settled), (100, 100));
+        assert_eq!(client.get_payments_page(&0, &10).settled, settled(100));
+
+        // The controller could deliver only 60.
+        client.mark_partially_swept();
+        client.record_undelivered(&soroban_sdk::vec![
+            &env,
+            crate::Payment {
+                asset: asset.clone(),
+                amount: 40,
+                timestamp: 0,
+                payer: None,
+            }
+        ]);
+        let page = client.get_payments_page(&0, &10);
+        assert_eq!(page.payments.get_unchecked(0).amount, 100);
+        assert_eq!(page.settled, settled(60));
+
+        client.complete_partial_sweep();
+        assert_eq!(client.get_payments_page(&0, &10).settled, settled(100));
+    }
+
+    #[test]
+    fn test_partial_sweep_status_transitions() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        assert_eq!(
+            client.try_mark_partially_swept(),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        client.mark_partially_swept();
+        assert_eq!(client.get_status(), AccountStatus::PartiallySwept);
+
+        // Still settled: no second sweep, no late payments.
+        assert_eq!(
+            client.try_sweep(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64])
+            ),
+            Err(Ok(Error::AlreadySwept))
+        );
+        assert_eq!(
+            client.try_record_payment(&5, &Address::generate(&env)),
+            Err(Ok(Error::AlreadySwept))
+        );
+
+        client.complete_partial_sweep();
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+        assert_eq!(
+            client.try_complete_partial_sweep(),
+            Err(Ok(Error::InvalidStatus))
+        );
+    }
+
+    #[test]
+    fn test_expire_after_partial_sweep_reports_lost_race() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        client.mark_partially_swept();
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+
+        assert_eq!(client.get_status(), AccountStatus::PartiallySwept);
+    }
+
+    // ── Two-phase sweep ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_prepared_sweep_settles_only_on_commit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let asset = Address::generate(&env);
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+
+        client.record_payment(&100, &asset);
+        let payments = client.prepare_sweep(&destination, &signature);
+        assert_eq!(payments.len(), 1);
+        assert_eq!(client.get_status(), AccountStatus::PendingSweep);
+        assert_eq!(client.get_info().swept_to, None);
+
+        assert_eq!(
+            client.try_record_payment(&5, &Address::generate(&env)),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_sweep(&destination, &signature),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_prepare_sweep(&destination, &signature),
+            Err(Ok(Error::SweepPending))
+        );
+
+        client.commit_sweep();
+        assert_eq!(sweep_event(&env).destination, destination);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+        assert_eq!(client.get_info().swept_to, Some(destination));
+        assert_eq!(client.try_commit_sweep(), Err(Ok(Error::InvalidStatus)));
+    }
+
+    #[test]
+    fn test_sweep_transfers_the_payments_to_the_destination() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
+        let destination = Address::generate(&env);
+        client.record_payment(&100, &asset);
+
+        client.sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        let token = soroban_sdk::token::TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&destination), 100);
+        assert_eq!(token.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_failed_transfer_reverts_the_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        // The books claim more than the account holds.
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 40));
+
+        assert_eq!(
+            client.try_sweep(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64])
+            ),
+            Err(Ok(Error::TransferFailed))
+        );
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.get_sweep_nonce(), 0);
+    }
+
+    #[test]
+    fn test_deliver_moves_a_prepared_sweep_for_the_controller() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let controller = Address::generate(&env);
+        client.initialize(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &controller,
+            &Address::generate(&env),
+        );
+        let asset = token_held_by(&env, &contract_id, 100);
+        let destination = Address::generate(&env);
+        client.record_payment(&100, &asset);
+        assert_eq!(
+            client.try_deliver(&asset, &destination, &60),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        client.prepare_sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        for (asset, amount) in [
+            (asset.clone(), -1),
+            (asset.clone(), 101),
+            (Address::generate(&env), 1),
+        ] {
+            assert_eq!(
+                client.try_deliver(&asset, &destination, &amount),
+                Err(Ok(Error::InvalidAmount))
+            );
+        }
+
+        client.deliver(&asset, &destination, &60);
+        assert_eq!(env.auths()[0].0, controller);
+        let token = soroban_sdk::token::TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&destination), 60);
+        assert_eq!(
+            client.try_deliver(&asset, &destination, &60),
+            Err(Ok(Error::TransferFailed))
+        );
+
+        client.commit_sweep();
+        assert_eq!(
+            client.try_deliver(&asset, &destination, &40),
+            Err(Ok(Error::InvalidStatus))
+        );
+    }
+
+    #[test]
+    fn test_aborted_sweep_returns_the_account_to_payment_received() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+
+        assert_eq!(client.try_abort_sweep(), Err(Ok(Error::InvalidStatus)));
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        client.prepare_sweep(&Address::generate(&env), &signature);
+
+        client.abort_sweep();
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.try_commit_sweep(), Err(Ok(Error::InvalidStatus)));
+
+        let destination = Address::generate(&env);
+        client.sweep(&destination, &signature);
+        assert_eq!(client.get_info().swept_to, Some(destination));
+    }
+
+    /// A paid account whose prepared sweeps claim it for 50 ledgers.
+    fn setup_contended(env: &Env) -> EphemeralAccountContractClient<'_> {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                sweep_contention_ledgers: 50,
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &Address::generate(env));
+        client
+    }
+
+    #[test]
+    fn test_aborted_sweep_keeps_other_destinations_out_until_the_claim_runs_out() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup_contended(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+
+        let prepared_at = env.ledger().sequence();
+        client.prepare_sweep(&first, &signature);
+        client.abort_sweep();
+        assert_eq!(
+            client.try_prepare_sweep(&second, &signature),
+            Err(Ok(Error::SweepContention))
+        );
+
+        env.ledger().set_sequence_number(prepared_at + 50);
+        assert_eq!(
+            client.try_prepare_sweep(&second, &signature),
+            Err(Ok(Error::SweepContention))
+        );
+
+        env.ledger().set_sequence_number(prepared_at + 51);
+        client.prepare_sweep(&second, &signature);
+        client.commit_sweep();
+        assert_eq!(client.get_info().swept_to, Some(second));
+    }
+
+    #[test]
+    fn test_claiming_destination_may_prepare_again_after_abort() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup_contended(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        let destination = Address::generate(&env);
+
+        client.prepare_sweep(&destination, &signature);
+        client.abort_sweep();
+        client.prepare_sweep(&destination, &signature);
+        client.commit_sweep();
+        assert_eq!(client.get_info().swept_to, Some(destination));
+    }
+
+    #[test]
+    fn test_sweeps_are_not_claimed_without_contention_ledgers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        client.record_payment(&100, &Address::generate(&env));
+
+        client.prepare_sweep(&Address::generate(&env), &signature);
+        client.abort_sweep();
+        let destination = Address::generate(&env);
+        client.prepare_sweep(&destination, &signature);
+        client.commit_sweep();
+        assert_eq!(client.get_info().swept_to, Some(destination));
+    }
+
+    #[test]
+    fn test_pending_sweep_refuses_every_record_path() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let asset = Address::generate(&env);
+        client.record_payment(&100, &asset);
+        client.prepare_sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.try_record_payment(&5, &other),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_record_payment_from(&Address::generate(&env), &5, &other),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_record_payment_with_reference(
+                &5,
+                &other,
+                &BytesN::from_array(&env, &[1; 32])
+            ),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_record_payments(&soroban_sdk::vec![
+                &env,
+                crate::AssetAmount {
+                    asset: other,
+                    amount: 5,
+                }
+            ]),
+            Err(Ok(Error::SweepPending))
+        );
+    }
+
+    #[test]
+    fn test_pending_sweep_refuses_refunds_until_aborted() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let payer = Address::generate(&env);
+        let asset = token_held_by(&env, &payer, 100);
+        client.record_payment_from(&payer, &100, &asset);
+        client.prepare_sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        assert_eq!(
+            client.try_refund_stale(&asset),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_refund_payment(&asset),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(
+            client.try_refund_excess(&asset),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(client.try_clawback(&asset), Err(Ok(Error::SweepPending)));
+
+        // Once released, each is judged on the payment again.
+        client.abort_sweep();
+        assert_eq!(
+            client.try_refund_stale(&asset),
+            Err(Ok(Error::NotRefundable))
+        );
+        assert_eq!(
+            client.try_refund_excess(&asset),
+            Err(Ok(Error::NotRefundable))
+        );
+        assert_eq!(
+            client.try_clawback(&asset),
+            Err(Ok(Error::ClawbackWindowClosed))
+        );
+    }
+
+    #[test]
+    fn test_pending_sweep_refuses_expiry_until_aborted() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        client.prepare_sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        let info = client.get_info();
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        assert_eq!(client.try_expire(), Err(Ok(Error::SweepPending)));
+        assert_eq!(
+            client.try_recover(&info.creator),
+            Err(Ok(Error::SweepPending))
+        );
+        assert_eq!(client.get_status(), AccountStatus::PendingSweep);
+
+        client.abort_sweep();
+        client.expire();
+        assert_eq!(client.get_status(), AccountStatus::Expired);
+        assert_eq!(client.get_info().swept_to, Some(info.recovery_address));
+    }
+
+    #[test]
+    fn test_pending_sweep_refused_recovery_leaves_the_sweep_to_commit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&100, &Address::generate(&env));
+        let destination = Address::generate(&env);
+        client.prepare_sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+
+        let info = client.get_info();
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        assert_eq!(
+            client.try_recover(&info.recovery_address),
+            Err(Ok(Error::SweepPending))
+        );
+
+        client.commit_sweep();
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+        assert_eq!(client.get_info().swept_to, Some(destination));
+    }
+
+    #[test]
+    fn test_sweep_nonce_increments_on_every_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (account, client) = setup_asset_sweeper(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        let usdc = token_held_by(&env, &account, 100);
+        let eurc = token_held_by(&env, &account, 50);
+        client.record_payment(&100, &usdc);
+        client.record_payment(&50, &eurc);
+        assert_eq!(client.get_sweep_nonce(), 0);
+
+        for (expected, asset) in [(1, usdc), (2, eurc)] {
+            let destination = Address::generate(&env);
+            let assets = soroban_sdk::vec![&env, asset];
+            let signature = sign_assets(&env, &account, &destination, &assets);
+            client.sweep_assets(&destination, &assets, &signature);
+            assert_eq!(client.get_sweep_nonce(), expected);
+        }
+
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&100, &Address::generate(&env));
+        client.prepare_sweep(&Address::generate(&env), &signature);
+        assert_eq!(client.get_sweep_nonce(), 0);
+        client.commit_sweep();
+        assert_eq!(client.get_sweep_nonce(), 1);
+    }
+
+    // ── Per-asset sweeps ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_sweep_assets_sweeps_only_listed_assets() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (account, client) = setup_asset_sweeper(&env);
+        let usdc = token_held_by(&env, &account, 100);
+        let eurc = token_held_by(&env, &account, 40);
+        client.record_payment(&100, &usdc);
+        client.record_payment(&40, &eurc);
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+
+        let assets = soroban_sdk::vec![&env, eurc.clone()];
+        let swept = client.sweep_assets(
+            &destination,
+            &assets,
+            &sign_assets(&env, &account, &destination, &assets),
+        );
+        assert_eq!(sweep_event(&env).total_settled, 40);
+        assert_eq!(swept.len(), 1);
+        assert_eq!(swept.get_unchecked(0).amount, 40);
+        assert_eq!(client.get_status(), AccountStatus::PartiallySwept);
+        assert_eq!(client.get_info().payment_count, 2);
+
+        // The rest stays recorded but closed to new payments and full sweeps.
+        assert_eq!(
+            client.try_record_payment(&5, &Address::generate(&env)),
+            Err(Ok(Error::AlreadySwept))
+        );
+        assert_eq!(
+            client.try_sweep(&destination, &signature),
+            Err(Ok(Error::AlreadySwept))
+        );
+
+        let assets = soroban_sdk::vec![&env, usdc.clone()];
+        client.sweep_assets(
+            &destination,
+            &assets,
+            &sign_assets(&env, &account, &destination, &assets),
+        );
+        assert_eq!(sweep_event(&env).total_settled, 140);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+        assert_eq!(
+            client.try_sweep_assets(&destination, &soroban_sdk::vec![&env, usdc], &signature),
+            Err(Ok(Error::AlreadySwept))
+        );
+    }
+
+    #[test]
+    fn test_sweep_assets_rejects_invalid_selections() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (account, client) = setup_asset_sweeper(&env);
+        let usdc = token_held_by(&env, &account, 100);
+        let eurc = token_held_by(&env, &account, 40);
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        assert_eq!(
+            client.try_sweep_assets(
+                &destination,
+                &soroban_sdk::vec![&env, usdc.clone()],
+                &signature
+            ),
+            Err(Ok(Error::NoPaymentReceived))
+        );
+
+        client.record_payment(&100, &usdc);
+        client.record_payment(&40, &eurc);
+        for invalid in [
+            soroban_sdk::Vec::new(&env),
+            soroban_sdk::vec![&env, usdc.clone(), usdc.clone()],
+            soroban_sdk::vec![&env, Address::generate(&env)],
+        ] {
+            assert_eq!(
+                client.try_sweep_assets(&destination, &invalid, &signature),
+                Err(Ok(Error::InvalidAssetSelection))
+            );
+        }
+
+        let assets = soroban_sdk::vec![&env, usdc.clone()];
+        client.sweep_assets(
+            &destination,
+            &assets,
+            &sign_assets(&env, &account, &destination, &assets),
+        );
+        assert_eq!(
+            client.try_sweep_assets(
+                &destination,
+                &soroban_sdk::vec![&env, eurc, usdc],
+                &signature
+            ),
+            Err(Ok(Error::AlreadySwept))
+        );
+        assert_eq!(client.get_status(), AccountStatus::PartiallySwept);
+    }
+
+    #[test]
+    fn test_sweep_assets_rejects_a_signature_for_another_asset_set() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (account, client) = setup_asset_sweeper(&env);
+        let usdc = token_held_by(&env, &account, 100);
+        let eurc = token_held_by(&env, &account, 40);
+        client.record_payment(&100, &usdc);
+        client.record_payment(&40, &eurc);
+        let destination = Address::generate(&env);
+
+        let signed = soroban_sdk::vec![&env, usdc.clone()];
+        let signature = sign_assets(&env, &account, &destination, &signed);
+        for swapped in [
+            soroban_sdk::vec![&env, eurc.clone()],
+            soroban_sdk::vec![&env, usdc.clone(), eurc.clone()],
+        ] {
+            assert!(client
+                .try_sweep_assets(&destination, &swapped, &signature)
+                .is_err());
+        }
+        let both = soroban_sdk::vec![&env, usdc.clone(), eurc.clone()];
+        let signature_for_both = sign_assets(&env, &account, &destination, &both);
+        assert!(client
+            .try_sweep_assets(
+                &destination,
+                &soroban_sdk::vec![&env, eurc, usdc],
+                &signature_for_both
+            )
+            .is_err());
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(client.get_sweep_nonce(), 0);
+
+        client.sweep_assets(&destination, &signed, &signature);
+        assert_eq!(client.get_status(), AccountStatus::PartiallySwept);
+    }
+
+    #[test]
+    fn test_sweep_assets_requires_a_sweep_signer() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let usdc = Address::generate(&env);
+        client.record_payment(&100, &usdc);
+
+        assert_eq!(
+            client.try_sweep_assets(
+                &Address::generate(&env),
+                &soroban_sdk::vec![&env, usdc],
+                &BytesN::from_array(&env, &[0u8; 64])
+            ),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_expire_returns_assets_sweep_assets_left_behind() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (account, client) = setup_asset_sweeper(&env);
+        let usdc = token_held_by(&env, &account, 100);
+        client.record_payment(&100, &usdc);
+        client.record_payment(&40, &token_held_by(&env, &account, 40));
+        let destination = Address::generate(&env);
+        let assets = soroban_sdk::vec![&env, usdc];
+        client.sweep_assets(
+            &destination,
+            &assets,
+            &sign_assets(&env, &account, &destination, &assets),
+        );
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                    == Some(soroban_sdk::symbol_short!("expired"))
+            })
+            .expect("AccountExpired should be emitted");
+        let event = event_body::<crate::AccountExpired>(&env, &data);
+        assert_eq!(event.amount_returned, 40);
+        assert_eq!(client.get_status(), AccountStatus::Expired);
+    }
+
+    // ── Lifecycle ledgers ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_lifecycle_tracks_creation_first_payment_and_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(100);
+        let (_, client) = setup_initialized(&env);
+
+        let lifecycle = client.get_lifecycle();
+        assert_eq!(lifecycle.created_ledger, 100);
+        assert_eq!(lifecycle.first_payment_ledger, None);
+        assert_eq!(lifecycle.settled_ledger, None);
+
+        env.ledger().set_sequence_number(120);
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+        env.ledger().set_sequence_number(130);
+        client.record_payment(&50, &token_held_by(&env, &client.address, 50));
+        env.ledger().set_sequence_number(150);
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        let lifecycle = client.get_lifecycle();
+        assert_eq!(lifecycle.created_ledger, 100);
+        assert_eq!(lifecycle.first_payment_ledger, Some(120));
+        assert_eq!(lifecycle.settled_ledger, Some(150));
+    }
+
+    #[test]
+    fn test_account_expired_event_carries_lifecycle_ledgers() {
+        use crate::events::AccountExpired;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_sequence_number(100);
+        let (contract_id, client) = setup_initialized(&env);
+
+        env.ledger().set_sequence_number(110);
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        env.ledger().set_sequence_number(1_100);
+        client.expire();
+
+        let expired = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(contract, _, _)| *contract == contract_id)
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("expired"))
+            })
+            .map(|(_, _, data)| event_body::<AccountExpired>(&env, &data))
+            .expect("AccountExpired was not emitted");
+        assert_eq!(expired.created_ledger, 100);
+        assert_eq!(expired.first_payment_ledger, Some(110));
+        assert_eq!(expired.settled_ledger, 1_100);
+    }
+
+    #[test]
+    fn test_get_lifecycle_requires_initialization() {
+        let env = Env::default();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        assert_eq!(client.try_get_lifecycle(), Err(Ok(Error::NotInitialized)));
+    }
+
+    // ── Attestation ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_initialize_with_attestation_surfaces_it_in_info_and_event() {
+        use crate::{events::AccountCreated, InitOptions};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let attestation = BytesN::from_array(&env, &[9u8; 32]);
+
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                attestation: Some(attestation.clone()),
+                ..Default::default()
+            },
+        );
+
+        let created = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("created"))
+            })
+            .map(|(_, _, data)| event_body::<AccountCreated>(&env, &data))
+            .expect("AccountCreated was not emitted");
+        assert_eq!(created.attestation, Some(attestation.clone()));
+        assert_eq!(client.get_info().attestation, Some(attestation));
+    }
+
+    #[test]
+    fn test_initialize_without_attestation() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        assert_eq!(client.get_info().attestation, None);
+    }
+
+    // ── Payment references ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_payment_with_reference_stores_it_and_emits_it() {
+        use crate::events::{MultiPaymentReceived, PaymentReceived};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let usdc = Address::generate(&env);
+        let eurc = Address::generate(&env);
+        let reference = BytesN::from_array(&env, &[7u8; 32]);
+        let data_of = |topic: soroban_sdk::Symbol| {
+            env.events()
+                .all()
+                .iter()
+                .find(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(topic.clone())
+                })
+                .map(|(_, _, data)| data)
+                .expect("event was not emitted")
+        };
+
+        client.record_payment_with_reference(&100, &usdc, &reference);
+        let received: PaymentReceived =
+            event_body(&env, &data_of(soroban_sdk::symbol_short!("payment")));
+        assert_eq!(received.reference, Some(reference.clone()));
+
+        client.record_payment(&50, &eurc);
+        let received: MultiPaymentReceived =
+            event_body(&env, &data_of(soroban_sdk::symbol_short!("multi_pay")));
+        assert_eq!(received.reference, None);
+
+        assert_eq!(client.get_payment_reference(&usdc), Some(reference));
+        assert_eq!(client.get_payment_reference(&eurc), None);
+    }
+
+    // ── Payment ordering ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_payments_iterate_in_ascending_asset_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let mut assets: std::vec::Vec<Address> = (0..5)
+            .map(|_| token_held_by(&env, &client.address, 5))
+            .collect();
+        // Record in descending order so insertion order differs from the
+        // canonical order.
+        assets.sort();
+        for (i, asset) in assets.iter().rev().enumerate() {
+            client.record_payment(&(i as i128 + 1), asset);
+        }
+
+        let indexed: std::vec::Vec<Address> = client.get_payment_assets().iter().collect();
+        assert_eq!(indexed, assets);
+
+        let info_order: std::vec::Vec<Address> = client
+            .get_payments_page(&0, &10)
+            .payments
+            .iter()
+            .map(|p| p.asset)
+            .collect();
+        assert_eq!(info_order, assets);
+
+        let swept_order: std::vec::Vec<Address> = client
+            .sweep_and_report(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64]),
+            )
+            .iter()
+            .map(|p| p.asset)
+            .collect();
+        assert_eq!(swept_order, assets);
+    }
+
+    #[test]
+    fn test_payments_page_slices_payments_in_asset_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        let mut assets: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&env)).collect();
+        assets.sort();
+        for (i, asset) in assets.iter().enumerate() {
+            client.record_payment(&(i as i128 + 1), asset);
+        }
+        assert_eq!(client.get_info().payment_count, 5);
+
+        let page = client.get_payments_page(&1, &3);
+        assert_eq!(page.total, 5);
+        let page_assets: std::vec::Vec<Address> = page.payments.iter().map(|p| p.asset).collect();
+        assert_eq!(page_assets, assets[1..4]);
+        let settled_assets: std::vec::Vec<Address> = page.settled.iter().map(|s| s.asset).collect();
+        assert_eq!(settled_assets, assets[1..4]);
+
+        // The tail page is short, and a page past the end is empty.
+        assert_eq!(client.get_payments_page(&4, &3).payments.len(), 1);
+        let past_end = client.get_payments_page(&5, &3);
+        assert!(past_end.payments.is_empty());
+        assert_eq!(past_end.total, 5);
+
+        // Oversized limits are capped rather than rejected.
+        assert_eq!(client.get_payments_page(&0, &u32::MAX).payments.len(), 5);
+    }
+
+    #[test]
+    fn test_payments_page_is_empty_before_initialization() {
+        let env = Env::default();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let page = client.get_payments_page(&0, &10);
+        assert!(page.payments.is_empty());
+        assert_eq!(page.total, 0);
+    }
+
+    // ── Custom account sweep policy ─────────────────────────────────────────
+
+    mod check_auth {
+        use super::*;
+        use crate::InitOptions;
+        use ed25519_dalek::{Signer, SigningKey};
+        use soroban_sdk::{
+            auth::{Context, ContractContext},
+            vec, IntoVal, Symbol, Val,
+        };
+
+        fn signing_key() -> SigningKey {
+            SigningKey::from_bytes(&[5u8; 32])
+        }
+
+        struct Fixture {
+            env: Env,
+            account: Address,
+            controller: Address,
+            asset: Address,
+            destination: Address,
+        }
+
+        /// A paid account whose sweep signer is `signing_key()` and whose
+        /// sweeps are locked to `destination`.
+        fn setup() -> Fixture {
+            setup_with(|options, _, _| options)
+        }
+
+        /// Same as [`setup`], with the options adjusted by `configure`,
+        /// which also receives the environment and `destination`.
+        fn setup_with(
+            configure: impl FnOnce(InitOptions, &Env, &Address) -> InitOptions,
+        ) -> Fixture {
+            let env = Env::default();
+            env.mock_all_auths();
+            let account = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(&env, &account);
+            let controller = Address::generate(&env);
+            let destination = Address::generate(&env);
+            client.initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &controller,
+                &Address::generate(&env),
+                &configure(
+                    InitOptions {
+                        sweep_signer: Some(BytesN::from_array(
+                            &env,
+                            &signing_key().verifying_key().to_bytes(),
+                        )),
+                        locked_destination: Some(destination.clone()),
+                        ..Default::default()
+                    },
+                    &env,
+                    &destination,
+                ),
+            );
+            let asset = token_held_by(&env, &account, 100);
+            client.record_payment(&100, &asset);
+            Fixture {
+                env,
+                account,
+                controller,
+                asset,
+                destination,
+            }
+        }
+
+        fn call(
+            env: &Env,
+            contract: &Address,
+            fn_name: &str,
+            args: soroban_sdk::Vec<Val>,
+        ) -> Context {
+            Context::Contract(ContractContext {
+                contract: contract.clone(),
+                fn_name: Symbol::new(env, fn_name),
+                args,
+            })
+        }
+
+        fn sweep_contexts(f: &Fixture, destination: &Address) -> soroban_sdk::Vec<Context> {
+            vec![
+                &f.env,
+                call(
+                    &f.env,
+                    &f.controller,
+                    "execute_sweep_as_account",
+                    (destination.clone(), None::<BytesN<32>>).into_val(&f.env),
+                ),
+                call(
+                    &f.env,
+                    &f.asset,
+                    "transfer",
+                    (f.account.clone(), destination.clone(), 100i128).into_val(&f.env),
+                ),
+            ]
+        }
+
+        fn check(
+            f: &Fixture,
+            key: &SigningKey,
+            contexts: &soroban_sdk::Vec<Context>,
+        ) -> Result<(), Result<Error, InvokeError>> {
+            let payload = BytesN::from_array(&f.env, &[3u8; 32]);
+            let signature = BytesN::from_array(&f.env, &key.sign(&payload.to_array()).to_bytes());
+            f.env.try_invoke_contract_check_auth::<Error>(
+                &f.account,
+                &payload,
+                signature.into_val(&f.env),
+                contexts,
+            )
+        }
+
+        #[test]
+        fn test_signed_sweep_to_locked_destination_is_authorized() {
+            let f = setup();
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn test_sweep_to_other_destination_is_rejected() {
+            let f = setup();
+            let other = Address::generate(&f.env);
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &other)),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_unrelated_call_is_rejected() {
+            let f = setup();
+            let contexts = vec![
+                &f.env,
+                call(
+                    &f.env,
+                    &Address::generate(&f.env),
+                    "transfer",
+                    (f.account.clone(), f.destination.clone(), 100i128).into_val(&f.env),
+                ),
+            ];
+            assert_eq!(
+                check(&f, &signing_key(), &contexts),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_expired_account_authorizes_nothing() {
+            let f = setup();
+            f.env
+                .ledger()
+                .set_sequence_number(f.env.ledger().sequence() + 1000);
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::AccountExpired))
+            );
+        }
+
+        #[test]
+        fn test_wrong_signer_is_rejected() {
+            let f = setup();
+            let result = check(
+                &f,
+                &SigningKey::from_bytes(&[6u8; 32]),
+                &sweep_contexts(&f, &f.destination),
+            );
+            assert!(result.is_err());
+        }
+
+        fn transfer(f: &Fixture, to: &Address, amount: i128) -> Context {
+            call(
+                &f.env,
+                &f.asset,
+                "transfer",
+                (f.account.clone(), to.clone(), amount).into_val(&f.env),
+            )
+        }
+
+        #[test]
+        fn test_transfer_above_the_recorded_amount_is_rejected() {
+            let f = setup();
+            let mut contexts = sweep_contexts(&f, &f.destination);
+            contexts.set(1, transfer(&f, &f.destination, 101));
+            assert_eq!(
+                check(&f, &signing_key(), &contexts),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_transfers_together_are_capped_at_the_recorded_amount() {
+            let f = setup();
+            let mut contexts = sweep_contexts(&f, &f.destination);
+            contexts.set(1, transfer(&f, &f.destination, 60));
+            assert_eq!(check(&f, &signing_key(), &contexts), Ok(()));
+
+            contexts.push_back(transfer(&f, &f.destination, 41));
+            assert_eq!(
+                check(&f, &signing_key(), &contexts),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_transfer_outside_a_sweep_is_rejected() {
+            let f = setup();
+            let contexts = vec![&f.env, transfer(&f, &f.destination, 100)];
+            assert_eq!(
+                check(&f, &signing_key(), &contexts),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+
+            // Nor may the sweep carry a transfer elsewhere
+            let mut contexts = sweep_contexts(&f, &f.destination);
+            contexts.push_back(transfer(&f, &Address::generate(&f.env), 0));
+            assert_eq!(
+                check(&f, &signing_key(), &contexts),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_swept_account_authorizes_nothing() {
+            let f = setup();
+            EphemeralAccountContractClient::new(&f.env, &f.account)
+                .sweep(&f.destination, &BytesN::from_array(&f.env, &[0u8; 64]));
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::InvalidStatus))
+            );
+        }
+
+        #[test]
+        fn test_vesting_account_authorizes_nothing() {
+            let f = setup_with(|options, env, _| InitOptions {
+                vesting_cliff_ledger: Some(env.ledger().sequence() + 10),
+                ..options
+            });
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_creator_cosign_is_enforced() {
+            let f = setup_with(|options, _, _| InitOptions {
+                require_creator_cosign: true,
+                ..options
+            });
+            f.env.set_auths(&[]);
+            assert!(check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)).is_err());
+
+            let f = setup();
+            f.env.set_auths(&[]);
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Ok(())
+            );
+        }
+
+        #[test]
+        fn test_destination_outside_allowlist_is_rejected() {
+            let f = setup_with(|options, env, _| InitOptions {
+                locked_destination: None,
+                allowed_destinations: Some(vec![env, Address::generate(env)]),
+                ..options
+            });
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::SweepPolicyViolation))
+            );
+        }
+
+        #[test]
+        fn test_paused_account_authorizes_nothing() {
+            let f = setup();
+            EphemeralAccountContractClient::new(&f.env, &f.account).pause();
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::Paused))
+            );
+        }
+
+        #[test]
+        fn test_single_signer_cannot_authorize_a_multisig_account() {
+            let f = setup_with(|options, env, _| InitOptions {
+                sweep_signers: Some(vec![
+                    env,
+                    BytesN::from_array(env, &[1u8; 32]),
+                    BytesN::from_array(env, &[2u8; 32]),
+                ]),
+                sweep_threshold: 2,
+                ..options
+            });
+            assert_eq!(
+                check(&f, &signing_key(), &sweep_contexts(&f, &f.destination)),
+                Err(Ok(Error::InsufficientSignatures))
+            );
+        }
+
+        #[test]
+        fn test_account_without_sweep_signer_authorizes_nothing() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (account, _) = setup_initialized(&env);
+            let payload = BytesN::from_array(&env, &[3u8; 32]);
+            let signature = BytesN::from_array(&env, &[0u8; 64]);
+
+            assert_eq!(
+                env.try_invoke_contract_check_auth::<Error>(
+                    &account,
+                    &payload,
+                    signature.into_val(&env),
+                    &soroban_sdk::Vec::new(&env),
+                ),
+                Err(Ok(Error::Unauthorized))
+            );
+        }
+    }
+
+    // ── Initialization state ────────────────────────────────────────────────
+
+    #[test]
+    fn test_initialize_marks_account_ready() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        assert_eq!(client.get_init_state(), crate::InitState::Uninitialized);
+
+        let (_, client) = setup_initialized(&env);
+        assert_eq!(client.get_init_state(), crate::InitState::Ready);
+    }
+
+    #[test]
+    fn test_partially_initialized_account_is_unusable() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        // An initialization interrupted after some keys were written.
+        env.as_contract(&contract_id, || {
+            storage::set_init_state(&env, crate::InitState::Initializing);
+            storage::set_status(&env, AccountStatus::Active);
+        });
+
+        assert_eq!(
+            client.try_initialize(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+            ),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        assert_eq!(
+            client.try_record_payment(&100, &Address::generate(&env)),
+            Err(Ok(Error::NotInitialized))
+        );
+        assert!(matches!(
+            client.try_get_info(),
+            Err(Ok(Error::NotInitialized))
+        ));
+    }
+
+    #[test]
+    fn test_legacy_initialized_flag_reads_as_ready() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .remove(&storage::DataKey::InitState);
+            env.storage()
+                .instance()
+                .set(&storage::DataKey::Initialized, &true);
+        });
+
+        assert_eq!(client.get_init_state(), crate::InitState::Ready);
+        client.record_payment(&100, &Address::generate(&env));
+    }
+
+    // ── Stale payment refunds ───────────────────────────────────────────────
+
+    mod stale_refunds {
+        use super::*;
+        use crate::InitOptions;
+        use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+        /// An account holding payments for 100 ledgers, funded with 500 of a
+        /// fresh SAC token by `payer`.
+        fn setup_held_payment(env: &Env) -> (EphemeralAccountContractClient<'_>, Address, Address) {
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(env, &contract_id);
+            client.initialize_with_options(
+                &Address::generate(env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(env),
+                &Address::generate(env),
+                &Address::generate(env),
+                &InitOptions {
+                    payment_hold_ledgers: Some(100),
+                    ..Default::default()
+                },
+            );
+
+            let payer = Address::generate(env);
+            let token = token_held_by(env, &payer, 500);
+            client.record_payment_from(&payer, &500, &token);
+            (client, token, payer)
+        }
+
+        #[test]
+        fn test_refund_stale_returns_payment_to_payer_after_hold() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, payer) = setup_held_payment(&env);
+
+            let hold = client.get_payment_hold(&token).unwrap();
+            assert_eq!(hold.payer, payer);
+            assert_eq!(
+                client.try_refund_stale(&token),
+                Err(Ok(Error::HoldNotElapsed))
+            );
+
+            env.ledger().set_sequence_number(hold.hold_until_ledger + 1);
+            assert_eq!(client.refund_stale(&token), 500);
+            let refunded = env
+                .events()
+                .all()
+                .iter()
+                .find(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(soroban_sdk::symbol_short!("stale_rfd"))
+                })
+                .expect("StalePaymentRefunded should be emitted");
+            assert_eq!(
+                event_body::<crate::StalePaymentRefunded>(&env, &refunded.2),
+                crate::StalePaymentRefunded {
+                    payer: payer.clone(),
+                    asset: token.clone(),
+                    amount: 500,
+                }
+            );
+
+            assert_eq!(TokenClient::new(&env, &token).balance(&payer), 500);
+            assert_eq!(client.get_payment(&token), None);
+            assert_eq!(client.get_payment_hold(&token), None);
+            assert_eq!(client.get_status(), AccountStatus::Active);
+        }
+
+        #[test]
+        fn test_refund_stale_needs_no_auth_on_a_cosign_account() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(&env, &contract_id);
+            client.initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &InitOptions {
+                    payment_hold_ledgers: Some(100),
+                    require_creator_cosign: true,
+                    ..Default::default()
+                },
+            );
+            let payer = Address::generate(&env);
+            let token = token_held_by(&env, &payer, 500);
+            client.record_payment_from(&payer, &500, &token);
+
+            // Neither the creator nor the controller signs.
+            env.set_auths(&[]);
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 101);
+            assert_eq!(client.refund_stale(&token), 500);
+            assert!(env.auths().is_empty());
+            assert_eq!(TokenClient::new(&env, &token).balance(&payer), 500);
+        }
+
+        #[test]
+        fn test_payment_without_payer_is_not_refundable() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, _, _) = setup_held_payment(&env);
+
+            let other = Address::generate(&env);
+            client.record_payment(&100, &other);
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 101);
+
+            assert_eq!(client.get_payment_hold(&other), None);
+            assert_eq!(
+                client.try_refund_stale(&other),
+                Err(Ok(Error::NotRefundable))
+            );
+        }
+
+        #[test]
+        fn test_record_payment_from_requires_payer_auth() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, _, _) = setup_held_payment(&env);
+            env.set_auths(&[]);
+
+            assert!(client
+                .try_record_payment_from(&Address::generate(&env), &100, &Address::generate(&env))
+                .is_err());
+        }
+
+        #[test]
+        fn test_record_payment_from_deposits_the_payers_own_funds() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, payer) = setup_held_payment(&env);
+            let token_client = TokenClient::new(&env, &token);
+            assert_eq!(token_client.balance(&payer), 0);
+            assert_eq!(token_client.balance(&client.address), 500);
+        }
+
+        #[test]
+        fn test_funds_sent_directly_are_not_refundable_to_whoever_records_them() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, _, _) = setup_held_payment(&env);
+            let token = env
+                .register_stellar_asset_contract_v2(Address::generate(&env))
+                .address();
+            StellarAssetClient::new(&env, &token).mint(&client.address, &100);
+
+            // Claiming the deposit as one's own means paying it again.
+            let attacker = Address::generate(&env);
+            assert!(client
+                .try_record_payment_from(&attacker, &100, &token)
+                .is_err());
+            assert_eq!(client.get_payment_hold(&token), None);
+
+            client.record_payment(&100, &token);
+            env.ledger()
+                .set_sequence_number(env.ledger().sequence() + 101);
+            assert_eq!(
+                client.try_refund_stale(&token),
+                Err(Ok(Error::NotRefundable))
+            );
+            assert_eq!(TokenClient::new(&env, &token).balance(&attacker), 0);
+        }
+
+        #[test]
+        fn test_payer_is_reported_in_info_and_events() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, payer) = setup_held_payment(&env);
+            assert_eq!(
+                client
+                    .get_payments_page(&0, &1)
+                    .payments
+                    .get_unchecked(0)
+                    .payer,
+                Some(payer.clone())
+            );
+
+            let second_payer = Address::generate(&env);
+            let other = token_held_by(&env, &second_payer, 100);
+            client.record_payment_from(&second_payer, &100, &other);
+            let event = env
+                .events()
+                .all()
+                .iter()
+                .find(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(soroban_sdk::symbol_short!("multi_pay"))
+                })
+                .expect("MultiPaymentReceived should be emitted");
+            assert_eq!(
+                event_body::<crate::MultiPaymentReceived>(&env, &event.2).payer,
+                Some(second_payer)
+            );
+
+            let anonymous = Address::generate(&env);
+            client.record_payment(&100, &anonymous);
+            assert_eq!(client.get_payment(&anonymous).unwrap().payer, None);
+            assert_eq!(client.get_payment(&token).unwrap().payer, Some(payer));
+        }
+
+        #[test]
+        fn test_refund_payment_returns_deposit_to_payer_before_sweep() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, payer) = setup_held_payment(&env);
+            let creator = client.get_info().creator;
+
+            // No need to wait for the hold.
+            assert_eq!(client.refund_payment(&token), 500);
+            assert_eq!(env.auths()[0].0, creator);
+            let (_, _, data) = env
+                .events()
+                .all()
+                .iter()
+                .find(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                        == Some(soroban_sdk::Symbol::new(&env, "refunded"))
+                })
+                .expect("PaymentRefunded should be emitted");
+            assert_eq!(
+                event_body::<crate::PaymentRefunded>(&env, &data),
+                crate::PaymentRefunded {
+                    payer: payer.clone(),
+                    asset: token.clone(),
+                    amount: 500
+                }
+            );
+
+            assert_eq!(TokenClient::new(&env, &token).balance(&payer), 500);
+            assert_eq!(client.get_payment(&token), None);
+            assert_eq!(client.get_payment_hold(&token), None);
+            assert_eq!(client.get_status(), AccountStatus::Active);
+            assert_eq!(
+                client.try_refund_payment(&token),
+                Err(Ok(Error::NotRefundable))
+            );
+        }
+
+        #[test]
+        fn test_refund_payment_needs_a_known_payer_and_an_unsettled_account() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, _) = setup_held_payment(&env);
+
+            let other = token_held_by(&env, &client.address, 100);
+            client.record_payment(&100, &other);
+            assert_eq!(
+                client.try_refund_payment(&other),
+                Err(Ok(Error::NotRefundable))
+            );
+
+            client.sweep(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64]),
+            );
+            assert_eq!(
+                client.try_refund_payment(&token),
+                Err(Ok(Error::InvalidStatus))
+            );
+        }
+
+        #[test]
+        fn test_clawback_returns_payment_only_within_its_window() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(&env, &contract_id);
+            client.initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &InitOptions {
+                    clawback_window_ledgers: Some(10),
+                    ..Default::default()
+                },
+            );
+            let admin = Address::generate(&env);
+            let usdc = env
+                .register_stellar_asset_contract_v2(admin.clone())
+                .address();
+            let eurc = env.register_stellar_asset_contract_v2(admin).address();
+            let payer = Address::generate(&env);
+            StellarAssetClient::new(&env, &usdc).mint(&payer, &300);
+            StellarAssetClient::new(&env, &eurc).mint(&payer, &200);
+            client.record_payment_from(&payer, &300, &usdc);
+            client.record_payment_from(&payer, &200, &eurc);
+
+            env.ledger().with_mut(|ledger| ledger.sequence_number += 10);
+            assert_eq!(client.clawback(&usdc), 300);
+            let auths = env.auths();
+            let clawed_back = env
+                .events()
+                .all()
+                .iter()
+                .find(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(soroban_sdk::symbol_short!("clawback"))
+                })
+                .expect("PaymentClawedBack should be emitted");
+            assert_eq!(
+                event_body::<crate::PaymentClawedBack>(&env, &clawed_back.2),
+                crate::PaymentClawedBack {
+                    payer: payer.clone(),
+                    asset: usdc.clone(),
+                    amount: 300,
+                }
+            );
+            assert_eq!(auths[0].0, client.get_info().creator);
+            assert_eq!(TokenClient::new(&env, &usdc).balance(&payer), 300);
+            assert_eq!(client.get_payment(&usdc), None);
+
+            env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+            assert_eq!(
+                client.try_clawback(&eurc),
+                Err(Ok(Error::ClawbackWindowClosed))
+            );
+            assert_eq!(client.get_payment(&eurc).unwrap().amount, 200);
+        }
+
+        #[test]
+        fn test_clawback_is_closed_without_a_window() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, token, _) = setup_held_payment(&env);
+
+            assert_eq!(
+                client.try_clawback(&token),
+                Err(Ok(Error::ClawbackWindowClosed))
+            );
+        }
+    }
+
+    // ── Configuration lock ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_lock_config_is_reported_and_leaves_upgrade_available() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        assert!(!client.get_info().config_locked);
+
+        client.lock_config();
+        let locked = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                    == Some(soroban_sdk::Symbol::new(&env, "cfg_lock"))
+            })
+            .expect("ConfigLocked should be emitted");
+        assert_eq!(
+            event_body::<crate::ConfigLocked>(&env, &locked.2),
+            crate::ConfigLocked {
+                ledger: env.ledger().sequence()
+            }
+        );
+
+        assert!(client.get_info().config_locked);
+        assert_eq!(client.try_lock_config(), Err(Ok(Error::ConfigLocked)));
+
+        // Stop before the WASM swap, which needs an uploaded build: the
+        // upgrade gets past the lock to the pending-sweep check.
+        env.as_contract(&contract_id, || {
+            storage::set_status(&env, AccountStatus::PendingSweep);
+        });
+        assert_eq!(
+            client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+            Err(Ok(Error::SweepPending))
+        );
+    }
+
+    #[test]
+    fn test_lock_config_requires_creator_auth() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        env.set_auths(&[]);
+
+        assert!(client.try_lock_config().is_err());
+        assert!(!client.get_info().config_locked);
+    }
+
+    // ── Pause ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pause_blocks_payments_and_sweeps_until_unpaused() {
+        use crate::AccountPaused;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+
+        client.pause();
+        let paused = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("paused"))
+            })
+            .map(|(_, _, data)| event_body::<AccountPaused>(&env, &data))
+            .expect("AccountPaused was not emitted");
+        assert_eq!(paused.ledger, env.ledger().sequence());
+        assert!(client.get_info().paused);
+        assert_eq!(client.try_pause(), Err(Ok(Error::Paused)));
+
+        assert_eq!(
+            client.try_record_payment(&5, &Address::generate(&env)),
+            Err(Ok(Error::Paused))
+        );
+        assert_eq!(
+            client.try_sweep(&Address::generate(&env), &signature),
+            Err(Ok(Error::Paused))
+        );
+        assert_eq!(
+            client.try_sweep_claim(&Address::generate(&env)),
+            Err(Ok(Error::Paused))
+        );
+
+        client.unpause();
+        assert!(!client.get_info().paused);
+        assert_eq!(client.try_unpause(), Err(Ok(Error::InvalidStatus)));
+        client.sweep(&Address::generate(&env), &signature);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+    }
+
+    #[test]
+    fn test_paused_account_can_still_expire() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        client.pause();
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+        assert_eq!(client.get_status(), AccountStatus::Expired);
+    }
+
+    // ── Creator co-signature ────────────────────────────────────────────────
+
+    #[test]
+    fn test_sweep_requires_creator_cosign_when_enabled() {
+        use crate::InitOptions;
+        use soroban_sdk::testutils::{MockAuth, MockAuthInvoke};
+        use soroban_sdk::IntoVal;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let creator = Address::generate(&env);
+        let controller = Address::generate(&env);
+        client.initialize_with_options(
+            &creator,
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &controller,
+            &Address::generate(&env),
+            &InitOptions {
+                require_creator_cosign: true,
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0; 64]);
+        let args = (destination.clone(), signature.clone()).into_val(&env);
+        let invoke = MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "sweep",
+            args,
+            sub_invokes: &[],
+        };
+
+        // The controller alone is no longer enough.
+        let controller_only = [MockAuth {
+            address: &controller,
+            invoke: &invoke,
+        }];
+        assert!(client
+            .mock_auths(&controller_only)
+            .try_sweep(&destination, &signature)
+            .is_err());
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+
+        let cosigned = [
+            MockAuth {
+                address: &controller,
+                invoke: &invoke,
+            },
+            MockAuth {
+                address: &creator,
+                invoke: &invoke,
+            },
+        ];
+        client.mock_auths(&cosigned).sweep(&destination, &signature);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+    }
+
+    // ── Cancellation ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_cancel_unused_account_reclaims_reserve_to_recovery() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let info = client.get_info();
+
+        client.cancel();
+        assert_eq!(env.auths()[0].0, info.creator);
+        let cancelled = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                    == Some(soroban_sdk::Symbol::new(&env, "cancelled"))
+            })
+            .expect("AccountCancelled should be emitted");
+        assert_eq!(
+            event_body::<crate::AccountCancelled>(&env, &cancelled.2),
+            crate::AccountCancelled {
+                recovery_address: info.recovery_address.clone(),
+                ledger: env.ledger().sequence()
+            }
+        );
+
+        assert_eq!(client.get_status(), AccountStatus::Cancelled);
+        let reserve = latest_reserve_event(&client);
+        assert_eq!(reserve.destination, info.recovery_address);
+        assert!(reserve.fully_reclaimed);
+
+        assert_eq!(
+            client.try_record_payment(&100, &Address::generate(&env)),
+            Err(Ok(Error::InvalidStatus))
+        );
+        assert_eq!(client.try_cancel(), Err(Ok(Error::InvalidStatus)));
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        assert_eq!(client.try_expire(), Err(Ok(Error::InvalidStatus)));
+        assert_eq!(client.get_status(), AccountStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_cancel_is_rejected_once_paid() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        client.record_payment(&100, &Address::generate(&env));
+        assert_eq!(client.try_cancel(), Err(Ok(Error::InvalidStatus)));
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+    }
+
+    // ── Closing ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_close_account_purges_a_completed_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let info = client.get_info();
+        let asset = token_held_by(&env, &contract_id, 100);
+        client.record_payment(&100, &asset);
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        client.expire();
+
+        client.close_account();
+        assert_eq!(env.auths()[0].0, info.creator);
+        let closed = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("closed"))
+            })
+            .expect("AccountClosed should be emitted");
+        assert_eq!(
+            event_body::<crate::AccountClosed>(&env, &closed.2),
+            crate::AccountClosed {
+                status: AccountStatus::Expired,
+                ledger: info.expiry_ledger,
+            }
+        );
+
+        env.as_contract(&contract_id, || {
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&storage::DataKey::Payment(asset.clone())));
+            assert!(!env.storage().instance().has(&storage::DataKey::Creator));
+            assert!(!env
+                .storage()
+                .persistent()
+                .has(&storage::DataKey::PaymentOrder));
+        });
+        assert_eq!(client.get_init_state(), crate::InitState::Closed);
+        assert_eq!(client.get_status(), AccountStatus::Expired);
+        assert!(matches!(
+            client.try_get_info(),
+            Err(Ok(Error::NotInitialized))
+        ));
+        assert_eq!(client.try_close_account(), Err(Ok(Error::NotInitialized)));
+        assert_eq!(
+            client.try_initialize(
+                &info.creator,
+                &(info.expiry_ledger + 1000),
+                &info.recovery_address,
+                &Address::generate(&env),
+                &Address::generate(&env),
+            ),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+    }
+
+    #[test]
+    fn test_close_account_purges_minimums_of_unpaid_assets() {
+        use crate::{AssetAmount, InitOptions};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let paid = token_held_by(&env, &contract_id, 100);
+        let unpaid = token_held_by(&env, &contract_id, 0);
+        let minimum = |asset: &Address| AssetAmount {
+            asset: asset.clone(),
+            amount: 10,
+        };
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                min_payment_per_asset: Some(soroban_sdk::vec![
+                    &env,
+                    minimum(&paid),
+                    minimum(&unpaid)
+                ]),
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &paid);
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+
+        client.close_account();
+        env.as_contract(&contract_id, || {
+            for asset in [paid, unpaid] {
+                assert!(!env
+                    .storage()
+                    .persistent()
+                    .has(&storage::DataKeyExt::AssetMinPayment(asset)));
+            }
+            assert!(!env
+                .storage()
+                .instance()
+                .has(&storage::DataKeyExt::MinPaymentAssets));
+        });
+    }
+
+    #[test]
+    fn test_close_account_returns_leftover_balances_to_the_recovery_address() {
+        use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let info = client.get_info();
+        let asset = token_held_by(&env, &contract_id, 100);
+        client.record_payment(&100, &asset);
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        client.expire();
+        // Sent after expiry, never recorded
+        StellarAssetClient::new(&env, &asset).mint(&contract_id, &7);
+
+        client.close_account();
+        let token = TokenClient::new(&env, &asset);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert_eq!(token.balance(&info.recovery_address), 107);
+    }
+
+    #[test]
+    fn test_close_account_archives_the_state_before_purging() {
+        use soroban_sdk::xdr::ToXdr;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let info = client.get_info();
+        let asset = token_held_by(&env, &contract_id, 100);
+        client.record_payment(&100, &asset);
+        env.ledger().set_sequence_number(info.expiry_ledger);
+        client.expire();
+        let payments = client.get_payments_page(&0, &10);
+        let audit_head = env.as_contract(&contract_id, || storage::get_audit_head(&env));
+
+        client.close_account();
+        let events = env.events().all();
+        let position = |topic: &str| {
+            events
+                .iter()
+                .position(|(_, topics, _)| {
+                    soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(soroban_sdk::Symbol::new(&env, topic))
+                })
+                .unwrap()
+        };
+        let archived = position("archived");
+        assert!(archived < position("closed"));
+
+        let body =
+            event_body::<crate::AccountArchived>(&env, &events.get_unchecked(archived as u32).2);
+        let state = (
+            AccountStatus::Expired,
+            info.creator.clone(),
+            Some(info.recovery_address.clone()),
+            payments.payments.clone(),
+            payments.settled.clone(),
+            0u64,
+        );
+        let digest: BytesN<32> = env.crypto().sha256(&state.to_xdr(&env)).into();
+        assert_eq!(
+            body,
+            crate::AccountArchived {
+                status: AccountStatus::Expired,
+                creator: info.creator,
+                swept_to: Some(info.recovery_address),
+                payments: payments.payments,
+                settled: payments.settled,
+                sweep_nonce: 0,
+                state_digest: digest,
+                audit_head,
+                ledger: info.expiry_ledger,
+            }
+        );
+    }
+
+    #[test]
+    fn test_close_account_requires_completion_and_reclaimed_reserve() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let expiry_ledger = client.get_info().expiry_ledger;
+
+        client.record_payment(&100, &token_held_by(&env, &contract_id, 100));
+        assert_eq!(client.try_close_account(), Err(Ok(Error::InvalidStatus)));
+
+        env.ledger().set_sequence_number(expiry_ledger);
+        client.expire();
+        env.as_contract(&contract_id, || {
+            storage::set_reserve_reclaimed(&env, false);
+        });
+        assert_eq!(
+            client.try_close_account(),
+            Err(Ok(Error::ReserveNotReclaimed))
+        );
+        assert_eq!(client.get_init_state(), crate::InitState::Ready);
+    }
+
+    // ── Recovery address ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_update_recovery_address_before_first_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let info = client.get_info();
+        let new_address = Address::generate(&env);
+
+        client.update_recovery_address(&new_address);
+        assert_eq!(env.auths()[0].0, info.creator);
+        let updated = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("recovery"))
+            })
+            .expect("RecoveryAddressUpdated should be emitted");
+        assert_eq!(
+            event_body::<crate::RecoveryAddressUpdated>(&env, &updated.2),
+            crate::RecoveryAddressUpdated {
+                old_address: info.recovery_address,
+                new_address: new_address.clone(),
+            }
+        );
+        assert_eq!(client.get_info().recovery_address, new_address);
+
+        client.record_payment(&100, &Address::generate(&env));
+        assert_eq!(
+            client.try_update_recovery_address(&Address::generate(&env)),
+            Err(Ok(Error::InvalidStatus))
+        );
+        assert_eq!(client.get_info().recovery_address, new_address);
+    }
+
+    #[test]
+    fn test_update_recovery_address_respects_config_lock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        client.lock_config();
+        assert_eq!(
+            client.try_update_recovery_address(&Address::generate(&env)),
+            Err(Ok(Error::ConfigLocked))
+        );
+    }
+
+    // ── Guardian recovery ───────────────────────────────────────────────────
+
+    /// A paid account guarded by `guardian`, who may act 100 ledgers after
+    /// expiry. Returns the client and the expiry ledger.
+    fn setup_guarded<'a>(
+        env: &'a Env,
+        guardian: &Address,
+    ) -> (EphemeralAccountContractClient<'a>, u32) {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        let expiry_ledger = env.ledger().sequence() + 10;
+        client.initialize_with_options(
+            &Address::generate(env),
+            &expiry_ledger,
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                guardian: Some(guardian.clone()),
+                guardian_delay_ledgers: 100,
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &token_held_by(env, &contract_id, 100));
+        (client, expiry_ledger)
+    }
+
+    #[test]
+    fn test_recover_to_redirects_after_guardian_delay() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let guardian = Address::generate(&env);
+        let (client, expiry_ledger) = setup_guarded(&env, &guardian);
+        let old_address = client.get_info().recovery_address;
+        let new_address = Address::generate(&env);
+        assert_eq!(client.get_guardian(), Some((guardian.clone(), 100)));
+
+        env.ledger().set_sequence_number(expiry_ledger + 99);
+        assert_eq!(
+            client.try_recover_to(&new_address),
+            Err(Ok(Error::GuardianDelayActive))
+        );
+
+        env.ledger().set_sequence_number(expiry_ledger + 100);
+        client.recover_to(&new_address);
+        assert_eq!(env.auths()[0].0, guardian);
+        let redirected = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("redirect"))
+            })
+            .expect("RecoveryRedirected should be emitted");
+        assert_eq!(
+            event_body::<crate::RecoveryRedirected>(&env, &redirected.2),
+            crate::RecoveryRedirected {
+                guardian,
+                old_address,
+                new_address: new_address.clone(),
+            }
+        );
+
+        let info = client.get_info();
+        assert_eq!(info.status, AccountStatus::Expired);
+        assert_eq!(info.recovery_address, new_address);
+        assert_eq!(info.swept_to, Some(new_address));
+    }
+
+    #[test]
+    fn test_recover_to_rejects_an_already_expired_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, expiry_ledger) = setup_guarded(&env, &Address::generate(&env));
+        let recovery_address = client.get_info().recovery_address;
+
+        env.ledger().set_sequence_number(expiry_ledger);
+        client.expire();
+        env.ledger().set_sequence_number(expiry_ledger + 100);
+        assert_eq!(
+            client.try_recover_to(&Address::generate(&env)),
+            Err(Ok(Error::InvalidStatus))
+        );
+
+        let info = client.get_info();
+        assert_eq!(info.recovery_address, recovery_address.clone());
+        assert_eq!(info.swept_to, Some(recovery_address));
+    }
+
+    #[test]
+    fn test_recover_to_requires_a_guardian() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger + 1_000);
+        assert_eq!(client.get_guardian(), None);
+        assert_eq!(
+            client.try_recover_to(&Address::generate(&env)),
+            Err(Ok(Error::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_recover_to_rejects_a_swept_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, expiry_ledger) = setup_guarded(&env, &Address::generate(&env));
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0; 64]),
+        );
+        env.ledger().set_sequence_number(expiry_ledger + 100);
+        assert_eq!(
+            client.try_recover_to(&Address::generate(&env)),
+            Err(Ok(Error::InvalidStatus))
+        );
+    }
+
+    // ── Dispute window ──────────────────────────────────────────────────────
+
+    /// A swept account whose reserve stays locked 50 ledgers after the
+    /// sweep. Returns the client and the arbiter.
+    fn setup_disputable(env: &Env) -> (EphemeralAccountContractClient<'_>, Address) {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        let arbiter = Address::generate(env);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                dispute_window_ledgers: 50,
+                arbiter: Some(arbiter.clone()),
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &token_held_by(env, &contract_id, 100));
+        client.sweep(&Address::generate(env), &BytesN::from_array(env, &[0; 64]));
+        (client, arbiter)
+    }
+
+    #[test]
+    fn test_reserve_stays_locked_during_dispute_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _) = setup_disputable(&env);
+        let swept_ledger = env.ledger().sequence();
+
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+        let locked = client.get_reserve_remaining();
+        assert!(locked > 0);
+        assert!(!client.is_reserve_reclaimed());
+        env.ledger().set_sequence_number(swept_ledger + 49);
+        assert_eq!(client.try_reclaim_reserve(), Err(Ok(Error::ReserveLocked)));
+
+        env.ledger().set_sequence_number(swept_ledger + 50);
+        assert_eq!(client.reclaim_reserve(), locked);
+        assert_eq!(client.get_reserve_remaining(), 0);
+    }
+
+    #[test]
+    fn test_dispute_freezes_reserve_until_resolved() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, arbiter) = setup_disputable(&env);
+        let creator = client.get_info().creator;
+        let locked = client.get_reserve_remaining();
+
+        client.dispute();
+        assert_eq!(env.auths()[0].0, creator);
+        assert!(client.is_disputed());
+        assert_eq!(client.try_dispute(), Err(Ok(Error::InvalidStatus)));
+
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 1_000);
+        assert_eq!(client.try_reclaim_reserve(), Err(Ok(Error::ReserveLocked)));
+
+        client.resolve_dispute();
+        assert_eq!(env.auths()[0].0, arbiter);
+        assert!(!client.is_disputed());
+        assert_eq!(client.reclaim_reserve(), locked);
+    }
+
+    #[test]
+    fn test_dispute_rejected_after_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _) = setup_disputable(&env);
+
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 50);
+        assert_eq!(client.try_dispute(), Err(Ok(Error::InvalidStatus)));
+        assert_eq!(client.try_resolve_dispute(), Err(Ok(Error::InvalidStatus)));
+    }
+
+    #[test]
+    fn test_dispute_window_requires_an_arbiter() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        let result = client.try_initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                dispute_window_ledgers: 50,
+                ..Default::default()
+            },
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidDisputeWindow)));
+    }
+
+    #[test]
+    fn test_dispute_unavailable_without_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        assert_eq!(client.try_dispute(), Err(Ok(Error::InvalidDisputeWindow)));
+    }
+
+    // ── Payer blocklist ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_blocked_payer_cannot_record_a_payment() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let flagged = Address::generate(&env);
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                blocked_payers: Some(soroban_sdk::vec![&env, flagged.clone()]),
+                ..Default::default()
+            },
+        );
+
+        assert!(client.is_payer_blocked(&flagged));
+        assert_eq!(
+            client.try_record_payment_from(&flagged, &100, &Address::generate(&env)),
+            Err(Ok(Error::PayerBlocked))
+        );
+        assert_eq!(client.get_info().payment_count, 0);
+        let payer = Address::generate(&env);
+        let asset = token_held_by(&env, &payer, 100);
+        client.record_payment_from(&payer, &100, &asset);
+        assert_eq!(client.get_info().payment_count, 1);
+    }
+
+    #[test]
+    fn test_creator_manages_the_blocklist() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let creator = client.get_info().creator;
+        let payer = Address::generate(&env);
+        let asset = token_held_by(&env, &payer, 100);
+
+        client.block_payer(&payer);
+        assert_eq!(env.auths()[0].0, creator);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("blocklist"))
+            })
+            .expect("PayerBlocklistUpdated should be emitted");
+        assert_eq!(
+            event_body::<crate::PayerBlocklistUpdated>(&env, &data),
+            crate::PayerBlocklistUpdated {
+                payer: payer.clone(),
+                blocked: true,
+            }
+        );
+        assert_eq!(
+            client.try_record_payment_from(&payer, &100, &asset),
+            Err(Ok(Error::PayerBlocked))
+        );
+
+        client.unblock_payer(&payer);
+        assert!(!client.is_payer_blocked(&payer));
+        client.record_payment_from(&payer, &100, &asset);
+
+        client.lock_config();
+        client.block_payer(&payer);
+        assert_eq!(
+            client.try_unblock_payer(&payer),
+            Err(Ok(Error::ConfigLocked))
+        );
+    }
+
+    #[test]
+    fn test_blocklist_is_bounded() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        for _ in 0..20 {
+            client.block_payer(&Address::generate(&env));
+        }
+        assert_eq!(
+            client.try_block_payer(&Address::generate(&env)),
+            Err(Ok(Error::InvalidBlocklist))
+        );
+    }
+
+    // ── Metadata ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_set_metadata_labels_the_account() {
+        use soroban_sdk::{symbol_short, Bytes};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let creator = client.get_info().creator;
+        let order = Bytes::from_slice(&env, b"order-1042");
+
+        client.set_metadata(&symbol_short!("order"), &order);
+        assert_eq!(env.auths()[0].0, creator);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(symbol_short!("metadata"))
+            })
+            .expect("MetadataUpdated should be emitted");
+        assert_eq!(
+            event_body::<crate::MetadataUpdated>(&env, &data),
+            crate::MetadataUpdated {
+                key: symbol_short!("order"),
+                value: order.clone(),
+            }
+        );
+
+        client.lock_config();
+        client.set_metadata(&symbol_short!("env"), &Bytes::from_slice(&env, b"prod"));
+        assert_eq!(client.get_metadata(&symbol_short!("order")), Some(order));
+        assert_eq!(client.get_info().metadata.len(), 2);
+
+        client.set_metadata(&symbol_short!("order"), &Bytes::new(&env));
+        assert_eq!(client.get_metadata(&symbol_short!("order")), None);
+        assert_eq!(client.get_info().metadata.len(), 1);
+    }
+
+    #[test]
+    fn test_set_metadata_is_bounded() {
+        use soroban_sdk::{Bytes, Symbol};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let value = Bytes::from_slice(&env, b"tag");
+
+        assert_eq!(
+            client.try_set_metadata(
+                &Symbol::new(&env, "long"),
+                &Bytes::from_array(&env, &[0; 65])
+            ),
+            Err(Ok(Error::InvalidMetadata))
+        );
+        for i in 0..10 {
+            client.set_metadata(&Symbol::new(&env, &std::format!("key{i}")), &value);
+        }
+        assert_eq!(
+            client.try_set_metadata(&Symbol::new(&env, "key10"), &value),
+            Err(Ok(Error::InvalidMetadata))
+        );
+        // Replacing an existing entry stays within the bound.
+        client.set_metadata(
+            &Symbol::new(&env, "key0"),
+            &Bytes::from_array(&env, &[1; 64]),
+        );
+    }
+
+    // ── Storage writes ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_payment_writes_only_its_own_payment_entry() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        // Status, first payment ledger, order index and audit head are
+        // packed into the account core, which instance storage writes back
+        // as one entry with the rest; the payment is a persistent entry of
+        // its own. Earlier payments are not rewritten.
+        client.record_payment(&100, &Address::generate(&env));
+        assert_eq!(env.cost_estimate().resources().write_entries, 2);
+
+        for amount in 200..208 {
+            client.record_payment(&amount, &Address::generate(&env));
+            assert_eq!(env.cost_estimate().resources().write_entries, 2);
+        }
+    }
+
+    /// Store the account's core fields under keys of their own, as schema
+    /// version 3 and earlier builds did.
+    fn unpack_core(env: &Env, contract_id: &Address) {
+        use storage::DataKey;
+
+        env.as_contract(contract_id, || {
+            let core = storage::get_core(env);
+            let instance = env.storage().instance();
+            instance.remove(&DataKey::Core);
+            instance.set(&DataKey::Status, &core.status);
+            if let Some(ledger) = core.first_payment_ledger {
+                instance.set(&DataKey::FirstPaymentLedger, &ledger);
+            }
+            instance.set(&DataKey::PaymentOrder, &core.payment_assets);
+            instance.set(&DataKey::AuditHead, &core.audit_head);
+        });
+    }
+
+    #[test]
+    fn test_migrate_packs_the_core_of_a_schema_3_account() {
+        use storage::DataKey;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let first = Address::generate(&env);
+        client.record_payment(&100, &first);
+        let lifecycle = client.get_lifecycle();
+        let audit_head = env.as_contract(&contract_id, || storage::get_audit_head(&env));
+
+        // Schema version 3 builds kept the payment index in a persistent entry
+        unpack_core(&env, &contract_id);
+        env.as_contract(&contract_id, || {
+            let order: soroban_sdk::Vec<Address> = env
+                .storage()
+                .instance()
+                .get(&DataKey::PaymentOrder)
+                .unwrap();
+            env.storage().instance().remove(&DataKey::PaymentOrder);
+            env.storage()
+                .persistent()
+                .set(&DataKey::PaymentOrder, &order);
+            storage::set_schema_version(&env, 3);
+        });
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(
+            client.get_payment_assets(),
+            soroban_sdk::vec![&env, first.clone()]
+        );
+        assert_eq!(client.get_lifecycle(), lifecycle);
+
+        assert_eq!(client.migrate(), 4);
+        env.as_contract(&contract_id, || {
+            let instance = env.storage().instance();
+            for key in [
+                DataKey::Status,
+                DataKey::FirstPaymentLedger,
+                DataKey::PaymentOrder,
+                DataKey::AuditHead,
+            ] {
+                assert!(!instance.has(&key));
+            }
+            assert!(!env.storage().persistent().has(&DataKey::PaymentOrder));
+            let core = storage::get_core(&env);
+            assert_eq!(core.status, AccountStatus::PaymentReceived);
+            assert_eq!(core.payment_assets, soroban_sdk::vec![&env, first]);
+            assert_eq!(core.first_payment_ledger, lifecycle.first_payment_ledger);
+            // The chain continued from the old head with the migration event
+            assert_ne!(core.audit_head, audit_head);
+        });
+
+        client.record_payment(&200, &Address::generate(&env));
+        assert_eq!(client.get_payment_assets().len(), 2);
+    }
+
+    #[test]
+    fn test_payments_in_the_legacy_instance_map_are_still_read() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let legacy_asset = Address::generate(&env);
+        let legacy = crate::Payment {
+            asset: legacy_asset.clone(),
+            amount: 70,
+            timestamp: 0,
+            payer: None,
+        };
+
+        // An account that recorded a payment before per-asset entries.
+        env.as_contract(&contract_id, || {
+            let mut payments = soroban_sdk::Map::new(&env);
+            payments.set(legacy_asset.clone(), legacy.clone());
+            env.storage()
+                .instance()
+                .set(&storage::DataKeyExt::Payments, &payments);
+            storage::set_status(&env, AccountStatus::PaymentReceived);
+        });
+        unpack_core(&env, &contract_id);
+        env.as_contract(&contract_id, || {
+            // Such accounts had no payment index either
+            env.storage()
+                .instance()
+                .remove(&storage::DataKey::PaymentOrder);
+        });
+
+        assert_eq!(client.get_payment(&legacy_asset), Some(legacy));
+        let usdc = Address::generate(&env);
+        client.record_payment(&30, &usdc);
+        assert_eq!(client.get_info().payment_count, 2);
+        assert_eq!(client.get_payment(&usdc).unwrap().amount, 30);
+
+        let (transfers, _, error_code) = client.simulate_sweep(&Address::generate(&env));
+        assert_eq!(error_code, 0);
+        assert_eq!(transfers.iter().map(|t| t.amount).sum::<i128>(), 100);
+    }
+
+    // ── Entry TTLs ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_payment_extends_earlier_payment_entries() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 10_000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+        let first = Address::generate(&env);
+        client.record_payment(&100, &first);
+
+        // Close to archival, a payment in another asset keeps the first
+        // payment's entry alive along with the instance.
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 4_000);
+        client.record_payment(&200, &Address::generate(&env));
+
+        env.as_contract(&contract_id, || {
+            use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+            let key = storage::DataKey::Payment(first.clone());
+            assert_eq!(env.storage().persistent().get_ttl(&key), 518_400);
+            assert_eq!(env.storage().instance().get_ttl(), 518_400);
+        });
+    }
+
+    #[test]
+    fn test_reclaim_reserve_extends_payment_entries() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let asset = token_held_by(&env, &contract_id, 100);
+        client.record_payment(&100, &asset);
+        env.ledger()
+            .set_sequence_number(client.get_info().expiry_ledger);
+        client.expire();
+
+        // Close to archival, reclaiming the reserve keeps the recorded
+        // payment's entry alive along with the instance.
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 3_000);
+        client.reclaim_reserve();
+
+        env.as_contract(&contract_id, || {
+            use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+            let key = storage::DataKey::Payment(asset.clone());
+            assert_eq!(env.storage().persistent().get_ttl(&key), 518_400);
+            assert_eq!(env.storage().instance().get_ttl(), 518_400);
+        });
+    }
+
+    #[test]
+    fn test_ttl_extend_to_is_configurable() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let initialize = |ttl_extend_to| {
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    ttl_extend_to: Some(ttl_extend_to),
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(initialize(100), Err(Ok(Error::InvalidTtl)));
+        assert_eq!(
+            initialize(env.ledger().get().max_entry_ttl + 1),
+            Err(Ok(Error::InvalidTtl))
+        );
+        initialize(20_000).unwrap().unwrap();
+
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 4_000);
+        client.expire();
+        env.as_contract(&contract_id, || {
+            use soroban_sdk::testutils::storage::Instance as _;
+            assert_eq!(env.storage().instance().get_ttl(), 20_000);
+        });
+    }
+
+    // ── Settlement preference ───────────────────────────────────────────────
+
+    #[test]
+    fn test_preferred_settlement_asset_is_surfaced_in_info() {
+        use crate::InitOptions;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, plain) = setup_initialized(&env);
+        assert_eq!(plain.get_info().preferred_settlement_asset, None);
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let usdc = Address::generate(&env);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                preferred_settlement_asset: Some(usdc.clone()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(client.get_info().preferred_settlement_asset, Some(usdc));
+    }
+
+    // ── Payment accumulation ────────────────────────────────────────────────
+
+    fn setup_accumulating(
+        env: &Env,
+        options: crate::InitOptions,
+    ) -> EphemeralAccountContractClient<'_> {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                accumulate_payments: true,
+                ..options
+            },
+        );
+        client
+    }
+
+    #[test]
+    fn test_repeated_payments_accumulate_when_enabled() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let client = setup_accumulating(
+            &env,
+            crate::InitOptions {
+                expected_payments: Some(soroban_sdk::vec![&env, expected(&usdc, 100, 0)]),
+                ..Default::default()
+            },
+        );
+
+        client.record_payment(&60, &usdc);
+        assert!(!client.is_expectation_met(&usdc));
+        env.ledger().with_mut(|ledger| ledger.timestamp += 60);
+        client.record_payment(&40, &usdc);
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap(),
+            soroban_sdk::Symbol::new(&env, "topped_up")
+        );
+        assert_eq!(
+            event_body::<crate::PaymentToppedUp>(&env, &data),
+            crate::PaymentToppedUp {
+                asset: usdc.clone(),
+                amount: 40,
+                total: 100
+            }
+        );
+
+        let payment = client.get_payment(&usdc).unwrap();
+        assert_eq!(payment.amount, 100);
+        assert_eq!(payment.timestamp, env.ledger().timestamp());
+        assert_eq!(client.get_info().payment_count, 1);
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert!(client.is_expectation_met(&usdc));
+
+        // Going over the tolerance undoes the match.
+        client.record_payment(&1, &usdc);
+        assert!(!client.is_expectation_met(&usdc));
+        assert_eq!(
+            client.try_record_payment(&i128::MAX, &usdc),
+            Err(Ok(Error::Overflow))
+        );
+    }
+
+    #[test]
+    fn test_top_up_by_another_payer_drops_the_payment_hold() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let client = setup_accumulating(
+            &env,
+            crate::InitOptions {
+                payment_hold_ledgers: Some(100),
+                ..Default::default()
+            },
+        );
+        let payer = Address::generate(&env);
+        let usdc = token_held_by(&env, &payer, 100);
+        let other_payer = Address::generate(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &usdc).mint(&other_payer, &5);
+
+        client.record_payment_from(&payer, &60, &usdc);
+        env.ledger().with_mut(|ledger| ledger.sequence_number += 10);
+        client.record_payment_from(&payer, &40, &usdc);
+        assert_eq!(
+            client.get_payment_hold(&usdc).unwrap().hold_until_ledger,
+            env.ledger().sequence() + 100
+        );
+        assert_eq!(client.get_payment(&usdc).unwrap().payer, Some(payer));
+
+        client.record_payment_from(&other_payer, &5, &usdc);
+        assert_eq!(client.get_payment_hold(&usdc), None);
+        assert_eq!(client.get_payment(&usdc).unwrap().amount, 105);
+        assert_eq!(client.get_payment(&usdc).unwrap().payer, None);
+    }
+
+    // ── Per-account asset cap ───────────────────────────────────────────────
+
+    #[test]
+    fn test_max_assets_caps_payments_and_sizes_the_reserve() {
+        use crate::InitOptions;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let init = |options: InitOptions| {
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(&env, &contract_id);
+            let result = client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &options,
+            );
+            (client, result)
+        };
+
+        let (client, _) = init(InitOptions {
+            max_assets: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(client.get_info().max_assets, 2);
+        // The account entry plus two trustlines.
+        assert_eq!(client.get_reserve_remaining(), 600_000_000);
+        client.record_payment(&100, &Address::generate(&env));
+        client.record_payment(&100, &Address::generate(&env));
+        assert_eq!(
+            client.try_record_payment(&100, &Address::generate(&env)),
+            Err(Ok(Error::TooManyPayments))
+        );
+
+        let (client, _) = init(InitOptions::default());
+        assert_eq!(client.get_info().max_assets, 10);
+        assert_eq!(client.get_reserve_remaining(), BASE_RESERVE_STROOPS);
+
+        for max_assets in [0, 11] {
+            let (_, result) = init(InitOptions {
+                max_assets: Some(max_assets),
+                ..Default::default()
+            });
+            assert_eq!(result, Err(Ok(Error::InvalidMaxAssets)));
+        }
+        let (_, result) = init(InitOptions {
+            max_assets: Some(1),
+            expected_payments: Some(soroban_sdk::vec![
+                &env,
+                expected(&Address::generate(&env), 10, 0),
+                expected(&Address::generate(&env), 10, 0)
+            ]),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(Ok(Error::InvalidExpectations)));
+    }
+
+    // ── Dust thresholds ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_payment_rejects_dust_below_the_minimum() {
+        use crate::{AssetAmount, InitOptions};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let eurc = Address::generate(&env);
+        let init = |options: InitOptions| {
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(&env, &contract_id);
+            let result = client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &options,
+            );
+            (client, result)
+        };
+
+        let (client, _) = init(InitOptions {
+            min_payment: Some(100),
+            min_payment_per_asset: Some(soroban_sdk::vec![
+                &env,
+                AssetAmount {
+                    asset: usdc.clone(),
+                    amount: 10,
+                }
+            ]),
+            accumulate_payments: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            client.try_record_payment(&99, &eurc),
+            Err(Ok(Error::DustAmount))
+        );
+        client.record_payment(&100, &eurc);
+        assert_eq!(
+            client.try_record_payment(&9, &usdc),
+            Err(Ok(Error::DustAmount))
+        );
+        client.record_payment(&10, &usdc);
+        assert_eq!(client.get_payment_assets().len(), 2);
+
+        let (_, result) = init(InitOptions {
+            min_payment: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let minimum = AssetAmount {
+            asset: usdc.clone(),
+            amount: 10,
+        };
+        let (_, result) = init(InitOptions {
+            min_payment_per_asset: Some(soroban_sdk::vec![&env, minimum.clone(), minimum]),
+            ..Default::default()
+        });
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    // ── Asset allowlist ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_record_payment_rejects_assets_off_the_allowlist() {
+        use crate::InitOptions;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let spam = Address::generate(&env);
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                allowed_assets: Some(soroban_sdk::vec![&env, usdc.clone()]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            client.try_record_payment(&100, &spam),
+            Err(Ok(Error::AssetNotAllowed))
+        );
+        assert_eq!(
+            client.can_record_payment(&spam).reason,
+            Some(Error::AssetNotAllowed as u32)
+        );
+        client.record_payment(&100, &usdc);
+        assert_eq!(client.get_info().payment_count, 1);
+    }
+
+    // ── Destination allowlist ───────────────────────────────────────────────
+
+    #[test]
+    fn test_destination_allowlist_rejects_other_destinations() {
+        use crate::InitOptions;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let approved = Address::generate(&env);
+        let other = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let asset = token_held_by(&env, &contract_id, 100);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                allowed_destinations: Some(soroban_sdk::vec![&env, approved.clone()]),
+                ..Default::default()
+            },
+        );
+        client.record_payment(&100, &asset);
+
+        assert_eq!(
+            client.try_sweep(&other, &signature),
+            Err(Ok(Error::DestinationNotAllowed))
+        );
+        assert_eq!(
+            client.try_sweep_assets(&other, &soroban_sdk::vec![&env, asset], &signature),
+            Err(Ok(Error::DestinationNotAllowed))
+        );
+        assert_eq!(
+            client.simulate_sweep(&other).2,
+            Error::DestinationNotAllowed as u32
+        );
+        client.sweep(&approved, &signature);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+    }
+
+    // ── Expected payments ───────────────────────────────────────────────────
+
+    fn setup_expecting(
+        env: &Env,
+        expected: soroban_sdk::Vec<crate::ExpectedPayment>,
+    ) -> EphemeralAccountContractClient<'_> {
+        use crate::InitOptions;
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &InitOptions {
+                expected_payments: Some(expected),
+                ..Default::default()
+            },
+        );
+        client
+    }
+
+    fn expected(asset: &Address, amount: i128, tolerance: i128) -> crate::ExpectedPayment {
+        crate::ExpectedPayment {
+            asset: asset.clone(),
+            amount,
+            tolerance,
+        }
+    }
+
+    #[test]
+    fn test_payment_within_tolerance_meets_expectation() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let client = setup_expecting(&env, soroban_sdk::vec![&env, expected(&usdc, 1_000, 5)]);
+
+        assert_eq!(client.get_expected_payment(&usdc).unwrap().amount, 1_000);
+        assert!(!client.is_expectation_met(&usdc));
+        client.record_payment(&997, &usdc);
+        assert!(client.is_expectation_met(&usdc));
+    }
+
+    #[test]
+    fn test_underpaid_and_overpaid_payments_are_flagged() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let eurc = Address::generate(&env);
+        let client = setup_expecting(
+            &env,
+            soroban_sdk::vec![&env, expected(&usdc, 1_000, 0), expected(&eurc, 500, 0)],
+        );
+        let flagged = |topic: &str| {
+            env.events().all().iter().find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                    == Some(soroban_sdk::Symbol::new(&env, topic))
+            })
+        };
+
+        client.record_payment(&999, &usdc);
+        let (_, _, data) = flagged("underpaid").expect("Underpaid should be emitted");
+        assert_eq!(
+            event_body::<crate::Underpaid>(&env, &data),
+            crate::Underpaid {
+                asset: usdc.clone(),
+                expected: 1_000,
+                received: 999
+            }
+        );
+
+        client.record_payment(&501, &eurc);
+        let (_, _, data) = flagged("overpaid").expect("Overpaid should be emitted");
+        assert_eq!(event_body::<crate::Overpaid>(&env, &data).received, 501);
+
+        // Flagged payments are recorded, but do not meet the expectation.
+        assert_eq!(client.get_info().payment_count, 2);
+        assert!(!client.is_expectation_met(&usdc));
+        assert!(!client.is_expectation_met(&eurc));
+    }
+
+    #[test]
+    fn test_invoice_becomes_sweepable_once_every_expectation_is_reached() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let eurc = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let client = setup_expecting(
+            &env,
+            soroban_sdk::vec![&env, expected(&usdc, 100, 0), expected(&eurc, 50, 0)],
+        );
+        for (token, amount) in [(&usdc, 100), (&eurc, 60)] {
+            soroban_sdk::token::StellarAssetClient::new(&env, token).mint(&client.address, &amount);
+        }
+        let progress = |asset: &Address, expected, received| crate::ExpectationProgress {
+            asset: asset.clone(),
+            expected,
+            received,
+        };
+
+        client.record_payment(&100, &usdc);
+        assert_eq!(client.get_status(), AccountStatus::Active);
+        assert_eq!(
+            client.try_sweep(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64])
+            ),
+            Err(Ok(Error::NoPaymentReceived))
+        );
+        assert_eq!(
+            client.get_info().expectations,
+            soroban_sdk::vec![&env, progress(&usdc, 100, 100), progress(&eurc, 50, 0)]
+        );
+
+        // Overpaying still fulfils the invoice; get_info shows the excess.
+        client.record_payment(&60, &eurc);
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(
+            client.get_info().expectations,
+            soroban_sdk::vec![&env, progress(&usdc, 100, 100), progress(&eurc, 50, 60)]
+        );
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+    }
+
+    #[test]
+    fn test_invalid_expectations_are_rejected() {
+        use crate::InitOptions;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let usdc = Address::generate(&env);
+        let init = |expected| {
+            let contract_id = env.register(EphemeralAccountContract, ());
+            EphemeralAccountContractClient::new(&env, &contract_id).try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &InitOptions {
+                    expected_payments: Some(expected),
+                    ..Default::default()
+                },
+            )
+        };
+
+        for invalid in [
+            soroban_sdk::vec![&env, expected(&usdc, 0, 0)],
+            soroban_sdk::vec![&env, expected(&usdc, 10, -1)],
+            soroban_sdk::vec![&env, expected(&usdc, 10, 0), expected(&usdc, 20, 0)],
+        ] {
+            assert_eq!(init(invalid), Err(Ok(Error::InvalidExpectations)));
+        }
+    }
+
+    #[test]
+    fn test_refund_excess_returns_only_the_surplus() {
+        use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let client = setup_expecting(&env, soroban_sdk::vec![&env, expected(&token, 100, 5)]);
+        let payer = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&payer, &130);
+
+        client.record_payment_from(&payer, &130, &token);
+        assert_eq!(client.get_overpayment(&token), 30);
+        assert!(!client.is_expectation_met(&token));
+
+        assert_eq!(client.refund_excess(&token), 30);
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("excess"))
+            })
+            .expect("ExcessRefunded should be emitted");
+        assert_eq!(
+            event_body::<crate::ExcessRefunded>(&env, &data),
+            crate::ExcessRefunded {
+                payer: payer.clone(),
+                asset: token.clone(),
+                amount: 30,
+            }
+        );
+
+        assert_eq!(TokenClient::new(&env, &token).balance(&payer), 30);
+        assert_eq!(client.get_payment(&token).unwrap().amount, 100);
+        assert_eq!(client.get_overpayment(&token), 0);
+        assert!(client.is_expectation_met(&token));
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        assert_eq!(
+            client.try_refund_excess(&token),
+            Err(Ok(Error::NotRefundable))
+        );
+    }
+
+    #[test]
+    fn test_refund_excess_needs_a_known_payer() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let asset = Address::generate(&env);
+        let client = setup_expecting(&env, soroban_sdk::vec![&env, expected(&asset, 100, 0)]);
+
+        client.record_payment(&150, &asset);
+        assert_eq!(client.get_overpayment(&asset), 50);
+        assert_eq!(
+            client.try_refund_excess(&asset),
+            Err(Ok(Error::NotRefundable))
+        );
+        assert_eq!(client.get_overpayment(&Address::generate(&env)), 0);
+    }
+
+    #[test]
+    fn test_refund_excess_needs_no_auth_on_a_cosign_account() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let payer = Address::generate(&env);
+        let token = token_held_by(&env, &payer, 130);
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                expected_payments: Some(soroban_sdk::vec![&env, expected(&token, 100, 0)]),
+                require_creator_cosign: true,
+                ..Default::default()
+            },
+        );
+        client.record_payment_from(&payer, &130, &token);
+
+        // Neither the creator nor the controller signs.
+        env.set_auths(&[]);
+        assert_eq!(client.refund_excess(&token), 30);
+        assert!(env.auths().is_empty());
+        assert_eq!(
+            soroban_sdk::token::TokenClient::new(&env, &token).balance(&payer),
+            30
+        );
+    }
+
+    // ── Protocol fee ────────────────────────────────────────────────────────
+
+    fn setup_fee_account(
+        env: &Env,
+        bps: u32,
+    ) -> (EphemeralAccountContractClient<'_>, Address, Address) {
+        use soroban_sdk::token::StellarAssetClient;
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        let recipient = Address::generate(env);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                protocol_fee_bps: bps,
+                protocol_fee_recipient: Some(recipient.clone()),
+                ..Default::default()
+            },
+        );
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        StellarAssetClient::new(env, &token).mint(&contract_id, &1_001);
+        client.record_payment(&1_001, &token);
+        (client, token, recipient)
+    }
+
+    #[test]
+    fn test_sweep_sends_the_protocol_fee_and_reports_net_payments() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, token, recipient) = setup_fee_account(&env, 250);
+
+        let (transfers, _, error_code) = client.simulate_sweep(&Address::generate(&env));
+        assert_eq!(error_code, 0);
+        assert_eq!(transfers.get_unchecked(0).amount, 976);
+
+        let swept = client.sweep_and_report(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        let event = sweep_event(&env);
+
+        // 2.5% of 1001 rounds down to 25; the destination gets the rest.
+        assert_eq!(swept.get_unchecked(0).amount, 976);
+        assert_eq!(TokenClient::new(&env, &token).balance(&recipient), 25);
+        assert_eq!(
+            (event.total_amount, event.total_fee, event.total_net),
+            (1_001, 25, 976)
+        );
+        assert_eq!(client.get_payment(&token).unwrap().amount, 1_001);
+    }
+
+    #[test]
+    fn test_sweep_without_a_protocol_fee_reports_zero_fee() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+
+        let swept = client.sweep_and_report(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        let event = sweep_event(&env);
+        assert_eq!(swept.get_unchecked(0).amount, 100);
+        assert_eq!((event.total_fee, event.total_net), (0, 100));
+        assert_eq!(client.get_protocol_fee(), None);
+    }
+
+    #[test]
+    fn test_initialize_rejects_out_of_range_protocol_fees() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        // The whole amount or more, or a fee without a recipient.
+        for (bps, recipient) in [
+            (10_000, Some(Address::generate(&env))),
+            (10_001, Some(Address::generate(&env))),
+            (50, None),
+        ] {
+            assert_eq!(
+                client.try_initialize_with_options(
+                    &Address::generate(&env),
+                    &(env.ledger().sequence() + 1000),
+                    &Address::generate(&env),
+                    &Address::generate(&env),
+                    &Address::generate(&env),
+                    &crate::InitOptions {
+                        protocol_fee_bps: bps,
+                        protocol_fee_recipient: recipient,
+                        ..Default::default()
+                    },
+                ),
+                Err(Ok(Error::InvalidFee))
+            );
+        }
+    }
+
+    // ── Checked arithmetic ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_expire_rejects_payment_totals_that_overflow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&i128::MAX, &Address::generate(&env));
+        client.record_payment(&1, &Address::generate(&env));
+
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 1000);
+        assert_eq!(client.try_expire(), Err(Ok(Error::Overflow)));
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+    }
+
+    #[test]
+    fn test_sweep_rejects_payment_totals_that_overflow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        client.record_payment(&i128::MAX, &Address::generate(&env));
+        client.record_payment(&1, &Address::generate(&env));
+
+        assert_eq!(
+            client.try_sweep(
+                &Address::generate(&env),
+                &BytesN::from_array(&env, &[0u8; 64]),
+            ),
+            Err(Ok(Error::Overflow))
+        );
+        assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+    }
+
+    #[test]
+    fn test_record_undelivered_rejects_negative_amounts() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+        let asset = token_held_by(&env, &client.address, 100);
+        client.record_payment(&100, &asset);
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+
+        assert_eq!(
+            client.try_record_undelivered(&soroban_sdk::vec![
+                &env,
+                crate::Payment {
+                    asset,
+                    amount: -1,
+                    timestamp: 0,
+                    payer: None,
+                }
+            ]),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    // ── Multisig sweep ──────────────────────────────────────────────────────
+
+    mod multisig {
+        use super::*;
+        use crate::InitOptions;
+        use ed25519_dalek::{Signer, SigningKey};
+        use soroban_sdk::{vec, Vec};
+
+        fn keys() -> [SigningKey; 3] {
+            [1u8, 2, 3].map(|seed| SigningKey::from_bytes(&[seed; 32]))
+        }
+
+        fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+            BytesN::from_array(env, &key.verifying_key().to_bytes())
+        }
+
+        /// A paid account guarded by a 2-of-3 signer set of `keys()`.
+        /// Returns the client and the authorized controller.
+        fn setup(env: &Env) -> (EphemeralAccountContractClient<'_>, Address) {
+            let contract_id = env.register(EphemeralAccountContract, ());
+            let client = EphemeralAccountContractClient::new(env, &contract_id);
+            let controller = Address::generate(env);
+            let signers = keys().map(|key| public_key(env, &key));
+            client.initialize_with_options(
+                &Address::generate(env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(env),
+                &controller,
+                &Address::generate(env),
+                &InitOptions {
+                    sweep_signers: Some(Vec::from_array(env, signers)),
+                    sweep_threshold: 2,
+                    ..Default::default()
+                },
+            );
+            client.record_payment(&100, &Address::generate(env));
+            (client, controller)
+        }
+
+        fn sign(
+            env: &Env,
+            client: &EphemeralAccountContractClient,
+            controller: &Address,
+            destination: &Address,
+            signers: &[&SigningKey],
+        ) -> Vec<(BytesN<32>, BytesN<64>)> {
+            let message = bridgelet_shared::bytes::account_sweep_message_digest(
+                env,
+                &env.ledger().network_id(),
+                &client.address,
+                client.get_sweep_nonce(),
+                destination,
+                controller,
+                None,
+            );
+            let mut signatures = Vec::new(env);
+            for key in signers {
+                let signature = key.sign(&message.to_array());
+                signatures.push_back((
+                    public_key(env, key),
+                    BytesN::from_array(env, &signature.to_bytes()),
+                ));
+            }
+            signatures
+        }
+
+        #[test]
+        fn test_threshold_of_signers_prepares_the_sweep() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, controller) = setup(&env);
+            let [first, _, third] = keys();
+            let destination = Address::generate(&env);
+
+            let signatures = sign(&env, &client, &controller, &destination, &[&third, &first]);
+            let payments = client.prepare_multisig_sweep(&destination, &None, &signatures);
+
+            assert_eq!(payments.get_unchecked(0).amount, 100);
+            assert_eq!(client.get_status(), AccountStatus::PendingSweep);
+            client.commit_sweep();
+            assert_eq!(client.get_status(), AccountStatus::Swept);
+        }
+
+        #[test]
+        fn test_signatures_below_threshold_or_from_unknown_keys_are_rejected() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, controller) = setup(&env);
+            let [first, second, _] = keys();
+            let stranger = SigningKey::from_bytes(&[9u8; 32]);
+            let destination = Address::generate(&env);
+
+            for (signers, error) in [
+                (std::vec![&first], Error::InsufficientSignatures),
+                (std::vec![&first, &first], Error::Unauthorized),
+                (std::vec![&first, &stranger], Error::Unauthorized),
+            ] {
+                let signatures = sign(&env, &client, &controller, &destination, &signers);
+                assert_eq!(
+                    client.try_prepare_multisig_sweep(&destination, &None, &signatures),
+                    Err(Ok(error))
+                );
+            }
+
+            // Signed for another destination
+            let signatures = sign(
+                &env,
+                &client,
+                &controller,
+                &Address::generate(&env),
+                &[&first, &second],
+            );
+            assert!(client
+                .try_prepare_multisig_sweep(&destination, &None, &signatures)
+                .is_err());
+            assert_eq!(client.get_status(), AccountStatus::PaymentReceived);
+        }
+
+        #[test]
+        fn test_single_signature_paths_are_refused_with_a_signer_set() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (client, _) = setup(&env);
+            let destination = Address::generate(&env);
+
+            assert_eq!(
+                client.try_sweep_and_report(&destination, &BytesN::from_array(&env, &[0u8; 64])),
+                Err(Ok(Error::InsufficientSignatures))
+            );
+            assert_eq!(
+                client.try_sweep_claim(&destination),
+                Err(Ok(Error::InsufficientSignatures))
+            );
+        }
+
+        #[test]
+        fn test_multisig_sweep_requires_a_signer_set() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let (_, client) = setup_initialized(&env);
+            client.record_payment(&100, &Address::generate(&env));
+
+            assert_eq!(
+                client.try_prepare_multisig_sweep(&Address::generate(&env), &None, &Vec::new(&env)),
+                Err(Ok(Error::Unauthorized))
+            );
+        }
+
+        #[test]
+        fn test_initialize_rejects_invalid_signer_sets() {
+            let env = Env::default();
+            env.mock_all_auths();
+            let [first, second, _] = keys();
+            let (first, second) = (public_key(&env, &first), public_key(&env, &second));
+            let eleven = Vec::from_array(
+                &env,
+                core::array::from_fn::<_, 11, _>(|i| BytesN::from_array(&env, &[i as u8; 32])),
+            );
+
+            for (signers, threshold) in [
+                (Some(Vec::new(&env)), 1),
+                (Some(vec![&env, first.clone(), second.clone()]), 0),
+                (Some(vec![&env, first.clone(), second.clone()]), 3),
+                (Some(vec![&env, first.clone(), first.clone()]), 1),
+                (Some(eleven), 1),
+                (None, 1),
+            ] {
+                let contract_id = env.register(EphemeralAccountContract, ());
+                let client = EphemeralAccountContractClient::new(&env, &contract_id);
+                assert_eq!(
+                    client.try_initialize_with_options(
+                        &Address::generate(&env),
+                        &(env.ledger().sequence() + 1000),
+                        &Address::generate(&env),
+                        &Address::generate(&env),
+                        &Address::generate(&env),
+                        &InitOptions {
+                            sweep_signers: signers,
+                            sweep_threshold: threshold,
+                            ..Default::default()
+                        },
+                    ),
+                    Err(Ok(Error::InvalidSigners))
+                );
+            }
+        }
+    }
+
+    // ── Sweep hook ──────────────────────────────────────────────────────────
+
+    /// Hook contracts: a vault that records what it was told arrived, and
+    /// one that always fails.
+    mod hooks {
+        use bridgelet_shared::{AssetAmount, SweepHookInterface};
+        use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+        #[contracttype]
+        enum Key {
+            Swept(Address),
+        }
+
+        #[contract]
+        pub struct Vault;
+
+        #[contractimpl]
+        impl Vault {
+            pub fn on_sweep(
+                env: Env,
+                account: Address,
+                destination: Address,
+                assets: Vec<AssetAmount>,
+            ) {
+                env.storage()
+                    .instance()
+                    .set(&Key::Swept(account), &(destination, assets));
+            }
+
+            pub fn swept(env: Env, account: Address) -> Option<(Address, Vec<AssetAmount>)> {
+                env.storage().instance().get(&Key::Swept(account))
+            }
+        }
+
+        impl SweepHookInterface for Vault {
+            fn on_sweep(
+                env: Env,
+                account: Address,
+                destination: Address,
+                assets: Vec<AssetAmount>,
+            ) {
+                Self::on_sweep(env, account, destination, assets)
+            }
+        }
+
+        pub mod failing {
+            use bridgelet_shared::AssetAmount;
+            use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
+
+            #[contract]
+            pub struct FailingVault;
+
+            #[contractimpl]
+            impl FailingVault {
+                pub fn on_sweep(
+                    _env: Env,
+                    _account: Address,
+                    _destination: Address,
+                    _assets: Vec<AssetAmount>,
+                ) {
+                    panic!("vault rejected deposit");
+                }
+            }
+        }
+    }
+
+    fn setup_hooked<'a>(
+        env: &'a Env,
+        hook: &Address,
+    ) -> (Address, EphemeralAccountContractClient<'a>) {
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                sweep_hook: Some(hook.clone()),
+                ..Default::default()
+            },
+        );
+        (contract_id, client)
+    }
+
+    fn hook_event(env: &Env) -> Option<crate::SweepHookCalled> {
+        env.events()
+            .all()
+            .iter()
+            .find(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(env, &topics.get_unchecked(0))
+                    == Ok(soroban_sdk::symbol_short!("hook"))
+            })
+            .map(|(_, _, data)| event_body::<crate::SweepHookCalled>(env, &data))
+    }
+
+    #[test]
+    fn test_sweep_calls_the_hook_with_the_delivered_assets() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let vault = env.register(hooks::Vault, ());
+        let (account, client) = setup_hooked(&env, &vault);
+        let asset = token_held_by(&env, &account, 100);
+        client.record_payment(&100, &asset);
+
+        let destination = Address::generate(&env);
+        client.sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        assert_eq!(
+            hook_event(&env),
+            Some(crate::SweepHookCalled {
+                hook: vault.clone(),
+                succeeded: true
+            })
+        );
+
+        let assets = soroban_sdk::vec![&env, crate::AssetAmount { asset, amount: 100 }];
+        assert_eq!(
+            hooks::VaultClient::new(&env, &vault).swept(&account),
+            Some((destination, assets))
+        );
+    }
+
+    #[test]
+    fn test_expire_calls_the_hook_with_the_recovery_address() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let vault = env.register(hooks::Vault, ());
+        let (account, client) = setup_hooked(&env, &vault);
+        client.record_payment(&100, &token_held_by(&env, &account, 100));
+
+        env.ledger()
+            .set_sequence_number(env.ledger().sequence() + 1000);
+        client.expire();
+
+        let (destination, assets) = hooks::VaultClient::new(&env, &vault)
+            .swept(&account)
+            .unwrap();
+        assert_eq!(destination, client.get_info().recovery_address);
+        assert_eq!(assets.get_unchecked(0).amount, 100);
+    }
+
+    #[test]
+    fn test_failing_hook_does_not_block_the_sweep() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let vault = env.register(hooks::failing::FailingVault, ());
+        let (_, client) = setup_hooked(&env, &vault);
+        client.record_payment(&100, &token_held_by(&env, &client.address, 100));
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        assert_eq!(
+            hook_event(&env),
+            Some(crate::SweepHookCalled {
+                hook: vault,
+                succeeded: false
+            })
+        );
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+    }
+
+    #[test]
+    fn test_initialize_rejects_a_hook_that_is_not_a_contract() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let issuer = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .issuer()
+            .address();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        assert_eq!(
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    sweep_hook: Some(issuer),
+                    ..Default::default()
+                },
+            ),
+            Err(Ok(Error::InvalidHook))
+        );
+    }
+
+    // ── Expiry countdown ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_expiry_countdown_tracks_the_ledger() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (_, client) = setup_initialized(&env);
+
+        assert_eq!(client.ledgers_until_expiry(), 1000);
+        assert_eq!(client.estimated_seconds_until_expiry(), 5000);
+
+        let sequence = env.ledger().sequence();
+        env.ledger().set_sequence_number(sequence + 400);
+        assert_eq!(client.ledgers_until_expiry(), 600);
+
+        // Past expiry the countdown stays at zero.
+        env.ledger().set_sequence_number(sequence + 1200);
+        assert_eq!(client.ledgers_until_expiry(), 0);
+        assert_eq!(client.estimated_seconds_until_expiry(), 0);
+    }
+
+    #[test]
+    fn test_expiry_countdown_uses_the_configured_ledger_time() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let init = |ledger_seconds| {
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    ledger_seconds,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(init(Some(0)), Err(Ok(Error::InvalidLedgerTime)));
+        assert_eq!(
+            client.try_ledgers_until_expiry(),
+            Err(Ok(Error::NotInitialized))
+        );
+
+        init(Some(6)).unwrap().unwrap();
+        assert_eq!(client.estimated_seconds_until_expiry(), 6000);
+    }
+
+    // ── Expiry reminders ────────────────────────────────────────────────────
+
+    fn reminders_emitted(env: &Env) -> std::vec::Vec<crate::ExpiryReminder> {
+        env.events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                soroban_sdk::Symbol::try_from_val(env, &topics.get_unchecked(0)).ok()
+                    == Some(soroban_sdk::Symbol::new(env, "reminder"))
+            })
+            .map(|(_, _, data)| event_body::<crate::ExpiryReminder>(env, &data))
+            .collect()
+    }
+
+    #[test]
+    fn test_ping_emits_each_reminder_window_once() {
+        use crate::InitOptions;
+        use soroban_sdk::vec;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let expiry_ledger = env.ledger().sequence() + 2000;
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &expiry_ledger,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &InitOptions {
+                expiry_reminders: Some(vec![&env, 1000, 100]),
+                ..Default::default()
+            },
+        );
+
+        // Outside every window.
+        assert_eq!(client.ping(), 0);
+        assert!(reminders_emitted(&env).is_empty());
+
+        env.ledger().set_sequence_number(expiry_ledger - 1000);
+        assert_eq!(client.ping(), 1);
+        assert_eq!(
+            reminders_emitted(&env),
+            std::vec![crate::ExpiryReminder {
+                offset: 1000,
+                expiry_ledger
+            }]
+        );
+
+        // The same window does not fire twice.
+        env.ledger().set_sequence_number(expiry_ledger - 500);
+        assert_eq!(client.ping(), 0);
+
+        env.ledger().set_sequence_number(expiry_ledger - 50);
+        assert_eq!(client.ping(), 1);
+        assert_eq!(reminders_emitted(&env)[0].offset, 100);
+        assert_eq!(client.ping(), 0);
+    }
+
+    #[test]
+    fn test_expiry_reminders_are_validated_and_stop_at_expiry() {
+        use crate::InitOptions;
+        use soroban_sdk::vec;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let expiry_ledger = env.ledger().sequence() + 2000;
+        let init = |reminders| {
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &expiry_ledger,
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &InitOptions {
+                    expiry_reminders: Some(reminders),
+                    ..Default::default()
+                },
+            )
+        };
+        assert_eq!(init(vec![&env, 100, 100]), Err(Ok(Error::InvalidReminders)));
+        assert_eq!(init(vec![&env, 0]), Err(Ok(Error::InvalidReminders)));
+        init(vec![&env, 100]).unwrap().unwrap();
+
+        env.ledger().set_sequence_number(expiry_ledger);
+        assert_eq!(client.ping(), 0);
+        assert!(reminders_emitted(&env).is_empty());
+    }
+
+    // ── Vesting ─────────────────────────────────────────────────────────────
+
+    /// Account vesting over 100 ledgers from its sweep, with a cliff 20
+    /// ledgers in, holding 1_000 of a real token. Returns the client and the
+    /// token.
+    fn setup_vesting(env: &Env) -> (EphemeralAccountContractClient<'_>, Address) {
+        use soroban_sdk::token::StellarAssetClient;
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(env),
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                vesting_cliff_ledger: Some(env.ledger().sequence() + 20),
+                vesting_duration_ledgers: 100,
+                ..Default::default()
+            },
+        );
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        StellarAssetClient::new(env, &token).mint(&contract_id, &1_000);
+        client.record_payment(&1_000, &token);
+        (client, token)
+    }
+
+    #[test]
+    fn test_vested_funds_are_claimed_in_tranches_after_the_cliff() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, token) = setup_vesting(&env);
+        let destination = Address::generate(&env);
+
+        client.sweep(&destination, &BytesN::from_array(&env, &[0u8; 64]));
+        assert!(client.get_info().vesting);
+        assert_eq!(client.get_vesting_schedule().unwrap().duration_ledgers, 100);
+        assert_eq!(
+            client.try_claim_vested(&token),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        // 25 ledgers in, past the cliff: a quarter has vested.
+        env.ledger().with_mut(|li| li.sequence_number += 25);
+        assert_eq!(client.get_claimable_vested(&token), 250);
+        assert_eq!(client.claim_vested(&token), 250);
+        let (_, _, data) = env.events().all().last().unwrap();
+        assert_eq!(
+            event_body::<crate::VestedClaimed>(&env, &data),
+            crate::VestedClaimed {
+                destination: destination.clone(),
+                asset: token.clone(),
+                amount: 250,
+                claimed_total: 250,
+            }
+        );
+        assert_eq!(
+            client.try_claim_vested(&token),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number += 500);
+        assert_eq!(client.claim_vested(&token), 750);
+        assert_eq!(TokenClient::new(&env, &token).balance(&destination), 1_000);
+        assert_eq!(client.get_vesting_grant(&token).unwrap().claimed, 1_000);
+        assert_eq!(client.get_claimable_vested(&token), 0);
+    }
+
+    #[test]
+    fn test_nothing_vests_before_the_cliff() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, token) = setup_vesting(&env);
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        env.ledger().with_mut(|li| li.sequence_number += 19);
+        assert_eq!(client.get_claimable_vested(&token), 0);
+        assert_eq!(
+            client.try_claim_vested(&token),
+            Err(Ok(Error::NothingToClaim))
+        );
+    }
+
+    #[test]
+    fn test_vesting_account_closes_only_once_fully_claimed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, token) = setup_vesting(&env);
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        assert_eq!(client.try_close_account(), Err(Ok(Error::InvalidStatus)));
+
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        client.claim_vested(&token);
+        client.close_account();
+        assert_eq!(client.get_init_state(), crate::InitState::Closed);
+    }
+
+    #[test]
+    fn test_vesting_schedule_is_validated_at_initialize() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let init = |cliff_ledger, duration_ledgers| {
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    vesting_cliff_ledger: cliff_ledger,
+                    vesting_duration_ledgers: duration_ledgers,
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            init(Some(u32::MAX), 1),
+            Err(Ok(Error::InvalidVestingSchedule))
+        );
+        assert_eq!(init(None, 100), Err(Ok(Error::InvalidVestingSchedule)));
+    }
+
+    // ── Streaming expiry ────────────────────────────────────────────────────
+
+    /// Account expiring at ledger 1_000 from now with a soft expiry at 600,
+    /// holding 1_000 of a real token. Returns the client, the token and the
+    /// recovery address.
+    fn setup_soft_expiry(env: &Env) -> (EphemeralAccountContractClient<'_>, Address, Address) {
+        use soroban_sdk::token::StellarAssetClient;
+
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(env, &contract_id);
+        let recovery_address = Address::generate(env);
+        client.initialize_with_options(
+            &Address::generate(env),
+            &(env.ledger().sequence() + 1000),
+            &recovery_address,
+            &Address::generate(env),
+            &Address::generate(env),
+            &crate::InitOptions {
+                soft_expiry_ledger: Some(env.ledger().sequence() + 600),
+                ..Default::default()
+            },
+        );
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(env))
+            .address();
+        StellarAssetClient::new(env, &token).mint(&contract_id, &1_000);
+        client.record_payment(&1_000, &token);
+        (client, token, recovery_address)
+    }
+
+    #[test]
+    fn test_expired_portion_grows_towards_expiry() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, token, recovery_address) = setup_soft_expiry(&env);
+        let token_client = TokenClient::new(&env, &token);
+
+        // A quarter of the way from the soft expiry to expiry
+        env.ledger().with_mut(|li| li.sequence_number += 700);
+        let claimed = client.claim_expired_portion();
+        let (_, _, data) = env.events().all().last().unwrap();
+        let event: crate::ExpiredPortionClaimed = event_body(&env, &data);
+        assert_eq!(event.amount, 250);
+        assert_eq!(event.released_total, 250);
+        assert_eq!(claimed.get(0).unwrap().amount, 250);
+        assert_eq!(token_client.balance(&recovery_address), 250);
+        assert_eq!(client.get_payment(&token).unwrap().amount, 750);
+
+        // Halfway: the share is of the payment as recorded, so 250 more
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        client.claim_expired_portion();
+        assert_eq!(token_client.balance(&recovery_address), 500);
+        assert_eq!(client.get_expired_portion(&token), 500);
+        assert_eq!(
+            client.try_claim_expired_portion(),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        client.sweep(
+            &Address::generate(&env),
+            &BytesN::from_array(&env, &[0u8; 64]),
+        );
+        assert_eq!(client.get_payment(&token).unwrap().amount, 500);
+    }
+
+    #[test]
+    fn test_expired_portion_needs_the_soft_expiry() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, _) = setup_soft_expiry(&env);
+
+        env.ledger().with_mut(|li| li.sequence_number += 599);
+        assert_eq!(
+            client.try_claim_expired_portion(),
+            Err(Ok(Error::NotExpired))
+        );
+        env.ledger().with_mut(|li| li.sequence_number += 1);
+        assert_eq!(
+            client.try_claim_expired_portion(),
+            Err(Ok(Error::NothingToClaim))
+        );
+        env.ledger().with_mut(|li| li.sequence_number += 400);
+        assert_eq!(
+            client.try_claim_expired_portion(),
+            Err(Ok(Error::AccountExpired))
+        );
+
+        let (_, client) = setup_initialized(&env);
+        assert_eq!(
+            client.try_claim_expired_portion(),
+            Err(Ok(Error::NoSoftExpiry))
+        );
+    }
+
+    #[test]
+    fn test_expired_portion_is_measured_against_the_payment_as_recorded() {
+        use soroban_sdk::token::TokenClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let payer = Address::generate(&env);
+        let token = token_held_by(&env, &payer, 2_000);
+        let recovery_address = Address::generate(&env);
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &recovery_address,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                soft_expiry_ledger: Some(env.ledger().sequence() + 600),
+                expected_payments: Some(soroban_sdk::vec![&env, expected(&token, 1_000, 0)]),
+                ..Default::default()
+            },
+        );
+        client.record_payment_from(&payer, &2_000, &token);
+
+        // Refunding the surplus does not shrink the share released later.
+        assert_eq!(client.refund_excess(&token), 1_000);
+        env.ledger().with_mut(|li| li.sequence_number += 700);
+        assert_eq!(client.claim_expired_portion().get(0).unwrap().amount, 500);
+        assert_eq!(client.get_payment(&token).unwrap().amount, 500);
+
+        // Three quarters of 2_000 is more than is left: the rest goes.
+        env.ledger().with_mut(|li| li.sequence_number += 200);
+        assert_eq!(client.claim_expired_portion().get(0).unwrap().amount, 500);
+        assert_eq!(
+            TokenClient::new(&env, &token).balance(&recovery_address),
+            1_000
+        );
+        assert_eq!(client.get_payment(&token), None);
+        assert_eq!(client.get_expired_portion(&token), 0);
+        assert_eq!(client.get_status(), AccountStatus::Active);
+    }
+
+    #[test]
+    fn test_soft_expiry_is_validated_at_initialize() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let expiry_ledger = env.ledger().sequence() + 1000;
+        let init = |soft_expiry_ledger| {
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &expiry_ledger,
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    soft_expiry_ledger: Some(soft_expiry_ledger),
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(init(expiry_ledger), Err(Ok(Error::InvalidExpiry)));
+        init(expiry_ledger - 1).unwrap().unwrap();
+        assert_eq!(client.get_soft_expiry_ledger(), Some(expiry_ledger - 1));
+    }
+
+    // ── Restricted recording ────────────────────────────────────────────────
+
+    #[test]
+    fn test_restricted_recording_requires_the_creator() {
+        let env = Env::default();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let creator = Address::generate(&env);
+        env.mock_all_auths();
+        client.initialize_with_options(
+            &creator,
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                restrict_recording: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(client.get_recorder(), Some(creator.clone()));
+
+        client.record_payment(&100, &Address::generate(&env));
+        assert_eq!(env.auths()[0].0, creator);
+
+        env.set_auths(&[]);
+        assert!(client
+            .try_record_payments(&soroban_sdk::vec![
+                &env,
+                asset_amount(&Address::generate(&env), 100)
+            ])
+            .is_err());
+        assert_eq!(client.get_info().payment_count, 1);
+    }
+
+    #[test]
+    fn test_recorder_stands_in_for_the_creator() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        let recorder = Address::generate(&env);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                restrict_recording: true,
+                recorder: Some(recorder.clone()),
+                ..Default::default()
+            },
+        );
+
+        let payer = Address::generate(&env);
+        let asset = token_held_by(&env, &payer, 100);
+        client.record_payment_from(&payer, &100, &asset);
+        let signers: std::vec::Vec<Address> = env
+            .auths()
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect();
+        assert!(signers.contains(&recorder));
+    }
+
+    #[test]
+    fn test_recorder_requires_restricted_recording() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+
+        assert_eq!(
+            client.try_initialize_with_options(
+                &Address::generate(&env),
+                &(env.ledger().sequence() + 1000),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &Address::generate(&env),
+                &crate::InitOptions {
+                    recorder: Some(Address::generate(&env)),
+                    ..Default::default()
+                },
+            ),
+            Err(Ok(Error::InvalidRecorder))
+        );
+    }
+
+    // ── Balance reconciliation ──────────────────────────────────────────────
+
+    #[test]
+    fn test_verify_balances_reports_drift_from_the_books() {
+        use soroban_sdk::token::StellarAssetClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let (contract_id, client) = setup_initialized(&env);
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let phantom = Address::generate(&env);
+
+        StellarAssetClient::new(&env, &token).mint(&contract_id, &1_000);
+        client.record_payment(&1_000, &token);
+        assert!(client.verify_balances().is_empty());
+
+        // An unrecorded deposit, and a payment never transferred at all
+        StellarAssetClient::new(&env, &token).mint(&contract_id, &50);
+        client.record_payment(&500, &phantom);
+        let discrepancies = client.verify_balances();
+        assert_eq!(discrepancies.len(), 2);
+        assert!(discrepancies.contains(crate::BalanceDiscrepancy {
+            asset: token,
+            recorded: 1_000,
+            balance: 1_050,
+        }));
+        assert!(discrepancies.contains(crate::BalanceDiscrepancy {
+            asset: phantom,
+            recorded: 500,
+            balance: 0,
+        }));
+    }
+
+    #[test]
+    fn test_verify_balances_on_sweep_refuses_a_shortfall() {
+        use soroban_sdk::token::StellarAssetClient;
+
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(EphemeralAccountContract, ());
+        let client = EphemeralAccountContractClient::new(&env, &contract_id);
+        client.initialize_with_options(
+            &Address::generate(&env),
+            &(env.ledger().sequence() + 1000),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &crate::InitOptions {
+                verify_balances_on_sweep: true,
+                ..Default::default()
+            },
+        );
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        StellarAssetClient::new(&env, &token).mint(&contract_id, &400);
+        client.record_payment(&1_000, &token);
+
+        let destination = Address::generate(&env);
+        let signature = BytesN::from_array(&env, &[0u8; 64]);
+        assert_eq!(
+            client.try_sweep(&destination, &signature),
+            Err(Ok(Error::BalanceShortfall))
+        );
+        assert_eq!(
+            client.try_sweep_assets(
+                &destination,
+                &soroban_sdk::vec![&env, token.clone()],
+                &signature
+            ),
+            Err(Ok(Error::BalanceShortfall))
+        );
+
+        StellarAssetClient::new(&env, &token).mint(&contract_id, &600);
+        client.sweep(&destination, &signature);
+        assert_eq!(client.get_status(), AccountStatus::Swept);
+    }
 }
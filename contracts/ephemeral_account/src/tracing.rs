@@ -0,0 +1,23 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use soroban_sdk::{contractimpl, BytesN, Env};
+
+/// Trace ids stamped into the account's events.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Stamp `trace_id` into this account's events for the rest of the transaction.
+    pub fn set_trace_id(env: Env, trace_id: Option<BytesN<16>>) -> Result<(), Error> {
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let controller = storage::get_authorized_controller(&env).ok_or(Error::Unauthorized)?;
+        controller.require_auth();
+
+        storage::set_trace_id(&env, trace_id.as_ref());
+        Ok(())
+    }
+}
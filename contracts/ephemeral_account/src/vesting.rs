@@ -0,0 +1,108 @@
+use crate::errors::Error;
+use crate::events;
+use crate::storage;
+use crate::storage::{VestingGrant, VestingSchedule};
+use crate::{
+    EphemeralAccountContract, EphemeralAccountContractArgs, EphemeralAccountContractClient,
+};
+use bridgelet_shared::Payment;
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, Vec};
+
+/// Timed release of swept funds, configured by `vesting_cliff_ledger` and
+/// `vesting_duration_ledgers`.
+#[contractimpl]
+impl EphemeralAccountContract {
+    /// Pay the destination the part of `asset` that has vested since its
+    /// last claim, on an account initialized with `vesting_cliff_ledger`.
+    pub fn claim_vested(env: Env, asset: Address) -> Result<i128, Error> {
+        storage::extend_ttls(&env);
+
+        if !storage::is_initialized(&env) {
+            return Err(Error::NotInitialized);
+        }
+
+        let mut grant = storage::get_vesting_grant(&env, &asset).ok_or(Error::NothingToClaim)?;
+        grant.destination.require_auth();
+
+        let amount = Self::vested(&env, &grant)?
+            .checked_sub(grant.claimed)
+            .ok_or(Error::Overflow)?;
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        grant.claimed = grant.claimed.checked_add(amount).ok_or(Error::Overflow)?;
+        storage::set_vesting_grant(&env, &asset, &grant);
+        TokenClient::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &grant.destination,
+            &amount,
+        );
+        events::emit_vested_claimed(&env, grant.destination, asset, amount, grant.claimed);
+        Ok(amount)
+    }
+
+    /// Vesting state of a swept `asset`, or `None` if it is not vesting.
+    pub fn get_vesting_grant(env: Env, asset: Address) -> Option<VestingGrant> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_vesting_grant(&env, &asset)
+    }
+
+    /// Schedule swept funds vest on, or `None` if the sweep delivers them.
+    pub fn get_vesting_schedule(env: Env) -> Option<VestingSchedule> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_vesting_schedule(&env)
+    }
+
+    /// Amount of `asset` `claim_vested` would pay out right now.
+    pub fn get_claimable_vested(env: Env, asset: Address) -> i128 {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_vesting_grant(&env, &asset)
+            .and_then(|grant| Self::vested(&env, &grant).ok()?.checked_sub(grant.claimed))
+            .unwrap_or(0)
+    }
+}
+
+impl EphemeralAccountContract {
+    /// Keep `payments` in the account to vest to `destination`, if the
+    /// account has a vesting schedule.
+    pub(crate) fn start_vesting(env: &Env, destination: &Address, payments: &Vec<Payment>) {
+        if storage::get_vesting_schedule(env).is_none() {
+            return;
+        }
+        for payment in payments.iter() {
+            storage::set_vesting_grant(
+                env,
+                &payment.asset,
+                &VestingGrant {
+                    destination: destination.clone(),
+                    total: payment.amount,
+                    claimed: 0,
+                    start_ledger: env.ledger().sequence(),
+                },
+            );
+        }
+    }
+
+    /// Part of `grant` vested at the current ledger, including what was
+    /// already claimed.
+    fn vested(env: &Env, grant: &VestingGrant) -> Result<i128, Error> {
+        let schedule = storage::get_vesting_schedule(env).ok_or(Error::NothingToClaim)?;
+        let ledger = env.ledger().sequence();
+        if ledger < schedule.cliff_ledger {
+            return Ok(0);
+        }
+        let elapsed = ledger.saturating_sub(grant.start_ledger);
+        if elapsed >= schedule.duration_ledgers {
+            return Ok(grant.total);
+        }
+        grant
+            .total
+            .checked_mul(elapsed as i128)
+            .map(|scaled| scaled / schedule.duration_ledgers as i128)
+            .ok_or(Error::Overflow)
+    }
+}
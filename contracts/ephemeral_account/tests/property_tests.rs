@@ -24,6 +24,7 @@ use ephemeral_account::{
 use proptest::prelude::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
+    token::StellarAssetClient,
     Address, BytesN, Env,
 };
 
@@ -57,7 +58,10 @@ proptest! {
         );
 
         for amount in amounts.iter() {
-            let asset = Address::generate(&env);
+            let asset = env
+                .register_stellar_asset_contract_v2(Address::generate(&env))
+                .address();
+            StellarAssetClient::new(&env, &asset).mint(&contract_id, amount);
             client.record_payment(amount, &asset);
         }
 
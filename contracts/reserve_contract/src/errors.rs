@@ -30,10 +30,32 @@ pub enum Error {
     /// was called.
     NotInitialized = 5,
 
-    /// The supplied amount exceeds the maximum allowed value.
+    /// [`ReserveContract::register_consumer`] was called for an address that
+    /// is already registered.
+    ConsumerAlreadyRegistered = 7,
+
+    /// [`ReserveContract::unregister_consumer`] was called for an address
+    /// that is not registered.
+    ConsumerNotRegistered = 8,
+
+    /// Registering another consumer would exceed `MAX_CONSUMERS`.
+    ///
+    /// The bound keeps the notification loop in
+    /// [`ReserveContract::set_base_reserve`] within a single transaction's
+    /// budget.
+    TooManyConsumers = 9,
+
+    /// A write was attempted while maintenance mode is on.
+    ///
+    /// See [`ReserveContract::set_maintenance`]. Reads keep serving the
+    /// last-known value, flagged as stale.
+    InMaintenance = 10,
+
+    /// The supplied per-entry reserve is outside the sane range of 0.1 to
+    /// 10 XLM (`MIN_SANE_RESERVE_STROOPS` to `MAX_SANE_RESERVE_STROOPS`).
     ///
-    /// An upper bound prevents accidental misconfiguration
-    /// (e.g. storing a value in XLM instead of stroops).
-    /// Current ceiling: 10,000 XLM = 100_000_000_000 stroops.
-    AmountTooLarge = 6,
+    /// Code 6 (`AmountTooLarge`, for values above a 10,000 XLM ceiling) is
+    /// retired: this range is the only upper bound, and every positive
+    /// value above it is rejected here.
+    ReserveOutOfBounds = 11,
 }
@@ -18,6 +18,58 @@ pub struct BaseReserveUpdated {
     pub admin: Address,
 }
 
+/// Parameters pushed to every registered consumer's `on_reserve_updated`
+/// hook when the base reserve changes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveParams {
+    /// New base reserve, in stroops.
+    pub base_reserve: i128,
+}
+
+/// Emitted when a contract registers for reserve update notifications.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumerRegistered {
+    pub consumer: Address,
+}
+
+/// Emitted when a contract stops receiving reserve update notifications.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumerUnregistered {
+    pub consumer: Address,
+}
+
+/// Emitted when a consumer's `on_reserve_updated` hook failed and it was
+/// skipped. The reserve update itself still succeeds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumerSkipped {
+    pub consumer: Address,
+    pub params: ReserveParams,
+}
+
+/// Base reserve as served to readers by
+/// [`ReserveContract::get_reserve_reading`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReserveReading {
+    /// Last stored base reserve, in stroops, or `None` if never set.
+    pub base_reserve: Option<i128>,
+    /// `true` while maintenance mode is on: the value may be about to change
+    /// and should not be cached.
+    pub stale: bool,
+}
+
+/// Emitted when maintenance mode is turned on or off.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceToggled {
+    pub enabled: bool,
+    pub admin: Address,
+}
+
 // ─── Emit helpers ───────────────────────────────────────────────────────────
 
 /// Publish the `initialized` event.
@@ -37,3 +89,27 @@ pub fn emit_base_reserve_updated(env: &Env, old_value: i128, new_value: i128, ad
     };
     env.events().publish((symbol_short!("reserve"),), event);
 }
+
+/// Publish the `consumer` event for a new registration.
+pub fn emit_consumer_registered(env: &Env, consumer: Address) {
+    let event = ConsumerRegistered { consumer };
+    env.events().publish((symbol_short!("consumer"),), event);
+}
+
+/// Publish the `unconsume` event for a removed registration.
+pub fn emit_consumer_unregistered(env: &Env, consumer: Address) {
+    let event = ConsumerUnregistered { consumer };
+    env.events().publish((symbol_short!("unconsume"),), event);
+}
+
+/// Publish the `skipped` event for a consumer whose hook failed.
+pub fn emit_consumer_skipped(env: &Env, consumer: Address, params: ReserveParams) {
+    let event = ConsumerSkipped { consumer, params };
+    env.events().publish((symbol_short!("skipped"),), event);
+}
+
+/// Publish the `maint` event when maintenance mode changes.
+pub fn emit_maintenance_toggled(env: &Env, enabled: bool, admin: Address) {
+    let event = MaintenanceToggled { enabled, admin };
+    env.events().publish((symbol_short!("maint"),), event);
+}
@@ -6,18 +6,37 @@ mod storage;
 #[cfg(test)]
 mod test;
 
-use soroban_sdk::{contract, contractimpl, Address, Env};
+use bridgelet_shared::{ContractVersion, STROOPS_PER_XLM};
+use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, Symbol, Vec};
 
 pub use errors::Error;
-pub use events::{BaseReserveUpdated, ContractInitialized};
+pub use events::{
+    BaseReserveUpdated, ConsumerRegistered, ConsumerSkipped, ConsumerUnregistered,
+    ContractInitialized, MaintenanceToggled, ReserveParams, ReserveReading,
+};
 pub use storage::DataKey;
 
-/// Maximum allowed base reserve: 10 000 XLM = 100_000_000_000 stroops.
+/// Smallest per-entry base reserve accepted: 0.1 XLM.
 ///
-/// This ceiling exists to catch operator mistakes (e.g. passing a value in
-/// XLM instead of stroops).  It can be raised if the Stellar network ever
-/// increases its base reserve beyond this threshold.
-const MAX_RESERVE_STROOPS: i128 = 100_000_000_000;
+/// The network's base reserve is 0.5 XLM per ledger entry. Values outside
+/// [`MIN_SANE_RESERVE_STROOPS`, `MAX_SANE_RESERVE_STROOPS`] are almost
+/// certainly typos (e.g. a value in XLM instead of stroops), and every
+/// downstream reserve calculation would silently inherit them. The bounds
+/// can be widened if the Stellar network ever moves its base reserve out
+/// of this range.
+pub const MIN_SANE_RESERVE_STROOPS: i128 = STROOPS_PER_XLM / 10;
+
+/// Largest per-entry base reserve accepted: 10 XLM.
+pub const MAX_SANE_RESERVE_STROOPS: i128 = 10 * STROOPS_PER_XLM;
+
+/// Maximum number of contracts registered for reserve update notifications.
+///
+/// [`ReserveContract::set_base_reserve`] invokes every consumer in the same
+/// transaction, so the list must stay small enough to fit its budget.
+pub const MAX_CONSUMERS: u32 = 10;
+
+/// Version of this build, reported by `get_version`.
+const BUILD_VERSION: ContractVersion = ContractVersion::parse(env!("CARGO_PKG_VERSION"));
 
 /// A focused on-chain contract that stores and exposes the base reserve
 /// configuration for the Bridgelet system.
@@ -63,6 +82,7 @@ impl ReserveContract {
         admin.require_auth();
 
         storage::set_admin(&env, &admin);
+        storage::set_version(&env, &BUILD_VERSION);
         events::emit_initialized(&env, admin);
 
         Ok(())
@@ -75,20 +95,21 @@ impl ReserveContract {
     /// `BaseReserveUpdated` event for off-chain auditability.
     ///
     /// # Arguments
-    /// * `amount` – Base reserve expressed in stroops.  Must satisfy
-    ///              `0 < amount <= MAX_RESERVE_STROOPS` (currently
-    ///              100 000 000 000, i.e. 10 000 XLM).
+    /// * `amount` – Per-entry base reserve expressed in stroops.  Must
+    ///              satisfy `MIN_SANE_RESERVE_STROOPS <= amount <=
+    ///              MAX_SANE_RESERVE_STROOPS` (0.1 to 10 XLM).
     ///
     /// # Errors
-    /// * [`Error::NotInitialized`] – contract has not been initialized.
-    /// * [`Error::Unauthorized`]   – caller is not the admin.
-    /// * [`Error::InvalidAmount`]  – `amount` is zero or negative.
-    /// * [`Error::AmountTooLarge`] – `amount` exceeds the safety ceiling.
+    /// * [`Error::NotInitialized`]     – contract has not been initialized.
+    /// * [`Error::Unauthorized`]       – caller is not the admin.
+    /// * [`Error::InvalidAmount`]      – `amount` is zero or negative.
+    /// * [`Error::ReserveOutOfBounds`] – `amount` is outside 0.1 to 10 XLM.
+    /// * [`Error::InMaintenance`]      – maintenance mode is on.
     ///
     /// # Example
     /// ```ignore
-    /// // 100 XLM = 1_000_000_000 stroops
-    /// client.set_base_reserve(&1_000_000_000i128);
+    /// // 0.5 XLM = 5_000_000 stroops
+    /// client.set_base_reserve(&5_000_000i128);
     /// ```
     pub fn set_base_reserve(env: Env, amount: i128) -> Result<(), Error> {
         storage::extend_instance_ttl(&env);
@@ -99,22 +120,120 @@ impl ReserveContract {
         // 2. Caller must be the admin
         admin.require_auth();
 
-        // 3. Amount validation
+        // 3. No writes during maintenance
+        if storage::is_maintenance(&env) {
+            return Err(Error::InMaintenance);
+        }
+
+        // 4. Amount validation
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
-        if amount > MAX_RESERVE_STROOPS {
-            return Err(Error::AmountTooLarge);
+        if !(MIN_SANE_RESERVE_STROOPS..=MAX_SANE_RESERVE_STROOPS).contains(&amount) {
+            return Err(Error::ReserveOutOfBounds);
         }
 
-        // ── 4. Persist & emit
+        // ── 5. Persist & emit
         let old_value = storage::get_base_reserve(&env).unwrap_or(0);
         storage::set_base_reserve(&env, amount);
         events::emit_base_reserve_updated(&env, old_value, amount, admin);
 
+        // ── 6. Push the new value to registered consumers
+        Self::notify_consumers(
+            &env,
+            ReserveParams {
+                base_reserve: amount,
+            },
+        );
+
         Ok(())
     }
 
+    /// Register the calling contract for reserve update notifications.
+    ///
+    /// Requires the authorization of both the admin and `consumer`: the
+    /// admin decides which contracts the reserve pushes to, so nobody can
+    /// fill the `MAX_CONSUMERS` slots with their own contracts.
+    ///
+    /// After every successful [`set_base_reserve`], `consumer` receives a
+    /// best-effort `on_reserve_updated(params: ReserveParams)` call so it
+    /// can refresh any cached value without polling. A failing hook is
+    /// skipped and never fails the update.
+    ///
+    /// # Errors
+    /// * [`Error::NotInitialized`]            – contract has not been initialized.
+    /// * [`Error::ConsumerAlreadyRegistered`] – `consumer` is already registered.
+    /// * [`Error::TooManyConsumers`]          – `MAX_CONSUMERS` are already registered.
+    /// * [`Error::InMaintenance`]             – maintenance mode is on.
+    pub fn register_consumer(env: Env, consumer: Address) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let admin = storage::get_admin(&env).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        consumer.require_auth();
+
+        if storage::is_maintenance(&env) {
+            return Err(Error::InMaintenance);
+        }
+
+        let mut consumers = storage::get_consumers(&env);
+        if consumers.contains(&consumer) {
+            return Err(Error::ConsumerAlreadyRegistered);
+        }
+        if consumers.len() >= MAX_CONSUMERS {
+            return Err(Error::TooManyConsumers);
+        }
+
+        consumers.push_back(consumer.clone());
+        storage::set_consumers(&env, &consumers);
+        events::emit_consumer_registered(&env, consumer);
+
+        Ok(())
+    }
+
+    /// Stop sending reserve update notifications to `consumer`.
+    ///
+    /// Either the consumer itself or the admin may unregister it, so a
+    /// consumer that was upgraded away from the hook can still be removed.
+    /// The admin may also do so during maintenance, to drop a consumer
+    /// before the reserve is next updated.
+    ///
+    /// # Errors
+    /// * [`Error::NotInitialized`]        – contract has not been initialized.
+    /// * [`Error::ConsumerNotRegistered`] – `consumer` is not registered.
+    /// * [`Error::InMaintenance`]         – maintenance mode is on and
+    ///                                      `caller` is not the admin.
+    pub fn unregister_consumer(env: Env, caller: Address, consumer: Address) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let admin = storage::get_admin(&env).ok_or(Error::NotInitialized)?;
+        if caller != consumer && caller != admin {
+            return Err(Error::Unauthorized);
+        }
+        caller.require_auth();
+
+        if storage::is_maintenance(&env) && caller != admin {
+            return Err(Error::InMaintenance);
+        }
+
+        let mut consumers = storage::get_consumers(&env);
+        let index = consumers
+            .first_index_of(&consumer)
+            .ok_or(Error::ConsumerNotRegistered)?;
+        consumers.remove(index);
+        storage::set_consumers(&env, &consumers);
+        events::emit_consumer_unregistered(&env, consumer);
+
+        Ok(())
+    }
+
+    /// Returns the contracts registered for reserve update notifications,
+    /// in registration order.
+    pub fn get_consumers(env: Env) -> Vec<Address> {
+        storage::extend_instance_ttl(&env);
+        storage::get_consumers(&env)
+    }
+
     /// Return the current base reserve amount (in stroops), if configured.
     ///
     /// # Returns
@@ -150,9 +269,79 @@ impl ReserveContract {
         storage::has_base_reserve(&env)
     }
 
+    /// Turn maintenance mode on or off. Admin only.
+    ///
+    /// While it is on, every write ([`set_base_reserve`], consumer
+    /// registration) fails with [`Error::InMaintenance`], and
+    /// [`get_reserve_reading`] flags the last-known value as stale. Dependent
+    /// contracts keep working on that value during parameter migrations
+    /// instead of failing hard.
+    ///
+    /// # Errors
+    /// * [`Error::NotInitialized`] – contract has not been initialized.
+    pub fn set_maintenance(env: Env, enabled: bool) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let admin = storage::get_admin(&env).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        storage::set_maintenance(&env, enabled);
+        events::emit_maintenance_toggled(&env, enabled, admin);
+
+        Ok(())
+    }
+
+    /// Returns `true` while maintenance mode is on.
+    pub fn is_maintenance(env: Env) -> bool {
+        storage::extend_instance_ttl(&env);
+        storage::is_maintenance(&env)
+    }
+
+    /// Return the last-known base reserve with a staleness indicator.
+    ///
+    /// Never fails: during maintenance the stored value is still returned,
+    /// with `stale` set, so readers can degrade gracefully.
+    pub fn get_reserve_reading(env: Env) -> ReserveReading {
+        storage::extend_instance_ttl(&env);
+        ReserveReading {
+            base_reserve: storage::get_base_reserve(&env),
+            stale: storage::is_maintenance(&env),
+        }
+    }
+
     /// Returns the admin address, if the contract has been initialized.
     pub fn get_admin(env: Env) -> Option<Address> {
         storage::extend_instance_ttl(&env);
         storage::get_admin(&env)
     }
+
+    /// Returns the version of the build that initialized the contract.
+    ///
+    /// Contracts initialized before versions were stored, or not yet
+    /// initialized, report the running build.
+    pub fn get_version(env: Env) -> ContractVersion {
+        storage::extend_instance_ttl(&env);
+        storage::get_version(&env).unwrap_or(BUILD_VERSION)
+    }
+}
+
+impl ReserveContract {
+    /// Best-effort `on_reserve_updated(params)` call on every registered
+    /// consumer. Iteration is bounded by `MAX_CONSUMERS`; a failing consumer
+    /// is reported with `ConsumerSkipped` and the loop moves on.
+    fn notify_consumers(env: &Env, params: ReserveParams) {
+        let hook = Symbol::new(env, "on_reserve_updated");
+        for consumer in storage::get_consumers(env).iter() {
+            let delivered = env
+                .try_invoke_contract::<(), soroban_sdk::Error>(
+                    &consumer,
+                    &hook,
+                    (params.clone(),).into_val(env),
+                )
+                .is_ok_and(|result| result.is_ok());
+            if !delivered {
+                events::emit_consumer_skipped(env, consumer, params.clone());
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use soroban_sdk::{contracttype, Address, Env};
+use bridgelet_shared::ContractVersion;
+use soroban_sdk::{contracttype, Address, Env, Vec};
 
 /// Storage keys used by the reserve contract.
 ///
@@ -18,6 +19,18 @@ pub enum DataKey {
     /// Set once during [`ReserveContract::initialize`] and immutable
     /// afterwards.
     Admin,
+
+    /// Contracts notified via `on_reserve_updated` whenever the base
+    /// reserve changes, in registration order.
+    Consumers,
+
+    /// Whether maintenance mode is on. While it is, writes are rejected and
+    /// reads report the stored value as stale. Absent means off.
+    Maintenance,
+
+    /// Version of the build that initialized the contract. Absent on
+    /// contracts initialized before versions were stored.
+    Version,
 }
 
 // Base Reserve helpers
@@ -71,6 +84,50 @@ pub fn has_admin(env: &Env) -> bool {
     env.storage().instance().has(&DataKey::Admin)
 }
 
+// Consumer helpers
+
+/// Read the registered consumers, or an empty list if none registered.
+pub fn get_consumers(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Consumers)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Overwrite the registered consumers.
+pub fn set_consumers(env: &Env, consumers: &Vec<Address>) {
+    env.storage().instance().set(&DataKey::Consumers, consumers);
+}
+
+// Maintenance helpers
+
+/// Turn maintenance mode on or off.
+pub fn set_maintenance(env: &Env, enabled: bool) {
+    env.storage()
+        .instance()
+        .set(&DataKey::Maintenance, &enabled);
+}
+
+/// Returns `true` while maintenance mode is on.
+pub fn is_maintenance(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Maintenance)
+        .unwrap_or(false)
+}
+
+// Version helpers
+
+/// Record the version of the build that initialized the contract.
+pub fn set_version(env: &Env, version: &ContractVersion) {
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+/// Returns the stored build version, if any.
+pub fn get_version(env: &Env) -> Option<ContractVersion> {
+    env.storage().instance().get(&DataKey::Version)
+}
+
 // TTL management
 
 /// If the remaining TTL drops below this threshold (in ledgers), extend it.
@@ -1,11 +1,16 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod test {
     extern crate std;
 
-    use crate::{ReserveContract, ReserveContractClient};
+    use crate::{
+        ConsumerSkipped, Error, ReserveContract, ReserveContractClient, ReserveParams,
+        ReserveReading, MAX_CONSUMERS, MAX_SANE_RESERVE_STROOPS, MIN_SANE_RESERVE_STROOPS,
+    };
     use soroban_sdk::{
-        testutils::{storage::Instance as _, Address as _},
-        Address, Env,
+        symbol_short,
+        testutils::{storage::Instance as _, Address as _, Events as _, MockAuth, MockAuthInvoke},
+        Address, Env, IntoVal, Symbol, TryFromVal,
     };
 
     use soroban_sdk::testutils::Ledger;
@@ -94,7 +99,7 @@ mod test {
     #[should_panic(expected = "Error(Contract, #5)")]
     fn test_set_base_reserve_before_initialize_panics() {
         let (_env, client, _) = setup_uninitialized();
-        client.set_base_reserve(&1_000_000_000i128);
+        client.set_base_reserve(&5_000_000i128);
     }
 
     //  Safe-default handling (reads don't require init)
@@ -130,8 +135,8 @@ mod test {
     fn test_set_and_get_base_reserve() {
         let (env, client, _admin, contract_id) = setup();
 
-        // 100 XLM expressed in stroops (1 XLM = 10_000_000 stroops)
-        let reserve = 1_000_000_000i128;
+        // 0.5 XLM expressed in stroops (1 XLM = 10_000_000 stroops)
+        let reserve = 5_000_000i128;
         client.set_base_reserve(&reserve);
 
         assert_eq!(client.get_base_reserve(), Some(reserve));
@@ -140,12 +145,12 @@ mod test {
         assert_ttl_extended(&env, &contract_id);
     }
 
-    /// The minimum meaningful value (1 stroop) must be accepted.
+    /// The smallest sane value (0.1 XLM) must be accepted.
     #[test]
     fn test_set_base_reserve_minimum_valid_value() {
         let (env, client, _admin, contract_id) = setup();
-        client.set_base_reserve(&1i128);
-        assert_eq!(client.get_base_reserve(), Some(1i128));
+        client.set_base_reserve(&MIN_SANE_RESERVE_STROOPS);
+        assert_eq!(client.get_base_reserve(), Some(MIN_SANE_RESERVE_STROOPS));
         assert_ttl_extended(&env, &contract_id);
     }
 
@@ -156,11 +161,11 @@ mod test {
     fn test_set_base_reserve_overwrites_previous_value() {
         let (env, client, _admin, contract_id) = setup();
 
-        client.set_base_reserve(&1_000_000_000i128);
-        assert_eq!(client.get_base_reserve(), Some(1_000_000_000i128));
+        client.set_base_reserve(&5_000_000i128);
+        assert_eq!(client.get_base_reserve(), Some(5_000_000i128));
 
-        client.set_base_reserve(&2_000_000_000i128);
-        assert_eq!(client.get_base_reserve(), Some(2_000_000_000i128));
+        client.set_base_reserve(&6_000_000i128);
+        assert_eq!(client.get_base_reserve(), Some(6_000_000i128));
 
         assert!(client.has_base_reserve());
         assert_ttl_extended(&env, &contract_id);
@@ -192,30 +197,52 @@ mod test {
         client.set_base_reserve(&i128::MIN);
     }
 
-    //  Range validation (upper bound)
+    //  Range validation
 
-    /// The maximum allowed value (10 000 XLM = 100_000_000_000 stroops)
-    /// must be accepted.
+    /// The largest sane value (10 XLM = 100_000_000 stroops) must be
+    /// accepted.
     #[test]
     fn test_set_base_reserve_at_max_is_accepted() {
         let (env, client, _admin, contract_id) = setup();
-        let max = 100_000_000_000i128;
-        client.set_base_reserve(&max);
-        assert_eq!(client.get_base_reserve(), Some(max));
+        client.set_base_reserve(&MAX_SANE_RESERVE_STROOPS);
+        assert_eq!(client.get_base_reserve(), Some(MAX_SANE_RESERVE_STROOPS));
         assert_ttl_extended(&env, &contract_id);
     }
 
-    /// One stroop above the ceiling must be rejected with error #6.
+    /// Positive values outside 0.1 to 10 XLM are rejected with
+    /// ReserveOutOfBounds and leave the stored value untouched.
+    #[test]
+    fn test_set_base_reserve_outside_sane_bounds_is_rejected() {
+        let (_env, client, _admin, _) = setup();
+        client.set_base_reserve(&5_000_000i128);
+
+        for amount in [
+            1i128,
+            MIN_SANE_RESERVE_STROOPS - 1,
+            MAX_SANE_RESERVE_STROOPS + 1,
+            // 1 000 XLM: a value entered in XLM-scaled units by mistake
+            10_000_000_000i128,
+        ] {
+            assert_eq!(
+                client.try_set_base_reserve(&amount),
+                Err(Ok(Error::ReserveOutOfBounds))
+            );
+        }
+        assert_eq!(client.get_base_reserve(), Some(5_000_000i128));
+    }
+
+    /// One stroop above the former 10,000 XLM ceiling must be rejected with
+    /// error #11.
     #[test]
-    #[should_panic(expected = "Error(Contract, #6)")]
+    #[should_panic(expected = "Error(Contract, #11)")]
     fn test_set_base_reserve_above_max_is_rejected() {
         let (_env, client, _admin, _) = setup();
         client.set_base_reserve(&100_000_000_001i128);
     }
 
-    /// An absurdly large value must be rejected with error #6.
+    /// An absurdly large value must be rejected with error #11.
     #[test]
-    #[should_panic(expected = "Error(Contract, #6)")]
+    #[should_panic(expected = "Error(Contract, #11)")]
     fn test_set_base_reserve_huge_value_is_rejected() {
         let (_env, client, _admin, _) = setup();
         client.set_base_reserve(&i128::MAX);
@@ -240,14 +267,14 @@ mod test {
         client_a.initialize(&admin_a);
         client_b.initialize(&admin_b);
 
-        client_a.set_base_reserve(&500_000_000i128);
+        client_a.set_base_reserve(&5_000_000i128);
 
         // Contract B must still be unset.
         assert_eq!(client_b.get_base_reserve(), None);
         assert!(!client_b.has_base_reserve());
 
         // Contract A's value is unchanged.
-        assert_eq!(client_a.get_base_reserve(), Some(500_000_000i128));
+        assert_eq!(client_a.get_base_reserve(), Some(5_000_000i128));
 
         // Both instances must have had their TTL extended.
         assert_ttl_extended(&env, &id_a);
@@ -272,6 +299,21 @@ mod test {
         assert_ttl_extended(&env, &contract_id);
     }
 
+    /// get_version reports the build version, stored at initialization.
+    #[test]
+    fn test_get_version_reports_the_build_version() {
+        let build = bridgelet_shared::ContractVersion::parse(env!("CARGO_PKG_VERSION"));
+        let (_, client, _) = setup_uninitialized();
+        assert_eq!(client.get_version(), build);
+
+        let (env, client, _, contract_id) = setup();
+        assert_eq!(
+            env.as_contract(&contract_id, || crate::storage::get_version(&env)),
+            Some(build)
+        );
+        assert_eq!(client.get_version(), build);
+    }
+
     //  TTL management
 
     /// After any interaction the instance TTL should be extended.
@@ -303,4 +345,246 @@ mod test {
 
         assert_ttl_extended(&env, &contract_id);
     }
+
+    //  Consumer notifications
+
+    mod consumers {
+        use crate::ReserveParams;
+        use soroban_sdk::{contract, contractimpl, contracttype, Env};
+
+        #[contracttype]
+        enum Key {
+            Params,
+        }
+
+        /// Caches the last pushed reserve parameters.
+        #[contract]
+        pub struct CachingConsumer;
+
+        #[contractimpl]
+        impl CachingConsumer {
+            pub fn on_reserve_updated(env: Env, params: ReserveParams) {
+                env.storage().instance().set(&Key::Params, &params);
+            }
+
+            pub fn cached(env: Env) -> Option<ReserveParams> {
+                env.storage().instance().get(&Key::Params)
+            }
+        }
+
+        pub mod failing {
+            use crate::ReserveParams;
+            use soroban_sdk::{contract, contractimpl, Env};
+
+            #[contract]
+            pub struct FailingConsumer;
+
+            #[contractimpl]
+            impl FailingConsumer {
+                pub fn on_reserve_updated(_env: Env, _params: ReserveParams) {
+                    panic!("cache unavailable");
+                }
+            }
+        }
+    }
+
+    /// register_consumer adds the consumer once and rejects duplicates.
+    #[test]
+    fn test_register_consumer_rejects_duplicates() {
+        let (env, client, _admin, _contract_id) = setup();
+        let consumer = Address::generate(&env);
+
+        client.register_consumer(&consumer);
+        assert_eq!(
+            client.get_consumers(),
+            soroban_sdk::vec![&env, consumer.clone()]
+        );
+        assert_eq!(
+            client.try_register_consumer(&consumer),
+            Err(Ok(Error::ConsumerAlreadyRegistered))
+        );
+    }
+
+    /// register_consumer is bounded by MAX_CONSUMERS.
+    #[test]
+    fn test_register_consumer_is_bounded() {
+        let (env, client, _admin, _contract_id) = setup();
+        for _ in 0..MAX_CONSUMERS {
+            client.register_consumer(&Address::generate(&env));
+        }
+        assert_eq!(
+            client.try_register_consumer(&Address::generate(&env)),
+            Err(Ok(Error::TooManyConsumers))
+        );
+    }
+
+    /// register_consumer requires the admin's authorization, not just the
+    /// consumer's.
+    #[test]
+    fn test_register_consumer_without_admin_auth_is_rejected() {
+        let (env, client, _admin, contract_id) = setup();
+        let consumer = Address::generate(&env);
+
+        env.mock_auths(&[MockAuth {
+            address: &consumer,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "register_consumer",
+                args: (consumer.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(client.try_register_consumer(&consumer).is_err());
+        assert!(client.get_consumers().is_empty());
+    }
+
+    /// register_consumer requires initialization.
+    #[test]
+    fn test_register_consumer_before_initialize_is_rejected() {
+        let (env, client, _contract_id) = setup_uninitialized();
+        assert_eq!(
+            client.try_register_consumer(&Address::generate(&env)),
+            Err(Ok(Error::NotInitialized))
+        );
+    }
+
+    /// set_base_reserve pushes the new value to every consumer, skipping
+    /// consumers whose hook fails.
+    #[test]
+    fn test_set_base_reserve_notifies_consumers_and_skips_failures() {
+        use consumers::failing::FailingConsumer;
+        use consumers::{CachingConsumer, CachingConsumerClient};
+
+        let (env, client, _admin, contract_id) = setup();
+        let failing = env.register(FailingConsumer, ());
+        let caching = env.register(CachingConsumer, ());
+        client.register_consumer(&failing);
+        client.register_consumer(&caching);
+
+        client.set_base_reserve(&5_000_000i128);
+
+        let params = ReserveParams {
+            base_reserve: 5_000_000,
+        };
+        let skipped: std::vec::Vec<ConsumerSkipped> = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(contract, topics, _)| {
+                *contract == contract_id
+                    && Symbol::try_from_val(&env, &topics.get_unchecked(0))
+                        == Ok(symbol_short!("skipped"))
+            })
+            .map(|(_, _, data)| ConsumerSkipped::try_from_val(&env, &data).unwrap())
+            .collect();
+        assert_eq!(
+            skipped,
+            std::vec![ConsumerSkipped {
+                consumer: failing,
+                params: params.clone(),
+            }]
+        );
+        assert_eq!(client.get_base_reserve(), Some(5_000_000));
+        assert_eq!(
+            CachingConsumerClient::new(&env, &caching).cached(),
+            Some(params)
+        );
+    }
+
+    /// A consumer or the admin may unregister; anyone else is rejected.
+    #[test]
+    fn test_unregister_consumer() {
+        let (env, client, admin, _contract_id) = setup();
+        let consumer_a = Address::generate(&env);
+        let consumer_b = Address::generate(&env);
+        client.register_consumer(&consumer_a);
+        client.register_consumer(&consumer_b);
+
+        assert_eq!(
+            client.try_unregister_consumer(&Address::generate(&env), &consumer_a),
+            Err(Ok(Error::Unauthorized))
+        );
+
+        client.unregister_consumer(&consumer_a, &consumer_a);
+        client.unregister_consumer(&admin, &consumer_b);
+        assert!(client.get_consumers().is_empty());
+
+        assert_eq!(
+            client.try_unregister_consumer(&admin, &consumer_a),
+            Err(Ok(Error::ConsumerNotRegistered))
+        );
+    }
+
+    /// During maintenance only the admin may unregister a consumer.
+    #[test]
+    fn test_admin_unregisters_consumers_during_maintenance() {
+        let (env, client, admin, _contract_id) = setup();
+        let consumer_a = Address::generate(&env);
+        let consumer_b = Address::generate(&env);
+        client.register_consumer(&consumer_a);
+        client.register_consumer(&consumer_b);
+        client.set_maintenance(&true);
+
+        assert_eq!(
+            client.try_unregister_consumer(&consumer_a, &consumer_a),
+            Err(Ok(Error::InMaintenance))
+        );
+        client.unregister_consumer(&admin, &consumer_b);
+        assert_eq!(client.get_consumers(), soroban_sdk::vec![&env, consumer_a]);
+    }
+
+    // MAINTENANCE MODE
+
+    /// During maintenance reads serve the last-known value flagged stale
+    /// and writes are rejected; turning it off restores both.
+    #[test]
+    fn test_maintenance_mode_serves_stale_reads_and_blocks_writes() {
+        let (env, client, _admin, _contract_id) = setup();
+        client.set_base_reserve(&5_000_000);
+        assert_eq!(
+            client.get_reserve_reading(),
+            ReserveReading {
+                base_reserve: Some(5_000_000),
+                stale: false,
+            }
+        );
+
+        client.set_maintenance(&true);
+        assert!(client.is_maintenance());
+        assert_eq!(
+            client.get_reserve_reading(),
+            ReserveReading {
+                base_reserve: Some(5_000_000),
+                stale: true,
+            }
+        );
+        assert_eq!(client.get_base_reserve(), Some(5_000_000));
+        assert_eq!(
+            client.try_set_base_reserve(&6_000_000),
+            Err(Ok(Error::InMaintenance))
+        );
+        assert_eq!(
+            client.try_register_consumer(&Address::generate(&env)),
+            Err(Ok(Error::InMaintenance))
+        );
+
+        client.set_maintenance(&false);
+        client.set_base_reserve(&6_000_000);
+        assert_eq!(
+            client.get_reserve_reading(),
+            ReserveReading {
+                base_reserve: Some(6_000_000),
+                stale: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_maintenance_requires_initialization() {
+        let (_env, client, _contract_id) = setup_uninitialized();
+        assert_eq!(
+            client.try_set_maintenance(&true),
+            Err(Ok(Error::NotInitialized))
+        );
+    }
 }
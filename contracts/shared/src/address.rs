@@ -0,0 +1,18 @@
+use soroban_sdk::{xdr::ToXdr, Address, Env};
+
+/// Offset of the last byte of the big-endian `ScAddress` tag in the XDR of
+/// an `ScVal::Address`.
+const SC_ADDRESS_TAG_LAST_BYTE: u32 = 7;
+
+const SC_ADDRESS_TYPE_ACCOUNT: u8 = 0;
+const SC_ADDRESS_TYPE_CONTRACT: u8 = 1;
+
+/// Returns `true` if `address` is a Stellar account (`G...`).
+pub fn is_account_address(env: &Env, address: &Address) -> bool {
+    address.clone().to_xdr(env).get(SC_ADDRESS_TAG_LAST_BYTE) == Some(SC_ADDRESS_TYPE_ACCOUNT)
+}
+
+/// Returns `true` if `address` is a contract (`C...`).
+pub fn is_contract_address(env: &Env, address: &Address) -> bool {
+    address.clone().to_xdr(env).get(SC_ADDRESS_TAG_LAST_BYTE) == Some(SC_ADDRESS_TYPE_CONTRACT)
+}
@@ -0,0 +1,184 @@
+//! Byte-string helpers for building signed messages.
+//!
+//! The sweep controller verifies signatures over a digest that off-chain
+//! signers must reproduce exactly, so the layout lives here once and both
+//! sides call [`sweep_message_digest`].
+
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+/// Concatenate `parts` into a single `Bytes`, in order.
+pub fn concat(env: &Env, parts: &[Bytes]) -> Bytes {
+    let mut out = Bytes::new(env);
+    for part in parts {
+        out.append(part);
+    }
+    out
+}
+
+/// SHA-256 of the concatenation of `parts`.
+pub fn sha256_concat(env: &Env, parts: &[Bytes]) -> BytesN<32> {
+    env.crypto().sha256(&concat(env, parts)).into()
+}
+
+/// Network id of the network with the given passphrase, as returned by
+/// `env.ledger().network_id()` on that network: `sha256(passphrase)`.
+pub fn network_id(env: &Env, passphrase: &[u8]) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_slice(env, passphrase))
+        .into()
+}
+
+/// Digest an authorized signer signs to approve a sweep:
+///
+/// `sha256(network_id || account.to_xdr() || destination.to_xdr() || nonce (u64 BE) || contract_id.to_xdr() [|| memo] [|| valid_until_ledger (u32 BE)])`
+///
+/// Binding the network id means a signature made for one network (e.g.
+/// testnet) can never be replayed against the same contract addresses on
+/// another. Binding the ephemeral account means it sweeps only the account
+/// it was signed for, not whichever account is submitted with it. The memo
+/// and the deadline are only appended when present; their lengths (32 and
+/// 4 bytes) keep every combination distinct.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_message_digest(
+    env: &Env,
+    network_id: &BytesN<32>,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    contract_id: &Address,
+    destination_memo: Option<&BytesN<32>>,
+    valid_until_ledger: Option<u32>,
+) -> BytesN<32> {
+    let mut message = concat(
+        env,
+        &[
+            network_id.clone().into(),
+            account.clone().to_xdr(env),
+            destination.clone().to_xdr(env),
+            Bytes::from_array(env, &nonce.to_be_bytes()),
+            contract_id.clone().to_xdr(env),
+        ],
+    );
+    if let Some(memo) = destination_memo {
+        message.append(&memo.clone().into());
+    }
+    if let Some(ledger) = valid_until_ledger {
+        message.extend_from_array(&ledger.to_be_bytes());
+    }
+    env.crypto().sha256(&message).into()
+}
+
+/// Domain tag prefixed to account-nonce sweep messages so they can never be
+/// mistaken for a controller-nonce sweep authorization.
+pub const ACCOUNT_SWEEP_TAG: &[u8] = b"bridgelet:account";
+
+/// Digest an authorized signer signs to approve a sweep of one account
+/// at that account's own sweep nonce:
+///
+/// `sha256(ACCOUNT_SWEEP_TAG || network_id || account.to_xdr() || account_nonce (u64 BE) || destination.to_xdr() || contract_id.to_xdr() [|| memo])`
+///
+/// Binding the account and its nonce means the signature sweeps that
+/// account once and is void after its next sweep, whatever else the
+/// controller signs in between.
+pub fn account_sweep_message_digest(
+    env: &Env,
+    network_id: &BytesN<32>,
+    account: &Address,
+    account_nonce: u64,
+    destination: &Address,
+    contract_id: &Address,
+    destination_memo: Option<&BytesN<32>>,
+) -> BytesN<32> {
+    let mut message = concat(
+        env,
+        &[
+            Bytes::from_slice(env, ACCOUNT_SWEEP_TAG),
+            network_id.clone().into(),
+            account.clone().to_xdr(env),
+            Bytes::from_array(env, &account_nonce.to_be_bytes()),
+            destination.clone().to_xdr(env),
+            contract_id.clone().to_xdr(env),
+        ],
+    );
+    if let Some(memo) = destination_memo {
+        message.append(&memo.clone().into());
+    }
+    env.crypto().sha256(&message).into()
+}
+
+/// Domain tag prefixed to per-asset sweep messages so they can never be
+/// mistaken for a whole-account sweep authorization.
+pub const ACCOUNT_ASSETS_SWEEP_TAG: &[u8] = b"bridgelet:assets";
+
+/// Digest an account's sweep signer signs to approve `sweep_assets`:
+///
+/// `sha256(ACCOUNT_ASSETS_SWEEP_TAG || network_id || account.to_xdr() || account_nonce (u64 BE) || destination.to_xdr() || contract_id.to_xdr() || asset.to_xdr() for each asset, in order)`
+///
+/// The assets come last, so a signature for one selection never sweeps
+/// another, nor the same assets in a different order.
+pub fn account_sweep_assets_message_digest(
+    env: &Env,
+    network_id: &BytesN<32>,
+    account: &Address,
+    account_nonce: u64,
+    destination: &Address,
+    contract_id: &Address,
+    assets: &Vec<Address>,
+) -> BytesN<32> {
+    let mut message = concat(
+        env,
+        &[
+            Bytes::from_slice(env, ACCOUNT_ASSETS_SWEEP_TAG),
+            network_id.clone().into(),
+            account.clone().to_xdr(env),
+            Bytes::from_array(env, &account_nonce.to_be_bytes()),
+            destination.clone().to_xdr(env),
+            contract_id.clone().to_xdr(env),
+        ],
+    );
+    for asset in assets.iter() {
+        message.append(&asset.to_xdr(env));
+    }
+    env.crypto().sha256(&message).into()
+}
+
+/// Domain tag prefixed to tenant sweep messages so they can never be
+/// mistaken for a single-tenant sweep authorization.
+pub const TENANT_SWEEP_TAG: &[u8] = b"bridgelet:tenant";
+
+/// Digest a tenant's signers sign to approve a sweep on a multi-tenant
+/// controller:
+///
+/// `sha256(TENANT_SWEEP_TAG || network_id || tenant_id (u32 BE) || account.to_xdr() || destination.to_xdr() || nonce (u64 BE) || contract_id.to_xdr() [|| memo])`
+///
+/// `nonce` is the tenant's own nonce, not the controller's. The account is
+/// part of the message, so the tenant's signers approve a sweep of one
+/// account and not of any account assigned to the tenant.
+#[allow(clippy::too_many_arguments)]
+pub fn tenant_sweep_message_digest(
+    env: &Env,
+    network_id: &BytesN<32>,
+    tenant_id: u32,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    contract_id: &Address,
+    destination_memo: Option<&BytesN<32>>,
+) -> BytesN<32> {
+    let mut message = concat(
+        env,
+        &[
+            Bytes::from_slice(env, TENANT_SWEEP_TAG),
+            network_id.clone().into(),
+            Bytes::from_array(env, &tenant_id.to_be_bytes()),
+            account.clone().to_xdr(env),
+            destination.clone().to_xdr(env),
+            Bytes::from_array(env, &nonce.to_be_bytes()),
+            contract_id.clone().to_xdr(env),
+        ],
+    );
+    if let Some(memo) = destination_memo {
+        message.append(&memo.clone().into());
+    }
+    env.crypto().sha256(&message).into()
+}
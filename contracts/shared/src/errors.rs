@@ -0,0 +1,17 @@
+//! Ephemeral account error codes that other contracts report.
+//!
+//! Errors cross the contract interface as plain `soroban_sdk::Error` codes
+//! (see `interfaces`), so a contract that names one of the account's codes,
+//! e.g. as `ArbitrationResult::loser_reason`, takes it from here. The
+//! account asserts at compile time that its own `Error` enum agrees.
+
+/// Codes of the ephemeral account's `Error` variants used outside the
+/// account.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AccountErrorCode {
+    /// The account was already swept
+    AlreadySwept = 7,
+    /// The account has expired
+    AccountExpired = 11,
+}
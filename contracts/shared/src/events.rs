@@ -0,0 +1,48 @@
+//! Versioned event envelope.
+//!
+//! Each event has its own body struct, but every account event is
+//! published with data `(EventHeader, body)`. The header names the emitting
+//! account and the ledger, so indexers can attribute and order events
+//! without knowing every body type, and detect layout changes from
+//! `schema_version`.
+
+use crate::trace;
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Val, Vec};
+
+/// Current `EventHeader::schema_version`. Bumped whenever the header or an
+/// event body changes shape.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// First element of every account event's data; the body follows it.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventHeader {
+    /// `EVENT_SCHEMA_VERSION` of the emitting contract
+    pub schema_version: u32,
+    /// The account that emitted the event
+    pub account: Address,
+    /// Ledger sequence the event was emitted at
+    pub ledger: u32,
+}
+
+impl EventHeader {
+    /// Header for an event emitted by the current contract now.
+    pub fn current(env: &Env) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            account: env.current_contract_address(),
+            ledger: env.ledger().sequence(),
+        }
+    }
+}
+
+/// Publish `body` as `(EventHeader, body)`, appending `trace_id` as the
+/// last topic when present.
+pub fn publish<T, D>(env: &Env, topics: T, body: D, trace_id: Option<BytesN<16>>)
+where
+    T: IntoVal<Env, Vec<Val>>,
+    D: IntoVal<Env, Val>,
+{
+    let body: Val = body.into_val(env);
+    trace::publish(env, topics, (EventHeader::current(env), body), trace_id);
+}
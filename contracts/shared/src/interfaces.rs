@@ -3,17 +3,26 @@
 //! These traits move the interface definitions that previously lived only in
 //! the README into real, type-checked Rust. Each contract implements the trait
 //! matching the methods it already exposes, so the interface stays in sync with
-//! the implementation at compile time. The error type is left as an associated
-//! type so each contract can keep its own `contracterror` enum.
+//! the implementation at compile time.
+//!
+//! The ephemeral account and sweep controller traits also generate the
+//! cross-contract clients (`EphemeralAccountClient`, `SweepControllerClient`)
+//! that other contracts call them through, so callers no longer depend on a
+//! prebuilt wasm being at a fixed path. Generated clients need concrete
+//! types, so errors cross the interface as `soroban_sdk::Error`; each
+//! contract keeps its own `contracterror` enum and converts at the boundary.
 
-use soroban_sdk::{Address, BytesN, Env};
+use crate::{
+    AccountInfo, AccountLifecycle, AccountStatus, AssetAmount, ContractVersion, InitOptions,
+    Payment, PaymentsPage, SweepReadiness, SweepReceipt,
+};
+use soroban_sdk::{contractclient, Address, BytesN, Env, Error, Vec};
 
 /// Interface exposed by the ephemeral account contract.
+#[contractclient(name = "EphemeralAccountClient")]
 pub trait EphemeralAccountInterface {
-    /// Contract-specific error type.
-    type Error;
-
-    /// Initialize the ephemeral account with its restrictions.
+    /// Initialize the ephemeral account with its restrictions. `admin` is
+    /// deprecated and ignored, and is removed in 0.2.0.
     fn initialize(
         env: Env,
         creator: Address,
@@ -21,44 +30,144 @@ pub trait EphemeralAccountInterface {
         recovery_address: Address,
         authorized_controller: Address,
         admin: Address,
-    ) -> Result<(), Self::Error>;
+    ) -> Result<(), Error>;
+
+    /// Same as `initialize`, with the optional restrictions in `options`.
+    fn initialize_with_options(
+        env: Env,
+        creator: Address,
+        expiry_ledger: u32,
+        recovery_address: Address,
+        authorized_controller: Address,
+        admin: Address,
+        options: InitOptions,
+    ) -> Result<(), Error>;
 
     /// Record an inbound payment to this account.
-    fn record_payment(env: Env, amount: i128, asset: Address) -> Result<(), Self::Error>;
+    fn record_payment(env: Env, amount: i128, asset: Address) -> Result<(), Error>;
 
     /// Sweep funds to `destination`. `auth_signature` is accepted but not
     /// cryptographically verified by this contract.
-    fn sweep(env: Env, destination: Address, auth_signature: BytesN<64>)
-        -> Result<(), Self::Error>;
+    fn sweep(env: Env, destination: Address, auth_signature: BytesN<64>) -> Result<(), Error>;
+
+    /// Same as `sweep`, returning the swept payments (interface version 2).
+    fn sweep_and_report(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, Error>;
+
+    /// Lock the account in `PendingSweep` for a sweep to `destination` and
+    /// return the payments to deliver (interface version 3).
+    fn prepare_sweep(
+        env: Env,
+        destination: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<Vec<Payment>, Error>;
+
+    /// Same as `prepare_sweep`, authorized by a threshold of the account's
+    /// `sweep_signers` instead of a single signature (interface version 4).
+    fn prepare_multisig_sweep(
+        env: Env,
+        destination: Address,
+        destination_memo: Option<BytesN<32>>,
+        signatures: Vec<(BytesN<32>, BytesN<64>)>,
+    ) -> Result<Vec<Payment>, Error>;
+
+    /// Settle a prepared sweep once its funds have moved (controller only).
+    fn commit_sweep(env: Env) -> Result<(), Error>;
+
+    /// Release a prepared sweep without settling it (controller only).
+    fn abort_sweep(env: Env) -> Result<(), Error>;
+
+    /// Transfer `amount` of `asset` out of the account for a prepared or
+    /// partially delivered sweep (controller only, interface version 5).
+    fn deliver(env: Env, asset: Address, recipient: Address, amount: i128) -> Result<(), Error>;
+
+    /// Number of sweeps the account has settled, bound into
+    /// account-nonce sweep signatures.
+    fn get_sweep_nonce(env: Env) -> u64;
+
+    /// Version of the cross-contract call shapes the account exposes.
+    fn interface_version(env: Env) -> u32;
+
+    /// Version of the build that initialized or last migrated the account.
+    fn get_version(env: Env) -> ContractVersion;
 
     /// Gas-free sweep path used by the sweep controller's claim flow.
-    fn sweep_claim(env: Env, destination: Address) -> Result<(), Self::Error>;
+    /// Returns the payments delivered, net of protocol fees; from interface
+    /// version 5 the account transfers them itself.
+    fn sweep_claim(env: Env, destination: Address) -> Result<Vec<Payment>, Error>;
 
     /// Whether the account has passed its expiry ledger.
     fn is_expired(env: Env) -> bool;
+
+    /// Whether the account can be swept right now and, if not, why.
+    fn can_sweep_detailed(env: Env) -> SweepReadiness;
+
+    /// Current account status.
+    fn get_status(env: Env) -> AccountStatus;
+
+    /// Account information, with payment counts but not the payments.
+    fn get_info(env: Env) -> Result<AccountInfo, Error>;
+
+    /// Up to `limit` payments starting at `offset`, in canonical asset order.
+    fn get_payments_page(env: Env, offset: u32, limit: u32) -> PaymentsPage;
+
+    /// Ledgers at which the account was created and first paid.
+    fn get_lifecycle(env: Env) -> Result<AccountLifecycle, Error>;
+
+    /// Move a swept account to `PartiallySwept` (controller only).
+    fn mark_partially_swept(env: Env) -> Result<(), Error>;
+
+    /// Move a `PartiallySwept` account back to `Swept` (controller only).
+    fn complete_partial_sweep(env: Env) -> Result<(), Error>;
 }
 
 /// Interface exposed by the sweep controller contract.
+#[contractclient(name = "SweepControllerClient")]
 pub trait SweepControllerInterface {
-    /// Contract-specific error type.
-    type Error;
-
     /// Initialize the controller with its authorized signer.
     fn initialize(
         env: Env,
         creator: Address,
         authorized_signer: BytesN<32>,
         authorized_destination: Option<Address>,
-    ) -> Result<(), Self::Error>;
+    ) -> Result<(), Error>;
 
-    /// Execute a sweep from an ephemeral account to `destination`.
+    /// Execute a sweep from an ephemeral account to `destination`, optionally
+    /// tagged with the memo the destination needs to credit the funds.
     fn execute_sweep(
         env: Env,
         ephemeral_account: Address,
         destination: Address,
         auth_signature: BytesN<64>,
-    ) -> Result<(), Self::Error>;
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error>;
 
     /// Claim funds to `recipient` using Soroban auth entries.
-    fn claim(env: Env, recipient: Address, ephemeral_account: Address) -> Result<(), Self::Error>;
+    fn claim(env: Env, recipient: Address, ephemeral_account: Address) -> Result<(), Error>;
+
+    /// Version of the build that initialized the controller.
+    fn get_version(env: Env) -> ContractVersion;
+}
+
+/// Optional hook a creator contract (e.g. a marketplace escrow) implements to
+/// be notified when one of its ephemeral accounts has been settled.
+///
+/// The sweep controller calls it best-effort after settlement: a creator
+/// contract that does not implement it, or whose hook fails, does not affect
+/// the sweep.
+pub trait CreatorCallbackInterface {
+    fn on_sweep_completed(env: Env, account: Address, receipt: SweepReceipt);
+}
+
+/// Hook a contract registered as an ephemeral account's `sweep_hook`
+/// implements, e.g. a vault that deposits swept funds on arrival.
+///
+/// The account calls it best-effort once a sweep or expiry has settled,
+/// with the assets that went to `destination`: a hook that is missing or
+/// fails does not affect the settlement.
+pub trait SweepHookInterface {
+    fn on_sweep(env: Env, account: Address, destination: Address, assets: Vec<AssetAmount>);
 }
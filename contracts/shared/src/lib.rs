@@ -1,7 +1,32 @@
 #![no_std]
 
+mod address;
+pub mod bytes;
+mod errors;
+pub mod events;
 mod interfaces;
+mod sac;
+pub mod trace;
 mod types;
+mod units;
 
-pub use interfaces::{EphemeralAccountInterface, SweepControllerInterface};
-pub use types::{AccountInfo, AccountInitRequest, AccountInitResult, AccountStatus, Payment};
+#[cfg(test)]
+mod test;
+
+pub use address::{is_account_address, is_contract_address};
+pub use errors::AccountErrorCode;
+pub use events::{EventHeader, EVENT_SCHEMA_VERSION};
+pub use interfaces::{
+    CreatorCallbackInterface, EphemeralAccountClient, EphemeralAccountInterface,
+    SweepControllerClient, SweepControllerInterface, SweepHookInterface,
+};
+pub use sac::{
+    classic_asset_xdr, is_stellar_asset_address, is_valid_asset_code, native_asset_address,
+    stellar_asset_address,
+};
+pub use types::{
+    AccountInfo, AccountLifecycle, AccountStatus, ArbitrationResult, AssetAmount, ContractVersion,
+    ExpectationProgress, ExpectedPayment, InitOptions, Payment, PaymentsPage, SettlementPath,
+    SweepReadiness, SweepReceipt,
+};
+pub use units::{to_xlm_display, STROOPS_PER_XLM};
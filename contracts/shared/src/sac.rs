@@ -0,0 +1,93 @@
+use crate::is_account_address;
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, Env, String};
+
+/// `AssetType::CreditAlphanum4` discriminant in the Stellar XDR.
+const ASSET_TYPE_CREDIT_ALPHANUM4: u32 = 1;
+/// `AssetType::CreditAlphanum12` discriminant in the Stellar XDR.
+const ASSET_TYPE_CREDIT_ALPHANUM12: u32 = 2;
+/// `PublicKeyType::Ed25519` discriminant in the Stellar XDR.
+const PUBLIC_KEY_TYPE_ED25519: u32 = 0;
+
+/// `ScVal::Address(ScAddress::Account(..))` is encoded as the `ScVal` tag,
+/// the `ScAddress` tag, the `PublicKey` tag and the 32-byte ed25519 key.
+const ED25519_KEY_OFFSET: u32 = 12;
+
+const MAX_ASSET_CODE_LEN: usize = 12;
+
+/// XDR encoding of `Asset::Native`: just the `ASSET_TYPE_NATIVE` (0)
+/// discriminant.
+const NATIVE_ASSET_XDR: [u8; 4] = [0; 4];
+
+/// Returns `true` if `code` is a valid classic asset code: 1–12 ASCII
+/// alphanumeric characters.
+pub fn is_valid_asset_code(code: &String) -> bool {
+    let len = code.len() as usize;
+    if len == 0 || len > MAX_ASSET_CODE_LEN {
+        return false;
+    }
+
+    let mut buf = [0u8; MAX_ASSET_CODE_LEN];
+    code.copy_into_slice(&mut buf[..len]);
+    buf[..len].iter().all(u8::is_ascii_alphanumeric)
+}
+
+/// Serialize the classic asset `code:issuer` as an XDR `Asset`.
+///
+/// Returns `None` if `code` is not a valid asset code or `issuer` is a
+/// contract address rather than a Stellar account.
+pub fn classic_asset_xdr(env: &Env, code: &String, issuer: &Address) -> Option<Bytes> {
+    if !is_valid_asset_code(code) {
+        return None;
+    }
+
+    if !is_account_address(env, issuer) {
+        return None;
+    }
+    let issuer_xdr = issuer.clone().to_xdr(env);
+
+    let len = code.len() as usize;
+    let mut code_buf = [0u8; MAX_ASSET_CODE_LEN];
+    code.copy_into_slice(&mut code_buf[..len]);
+
+    let (asset_type, padded_len) = if len <= 4 {
+        (ASSET_TYPE_CREDIT_ALPHANUM4, 4)
+    } else {
+        (ASSET_TYPE_CREDIT_ALPHANUM12, MAX_ASSET_CODE_LEN)
+    };
+
+    let mut asset = Bytes::from_array(env, &asset_type.to_be_bytes());
+    asset.extend_from_slice(&code_buf[..padded_len]);
+    asset.extend_from_array(&PUBLIC_KEY_TYPE_ED25519.to_be_bytes());
+    asset.append(&issuer_xdr.slice(ED25519_KEY_OFFSET..));
+    Some(asset)
+}
+
+/// Compute the Stellar Asset Contract address of the classic asset
+/// `code:issuer` on the current network, without deploying anything.
+///
+/// Returns `None` under the same conditions as [`classic_asset_xdr`].
+pub fn stellar_asset_address(env: &Env, code: &String, issuer: &Address) -> Option<Address> {
+    let serialized = classic_asset_xdr(env, code, issuer)?;
+    Some(
+        env.deployer()
+            .with_stellar_asset(serialized)
+            .deployed_address(),
+    )
+}
+
+/// Address of the native XLM Stellar Asset Contract on this network.
+pub fn native_asset_address(env: &Env) -> Address {
+    env.deployer()
+        .with_stellar_asset(Bytes::from_array(env, &NATIVE_ASSET_XDR))
+        .deployed_address()
+}
+
+/// Returns `true` if `asset` is the Stellar Asset Contract of `code:issuer`.
+pub fn is_stellar_asset_address(
+    env: &Env,
+    asset: &Address,
+    code: &String,
+    issuer: &Address,
+) -> bool {
+    stellar_asset_address(env, code, issuer).as_ref() == Some(asset)
+}
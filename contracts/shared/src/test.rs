@@ -0,0 +1,427 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod test {
+    use crate::{
+        classic_asset_xdr, is_stellar_asset_address, is_valid_asset_code, stellar_asset_address,
+    };
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    // ── Stellar Asset Contract helpers ──────────────────────────────────────
+
+    #[test]
+    fn test_stellar_asset_address_matches_deployed_sac() {
+        let env = Env::default();
+        let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+        let issuer = sac.issuer().address();
+
+        // `register_stellar_asset_contract_v2` deploys the classic asset `aaa`.
+        let code = String::from_str(&env, "aaa");
+        assert_eq!(
+            stellar_asset_address(&env, &code, &issuer),
+            Some(sac.address())
+        );
+        assert!(is_stellar_asset_address(
+            &env,
+            &sac.address(),
+            &code,
+            &issuer
+        ));
+
+        let other_code = String::from_str(&env, "aab");
+        assert!(!is_stellar_asset_address(
+            &env,
+            &sac.address(),
+            &other_code,
+            &issuer
+        ));
+    }
+
+    #[test]
+    fn test_classic_asset_xdr_encodes_alphanum4_and_alphanum12() {
+        let env = Env::default();
+        let issuer = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .issuer()
+            .address();
+
+        let usdc = classic_asset_xdr(&env, &String::from_str(&env, "USDC"), &issuer).unwrap();
+        // type + 4-byte code + key type + ed25519 key
+        assert_eq!(usdc.len(), 4 + 4 + 4 + 32);
+        assert_eq!(usdc.get(3), Some(1));
+
+        let long = classic_asset_xdr(&env, &String::from_str(&env, "BRIDGELET"), &issuer).unwrap();
+        assert_eq!(long.len(), 4 + 12 + 4 + 32);
+        assert_eq!(long.get(3), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_code_or_contract_issuer_is_rejected() {
+        let env = Env::default();
+        let sac = env.register_stellar_asset_contract_v2(Address::generate(&env));
+        let issuer = sac.issuer().address();
+
+        assert!(!is_valid_asset_code(&String::from_str(&env, "")));
+        assert!(!is_valid_asset_code(&String::from_str(
+            &env,
+            "TOOLONGASSETCODE"
+        )));
+        assert!(!is_valid_asset_code(&String::from_str(&env, "US-D")));
+        assert!(is_valid_asset_code(&String::from_str(&env, "USDC")));
+
+        assert_eq!(
+            stellar_asset_address(&env, &String::from_str(&env, "US-D"), &issuer),
+            None
+        );
+        // A contract address cannot issue classic assets.
+        assert_eq!(
+            stellar_asset_address(&env, &String::from_str(&env, "USDC"), &sac.address()),
+            None
+        );
+    }
+
+    // ── Byte concatenation and sweep message digests ────────────────────────
+
+    use crate::bytes::{
+        concat, network_id, sha256_concat, sweep_message_digest, tenant_sweep_message_digest,
+    };
+    use soroban_sdk::{Bytes, BytesN};
+
+    const DESTINATION: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const CONTROLLER: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+    const ACCOUNT: &str = "CAIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRDB3V";
+
+    /// Network passphrase of the public testnet.
+    const TESTNET: &[u8] = b"Test SDF Network ; September 2015";
+
+    /// Golden digests on testnet, computed independently of the SDK from the
+    /// XDR layout (`SCV_ADDRESS` tag, address type, key/hash bytes).
+    const DIGEST_NONCE_0: [u8; 32] = [
+        0x19, 0xb5, 0xec, 0x53, 0x0e, 0x64, 0xdc, 0xcc, 0xb6, 0xc1, 0x78, 0xe5, 0x2d, 0x74, 0xf0,
+        0x0d, 0x4f, 0x74, 0xf1, 0x44, 0x71, 0x38, 0xb9, 0x26, 0x10, 0xf1, 0xcb, 0x85, 0x80, 0xec,
+        0xb0, 0xa3,
+    ];
+    const DIGEST_NONCE_7: [u8; 32] = [
+        0xa8, 0x7d, 0x1d, 0xf6, 0x3a, 0xdf, 0xfd, 0x1b, 0xa9, 0xc5, 0xee, 0xd6, 0xbb, 0xc6, 0x30,
+        0x74, 0x6a, 0x55, 0xd6, 0xac, 0xe1, 0x2c, 0xc2, 0x3a, 0x29, 0xb8, 0x0f, 0xc9, 0xfa, 0xd3,
+        0x49, 0x3a,
+    ];
+    const DIGEST_NONCE_7_MEMO: [u8; 32] = [
+        0x4e, 0x58, 0xfa, 0xf6, 0x33, 0x65, 0xab, 0x05, 0x04, 0x87, 0x08, 0x9a, 0x99, 0xeb, 0x0d,
+        0x61, 0x46, 0xbf, 0x42, 0x19, 0x30, 0x1d, 0x48, 0xf2, 0xb8, 0xbb, 0x93, 0x0a, 0x2d, 0x9d,
+        0x51, 0x88,
+    ];
+
+    #[test]
+    fn test_concat_joins_parts_in_order() {
+        let env = Env::default();
+        let parts = [
+            Bytes::from_array(&env, &[1, 2]),
+            Bytes::new(&env),
+            Bytes::from_array(&env, &[3]),
+        ];
+        assert_eq!(concat(&env, &parts), Bytes::from_array(&env, &[1, 2, 3]));
+        assert!(concat(&env, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_sha256_concat_matches_hash_of_joined_bytes() {
+        let env = Env::default();
+        let parts = [Bytes::from_array(&env, b"abc"), Bytes::new(&env)];
+        let expected: BytesN<32> = env.crypto().sha256(&Bytes::from_array(&env, b"abc")).into();
+        assert_eq!(sha256_concat(&env, &parts), expected);
+    }
+
+    #[test]
+    fn test_sweep_message_digest_golden_values() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let account = Address::from_str(&env, ACCOUNT);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let memo = BytesN::from_array(&env, &[42u8; 32]);
+
+        assert_eq!(
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                0,
+                &controller,
+                None,
+                None
+            )
+            .to_array(),
+            DIGEST_NONCE_0
+        );
+        assert_eq!(
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                7,
+                &controller,
+                None,
+                None
+            )
+            .to_array(),
+            DIGEST_NONCE_7
+        );
+        assert_eq!(
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                7,
+                &controller,
+                Some(&memo),
+                None
+            )
+            .to_array(),
+            DIGEST_NONCE_7_MEMO
+        );
+    }
+
+    #[test]
+    fn test_sweep_message_digest_binds_the_deadline() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let account = Address::from_str(&env, ACCOUNT);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let digest = |valid_until_ledger| {
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                7,
+                &controller,
+                None,
+                valid_until_ledger,
+            )
+        };
+
+        assert_eq!(digest(None).to_array(), DIGEST_NONCE_7);
+        assert_ne!(digest(Some(100)), digest(None));
+        assert_ne!(digest(Some(100)), digest(Some(101)));
+    }
+
+    #[test]
+    fn test_sweep_message_digest_binds_the_account() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let digest = |account: &Address| {
+            sweep_message_digest(
+                &env,
+                &testnet,
+                account,
+                &destination,
+                0,
+                &controller,
+                None,
+                None,
+            )
+        };
+
+        assert_ne!(
+            digest(&Address::from_str(&env, ACCOUNT)),
+            digest(&controller)
+        );
+    }
+
+    #[test]
+    fn test_account_sweep_message_digest_binds_account_and_nonce() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let account = Address::from_str(&env, ACCOUNT);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let digest = |account: &Address, nonce| {
+            crate::bytes::account_sweep_message_digest(
+                &env,
+                &testnet,
+                account,
+                nonce,
+                &destination,
+                &controller,
+                None,
+            )
+        };
+
+        assert_ne!(digest(&controller, 0), digest(&controller, 1));
+        assert_ne!(digest(&controller, 0), digest(&destination, 0));
+        assert_ne!(
+            digest(&controller, 0),
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                0,
+                &controller,
+                None,
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_account_sweep_assets_message_digest_binds_the_asset_list() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let digest = |assets: soroban_sdk::Vec<Address>| {
+            crate::bytes::account_sweep_assets_message_digest(
+                &env,
+                &testnet,
+                &controller,
+                0,
+                &destination,
+                &controller,
+                &assets,
+            )
+        };
+
+        let one = soroban_sdk::vec![&env, destination.clone()];
+        let other = soroban_sdk::vec![&env, controller.clone()];
+        let both = soroban_sdk::vec![&env, destination.clone(), controller.clone()];
+        let swapped = soroban_sdk::vec![&env, controller.clone(), destination.clone()];
+        assert_ne!(digest(one.clone()), digest(other));
+        assert_ne!(digest(one), digest(both.clone()));
+        assert_ne!(digest(both), digest(swapped));
+        assert_ne!(
+            digest(soroban_sdk::Vec::new(&env)),
+            crate::bytes::account_sweep_message_digest(
+                &env,
+                &testnet,
+                &controller,
+                0,
+                &destination,
+                &controller,
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn test_sweep_message_digests_differ_across_networks() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let mainnet = network_id(&env, b"Public Global Stellar Network ; September 2015");
+        let account = Address::from_str(&env, ACCOUNT);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+
+        assert_ne!(
+            sweep_message_digest(
+                &env,
+                &testnet,
+                &account,
+                &destination,
+                0,
+                &controller,
+                None,
+                None
+            ),
+            sweep_message_digest(
+                &env,
+                &mainnet,
+                &account,
+                &destination,
+                0,
+                &controller,
+                None,
+                None
+            )
+        );
+        assert_ne!(
+            tenant_sweep_message_digest(
+                &env,
+                &testnet,
+                1,
+                &controller,
+                &destination,
+                0,
+                &controller,
+                None
+            ),
+            tenant_sweep_message_digest(
+                &env,
+                &mainnet,
+                1,
+                &controller,
+                &destination,
+                0,
+                &controller,
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_tenant_sweep_digest_binds_the_account() {
+        let env = Env::default();
+        let testnet = network_id(&env, TESTNET);
+        let destination = Address::from_str(&env, DESTINATION);
+        let controller = Address::from_str(&env, CONTROLLER);
+        let digest = |account: &Address| {
+            tenant_sweep_message_digest(
+                &env,
+                &testnet,
+                1,
+                account,
+                &destination,
+                0,
+                &controller,
+                None,
+            )
+        };
+
+        assert_ne!(digest(&controller), digest(&destination));
+    }
+
+    // ── Contract versions ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_contract_version_parses_semver() {
+        let version = |major, minor, patch| crate::ContractVersion {
+            major,
+            minor,
+            patch,
+        };
+
+        assert_eq!(crate::ContractVersion::parse("0.1.0"), version(0, 1, 0));
+        assert_eq!(
+            crate::ContractVersion::parse("2.10.3-rc.1+build.5"),
+            version(2, 10, 3)
+        );
+        assert_eq!(crate::ContractVersion::parse("1.x"), version(1, 0, 0));
+    }
+
+    // ── XLM display ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_to_xlm_display_renders_seven_decimals() {
+        let env = Env::default();
+        let display = |stroops| crate::to_xlm_display(&env, stroops);
+
+        assert_eq!(display(5_000_000), String::from_str(&env, "0.5000000"));
+        assert_eq!(display(1), String::from_str(&env, "0.0000001"));
+        assert_eq!(display(0), String::from_str(&env, "0.0000000"));
+        assert_eq!(
+            display(100 * crate::STROOPS_PER_XLM + 25),
+            String::from_str(&env, "100.0000025")
+        );
+        assert_eq!(display(-15_000_000), String::from_str(&env, "-1.5000000"));
+        assert_eq!(
+            display(i128::MIN),
+            String::from_str(&env, "-17014118346046923173168730371588.4105728")
+        );
+    }
+}
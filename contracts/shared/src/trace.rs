@@ -0,0 +1,21 @@
+//! Trace correlation ids.
+//!
+//! An off-chain caller may tag a sweep with a 16-byte trace id. Every event
+//! the controller and the account emit while handling that sweep carries the
+//! id as its last topic, so SDK logs, controller events and account events
+//! can be joined on it. Untraced invocations keep their usual topics.
+
+use soroban_sdk::{BytesN, Env, IntoVal, Val, Vec};
+
+/// Publish an event, appending `trace_id` as the last topic when present.
+pub fn publish<T, D>(env: &Env, topics: T, data: D, trace_id: Option<BytesN<16>>)
+where
+    T: IntoVal<Env, Vec<Val>>,
+    D: IntoVal<Env, Val>,
+{
+    let mut topics: Vec<Val> = topics.into_val(env);
+    if let Some(trace_id) = trace_id {
+        topics.push_back(trace_id.into_val(env));
+    }
+    env.events().publish(topics, data);
+}
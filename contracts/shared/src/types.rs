@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Bytes, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Map, Symbol, Vec};
 
 // Represents a payment received by the ephemeral account.
 #[contracttype]
@@ -7,7 +7,19 @@ pub struct Payment {
     pub asset: Address,
     pub amount: i128,
     pub timestamp: u64,
+    /// Who funded the payment, if it was deposited through
+    /// `record_payment_from`, which moves the funds from this payer itself.
+    /// Cleared when another payer tops it up.
+    pub payer: Option<Address>,
 }
+/// An amount of one asset.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetAmount {
+    pub asset: Address,
+    pub amount: i128,
+}
+
 // The current status of an ephemeral account.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq, Copy)]
@@ -17,6 +29,133 @@ pub enum AccountStatus {
     PaymentReceived = 1,
     Swept = 2,
     Expired = 3,
+    /// Swept, with some assets awaiting `sweep_remaining`.
+    PartiallySwept = 4,
+    /// Cancelled by the creator before any payment arrived.
+    Cancelled = 5,
+    /// Locked by `prepare_sweep` until `commit_sweep` or `abort_sweep`.
+    PendingSweep = 6,
+}
+
+/// Whether an account can be swept right now and, if not, why. Checked in
+/// this order, so the first obstacle is reported.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+#[repr(u32)]
+pub enum SweepReadiness {
+    Ready = 0,
+    NotInitialized = 1,
+    /// Swept, fully or partially.
+    AlreadySwept = 2,
+    /// Locked by `prepare_sweep`.
+    SweepPending = 3,
+    Paused = 4,
+    /// No payment recorded yet, or an invoice not fulfilled yet.
+    NoPayments = 5,
+    /// At or past the expiry ledger; only `expire` can settle it.
+    Expired = 6,
+}
+
+/// Semantic version of a deployed contract build, so off-chain tooling can
+/// branch on behavior without fingerprinting WASM hashes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+pub struct ContractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ContractVersion {
+    /// Parse a `major.minor.patch` version such as `CARGO_PKG_VERSION`.
+    /// Pre-release and build suffixes are ignored; a missing or malformed
+    /// component reads as 0.
+    ///
+    /// A `const fn`, so contracts evaluate it at compile time and ship no
+    /// parsing code.
+    pub const fn parse(version: &str) -> Self {
+        let bytes = version.as_bytes();
+        let mut parts = [0u32; 3];
+        let mut part = 0;
+        let mut valid = true;
+        let mut i = 0;
+        while i < bytes.len() && part < parts.len() {
+            match bytes[i] {
+                b'-' | b'+' => break,
+                b'.' => {
+                    if !valid {
+                        parts[part] = 0;
+                    }
+                    part += 1;
+                    valid = true;
+                }
+                digit @ b'0'..=b'9' => {
+                    parts[part] = parts[part]
+                        .saturating_mul(10)
+                        .saturating_add((digit - b'0') as u32);
+                }
+                _ => valid = false,
+            }
+            i += 1;
+        }
+        if !valid && part < parts.len() {
+            parts[part] = 0;
+        }
+        ContractVersion {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+        }
+    }
+}
+
+/// Terminal transition that settled an account when a sweep and an expiry
+/// race for it.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq, Copy)]
+#[repr(u32)]
+pub enum SettlementPath {
+    Sweep = 0,
+    Expiry = 1,
+}
+
+/// Emitted by the losing side of a sweep/expiry race.
+///
+/// `loser_reason` is the `ephemeral_account` error code the losing call
+/// would otherwise have failed with. The losing call completes as a no-op
+/// instead of failing, because Soroban discards the events of a failed
+/// invocation and the keeper/sweep bot would never see this result.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitrationResult {
+    pub winner: SettlementPath,
+    pub loser_reason: u32,
+}
+
+/// Settlement summary passed to a creator contract's `on_sweep_completed`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepReceipt {
+    pub destination: Address,
+    pub payments: Vec<Payment>,
+    pub total_amount: i128,
+    pub ledger: u32,
+    /// Trace id of the sweep, when it was submitted with one.
+    pub trace_id: Option<BytesN<16>>,
+    /// Asset the account's creator prefers to be settled in, if any.
+    pub preferred_settlement_asset: Option<Address>,
+}
+
+/// Ledgers at which an account reached each lifecycle stage, for funnel
+/// analytics (time-to-pay, time-to-settle).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountLifecycle {
+    /// `0` for accounts initialized before lifecycle tracking existed.
+    pub created_ledger: u32,
+    pub first_payment_ledger: Option<u32>,
+    /// Ledger of the sweep or expiry that settled the account.
+    pub settled_ledger: Option<u32>,
 }
 
 /// Account information structure
@@ -28,24 +167,95 @@ pub struct AccountInfo {
     pub expiry_ledger: u32,
     pub recovery_address: Address,
     pub payment_received: bool,
+    /// Number of recorded payments; page through them with `get_payments_page`.
     pub payment_count: u32,
-    pub payments: Vec<Payment>,
     pub swept_to: Option<Address>,
+    /// Hash of the off-chain policy document.
+    pub attestation: Option<BytesN<32>>,
+    /// Whether `lock_config` froze the account's settings.
+    pub config_locked: bool,
+    /// Asset the creator prefers settlement in; informational.
+    pub preferred_settlement_asset: Option<Address>,
+    /// Progress towards each expected payment, in the order given.
+    pub expectations: Vec<ExpectationProgress>,
+    /// Most distinct assets the account accepts.
+    pub max_assets: u32,
+    /// Whether the creator paused payments and sweeps with `pause`.
+    pub paused: bool,
+    /// Labels the creator attached with `set_metadata`.
+    pub metadata: Map<Symbol, Bytes>,
+    /// Whether swept funds vest in the account until `claim_vested`.
+    pub vesting: bool,
 }
 
-/// Request to initialize a single ephemeral account
+/// One page of an account's payments, in canonical asset order.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct AccountInitRequest {
-    pub expiry_ledger: u32,
-    pub recovery_address: Address,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentsPage {
+    pub payments: Vec<Payment>,
+    /// Amount of each payment on the page settled so far, in the same order.
+    pub settled: Vec<AssetAmount>,
+    /// Number of payments across all pages.
+    pub total: u32,
+}
+
+/// Amount recorded so far against one expected payment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpectationProgress {
+    pub asset: Address,
+    pub expected: i128,
+    pub received: i128,
 }
 
-/// Result of initializing an ephemeral account
+/// Optional settings accepted by `initialize_with_options`. `initialize`
+/// uses all defaults. See `docs/api-reference.md` for each option.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct AccountInitResult {
-    pub account_address: Address,
-    pub success: bool,
-    pub error: Option<Bytes>,
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InitOptions {
+    pub attestation: Option<BytesN<32>>,
+    pub sweep_signer: Option<BytesN<32>>,
+    pub locked_destination: Option<Address>,
+    pub payment_hold_ledgers: Option<u32>,
+    pub require_creator_cosign: bool,
+    pub expiry_reminders: Option<Vec<u32>>,
+    pub preferred_settlement_asset: Option<Address>,
+    pub expected_payments: Option<Vec<ExpectedPayment>>,
+    pub accumulate_payments: bool,
+    pub allowed_assets: Option<Vec<Address>>,
+    pub max_assets: Option<u32>,
+    pub min_payment: Option<i128>,
+    pub min_payment_per_asset: Option<Vec<AssetAmount>>,
+    pub allowed_destinations: Option<Vec<Address>>,
+    pub clawback_window_ledgers: Option<u32>,
+    pub ttl_extend_to: Option<u32>,
+    pub ledger_seconds: Option<u32>,
+    pub protocol_fee_bps: u32,
+    pub protocol_fee_recipient: Option<Address>,
+    pub sweep_hook: Option<Address>,
+    pub sweep_signers: Option<Vec<BytesN<32>>>,
+    pub sweep_threshold: u32,
+    pub guardian: Option<Address>,
+    pub guardian_delay_ledgers: u32,
+    pub dispute_window_ledgers: u32,
+    pub arbiter: Option<Address>,
+    pub blocked_payers: Option<Vec<Address>>,
+    pub vesting_cliff_ledger: Option<u32>,
+    pub vesting_duration_ledgers: u32,
+    pub soft_expiry_ledger: Option<u32>,
+    pub restrict_recording: bool,
+    pub recorder: Option<Address>,
+    pub verify_balances_on_sweep: bool,
+    pub sweep_contention_ledgers: u32,
+}
+
+/// Amount an account expects to receive in one asset, e.g. an invoice line.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpectedPayment {
+    pub asset: Address,
+    pub amount: i128,
+    /// Accepted deviation from `amount` in either direction; `0` for an
+    /// exact match.
+    pub tolerance: i128,
 }
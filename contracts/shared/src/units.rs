@@ -0,0 +1,38 @@
+use soroban_sdk::{Env, String};
+
+/// Stroops in one XLM. Contracts store XLM amounts as integer stroops.
+pub const STROOPS_PER_XLM: i128 = 10_000_000;
+
+/// Decimal places of an XLM amount.
+const XLM_DECIMALS: usize = 7;
+
+/// Longest rendering of an `i128`: sign, 39 digits and the decimal point.
+const MAX_DISPLAY_LEN: usize = 41;
+
+/// Render `stroops` as an XLM amount with all seven decimals, the way
+/// Horizon and wallets display it: `5_000_000` becomes `"0.5000000"`.
+pub fn to_xlm_display(env: &Env, stroops: i128) -> String {
+    let mut buf = [0u8; MAX_DISPLAY_LEN];
+    let mut pos = MAX_DISPLAY_LEN;
+    let mut value = stroops.unsigned_abs();
+
+    // Digits are written right to left; the point goes after the decimals
+    // and at least one whole digit is always written.
+    let mut written = 0;
+    while value > 0 || written <= XLM_DECIMALS {
+        if written == XLM_DECIMALS {
+            pos -= 1;
+            buf[pos] = b'.';
+        }
+        pos -= 1;
+        buf[pos] = b'0' + (value % 10) as u8;
+        value /= 10;
+        written += 1;
+    }
+    if stroops < 0 {
+        pos -= 1;
+        buf[pos] = b'-';
+    }
+
+    String::from_bytes(env, &buf[pos..])
+}
@@ -0,0 +1,55 @@
+use crate::adapter::account_info;
+use crate::authorization::{self, AccountSigner, SignatureScheme};
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, Bytes, Env};
+
+/// Signers set per ephemeral account.
+#[contractimpl]
+impl SweepController {
+    /// Verify `ephemeral_account`'s sweep authorizations against `signer`,
+    /// a public key of `scheme`, instead of the controller's authorized
+    /// signer. One controller can then sweep accounts signed by Ed25519,
+    /// EVM (secp256k1) and passkey infrastructure alike; accounts without a
+    /// signer of their own keep using the controller's. The account's
+    /// creator must authorize it, and it cannot be changed afterwards.
+    ///
+    /// # Errors
+    /// Returns Error::InvalidSignerKey if the key does not fit the scheme
+    /// Returns Error::AuthorizationFailed if the account already has a signer
+    pub fn set_account_signer(
+        env: Env,
+        ephemeral_account: Address,
+        scheme: SignatureScheme,
+        signer: Bytes,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        authorization::validate_signer_key(scheme, &signer)?;
+        if storage::get_account_signer(&env, &ephemeral_account).is_some() {
+            return Err(Error::AuthorizationFailed);
+        }
+
+        let creator = account_info(&env, &ephemeral_account).creator;
+        creator.require_auth();
+
+        storage::set_account_signer(
+            &env,
+            &ephemeral_account,
+            &AccountSigner {
+                scheme,
+                key: signer,
+            },
+        );
+        Ok(())
+    }
+
+    /// Signer set for `ephemeral_account` with `set_account_signer`, or
+    /// None if its sweeps are signed by the controller's authorized signer
+    pub fn get_account_signer(env: Env, ephemeral_account: Address) -> Option<AccountSigner> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_account_signer(&env, &ephemeral_account)
+    }
+}
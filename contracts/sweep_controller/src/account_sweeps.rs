@@ -0,0 +1,65 @@
+use crate::adapter::SweepAuthorization;
+use crate::authorization;
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::EphemeralAccountClient;
+use soroban_sdk::{contractimpl, Address, BytesN, Env};
+
+/// Sweeps authorized at the account's own nonce.
+#[contractimpl]
+impl SweepController {
+    /// Same as `execute_sweep`, authorized at the account's own sweep nonce
+    /// instead of the controller's.
+    ///
+    /// The signer signs `account_sweep_message_digest(network_id, account,
+    /// account_nonce, destination, contract_id, destination_memo)`, reading
+    /// `account_nonce` from the account's `get_sweep_nonce()`. The account
+    /// bumps it on every successful sweep, so the signature cannot be
+    /// replayed against the account once it has swept, and is never
+    /// invalidated by sweeps of other accounts. The controller nonce is left
+    /// untouched.
+    ///
+    /// # Errors
+    /// Returns Error::InvalidAccount if the account has no sweep nonce
+    /// Otherwise the same as `execute_sweep`
+    pub fn execute_account_sweep(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        Self::validate_destination(&env, &destination)?;
+
+        let account_nonce =
+            match EphemeralAccountClient::new(&env, &ephemeral_account).try_get_sweep_nonce() {
+                Ok(Ok(nonce)) => nonce,
+                _ => return Err(Error::InvalidAccount),
+            };
+        authorization::verify_account_sweep_auth(
+            &env,
+            &ephemeral_account,
+            account_nonce,
+            &destination,
+            &auth_signature,
+            &destination_memo,
+        )?;
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
+
+        Self::sweep_account(
+            &env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signature(auth_signature),
+            destination_memo,
+            false,
+        )
+        .map(|_| ())
+    }
+}
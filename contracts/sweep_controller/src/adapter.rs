@@ -0,0 +1,312 @@
+use bridgelet_shared::{AccountInfo, EphemeralAccountClient, Payment};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    Address, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+/// How a sweep is authorized to the account.
+pub enum SweepAuthorization {
+    /// Signature blob passed to the account's single-signature sweep
+    /// function; the account gates it on this controller's authorization.
+    Signature(BytesN<64>),
+    /// Signatures by the account's own signer set, checked by its
+    /// `prepare_multisig_sweep` (interface version 4).
+    Signers(Vec<(BytesN<32>, BytesN<64>)>),
+}
+
+/// Cross-contract call shapes of an ephemeral account, keyed by the
+/// interface version the account reports.
+///
+/// A single controller services a mixed-version fleet during migrations:
+/// accounts deployed before `interface_version()` existed are treated as
+/// `V1`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccountAdapter {
+    /// `sweep(destination, signature)` followed by `get_info()`.
+    V1,
+    /// `sweep_and_report(destination, signature)`, which returns the swept
+    /// payments directly.
+    V2,
+    /// `prepare_sweep(destination, signature)`, which locks the account and
+    /// returns the payments, then `commit_sweep()` once they are delivered.
+    V3,
+    /// The `V3` calls, but the account moves its own funds: the controller
+    /// delivers a prepared sweep through `deliver(asset, recipient, amount)`
+    /// and `sweep_claim` transfers the claimed payments itself.
+    V5,
+}
+
+impl AccountAdapter {
+    /// Resolve the adapter for `account` from its reported interface version.
+    ///
+    /// A failing `interface_version()` call means the function does not
+    /// exist on the account, i.e. a pre-versioning (`V1`) deployment.
+    /// Unknown future versions use the newest shape this controller knows.
+    pub fn resolve(env: &Env, account: &Address) -> Self {
+        let client = EphemeralAccountClient::new(env, account);
+        match client.try_interface_version() {
+            Ok(Ok(version)) if version >= 5 => AccountAdapter::V5,
+            Ok(Ok(version)) if version >= 3 => AccountAdapter::V3,
+            Ok(Ok(2)) => AccountAdapter::V2,
+            _ => AccountAdapter::V1,
+        }
+    }
+
+    /// Name of the account function that performs the signed sweep.
+    fn sweep_fn_name(&self, env: &Env) -> Symbol {
+        match self {
+            AccountAdapter::V1 => Symbol::new(env, "sweep"),
+            AccountAdapter::V2 => Symbol::new(env, "sweep_and_report"),
+            AccountAdapter::V3 | AccountAdapter::V5 => Symbol::new(env, "prepare_sweep"),
+        }
+    }
+
+    /// Whether the account transfers its own funds. Older accounts are
+    /// debited by the controller directly, which takes the account's own
+    /// authorization of each transfer.
+    pub fn moves_own_funds(&self) -> bool {
+        *self == AccountAdapter::V5
+    }
+
+    /// Sweep `account` to `destination` and return the swept payments.
+    /// From `V3` the sweep is only prepared; [`Self::commit`] settles it.
+    ///
+    /// Authorizes this controller as the invoker of the version-specific
+    /// sweep function before calling it, which satisfies the account's
+    /// `authorized_controller.require_auth()` gate.
+    pub fn sweep(
+        &self,
+        env: &Env,
+        account: &Address,
+        destination: &Address,
+        auth_signature: &BytesN<64>,
+    ) -> Vec<Payment> {
+        let args: Vec<Val> = (destination.clone(), auth_signature.clone()).into_val(env);
+        let context = ContractContext {
+            contract: account.clone(),
+            fn_name: self.sweep_fn_name(env),
+            args,
+        };
+        env.authorize_as_current_contract(Vec::from_array(
+            env,
+            [InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context,
+                sub_invocations: Vec::new(env),
+            })],
+        ));
+
+        match self {
+            AccountAdapter::V1 => {
+                EphemeralAccountClient::new(env, account).sweep(destination, auth_signature);
+                account_payments(env, account)
+            }
+            // Read leniently: accounts built before `Payment::payer` return
+            // payments without it.
+            AccountAdapter::V2 | AccountAdapter::V3 | AccountAdapter::V5 => {
+                let payments = env.invoke_contract(
+                    account,
+                    &self.sweep_fn_name(env),
+                    (destination.clone(), auth_signature.clone()).into_val(env),
+                );
+                decode_payments(env, &payments)
+            }
+        }
+    }
+
+    /// Prepare a sweep of `account` authorized by its signer set and return
+    /// the payments to deliver; [`Self::commit`] settles it. Only accounts
+    /// reporting interface version 4 or later have `prepare_multisig_sweep`.
+    pub fn prepare_multisig(
+        &self,
+        env: &Env,
+        account: &Address,
+        destination: &Address,
+        destination_memo: &Option<BytesN<32>>,
+        signatures: &Vec<(BytesN<32>, BytesN<64>)>,
+    ) -> Vec<Payment> {
+        let args: Vec<Val> = (
+            destination.clone(),
+            destination_memo.clone(),
+            signatures.clone(),
+        )
+            .into_val(env);
+        env.authorize_as_current_contract(Vec::from_array(
+            env,
+            [InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: account.clone(),
+                    fn_name: Symbol::new(env, "prepare_multisig_sweep"),
+                    args,
+                },
+                sub_invocations: Vec::new(env),
+            })],
+        ));
+
+        EphemeralAccountClient::new(env, account).prepare_multisig_sweep(
+            destination,
+            destination_memo,
+            signatures,
+        )
+    }
+
+    /// Settle a sweep once its funds are delivered. Earlier versions
+    /// settled it when `sweep` was called.
+    pub fn commit(&self, env: &Env, account: &Address) {
+        if matches!(self, AccountAdapter::V3 | AccountAdapter::V5) {
+            EphemeralAccountClient::new(env, account).commit_sweep();
+        }
+    }
+}
+
+/// Sweep `account` to the claim `recipient` through `sweep_claim` and
+/// return the payments claimed. `V5` accounts have already transferred
+/// them; older ones leave them for the controller to deliver.
+///
+/// Accounts that predate protocol fees return nothing from `sweep_claim`;
+/// what they `recorded` is delivered in full.
+pub fn sweep_claim(
+    env: &Env,
+    account: &Address,
+    recipient: &Address,
+    recorded: &Vec<Payment>,
+) -> Vec<Payment> {
+    let fn_name = Symbol::new(env, "sweep_claim");
+    let args: Vec<Val> = (recipient.clone(),).into_val(env);
+    env.authorize_as_current_contract(Vec::from_array(
+        env,
+        [InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: account.clone(),
+                fn_name: fn_name.clone(),
+                args: args.clone(),
+            },
+            sub_invocations: Vec::new(env),
+        })],
+    ));
+
+    let swept: Val = env.invoke_contract(account, &fn_name, args);
+    match Vec::<Val>::try_from_val(env, &swept) {
+        Ok(payments) => decode_payments(env, &payments),
+        Err(_) => recorded.clone(),
+    }
+}
+
+/// Read `account`'s `AccountInfo`, tolerating accounts built against an
+/// older version of the struct.
+///
+/// Current accounts return the shared `AccountInfo` as is, but decoding an
+/// older account's response strictly fails as soon as its struct lacks a
+/// field added since, so the response is read as a field map instead.
+/// Fields an older account does not report take their default (`None`,
+/// `false`, or empty); fields every version has are required.
+pub fn account_info(env: &Env, account: &Address) -> AccountInfo {
+    let fields = env.invoke_contract(account, &Symbol::new(env, "get_info"), Vec::new(env));
+    decode_account_info(env, &fields)
+}
+
+/// Same as [`account_info`], but `None` if the account cannot report its
+/// info at all (e.g. it is not initialized or not an ephemeral account).
+pub fn try_account_info(env: &Env, account: &Address) -> Option<AccountInfo> {
+    let fields = env
+        .try_invoke_contract::<Map<Symbol, Val>, soroban_sdk::Error>(
+            account,
+            &Symbol::new(env, "get_info"),
+            Vec::new(env),
+        )
+        .ok()?
+        .ok()?;
+    Some(decode_account_info(env, &fields))
+}
+
+/// Read every payment `account` recorded, a page at a time.
+///
+/// Accounts built before `get_payments_page()` existed report their
+/// payments in `get_info()` instead, and are read from there.
+pub fn account_payments(env: &Env, account: &Address) -> Vec<Payment> {
+    let mut payments = Vec::new(env);
+    loop {
+        // The account caps each page at its own maximum.
+        let args: Vec<Val> = (payments.len(), u32::MAX).into_val(env);
+        let page = match env.try_invoke_contract::<Map<Symbol, Val>, soroban_sdk::Error>(
+            account,
+            &Symbol::new(env, "get_payments_page"),
+            args,
+        ) {
+            Ok(Ok(page)) => page,
+            _ => return legacy_payments(env, account),
+        };
+        let batch = decode_payments(env, &required(env, &page, "payments"));
+        let total: u32 = required(env, &page, "total");
+        if batch.is_empty() {
+            return payments;
+        }
+        payments.append(&batch);
+        if payments.len() >= total {
+            return payments;
+        }
+    }
+}
+
+/// Payments as reported by `get_info()` on accounts that still carry them.
+fn legacy_payments(env: &Env, account: &Address) -> Vec<Payment> {
+    let fields: Map<Symbol, Val> =
+        env.invoke_contract(account, &Symbol::new(env, "get_info"), Vec::new(env));
+    match optional::<Vec<Val>>(env, &fields, "payments") {
+        Some(payments) => decode_payments(env, &payments),
+        None => Vec::new(env),
+    }
+}
+
+fn decode_account_info(env: &Env, fields: &Map<Symbol, Val>) -> AccountInfo {
+    AccountInfo {
+        creator: required(env, fields, "creator"),
+        status: required(env, fields, "status"),
+        expiry_ledger: required(env, fields, "expiry_ledger"),
+        recovery_address: required(env, fields, "recovery_address"),
+        payment_received: required(env, fields, "payment_received"),
+        payment_count: required(env, fields, "payment_count"),
+        swept_to: optional(env, fields, "swept_to"),
+        attestation: optional(env, fields, "attestation"),
+        config_locked: optional(env, fields, "config_locked").unwrap_or(false),
+        preferred_settlement_asset: optional(env, fields, "preferred_settlement_asset"),
+        expectations: optional(env, fields, "expectations").unwrap_or(Vec::new(env)),
+        // Accounts without the field have the protocol maximum of 10.
+        max_assets: optional(env, fields, "max_assets").unwrap_or(10),
+        paused: optional(env, fields, "paused").unwrap_or(false),
+        metadata: optional(env, fields, "metadata").unwrap_or(Map::new(env)),
+        vesting: optional(env, fields, "vesting").unwrap_or(false),
+    }
+}
+
+/// Decode payments field by field, so payments without `payer` (accounts
+/// built before it was added) read as having no known payer.
+fn decode_payments(env: &Env, payments: &Vec<Val>) -> Vec<Payment> {
+    let mut decoded = Vec::new(env);
+    for payment in payments.iter() {
+        let fields: Map<Symbol, Val> =
+            Map::try_from_val(env, &payment).expect("payment is not a struct");
+        decoded.push_back(Payment {
+            asset: required(env, &fields, "asset"),
+            amount: required(env, &fields, "amount"),
+            timestamp: required(env, &fields, "timestamp"),
+            payer: optional(env, &fields, "payer"),
+        });
+    }
+    decoded
+}
+
+/// A field reported by every version of `AccountInfo`.
+fn required<T: TryFromVal<Env, Val>>(env: &Env, fields: &Map<Symbol, Val>, name: &str) -> T {
+    optional(env, fields, name).expect("account info lacks a required field")
+}
+
+/// A field older accounts may not report. `Option` fields come back
+/// flattened: absent and `None` both read as `None`.
+fn optional<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    fields: &Map<Symbol, Val>,
+    name: &str,
+) -> Option<T> {
+    let value = fields.get(Symbol::new(env, name))?;
+    T::try_from_val(env, &value).ok()
+}
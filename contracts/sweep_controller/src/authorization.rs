@@ -1,83 +1,245 @@
 use crate::errors::Error;
 use crate::storage;
-use soroban_sdk::{xdr::ToXdr, Address, BytesN, Env};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env};
+
+/// Signature scheme of the controller's authorized signer, chosen at
+/// initialization.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureScheme {
+    /// Ed25519 signatures over the sweep digest (32-byte public key)
+    Ed25519,
+    /// ECDSA secp256k1 signatures over `keccak256(sweep digest)`, for
+    /// EVM-based signing infrastructure (33-byte compressed public key)
+    Secp256k1,
+    /// WebAuthn passkey assertions over the sweep digest, verified with
+    /// secp256r1 (65-byte uncompressed public key). Only
+    /// `execute_passkey_sweep` can carry them.
+    Secp256r1,
+}
+
+/// Signer one ephemeral account's sweeps are verified against instead of
+/// the controller's authorized signer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountSigner {
+    pub scheme: SignatureScheme,
+    /// Public key in the scheme's encoding, as for `initialize_with_scheme`
+    pub key: Bytes,
+}
+
+/// Check `key` is a public key of `scheme`: 32 bytes for Ed25519, a SEC1
+/// compressed key for secp256k1, a SEC1 uncompressed key for secp256r1.
+///
+/// # Returns
+/// Error::InvalidSignerKey if it is not
+pub fn validate_signer_key(scheme: SignatureScheme, key: &Bytes) -> Result<(), Error> {
+    let valid = match scheme {
+        SignatureScheme::Ed25519 => key.len() == 32,
+        SignatureScheme::Secp256k1 => key.len() == 33 && matches!(key.get(0), Some(0x02 | 0x03)),
+        SignatureScheme::Secp256r1 => key.len() == 65 && key.get(0) == Some(0x04),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignerKey)
+    }
+}
+
+/// Scheme and public key `account`'s sweep authorizations are signed
+/// with: the account's own signer if one was set, the controller's
+/// authorized signer otherwise.
+///
+/// # Returns
+/// Error::AuthorizedSignerNotSet if neither is set
+pub fn signer_for(env: &Env, account: &Address) -> Result<(SignatureScheme, Bytes), Error> {
+    if let Some(signer) = storage::get_account_signer(env, account) {
+        return Ok((signer.scheme, signer.key));
+    }
+    let scheme = storage::get_signature_scheme(env);
+    let key = match scheme {
+        SignatureScheme::Ed25519 => storage::get_authorized_signer(env).map(Bytes::from),
+        SignatureScheme::Secp256k1 => storage::get_secp256k1_signer(env).map(Bytes::from),
+        SignatureScheme::Secp256r1 => storage::get_secp256r1_signer(env).map(Bytes::from),
+    };
+    Ok((scheme, key.ok_or(Error::AuthorizedSignerNotSet)?))
+}
 
 /// Construct the message to be signed for sweep authorization
 ///
-/// Message format: hash(destination + nonce + contract_id)
+/// Message format: hash(network_id + account + destination + nonce + contract_id [+ destination_memo] [+ valid_until_ledger])
+///
+/// The layout is shared with off-chain signers through
+/// `bridgelet_shared::bytes::sweep_message_digest`, so both sides produce
+/// byte-identical digests.
 ///
 /// # Arguments
 /// * `env` - Soroban environment
+/// * `account` - Ephemeral account being swept
 /// * `destination` - Destination wallet address
 /// * `contract_id` - The sweep controller contract address
+/// * `destination_memo` - Optional memo the destination needs to credit the funds
+/// * `valid_until_ledger` - Optional last ledger the signature is valid in
 ///
 /// # Returns
 /// BytesN<32> containing the hash of the message components
-fn construct_sweep_message(env: &Env, destination: &Address, contract_id: &Address) -> BytesN<32> {
-    // Get current nonce
+fn construct_sweep_message(
+    env: &Env,
+    account: &Address,
+    destination: &Address,
+    contract_id: &Address,
+    destination_memo: &Option<BytesN<32>>,
+    valid_until_ledger: Option<u32>,
+) -> BytesN<32> {
     let nonce = storage::get_sweep_nonce(env);
-
-    // Construct the message by concatenating:
-    // - destination (serialized as bytes)
-    // - nonce (as u64, 8 bytes)
-    // - contract_id (serialized as bytes)
-    let mut message = soroban_sdk::Bytes::new(env);
-
-    // Add destination address bytes
-    let dest_bytes = destination.to_xdr(env);
-    message.append(&dest_bytes);
-
-    // Add nonce bytes (big-endian u64)
-    message.push_back(((nonce >> 56) & 0xFF) as u8);
-    message.push_back(((nonce >> 48) & 0xFF) as u8);
-    message.push_back(((nonce >> 40) & 0xFF) as u8);
-    message.push_back(((nonce >> 32) & 0xFF) as u8);
-    message.push_back(((nonce >> 24) & 0xFF) as u8);
-    message.push_back(((nonce >> 16) & 0xFF) as u8);
-    message.push_back(((nonce >> 8) & 0xFF) as u8);
-    message.push_back((nonce & 0xFF) as u8);
-
-    // Add contract id bytes
-    let contract_bytes = contract_id.to_xdr(env);
-    message.append(&contract_bytes);
-
-    // Hash the message using SHA256
-    env.crypto().sha256(&message).into()
+    bridgelet_shared::bytes::sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        destination,
+        nonce,
+        contract_id,
+        destination_memo.as_ref(),
+        valid_until_ledger,
+    )
 }
 
-/// Verify sweep authorization signature using Ed25519
+/// Verify sweep authorization signature
 ///
-/// This function verifies that the provided signature was created by the authorized signer
-/// using the private key corresponding to the authorized public key.
+/// This function verifies that the provided signature was created by the
+/// signer of `account` (see [`signer_for`]), under that signer's scheme.
 ///
 /// # Arguments
 /// * `env` - Soroban environment
-/// * `account` - Ephemeral account address (used as context)
+/// * `account` - Ephemeral account address, whose signer must sign
 /// * `destination` - Destination wallet address
-/// * `signature` - Ed25519 signature (64 bytes)
+/// * `signature` - Ed25519 or secp256k1 signature (64 bytes)
+/// * `destination_memo` - Optional memo covered by the signature
+/// * `valid_until_ledger` - Optional deadline covered by the signature
 ///
 /// # Returns
-/// Ok(()) if signature is valid, Error otherwise
+/// Ok(()) if signature is valid, Error::SignatureExpired if the current
+/// ledger is past the deadline, Error::AuthorizationRevoked if the digest
+/// was revoked, another Error otherwise
 pub fn verify_sweep_auth(
     env: &Env,
-    _account: &Address,
+    account: &Address,
     destination: &Address,
     signature: &BytesN<64>,
+    destination_memo: &Option<BytesN<32>>,
+    valid_until_ledger: Option<u32>,
 ) -> Result<(), Error> {
-    // Get the authorized signer public key from storage
-    let authorized_signer =
-        storage::get_authorized_signer(env).ok_or(Error::AuthorizedSignerNotSet)?;
+    if valid_until_ledger.is_some_and(|ledger| env.ledger().sequence() > ledger) {
+        return Err(Error::SignatureExpired);
+    }
 
     // Get the sweep controller contract address
     let contract_id = env.current_contract_address();
 
     // Construct the message that should have been signed
-    let message = construct_sweep_message(env, destination, &contract_id);
+    let message = construct_sweep_message(
+        env,
+        account,
+        destination,
+        &contract_id,
+        destination_memo,
+        valid_until_ledger,
+    );
+
+    // Reject individually revoked authorizations without touching the nonce
+    if storage::is_digest_revoked(env, &message) {
+        return Err(Error::AuthorizationRevoked);
+    }
+
+    // Verify the signature under the signer's scheme
+    verify_signer_signature(env, account, &message, signature)
+}
+
+/// Verify a sweep authorization bound to `account` and its own sweep nonce
+///
+/// The signature covers `bridgelet_shared::bytes::account_sweep_message_digest`
+/// instead of the controller-nonce message, so it can only sweep `account`,
+/// and only until the account's next sweep.
+///
+/// # Returns
+/// Ok(()) if signature is valid, Error::AuthorizationRevoked if the digest
+/// was revoked, another Error otherwise
+pub fn verify_account_sweep_auth(
+    env: &Env,
+    account: &Address,
+    account_nonce: u64,
+    destination: &Address,
+    signature: &BytesN<64>,
+    destination_memo: &Option<BytesN<32>>,
+) -> Result<(), Error> {
+    let message = bridgelet_shared::bytes::account_sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        account_nonce,
+        destination,
+        &env.current_contract_address(),
+        destination_memo.as_ref(),
+    );
+    if storage::is_digest_revoked(env, &message) {
+        return Err(Error::AuthorizationRevoked);
+    }
+
+    verify_signer_signature(env, account, &message, signature)
+}
+
+/// Verify the signature of `account`'s signer over a sweep digest
+///
+/// Ed25519 signatures sign `message` itself. Secp256k1 signatures are the
+/// 64-byte `r || s` (low-S) over `keccak256(message)`; the recovery id is
+/// not transmitted, so both candidates are recovered and compared with the
+/// stored compressed key.
+///
+/// # Returns
+/// Error::AuthorizedSignerNotSet if neither the account nor the controller
+/// has a signer, Error::AuthorizationFailed if a secp256k1 signature
+/// recovers another key, or for a passkey signer, whose assertions a bare
+/// signature cannot carry
+pub fn verify_signer_signature(
+    env: &Env,
+    account: &Address,
+    message: &BytesN<32>,
+    signature: &BytesN<64>,
+) -> Result<(), Error> {
+    let (scheme, key) = signer_for(env, account)?;
+    match scheme {
+        SignatureScheme::Ed25519 => {
+            let authorized_signer =
+                BytesN::<32>::try_from(key).map_err(|_| Error::InvalidSignerKey)?;
+            env.crypto()
+                .ed25519_verify(&authorized_signer, &message.clone().into(), signature);
+            Ok(())
+        }
+        SignatureScheme::Secp256k1 => {
+            let authorized_signer =
+                BytesN::<33>::try_from(key).map_err(|_| Error::InvalidSignerKey)?;
+            let digest = env.crypto().keccak256(&message.clone().into());
+            for recovery_id in 0..2 {
+                let recovered = env
+                    .crypto()
+                    .secp256k1_recover(&digest, signature, recovery_id);
+                if compress_public_key(env, &recovered) == authorized_signer {
+                    return Ok(());
+                }
+            }
+            Err(Error::AuthorizationFailed)
+        }
+        _ => Err(Error::AuthorizationFailed),
+    }
+}
 
-    // Verify the Ed25519 signature
-    env.crypto()
-        .ed25519_verify(&authorized_signer, &message.into(), signature);
-    Ok(())
+/// SEC1-compress an uncompressed (`0x04 || x || y`) secp256k1 public key
+fn compress_public_key(env: &Env, key: &BytesN<65>) -> BytesN<33> {
+    let key = key.to_array();
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x02 | (key[64] & 1);
+    compressed[1..].copy_from_slice(&key[1..33]);
+    BytesN::from_array(env, &compressed)
 }
 
 /// Increment the nonce after successful authorization
@@ -95,18 +257,35 @@ pub struct AuthContext {
     pub account: Address,
     pub destination: Address,
     pub signature: BytesN<64>,
+    pub destination_memo: Option<BytesN<32>>,
+    pub valid_until_ledger: Option<u32>,
 }
 
 impl AuthContext {
-    pub fn new(account: Address, destination: Address, signature: BytesN<64>) -> Self {
+    pub fn new(
+        account: Address,
+        destination: Address,
+        signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+        valid_until_ledger: Option<u32>,
+    ) -> Self {
         Self {
             account,
             destination,
             signature,
+            destination_memo,
+            valid_until_ledger,
         }
     }
 
     pub fn verify(&self, env: &Env) -> Result<(), Error> {
-        verify_sweep_auth(env, &self.account, &self.destination, &self.signature)
+        verify_sweep_auth(
+            env,
+            &self.account,
+            &self.destination,
+            &self.signature,
+            &self.destination_memo,
+            self.valid_until_ledger,
+        )
     }
 }
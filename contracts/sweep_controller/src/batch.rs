@@ -0,0 +1,172 @@
+use crate::errors::Error;
+use crate::storage::{self, SweepRecord};
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::{trace, AssetAmount};
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Vec,
+};
+
+/// Largest number of accounts one `execute_sweep_batch` call may sweep.
+pub const MAX_BATCH_SWEEP: u32 = 50;
+
+/// One signed sweep of a batch, with the same arguments as `execute_sweep`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSweepRequest {
+    pub ephemeral_account: Address,
+    pub destination: Address,
+    pub auth_signature: BytesN<64>,
+    pub destination_memo: Option<BytesN<32>>,
+}
+
+/// Key of a compacted batch's receipts: `sha256` of the XDR-encoded
+/// requests, so a caller can derive it before submitting.
+pub fn batch_digest(env: &Env, requests: &Vec<BatchSweepRequest>) -> BytesN<32> {
+    env.crypto().sha256(&requests.clone().to_xdr(env)).into()
+}
+
+/// Sum the delivered payments of `records` per asset, in the order each
+/// asset first appears.
+pub fn total_per_asset(env: &Env, records: &Vec<SweepRecord>) -> Vec<AssetAmount> {
+    let mut totals: Vec<AssetAmount> = Vec::new(env);
+    for record in records.iter() {
+        for payment in record.receipt.payments.iter() {
+            match totals.iter().position(|total| total.asset == payment.asset) {
+                Some(index) => {
+                    let index = index as u32;
+                    let mut total = totals.get_unchecked(index);
+                    total.amount += payment.amount;
+                    totals.set(index, total);
+                }
+                None => totals.push_back(AssetAmount {
+                    asset: payment.asset,
+                    amount: payment.amount,
+                }),
+            }
+        }
+    }
+    totals
+}
+
+/// Summary of a compacted batch sweep, emitted instead of the per-account
+/// `SweepTransfer`, `SweepCompleted` and `CreatorNotified` events
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSweepCompleted {
+    /// Number of accounts swept
+    pub count: u32,
+    /// Amount delivered in each asset across the batch
+    pub total_per_asset: Vec<AssetAmount>,
+    /// Key of the per-account receipts, see `get_batch_receipts`
+    pub batch_digest: BytesN<32>,
+}
+
+fn emit_batch_sweep_completed(env: &Env, event: BatchSweepCompleted) {
+    trace::publish(
+        env,
+        (symbol_short!("batch_swp"),),
+        event,
+        storage::get_trace_id(env),
+    );
+}
+
+/// Batch sweeps.
+#[contractimpl]
+impl SweepController {
+    /// Execute several signed sweeps in one transaction.
+    ///
+    /// Each request is verified and swept like `execute_sweep`, in order,
+    /// so request `i` must be signed at nonce `get_nonce() + i`. Any failing
+    /// sweep fails the whole batch. Accounts that `expire()` or `recover()`
+    /// already settled are skipped, as `execute_sweep` does.
+    ///
+    /// Batches larger than the threshold set with
+    /// `set_batch_compaction_threshold` are compacted: the per-account
+    /// `SweepTransfer`, `SweepCompleted` and `CreatorNotified` events are
+    /// replaced by one `BatchSweepCompleted`, and the receipts are stored
+    /// for `get_batch_receipts`, keeping large batches within event limits.
+    ///
+    /// # Returns
+    /// The receipt of each account swept, in request order
+    ///
+    /// # Errors
+    /// Returns Error::InvalidBatchSize if the batch is empty or larger than MAX_BATCH_SWEEP
+    /// Otherwise the same as `execute_sweep`, for the first failing request
+    pub fn execute_sweep_batch(
+        env: Env,
+        requests: Vec<BatchSweepRequest>,
+    ) -> Result<Vec<SweepRecord>, Error> {
+        storage::extend_instance_ttl(&env);
+
+        if requests.is_empty() || requests.len() > MAX_BATCH_SWEEP {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let compact = storage::get_batch_compaction_threshold(&env)
+            .is_some_and(|threshold| requests.len() > threshold);
+        storage::set_events_compacted(&env, compact);
+
+        let mut records = Vec::new(&env);
+        for request in requests.iter() {
+            let receipt = Self::sweep(
+                &env,
+                request.ephemeral_account.clone(),
+                request.destination,
+                request.auth_signature,
+                request.destination_memo,
+                None,
+            )?;
+            if let Some(receipt) = receipt {
+                records.push_back(SweepRecord {
+                    ephemeral_account: request.ephemeral_account,
+                    receipt,
+                });
+            }
+        }
+
+        storage::set_events_compacted(&env, false);
+        if compact {
+            let batch_digest = batch_digest(&env, &requests);
+            storage::set_batch_receipts(&env, &batch_digest, &records);
+            emit_batch_sweep_completed(
+                &env,
+                BatchSweepCompleted {
+                    count: records.len(),
+                    total_per_asset: total_per_asset(&env, &records),
+                    batch_digest,
+                },
+            );
+        }
+        Ok(records)
+    }
+
+    /// Receipts of a compacted batch sweep, keyed by the digest reported in
+    /// its `BatchSweepCompleted` event.
+    pub fn get_batch_receipts(env: Env, batch_digest: BytesN<32>) -> Option<Vec<SweepRecord>> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_batch_receipts(&env, &batch_digest)
+    }
+
+    /// Compact batch sweeps of more than `threshold` accounts into one
+    /// summary event, or never compact with `None`.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    pub fn set_batch_compaction_threshold(env: Env, threshold: Option<u32>) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        storage::set_batch_compaction_threshold(&env, threshold);
+        Ok(())
+    }
+
+    /// Batch size above which batch sweeps are compacted, if any.
+    pub fn get_batch_compaction_threshold(env: Env) -> Option<u32> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_batch_compaction_threshold(&env)
+    }
+}
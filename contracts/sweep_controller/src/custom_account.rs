@@ -0,0 +1,54 @@
+use crate::adapter::SweepAuthorization;
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, BytesN, Env, IntoVal};
+
+/// Sweeps authorized by the account itself, as a Soroban custom account.
+///
+#[contractimpl]
+impl SweepController {
+    /// Execute a sweep authorized by the ephemeral account itself through
+    /// Soroban's native authorization framework instead of an Ed25519 blob
+    /// from the external signer.
+    ///
+    /// The account must authorize `(destination, destination_memo)` for this
+    /// call, i.e. act as a custom account whose `__check_auth` approves the
+    /// sweep (for example a wallet-signed payload). Replay protection comes
+    /// from the auth entry's own nonce, so the controller's sweep nonce is
+    /// left untouched.
+    ///
+    /// # Errors
+    /// Returns Error::UnauthorizedDestination if destination doesn't match authorized destination (when set)
+    /// Returns Error::AccountNotReady if the account holds no payments
+    /// Returns Error::TransferFailed if token transfer fails
+    pub fn execute_sweep_as_account(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        Self::validate_destination(&env, &destination)?;
+
+        ephemeral_account
+            .require_auth_for_args((destination.clone(), destination_memo.clone()).into_val(&env));
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
+
+        // Accounts gate `sweep` on this controller's authorization and do not
+        // inspect the signature blob, so no external signature is needed.
+        Self::sweep_account(
+            &env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signature(BytesN::from_array(&env, &[0u8; 64])),
+            destination_memo,
+            false,
+        )
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::errors::Error;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, BytesN, Env};
+
+/// Sweep signatures that expire.
+#[contractimpl]
+impl SweepController {
+    /// Same as `execute_sweep`, for a signature that carries a deadline.
+    ///
+    /// The signer signs the usual sweep message with `valid_until_ledger`
+    /// appended, so a leaked signature stops working after that ledger
+    /// instead of staying valid until the nonce changes. The deadline cannot
+    /// be stripped: without it the message, and so the signature, differs.
+    ///
+    /// # Errors
+    /// Returns Error::SignatureExpired if the current ledger is past `valid_until_ledger`
+    /// Otherwise the same as `execute_sweep`
+    pub fn execute_sweep_with_deadline(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+        valid_until_ledger: u32,
+    ) -> Result<(), Error> {
+        Self::sweep(
+            &env,
+            ephemeral_account,
+            destination,
+            auth_signature,
+            destination_memo,
+            Some(valid_until_ledger),
+        )
+        .map(|_| ())
+    }
+}
@@ -0,0 +1,109 @@
+use crate::adapter::{account_payments, try_account_info};
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::{AccountStatus, Payment};
+use soroban_sdk::{contractimpl, contracttype, token::TokenClient, Address, Env, Vec};
+
+/// One problem `diagnose` found with an account.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// The account did not answer `get_info`: not initialized, or not an
+    /// ephemeral account. Nothing else could be checked.
+    Unreadable,
+    /// `(asset, outstanding, balance)`: the account holds less of `asset`
+    /// than its recorded, not yet settled amount, so a sweep would fail.
+    BalanceMismatch(Address, i128, i128),
+    /// `(asset, destination)`: the destination the account will settle to
+    /// cannot hold `asset`, typically a missing trustline.
+    MissingTrustline(Address, Address),
+    /// A partial settlement is waiting for `sweep_remaining`.
+    SettlementPending,
+    /// The account holds funds but its sweep deadline (`sweepable_until`)
+    /// has passed; it can only expire to its recovery address now.
+    SweepWindowClosed(u32),
+}
+
+/// Everything wrong with `account`, in one call.
+///
+/// Balances are checked for assets still owed: all of them while the
+/// account awaits a sweep, the undelivered ones after a partial settlement.
+/// The destination is the pending settlement's, else the locked
+/// `authorized_destination`; without either, trustlines are not checked.
+pub fn diagnose(env: &Env, account: &Address) -> Vec<Diagnostic> {
+    let mut findings = Vec::new(env);
+    let info = match try_account_info(env, account) {
+        Some(info) => info,
+        None => {
+            findings.push_back(Diagnostic::Unreadable);
+            return findings;
+        }
+    };
+
+    let (outstanding, destination) = match pending_settlement(env, account) {
+        Some((remaining, destination)) => {
+            findings.push_back(Diagnostic::SettlementPending);
+            (remaining, Some(destination))
+        }
+        None if info.status == AccountStatus::PaymentReceived => (
+            account_payments(env, account),
+            storage::get_authorized_destination(env),
+        ),
+        None => (Vec::new(env), storage::get_authorized_destination(env)),
+    };
+
+    for payment in outstanding.iter() {
+        let token = TokenClient::new(env, &payment.asset);
+        if let Ok(Ok(balance)) = token.try_balance(account) {
+            if balance < payment.amount {
+                findings.push_back(Diagnostic::BalanceMismatch(
+                    payment.asset.clone(),
+                    payment.amount,
+                    balance,
+                ));
+            }
+        }
+        if let Some(destination) = &destination {
+            if !matches!(token.try_balance(destination), Ok(Ok(_))) {
+                findings.push_back(Diagnostic::MissingTrustline(
+                    payment.asset.clone(),
+                    destination.clone(),
+                ));
+            }
+        }
+    }
+
+    if info.status == AccountStatus::PaymentReceived {
+        let deadline = info
+            .expiry_ledger
+            .saturating_sub(1)
+            .saturating_sub(storage::get_submission_margin(env));
+        if env.ledger().sequence() > deadline {
+            findings.push_back(Diagnostic::SweepWindowClosed(deadline));
+        }
+    }
+
+    findings
+}
+
+/// Undelivered assets and destination of `account`'s partial settlement.
+fn pending_settlement(env: &Env, account: &Address) -> Option<(Vec<Payment>, Address)> {
+    if let Some(pending) = storage::get_pending_settlement(env, account) {
+        return Some((pending.remaining, pending.destination));
+    }
+    None
+}
+
+/// Support triage.
+#[contractimpl]
+impl SweepController {
+    /// List everything that would keep `ephemeral_account` from settling:
+    /// balance shortfalls, destinations missing a trustline, a pending
+    /// partial settlement, a closed sweep window. Empty when nothing is
+    /// wrong. Read-only, for support triage.
+    pub fn diagnose(env: Env, ephemeral_account: Address) -> Vec<Diagnostic> {
+        storage::extend_instance_ttl(&env);
+
+        diagnose(&env, &ephemeral_account)
+    }
+}
@@ -13,4 +13,17 @@ pub enum Error {
     AuthorizedSignerNotSet = 10,
     InvalidNonce = 11,
     UnauthorizedDestination = 13,
+    InvalidFeatureFlags = 14,
+    NothingToSweep = 15,
+    AuthorizationRevoked = 16,
+    TenantNotFound = 17,
+    TenantAlreadyExists = 18,
+    InvalidTenantConfig = 19,
+    InsufficientSignatures = 20,
+    IdempotencyKeyConflict = 21,
+    InvalidAmount = 22,
+    InvalidBatchSize = 23,
+    SignatureExpired = 24,
+    Overflow = 25,
+    InvalidSignerKey = 26,
 }
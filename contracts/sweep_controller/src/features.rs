@@ -0,0 +1,35 @@
+use crate::storage;
+use soroban_sdk::Env;
+
+/// Optional controller behaviors, stored as a `u32` bitfield and toggled at
+/// runtime by the creator via `set_features()`.
+///
+/// Every flag defaults to off, so a fresh deployment behaves conservatively;
+/// advanced deployments opt in without a separate wasm build.
+pub struct FeatureFlags;
+
+impl FeatureFlags {
+    /// Protocol fee deduction on sweeps.
+    pub const FEES: u32 = 1 << 0;
+    /// Skip transferring assets whose amount is below the dust threshold.
+    pub const DUST_SKIPPING: u32 = 1 << 1;
+    /// Per-window sweep rate limits.
+    pub const RATE_LIMITS: u32 = 1 << 2;
+    /// Restrict destinations to an address book.
+    pub const DESTINATION_BOOK: u32 = 1 << 3;
+    /// Settle the assets the destination can receive and leave the rest for
+    /// `sweep_remaining`, instead of failing the whole sweep.
+    pub const PARTIAL_SETTLEMENT: u32 = 1 << 4;
+
+    /// Every flag this build knows about.
+    pub const ALL: u32 = Self::FEES
+        | Self::DUST_SKIPPING
+        | Self::RATE_LIMITS
+        | Self::DESTINATION_BOOK
+        | Self::PARTIAL_SETTLEMENT;
+}
+
+/// Whether `flag` is enabled for this deployment.
+pub fn is_enabled(env: &Env, flag: u32) -> bool {
+    storage::get_features(env) & flag == flag
+}
@@ -0,0 +1,81 @@
+use crate::adapter::{account_info, account_payments};
+use crate::errors::Error;
+use crate::features::{self, FeatureFlags};
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::is_contract_address;
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Vec};
+
+/// Ledger entries an `execute_sweep` is expected to touch, so an SDK can
+/// build the transaction footprint up front instead of retrying
+/// simulation under load.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FootprintHint {
+    /// Contract instances invoked: the controller, the account, each asset
+    /// contract, and the creator and registry contracts when notified.
+    pub instances: Vec<Address>,
+    /// Persistent entries read or written: the revocation check, the
+    /// account's payment entry and the account and destination balance of
+    /// each asset, and the pending settlement when partial settlement is
+    /// enabled.
+    pub persistent_keys: u32,
+}
+
+/// Build the footprint hint for sweeping `account` in its current state.
+pub fn sweep_footprint(env: &Env, account: &Address) -> FootprintHint {
+    let info = account_info(env, account);
+    let payments = account_payments(env, account);
+
+    let mut instances = Vec::new(env);
+    instances.push_back(env.current_contract_address());
+    instances.push_back(account.clone());
+    for payment in payments.iter() {
+        if !instances.contains(&payment.asset) {
+            instances.push_back(payment.asset);
+        }
+    }
+    if is_contract_address(env, &info.creator) && !instances.contains(&info.creator) {
+        instances.push_back(info.creator);
+    }
+    if let Some(registry) = storage::get_registry(env) {
+        if !instances.contains(&registry) {
+            instances.push_back(registry);
+        }
+    }
+
+    // The account's payment entry and a balance entry for each side of
+    // each transfer, plus the revocation lookup and, with partial
+    // settlement on, the pending settlement.
+    let mut persistent_keys = 3 * payments.len() + 1;
+    if features::is_enabled(env, FeatureFlags::PARTIAL_SETTLEMENT) {
+        persistent_keys += 1;
+    }
+
+    FootprintHint {
+        instances,
+        persistent_keys,
+    }
+}
+
+/// Footprint hints for SDKs.
+#[contractimpl]
+impl SweepController {
+    /// Preview the ledger footprint of sweeping `ephemeral_account` to
+    /// `destination`, so the SDK can set transaction footprints and
+    /// resources without simulation retries. A hint: it reflects the
+    /// account's current payments and the controller's current settings.
+    ///
+    /// # Errors
+    /// Returns Error::UnauthorizedDestination if destination doesn't match authorized destination (when set)
+    pub fn get_sweep_footprint(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+    ) -> Result<FootprintHint, Error> {
+        storage::extend_instance_ttl(&env);
+
+        Self::validate_destination(&env, &destination)?;
+        Ok(sweep_footprint(&env, &ephemeral_account))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::errors::Error;
+use crate::storage::{self, SweepRecord};
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::SweepReceipt;
+use soroban_sdk::{contractimpl, Address, BytesN, Env};
+
+/// Sweeps that are safe to retry.
+#[contractimpl]
+impl SweepController {
+    /// Same as `execute_sweep`, made safe to retry.
+    ///
+    /// The receipt of a successful sweep is stored under `idempotency_key`.
+    /// Calling again with the same key returns that receipt instead of
+    /// failing on the already swept account, so off-chain retry loops can
+    /// tell "already done" from "failed". The key is not part of the signed
+    /// message.
+    ///
+    /// # Returns
+    /// The sweep receipt, or `None` if `expire()` or `recover()` settled the
+    /// account first and nothing was swept
+    ///
+    /// # Errors
+    /// Returns Error::IdempotencyKeyConflict if the key was used for another account
+    /// Otherwise the same as `execute_sweep`
+    pub fn execute_sweep_idempotent(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+        idempotency_key: BytesN<32>,
+    ) -> Result<Option<SweepReceipt>, Error> {
+        if let Some(record) = storage::get_sweep_record(&env, &idempotency_key) {
+            storage::extend_instance_ttl(&env);
+            if record.ephemeral_account != ephemeral_account {
+                return Err(Error::IdempotencyKeyConflict);
+            }
+            return Ok(Some(record.receipt));
+        }
+
+        let receipt = Self::sweep(
+            &env,
+            ephemeral_account.clone(),
+            destination,
+            auth_signature,
+            destination_memo,
+            None,
+        )?;
+        if let Some(receipt) = &receipt {
+            storage::set_sweep_record(
+                &env,
+                &idempotency_key,
+                &SweepRecord {
+                    ephemeral_account,
+                    receipt: receipt.clone(),
+                },
+            );
+        }
+        Ok(receipt)
+    }
+
+    /// Get the receipt stored for an idempotent sweep.
+    pub fn get_sweep_receipt(env: Env, idempotency_key: BytesN<32>) -> Option<SweepRecord> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_sweep_record(&env, &idempotency_key)
+    }
+}
@@ -1,25 +1,62 @@
 #![no_std]
 
+mod account_signers;
+mod account_sweeps;
+mod adapter;
 mod authorization;
+mod batch;
+mod custom_account;
+mod deadlines;
+mod diagnostics;
 mod errors;
+mod features;
+mod footprint;
+mod idempotency;
+mod multisig;
+mod native_withdrawal;
+mod partial_settlement;
+mod registry;
+mod revocation;
+mod signature_schemes;
 mod storage;
+mod sweep_window;
+mod tenants;
+mod tracing;
 mod transfers;
-
-mod ephemeral_account_contract {
-    soroban_sdk::contractimport!(
-        file = "../../target/wasm32v1-none/release/ephemeral_account.wasm"
-    );
-}
-use ephemeral_account_contract::Client as EphemeralAccountClient;
+mod webauthn;
 
 use soroban_sdk::{
-    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
-    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Vec,
+    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
+use adapter::{account_info, account_payments, AccountAdapter, SweepAuthorization};
+pub use authorization::AccountSigner;
 use authorization::AuthContext;
-use bridgelet_shared::{AccountStatus, Payment, SweepControllerInterface};
+pub use authorization::SignatureScheme;
+pub use batch::{BatchSweepCompleted, BatchSweepRequest, MAX_BATCH_SWEEP};
+use bridgelet_shared::{
+    is_contract_address, trace, AccountErrorCode, AccountStatus, ArbitrationResult,
+    ContractVersion, EphemeralAccountClient, Payment, SettlementPath, SweepControllerInterface,
+    SweepReceipt,
+};
+pub use diagnostics::Diagnostic;
 pub use errors::Error;
+pub use features::FeatureFlags;
+pub use footprint::FootprintHint;
+pub use native_withdrawal::NativeWithdrawn;
+pub use partial_settlement::PartialSettlement;
+pub use revocation::AuthorizationRevoked;
+pub use storage::PendingSettlement;
+pub use storage::SweepRecord;
+pub use tenants::{TenantConfig, TenantUpdated};
+use transfers::Fee;
+pub use transfers::FeeCollected;
+pub use transfers::SweepTransfer;
+pub use transfers::TransferSkipped;
+pub use webauthn::{WebAuthnAssertion, MAX_CLIENT_DATA_LEN};
+
+/// Version of this build, reported by `get_version`.
+const BUILD_VERSION: ContractVersion = ContractVersion::parse(env!("CARGO_PKG_VERSION"));
 
 #[contract]
 pub struct SweepController;
@@ -41,28 +78,38 @@ impl SweepController {
         authorized_signer: BytesN<32>,
         authorized_destination: Option<Address>,
     ) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
+        Self::init(&env, &creator, authorized_destination)?;
+        storage::set_authorized_signer(&env, &authorized_signer);
+        Ok(())
+    }
+
+    /// Initialization shared by every scheme; the caller stores the
+    /// authorized signer.
+    fn init(
+        env: &Env,
+        creator: &Address,
+        authorized_destination: Option<Address>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(env);
 
         // Check if already initialized
-        if storage::get_authorized_signer(&env).is_some() {
+        if storage::has_authorized_signer(env) {
             return Err(Error::AuthorizationFailed);
         }
 
         // Require the creator to authorize this initialization
         creator.require_auth();
 
-        storage::set_creator(&env, &creator);
-
-        // Store the authorized signer public key
-        storage::set_authorized_signer(&env, &authorized_signer);
+        storage::set_creator(env, creator);
+        storage::set_version(env, &BUILD_VERSION);
 
         // Initialize the sweep nonce to 0
-        storage::init_sweep_nonce(&env);
+        storage::init_sweep_nonce(env);
 
         // Store authorized destination if provided
         if let Some(destination) = authorized_destination {
-            storage::set_authorized_destination(&env, &destination);
-            emit_destination_authorized(&env, destination);
+            storage::set_authorized_destination(env, &destination);
+            emit_destination_authorized(env, destination);
         }
 
         Ok(())
@@ -74,6 +121,9 @@ impl SweepController {
     /// * `ephemeral_account` - Address of the ephemeral account contract
     /// * `destination` - Destination wallet address
     /// * `auth_signature` - Authorization signature
+    /// * `destination_memo` - Optional memo/muxed identifier the destination
+    ///                        (e.g. an exchange) needs to credit the funds. Covered
+    ///                        by the signature and echoed in the sweep events.
     ///
     /// # Errors
     /// Returns Error::AuthorizationFailed if signature is invalid
@@ -85,20 +135,17 @@ impl SweepController {
         ephemeral_account: Address,
         destination: Address,
         auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
     ) -> Result<(), Error> {
-        storage::extend_instance_ttl(&env);
-
-        Self::validate_destination(&env, &destination)?;
-
-        // Verify authorization
-        let auth_ctx = AuthContext::new(
-            ephemeral_account.clone(),
-            destination.clone(),
-            auth_signature.clone(),
-        );
-        auth_ctx.verify(&env)?;
-
-        Self::sweep_account(&env, ephemeral_account, destination, auth_signature, true)
+        Self::sweep(
+            &env,
+            ephemeral_account,
+            destination,
+            auth_signature,
+            destination_memo,
+            None,
+        )
+        .map(|_| ())
     }
 
     /// Claim funds to the recipient using Soroban auth entries instead of a
@@ -109,18 +156,118 @@ impl SweepController {
 
         recipient.require_auth();
         Self::validate_destination(&env, &recipient)?;
+        Self::ensure_not_tenant_account(&env, &ephemeral_account)?;
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
 
         // Read payment info before sweep_claim() changes the account state
-        let account_client = EphemeralAccountClient::new(&env, &ephemeral_account);
-        let info = account_client.get_info();
-        let amount: i128 = info.payments.iter().map(|p| p.amount).sum();
+        let info = account_info(&env, &ephemeral_account);
+        let recorded = account_payments(&env, &ephemeral_account);
+        let recorded_amount = transfers::total_amount(&recorded)?;
+
+        let adapter = AccountAdapter::resolve(&env, &ephemeral_account);
+        let payments = adapter::sweep_claim(&env, &ephemeral_account, &recipient, &recorded);
+        let amount = transfers::total_amount(&payments)?;
+        // A vesting account keeps its funds and releases them itself, and
+        // a current account has already transferred them in `sweep_claim`.
+        if !info.vesting && !adapter.moves_own_funds() {
+            transfers::execute_transfers(
+                &env,
+                adapter,
+                &ephemeral_account,
+                &recipient,
+                &payments,
+                &None,
+            )?;
+        }
+        emit_sweep_completed(
+            &env,
+            Self::sweep_completed(
+                &env,
+                &ephemeral_account,
+                &recipient,
+                amount,
+                recorded_amount,
+                None,
+            ),
+        );
 
-        Self::authorize_claim(&env, &ephemeral_account, &recipient)?;
-        emit_sweep_completed(&env, ephemeral_account, recipient, amount);
+        Self::notify_creator(
+            &env,
+            &info.creator,
+            &ephemeral_account,
+            &Self::receipt(
+                &env,
+                &recipient,
+                payments,
+                amount,
+                info.preferred_settlement_asset,
+            ),
+        );
 
         Ok(())
     }
 
+    /// Verify a signed sweep and settle the account. Returns `None` if the
+    /// account was lost to expiry and nothing was swept.
+    fn sweep(
+        env: &Env,
+        ephemeral_account: Address,
+        destination: Address,
+        auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+        valid_until_ledger: Option<u32>,
+    ) -> Result<Option<SweepReceipt>, Error> {
+        storage::extend_instance_ttl(env);
+
+        Self::validate_destination(env, &destination)?;
+
+        // Verify authorization
+        let auth_ctx = AuthContext::new(
+            ephemeral_account.clone(),
+            destination.clone(),
+            auth_signature.clone(),
+            destination_memo.clone(),
+            valid_until_ledger,
+        );
+        auth_ctx.verify(env)?;
+
+        if Self::report_lost_to_expiry(env, &ephemeral_account) {
+            return Ok(None);
+        }
+
+        Self::sweep_account(
+            env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signature(auth_signature),
+            destination_memo,
+            true,
+        )
+        .map(Some)
+    }
+
+    /// Emit the arbitration event for a sweep that arrived after `expire()` or
+    /// `recover()` already settled the account. Returns `true` if the race
+    /// was lost, in which case the caller must return without sweeping: the
+    /// call completes as a no-op so the event survives the invocation.
+    fn report_lost_to_expiry(env: &Env, ephemeral_account: &Address) -> bool {
+        let account_client = EphemeralAccountClient::new(env, ephemeral_account);
+        if account_client.get_status() != AccountStatus::Expired {
+            return false;
+        }
+
+        emit_arbitration_result(
+            env,
+            ephemeral_account.clone(),
+            SettlementPath::Expiry,
+            AccountErrorCode::AccountExpired as u32,
+        );
+        true
+    }
+
     fn validate_destination(env: &Env, destination: &Address) -> Result<(), Error> {
         if storage::has_authorized_destination(env) {
             let authorized_dest =
@@ -133,126 +280,312 @@ impl SweepController {
         Ok(())
     }
 
-    fn authorize_ephemeral_sweep(
+    /// Settle an account on one of the controller's own sweep paths. An
+    /// account assigned to a tenant only settles through
+    /// `execute_tenant_sweep`, under the tenant's destination and fee.
+    fn sweep_account(
         env: &Env,
-        ephemeral_account: &Address,
-        destination: &Address,
-        auth_signature: &BytesN<64>,
-    ) {
-        let args = (destination.clone(), auth_signature.clone()).into_val(env);
-        let context = ContractContext {
-            contract: ephemeral_account.clone(),
-            fn_name: symbol_short!("sweep"),
-            args,
-        };
-        let auth_entries = Vec::from_array(
+        ephemeral_account: Address,
+        destination: Address,
+        authorization: SweepAuthorization,
+        destination_memo: Option<BytesN<32>>,
+        increment_nonce: bool,
+    ) -> Result<SweepReceipt, Error> {
+        Self::ensure_not_tenant_account(env, &ephemeral_account)?;
+        Self::settle_account(
             env,
-            [InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context,
-                sub_invocations: Vec::new(env),
-            })],
-        );
-        env.authorize_as_current_contract(auth_entries);
+            ephemeral_account,
+            destination,
+            authorization,
+            destination_memo,
+            increment_nonce,
+            None,
+        )
     }
 
-    fn sweep_account(
+    fn ensure_not_tenant_account(env: &Env, ephemeral_account: &Address) -> Result<(), Error> {
+        if storage::get_account_tenant(env, ephemeral_account).is_some() {
+            return Err(Error::InvalidAccount);
+        }
+        Ok(())
+    }
+
+    fn settle_account(
         env: &Env,
         ephemeral_account: Address,
         destination: Address,
-        auth_signature: BytesN<64>,
+        authorization: SweepAuthorization,
+        destination_memo: Option<BytesN<32>>,
         increment_nonce: bool,
-    ) -> Result<(), Error> {
+        fee: Option<Fee>,
+    ) -> Result<SweepReceipt, Error> {
+        // Keep the account live for the whole settlement before moving funds.
+        storage::extend_account_ttl(env, &ephemeral_account);
+
+        // A vesting account keeps its funds and releases them to the
+        // destination itself through `claim_vested`, so there is nothing to
+        // deliver, nor a transfer to take a tenant fee out of.
+        let vesting = account_info(env, &ephemeral_account).vesting;
+        if vesting && fee.is_some() {
+            return Err(Error::InvalidAccount);
+        }
+
         if increment_nonce {
             // Increment nonce after successful verification to prevent replay attacks.
             authorization::increment_nonce(env);
         }
 
-        Self::authorize_ephemeral_sweep(env, &ephemeral_account, &destination, &auth_signature);
-
-        // Call ephemeral account contract to validate and authorize sweep.
-        let account_client = EphemeralAccountClient::new(env, &ephemeral_account);
-
-        // The account contract validates state and authorizes the sweep.
-        account_client.sweep(&destination, &auth_signature);
-
-        // Get payment details from account.
-        let info = account_client.get_info();
+        // Dispatch on the account's interface version so accounts deployed
+        // from older wasm keep working alongside newer ones.
+        let adapter = AccountAdapter::resolve(env, &ephemeral_account);
+        let payments_vec = match &authorization {
+            SweepAuthorization::Signature(auth_signature) => {
+                adapter.sweep(env, &ephemeral_account, &destination, auth_signature)
+            }
+            SweepAuthorization::Signers(signatures) => adapter.prepare_multisig(
+                env,
+                &ephemeral_account,
+                &destination,
+                &destination_memo,
+                signatures,
+            ),
+        };
 
         // Verify payment was received
-        if !info.payment_received {
+        if payments_vec.is_empty() {
             return Err(Error::AccountNotReady);
         }
 
-        let amount = info.payments.iter().map(|p| p.amount).sum();
+        let amount = transfers::total_amount(&payments_vec)?;
         if amount == 0 {
             return Err(Error::AccountNotReady);
         }
 
-        // Execute the actual token transfers for all recorded payments.
-        //
-        // info.payments yields ephemeral_account_contract::Payment (the
-        // contractimport!-generated type) — structurally identical to
-        // bridgelet_shared::Payment but a distinct Rust type, since
-        // contractimport! derives its own types from the wasm's interface
-        // metadata rather than reusing the shared crate. Convert explicitly
-        // field-by-field; transfers::execute_transfers expects the
-        // bridgelet_shared version.
-        let mut payments_vec = Vec::new(env);
-        for payment in info.payments.iter() {
-            payments_vec.push_back(Payment {
-                asset: payment.asset.clone(),
-                amount: payment.amount,
-                timestamp: payment.timestamp,
-            });
+        let payments_vec = match fee {
+            Some(fee) => {
+                transfers::collect_fee(env, adapter, &ephemeral_account, &fee, &payments_vec)?
+            }
+            None => payments_vec,
+        };
+
+        let undelivered = if vesting {
+            Vec::new(env)
+        } else {
+            Self::deliver(
+                env,
+                adapter,
+                &ephemeral_account,
+                &destination,
+                &payments_vec,
+                &destination_memo,
+            )?
+        };
+        // The account only reads as swept once its funds have moved.
+        adapter.commit(env, &ephemeral_account);
+
+        if !undelivered.is_empty() {
+            EphemeralAccountClient::new(env, &ephemeral_account).mark_partially_swept();
+            Self::keep_pending(env, &ephemeral_account, &destination, &undelivered);
         }
 
-        transfers::execute_transfers(env, &ephemeral_account, &destination, &payments_vec)
-            .map_err(|_| Error::TransferFailed)?;
+        Self::complete_settlement(
+            env,
+            &ephemeral_account,
+            &destination,
+            payments_vec,
+            &undelivered,
+            destination_memo,
+        )
+    }
 
-        // Emit sweep completed event after successful transfer.
-        emit_sweep_completed(env, ephemeral_account, destination, amount);
+    /// Transfer `payments` to `destination`.
+    ///
+    /// With `FeatureFlags::PARTIAL_SETTLEMENT` enabled, transfers the
+    /// destination cannot receive are skipped and returned; otherwise any
+    /// failing transfer fails the sweep.
+    fn deliver(
+        env: &Env,
+        adapter: AccountAdapter,
+        ephemeral_account: &Address,
+        destination: &Address,
+        payments: &Vec<Payment>,
+        destination_memo: &Option<BytesN<32>>,
+    ) -> Result<Vec<Payment>, Error> {
+        if features::is_enabled(env, FeatureFlags::PARTIAL_SETTLEMENT) {
+            let undelivered = transfers::try_execute_transfers(
+                env,
+                adapter,
+                ephemeral_account,
+                destination,
+                payments,
+                destination_memo,
+            );
+            if undelivered.len() == payments.len() {
+                return Err(Error::TransferFailed);
+            }
+            return Ok(undelivered);
+        }
 
-        Ok(())
+        transfers::execute_transfers(
+            env,
+            adapter,
+            ephemeral_account,
+            destination,
+            payments,
+            destination_memo,
+        )?;
+        Ok(Vec::new(env))
     }
 
-    // Replace the entire authorize_claim function:
-    fn authorize_claim(
+    /// Emit `SweepCompleted` for the delivered part of `payments`, notify
+    /// a creator contract, and return the receipt of the delivery.
+    ///
+    /// # Errors
+    /// Returns Error::Overflow if the delivered or recorded amounts do not
+    /// sum within an `i128`
+    fn complete_settlement(
         env: &Env,
         ephemeral_account: &Address,
-        recipient: &Address,
-    ) -> Result<(), Error> {
-        // Authorize the controller as the invoker of sweep_claim on the ephemeral account
-        let args = (recipient.clone(),).into_val(env);
-        let context = ContractContext {
-            contract: ephemeral_account.clone(),
-            fn_name: symbol_short!("swp_claim"), // symbol_short! max 9 chars — abbreviated
-            args,
-        };
-        let auth_entries = Vec::from_array(
+        destination: &Address,
+        payments: Vec<Payment>,
+        undelivered: &Vec<Payment>,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<SweepReceipt, Error> {
+        let mut delivered = Vec::new(env);
+        for payment in payments.iter() {
+            if !undelivered.contains(&payment) {
+                delivered.push_back(payment);
+            }
+        }
+        let amount = transfers::total_amount(&delivered)?;
+        let info = account_info(env, ephemeral_account);
+        let recorded_amount = transfers::total_amount(&account_payments(env, ephemeral_account))?;
+
+        // Emit sweep completed event after successful transfer.
+        emit_sweep_completed(
             env,
-            [InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context,
-                sub_invocations: Vec::new(env),
-            })],
+            Self::sweep_completed(
+                env,
+                ephemeral_account,
+                destination,
+                amount,
+                recorded_amount,
+                destination_memo,
+            ),
         );
-        env.authorize_as_current_contract(auth_entries);
 
-        let account_client = EphemeralAccountClient::new(env, ephemeral_account);
-        account_client.sweep_claim(recipient);
-        Ok(())
+        let creator = info.creator;
+        let receipt = Self::receipt(
+            env,
+            destination,
+            delivered,
+            amount,
+            info.preferred_settlement_asset,
+        );
+        Self::notify_creator(env, &creator, ephemeral_account, &receipt);
+        Self::notify_registry(env, ephemeral_account);
+        Ok(receipt)
     }
-    /// Check if an account is ready for sweep
-    pub fn can_sweep(env: Env, ephemeral_account: Address) -> bool {
-        storage::extend_instance_ttl(&env);
 
-        let account_client = EphemeralAccountClient::new(&env, &ephemeral_account);
+    /// Settlement summary of `payments` delivered to `destination` in this
+    /// ledger, carrying the current trace id and the account's settlement
+    /// preference.
+    fn receipt(
+        env: &Env,
+        destination: &Address,
+        payments: Vec<Payment>,
+        total_amount: i128,
+        preferred_settlement_asset: Option<Address>,
+    ) -> SweepReceipt {
+        SweepReceipt {
+            destination: destination.clone(),
+            payments,
+            total_amount,
+            ledger: env.ledger().sequence(),
+            trace_id: storage::get_trace_id(env),
+            preferred_settlement_asset,
+        }
+    }
+
+    /// Submission deadline behind `can_sweep` and `sweepable_until`.
+    fn sweep_deadline(env: &Env, ephemeral_account: &Address) -> Option<u32> {
+        let info = account_info(env, ephemeral_account);
+        if !info.payment_received || info.status != AccountStatus::PaymentReceived || info.paused {
+            return None;
+        }
+        info.expiry_ledger
+            .checked_sub(1)?
+            .checked_sub(storage::get_submission_margin(env))
+    }
+
+    /// Build the `SweepCompleted` event, including the account's lifecycle
+    /// ledgers. Accounts deployed before lifecycle tracking have no
+    /// `get_lifecycle` and report `None`.
+    fn sweep_completed(
+        env: &Env,
+        ephemeral_account: &Address,
+        destination: &Address,
+        amount: i128,
+        recorded_amount: i128,
+        destination_memo: Option<BytesN<32>>,
+    ) -> SweepCompleted {
+        let (created_ledger, first_payment_ledger) =
+            match EphemeralAccountClient::new(env, ephemeral_account).try_get_lifecycle() {
+                Ok(Ok(lifecycle)) => (
+                    Some(lifecycle.created_ledger),
+                    lifecycle.first_payment_ledger,
+                ),
+                _ => (None, None),
+            };
+
+        SweepCompleted {
+            ephemeral_account: ephemeral_account.clone(),
+            destination: destination.clone(),
+            amount,
+            recorded_amount,
+            destination_memo,
+            created_ledger,
+            first_payment_ledger,
+            settled_ledger: env.ledger().sequence(),
+        }
+    }
+
+    /// Best-effort `on_sweep_completed(account, receipt)` callback to a
+    /// creator contract after settlement. Skipped for account creators; a
+    /// missing or failing hook is reported in `CreatorNotified` and never
+    /// fails the sweep.
+    fn notify_creator(
+        env: &Env,
+        creator: &Address,
+        ephemeral_account: &Address,
+        receipt: &SweepReceipt,
+    ) {
+        if !is_contract_address(env, creator) {
+            return;
+        }
+
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            creator,
+            &Symbol::new(env, "on_sweep_completed"),
+            (ephemeral_account.clone(), receipt.clone()).into_val(env),
+        );
+
+        emit_creator_notified(
+            env,
+            ephemeral_account.clone(),
+            creator.clone(),
+            matches!(result, Ok(Ok(()))),
+        );
+    }
 
-        // Check if account exists and has payment
-        let info = account_client.get_info();
+    /// Check if an account is ready for sweep (paid and not paused), and a
+    /// sweep submitted now would still land before the account expires
+    /// (see `sweepable_until`)
+    pub fn can_sweep(env: Env, ephemeral_account: Address) -> bool {
+        storage::extend_instance_ttl(&env);
 
-        info.payment_received
-            && info.status as u32 == AccountStatus::PaymentReceived as u32
-            && !account_client.is_expired()
+        Self::sweep_deadline(&env, &ephemeral_account)
+            .is_some_and(|deadline| env.ledger().sequence() <= deadline)
     }
 
     /// Return the current sweep nonce for this controller.
@@ -302,20 +635,69 @@ impl SweepController {
 
         Ok(())
     }
+
+    /// Replace the enabled optional behaviors with `features`, a
+    /// `FeatureFlags` bitfield.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    /// Returns Error::InvalidFeatureFlags if `features` sets an unknown bit
+    pub fn set_features(env: Env, features: u32) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        if features & !FeatureFlags::ALL != 0 {
+            return Err(Error::InvalidFeatureFlags);
+        }
+
+        let old_features = storage::get_features(&env);
+        storage::set_features(&env, features);
+        emit_features_updated(&env, old_features, features);
+
+        Ok(())
+    }
+
+    /// Version of the build that initialized the controller. Controllers
+    /// initialized before versions were stored report the running build.
+    pub fn get_version(env: Env) -> ContractVersion {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_version(&env).unwrap_or(BUILD_VERSION)
+    }
+
+    /// Return the enabled optional behaviors as a `FeatureFlags` bitfield.
+    /// All flags are off until the creator enables them.
+    pub fn get_features(env: Env) -> u32 {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_features(&env)
+    }
+
+    /// Whether every bit of `flag` is enabled.
+    pub fn is_feature_enabled(env: Env, flag: u32) -> bool {
+        storage::extend_instance_ttl(&env);
+
+        features::is_enabled(&env, flag)
+    }
 }
 
 /// Issue #43: conform to the shared interface for type-safe SDK integration.
 /// Each method delegates to the inherent contract implementation above.
 impl SweepControllerInterface for SweepController {
-    type Error = Error;
-
     fn initialize(
         env: Env,
         creator: Address,
         authorized_signer: BytesN<32>,
         authorized_destination: Option<Address>,
-    ) -> Result<(), Error> {
-        Self::initialize(env, creator, authorized_signer, authorized_destination)
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::initialize(
+            env,
+            creator,
+            authorized_signer,
+            authorized_destination,
+        )?)
     }
 
     fn execute_sweep(
@@ -323,47 +705,130 @@ impl SweepControllerInterface for SweepController {
         ephemeral_account: Address,
         destination: Address,
         auth_signature: BytesN<64>,
-    ) -> Result<(), Error> {
-        Self::execute_sweep(env, ephemeral_account, destination, auth_signature)
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::execute_sweep(
+            env,
+            ephemeral_account,
+            destination,
+            auth_signature,
+            destination_memo,
+        )?)
     }
 
-    fn claim(env: Env, recipient: Address, ephemeral_account: Address) -> Result<(), Error> {
-        Self::claim(env, recipient, ephemeral_account)
+    fn claim(
+        env: Env,
+        recipient: Address,
+        ephemeral_account: Address,
+    ) -> Result<(), soroban_sdk::Error> {
+        Ok(Self::claim(env, recipient, ephemeral_account)?)
+    }
+
+    fn get_version(env: Env) -> ContractVersion {
+        Self::get_version(env)
     }
 }
 
 /// Sweep completed event
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug)]
 pub struct SweepCompleted {
     pub ephemeral_account: Address,
     pub destination: Address,
+    /// Amount delivered by this settlement.
     pub amount: i128,
+    /// Sum of the payments recorded on the account, before fees and
+    /// whatever a partial settlement left undelivered.
+    pub recorded_amount: i128,
+    pub destination_memo: Option<BytesN<32>>,
+    /// `None` when the account predates lifecycle tracking.
+    pub created_ledger: Option<u32>,
+    pub first_payment_ledger: Option<u32>,
+    pub settled_ledger: u32,
+}
+
+/// Creator callback event (emitted after `on_sweep_completed` was attempted
+/// on a creator contract)
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct CreatorNotified {
+    pub ephemeral_account: Address,
+    pub creator: Address,
+    pub delivered: bool,
 }
 
 /// Destination authorized event (emitted when destination is set during initialization)
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug)]
 pub struct DestinationAuthorized {
     pub destination: Address,
 }
 
 /// Destination updated event (emitted when authorized destination is updated)
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone, Debug)]
 pub struct DestinationUpdated {
     pub old_destination: Option<Address>,
     pub new_destination: Address,
 }
 
-fn emit_sweep_completed(env: &Env, account: Address, destination: Address, amount: i128) {
-    let event = SweepCompleted {
+fn emit_sweep_completed(env: &Env, event: SweepCompleted) {
+    if storage::are_events_compacted(env) {
+        return;
+    }
+    trace::publish(
+        env,
+        (soroban_sdk::symbol_short!("sweep"),),
+        event,
+        storage::get_trace_id(env),
+    );
+}
+
+fn emit_arbitration_result(env: &Env, account: Address, winner: SettlementPath, loser_reason: u32) {
+    let event = ArbitrationResult {
+        winner,
+        loser_reason,
+    };
+    trace::publish(
+        env,
+        (soroban_sdk::symbol_short!("arbitrate"), account),
+        event,
+        storage::get_trace_id(env),
+    );
+}
+
+fn emit_creator_notified(env: &Env, account: Address, creator: Address, delivered: bool) {
+    if storage::are_events_compacted(env) {
+        return;
+    }
+    let event = CreatorNotified {
         ephemeral_account: account,
-        destination,
-        amount,
+        creator,
+        delivered,
+    };
+    trace::publish(
+        env,
+        (soroban_sdk::symbol_short!("notified"),),
+        event,
+        storage::get_trace_id(env),
+    );
+}
+
+/// Feature flags updated event (emitted by `set_features`)
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FeaturesUpdated {
+    pub old_features: u32,
+    pub new_features: u32,
+}
+
+fn emit_features_updated(env: &Env, old_features: u32, new_features: u32) {
+    let event = FeaturesUpdated {
+        old_features,
+        new_features,
     };
     env.events()
-        .publish((soroban_sdk::symbol_short!("sweep"),), event);
+        .publish((soroban_sdk::symbol_short!("features"),), event);
 }
 
 fn emit_destination_authorized(env: &Env, destination: Address) {
@@ -0,0 +1,56 @@
+use crate::adapter::SweepAuthorization;
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::EphemeralAccountClient;
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Vec};
+
+/// Sweeps authorized by the account's own signer set.
+#[contractimpl]
+impl SweepController {
+    /// Execute a sweep authorized by the ephemeral account's own signer
+    /// set, for accounts initialized with `sweep_signers`.
+    ///
+    /// `signatures` pairs each signer's public key with its Ed25519
+    /// signature over `account_sweep_message_digest(network_id, account,
+    /// account_nonce, destination, contract_id, destination_memo)`, the
+    /// message `execute_account_sweep` uses. The account checks them against
+    /// its threshold in `prepare_multisig_sweep`; this controller's signer
+    /// and nonce take no part.
+    ///
+    /// # Errors
+    /// Returns Error::InvalidAccount if the account predates multisig sweeps (interface version 4)
+    /// Returns Error::UnauthorizedDestination if destination doesn't match authorized destination (when set)
+    /// Returns Error::AccountNotReady if the account holds no payments
+    /// Returns Error::TransferFailed if token transfer fails
+    pub fn execute_multisig_sweep(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        signatures: Vec<(BytesN<32>, BytesN<64>)>,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        Self::validate_destination(&env, &destination)?;
+
+        match EphemeralAccountClient::new(&env, &ephemeral_account).try_interface_version() {
+            Ok(Ok(version)) if version >= 4 => {}
+            _ => return Err(Error::InvalidAccount),
+        }
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
+
+        Self::sweep_account(
+            &env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signers(signatures),
+            destination_memo,
+            false,
+        )
+        .map(|_| ())
+    }
+}
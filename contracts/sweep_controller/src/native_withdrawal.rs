@@ -0,0 +1,57 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token::TokenClient, Address, Env};
+
+/// Native XLM held by the controller.
+#[contractimpl]
+impl SweepController {
+    /// Native XLM held by the controller itself, e.g. fee revenue or
+    /// reclaimed reserves sent to its address.
+    pub fn get_native_balance(env: Env) -> i128 {
+        storage::extend_instance_ttl(&env);
+
+        TokenClient::new(&env, &bridgelet_shared::native_asset_address(&env))
+            .balance(&env.current_contract_address())
+    }
+
+    /// Withdraw native XLM held by the controller, so funds sent to its
+    /// address never become unreachable.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if the creator did not authorize the call
+    /// Returns Error::InvalidAmount if amount is not positive
+    /// Returns Error::InsufficientBalance if the controller holds less than amount
+    pub fn withdraw_native(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let native = TokenClient::new(&env, &bridgelet_shared::native_asset_address(&env));
+        let controller = env.current_contract_address();
+        if native.balance(&controller) < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        native.transfer(&controller, &to, &amount);
+        emit_native_withdrawn(&env, to, amount);
+        Ok(())
+    }
+}
+
+/// Native XLM withdrawal event (emitted by `withdraw_native`)
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct NativeWithdrawn {
+    pub to: Address,
+    pub amount: i128,
+}
+
+fn emit_native_withdrawn(env: &Env, to: Address, amount: i128) {
+    let event = NativeWithdrawn { to, amount };
+    env.events().publish((symbol_short!("native_wd"),), event);
+}
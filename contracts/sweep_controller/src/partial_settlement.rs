@@ -0,0 +1,152 @@
+use crate::adapter::AccountAdapter;
+use crate::authorization::{self, AuthContext};
+use crate::errors::Error;
+use crate::storage::{self, PendingSettlement};
+use crate::transfers;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::{trace, EphemeralAccountClient, Payment};
+use soroban_sdk::{
+    contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Settlement of what a partial sweep left behind.
+#[contractimpl]
+impl SweepController {
+    /// Deliver the assets a partial settlement left behind, once the
+    /// destination can receive them (e.g. after adding trustlines).
+    ///
+    /// The signature is the one `execute_sweep` expects for the original
+    /// destination, at the current nonce and without a memo. Assets that
+    /// still cannot be delivered stay pending for another call; when none
+    /// remain the account returns to `Swept`.
+    ///
+    /// # Errors
+    /// Returns Error::NothingToSweep if the account has no pending assets
+    /// Returns Error::TransferFailed if none of the pending assets can be delivered
+    pub fn sweep_remaining(
+        env: Env,
+        ephemeral_account: Address,
+        auth_signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let pending = storage::get_pending_settlement(&env, &ephemeral_account)
+            .ok_or(Error::NothingToSweep)?;
+
+        AuthContext::new(
+            ephemeral_account.clone(),
+            pending.destination.clone(),
+            auth_signature,
+            None,
+            None,
+        )
+        .verify(&env)?;
+        authorization::increment_nonce(&env);
+        storage::extend_account_ttl(&env, &ephemeral_account);
+
+        let undelivered = transfers::try_execute_transfers(
+            &env,
+            AccountAdapter::resolve(&env, &ephemeral_account),
+            &ephemeral_account,
+            &pending.destination,
+            &pending.remaining,
+            &None,
+        );
+        if undelivered.len() == pending.remaining.len() {
+            return Err(Error::TransferFailed);
+        }
+
+        if undelivered.is_empty() {
+            storage::remove_pending_settlement(&env, &ephemeral_account);
+            EphemeralAccountClient::new(&env, &ephemeral_account).complete_partial_sweep();
+        } else {
+            Self::keep_pending(&env, &ephemeral_account, &pending.destination, &undelivered);
+        }
+
+        Self::complete_settlement(
+            &env,
+            &ephemeral_account,
+            &pending.destination,
+            pending.remaining,
+            &undelivered,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// Assets of a partially settled account still owed to its destination.
+    pub fn get_pending_settlement(
+        env: Env,
+        ephemeral_account: Address,
+    ) -> Option<PendingSettlement> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_pending_settlement(&env, &ephemeral_account)
+    }
+
+    /// Record `undelivered` as still owed to `destination`: on the account,
+    /// in the pending settlement, and in a `PartialSettlement` event.
+    pub(crate) fn keep_pending(
+        env: &Env,
+        ephemeral_account: &Address,
+        destination: &Address,
+        undelivered: &Vec<Payment>,
+    ) {
+        Self::report_undelivered(env, ephemeral_account, undelivered);
+        storage::set_pending_settlement(
+            env,
+            ephemeral_account,
+            &PendingSettlement {
+                destination: destination.clone(),
+                remaining: undelivered.clone(),
+            },
+        );
+        emit_partial_settlement(
+            env,
+            ephemeral_account.clone(),
+            destination.clone(),
+            undelivered.clone(),
+        );
+    }
+
+    /// Best-effort `record_undelivered` on the account so its settled
+    /// amounts exclude what a partial settlement still owes. Accounts
+    /// deployed before settled-amount tracking skip it.
+    fn report_undelivered(env: &Env, ephemeral_account: &Address, undelivered: &Vec<Payment>) {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            ephemeral_account,
+            &Symbol::new(env, "record_undelivered"),
+            (undelivered.clone(),).into_val(env),
+        );
+    }
+}
+
+/// Partial settlement event (emitted when a sweep leaves assets the
+/// destination could not receive yet)
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct PartialSettlement {
+    pub ephemeral_account: Address,
+    pub destination: Address,
+    pub remaining: Vec<Payment>,
+}
+
+fn emit_partial_settlement(
+    env: &Env,
+    account: Address,
+    destination: Address,
+    remaining: Vec<Payment>,
+) {
+    let event = PartialSettlement {
+        ephemeral_account: account,
+        destination,
+        remaining,
+    };
+    trace::publish(
+        env,
+        (symbol_short!("partial"),),
+        event,
+        storage::get_trace_id(env),
+    );
+}
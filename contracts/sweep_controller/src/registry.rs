@@ -0,0 +1,36 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, Env, IntoVal, Symbol};
+
+/// Account registry notified after settlements.
+#[contractimpl]
+impl SweepController {
+    /// Set the account registry (e.g. the account factory) whose
+    /// `sync_account` hook is called after every settlement.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    pub fn set_registry(env: Env, registry: Address) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        storage::set_registry(&env, &registry);
+        Ok(())
+    }
+
+    /// Best-effort `sync_account(account)` hook on the configured registry
+    /// so per-creator statistics stay current. A failing registry never
+    /// fails the sweep.
+    pub(crate) fn notify_registry(env: &Env, ephemeral_account: &Address) {
+        if let Some(registry) = storage::get_registry(env) {
+            let _ = env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "sync_account"),
+                (ephemeral_account.clone(),).into_val(env),
+            );
+        }
+    }
+}
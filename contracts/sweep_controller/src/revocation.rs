@@ -0,0 +1,45 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, contracttype, symbol_short, BytesN, Env};
+
+/// Revocation of single sweep authorizations.
+#[contractimpl]
+impl SweepController {
+    /// Revoke one signed sweep authorization by its message digest
+    /// (`sha256(destination || nonce || contract_id [|| memo])`).
+    ///
+    /// Unlike advancing the nonce, this only invalidates the given
+    /// authorization; other in-flight authorizations stay valid.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    pub fn revoke_authorization_digest(env: Env, digest: BytesN<32>) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        storage::revoke_digest(&env, &digest);
+        emit_authorization_revoked(&env, digest);
+        Ok(())
+    }
+
+    /// Whether a sweep authorization digest has been revoked.
+    pub fn is_authorization_revoked(env: Env, digest: BytesN<32>) -> bool {
+        storage::extend_instance_ttl(&env);
+
+        storage::is_digest_revoked(&env, &digest)
+    }
+}
+
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationRevoked {
+    pub digest: BytesN<32>,
+}
+
+fn emit_authorization_revoked(env: &Env, digest: BytesN<32>) {
+    let event = AuthorizationRevoked { digest };
+    env.events().publish((symbol_short!("revoked"),), event);
+}
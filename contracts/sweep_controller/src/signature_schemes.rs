@@ -0,0 +1,66 @@
+use crate::authorization::SignatureScheme;
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, Bytes, BytesN, Env};
+
+/// Authorized signers of other schemes than Ed25519.
+#[contractimpl]
+impl SweepController {
+    /// Same as `initialize`, with an authorized signer of the given
+    /// signature scheme.
+    ///
+    /// # Arguments
+    /// * `scheme` - How the authorized signer signs sweep authorizations
+    /// * `authorized_signer` - Public key: 32 bytes for Ed25519, a 33-byte
+    ///   SEC1 compressed key for secp256k1, a 65-byte uncompressed key for
+    ///   a secp256r1 passkey
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if called more than once
+    /// Returns Error::InvalidSignerKey if the key does not fit the scheme
+    pub fn initialize_with_scheme(
+        env: Env,
+        creator: Address,
+        scheme: SignatureScheme,
+        authorized_signer: Bytes,
+        authorized_destination: Option<Address>,
+    ) -> Result<(), Error> {
+        Self::init(&env, &creator, authorized_destination)?;
+
+        // Store the authorized signer public key
+        match scheme {
+            SignatureScheme::Ed25519 => {
+                let key = BytesN::<32>::try_from(authorized_signer)
+                    .map_err(|_| Error::InvalidSignerKey)?;
+                storage::set_authorized_signer(&env, &key);
+            }
+            SignatureScheme::Secp256k1 => {
+                let key = BytesN::<33>::try_from(authorized_signer)
+                    .map_err(|_| Error::InvalidSignerKey)?;
+                if !matches!(key.get(0), Some(0x02 | 0x03)) {
+                    return Err(Error::InvalidSignerKey);
+                }
+                storage::set_secp256k1_signer(&env, &key);
+            }
+            SignatureScheme::Secp256r1 => {
+                let key = BytesN::<65>::try_from(authorized_signer)
+                    .map_err(|_| Error::InvalidSignerKey)?;
+                if key.get(0) != Some(0x04) {
+                    return Err(Error::InvalidSignerKey);
+                }
+                storage::set_secp256r1_signer(&env, &key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Signature scheme the authorized signer signs sweep authorizations
+    /// with, as chosen at initialization.
+    pub fn get_signature_scheme(env: Env) -> SignatureScheme {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_signature_scheme(&env)
+    }
+}
@@ -1,7 +1,14 @@
+use crate::authorization::AccountSigner;
+use crate::authorization::SignatureScheme;
+use crate::tenants::TenantConfig;
+use bridgelet_shared::ContractVersion;
+use bridgelet_shared::Payment;
+use bridgelet_shared::SweepReceipt;
+use soroban_sdk::Vec;
 use soroban_sdk::{contracttype, Address, BytesN, Env};
 
 /// Data keys for contract storage
-#[contracttype]
+#[contracttype(export = false)]
 #[derive(Clone)]
 pub enum DataKey {
     /// Authorized signer public key (BytesN<32> for Ed25519)
@@ -12,6 +19,59 @@ pub enum DataKey {
     AuthorizedDestination,
     /// Creator address (the address that initialized the contract)
     Creator,
+    /// Enabled optional behaviors (`FeatureFlags` bitfield)
+    Features,
+    /// Undelivered assets of a partially settled account (persistent)
+    PartialSettlement(Address),
+    /// Account registry notified after each settlement (optional)
+    Registry,
+    /// Revoked sweep authorization digest (persistent)
+    RevokedDigest(BytesN<32>),
+    /// Trace id of the sweep being handled (temporary)
+    TraceId,
+    /// Configuration of a tenant sharing this controller (persistent)
+    Tenant(u32),
+    /// Sweep nonce of a tenant (persistent)
+    TenantNonce(u32),
+    /// Tenant an ephemeral account was assigned to (persistent)
+    AccountTenant(Address),
+    /// Receipt of a sweep made with an idempotency key (persistent)
+    SweepRecord(BytesN<32>),
+    /// Ledgers reserved for a sweep to land before an account expires
+    SubmissionMargin,
+    /// Batch size above which batch sweeps emit one summary event
+    BatchCompactionThreshold,
+    /// Whether per-account sweep events are suppressed (temporary)
+    CompactEvents,
+    /// Receipts of a compacted batch sweep, by batch digest (persistent)
+    BatchReceipts(BytesN<32>),
+    /// Version of the build that initialized the controller
+    Version,
+    /// Scheme of the authorized signer's signatures (Ed25519 when absent)
+    SignatureScheme,
+    /// Authorized signer compressed public key (BytesN<33> for secp256k1)
+    Secp256k1Signer,
+    /// Authorized passkey public key (BytesN<65> uncompressed secp256r1)
+    Secp256r1Signer,
+    /// Signer of an ephemeral account's sweeps, overriding the
+    /// authorized signer (persistent)
+    AccountSigner(Address),
+}
+
+/// Assets of a partially settled account still owed to its destination
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingSettlement {
+    pub destination: Address,
+    pub remaining: Vec<Payment>,
+}
+
+/// Outcome of a sweep made with an idempotency key
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SweepRecord {
+    pub ephemeral_account: Address,
+    pub receipt: SweepReceipt,
 }
 
 /// Set the authorized signer public key
@@ -36,6 +96,62 @@ pub fn get_authorized_signer(env: &Env) -> Option<BytesN<32>> {
     env.storage().instance().get(&DataKey::AuthorizedSigner)
 }
 
+/// Set the authorized signer's secp256k1 public key, selecting the
+/// secp256k1 signature scheme
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `signer` - SEC1 compressed secp256k1 public key (33 bytes)
+pub fn set_secp256k1_signer(env: &Env, signer: &BytesN<33>) {
+    let instance = env.storage().instance();
+    instance.set(&DataKey::Secp256k1Signer, signer);
+    instance.set(&DataKey::SignatureScheme, &SignatureScheme::Secp256k1);
+}
+
+/// Get the authorized signer's secp256k1 public key
+///
+/// # Returns
+/// The compressed public key, or None if the signer uses Ed25519
+pub fn get_secp256k1_signer(env: &Env) -> Option<BytesN<33>> {
+    env.storage().instance().get(&DataKey::Secp256k1Signer)
+}
+
+/// Set the authorized signer's passkey public key, selecting the
+/// secp256r1 (WebAuthn) signature scheme
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `signer` - SEC1 uncompressed secp256r1 public key (65 bytes)
+pub fn set_secp256r1_signer(env: &Env, signer: &BytesN<65>) {
+    let instance = env.storage().instance();
+    instance.set(&DataKey::Secp256r1Signer, signer);
+    instance.set(&DataKey::SignatureScheme, &SignatureScheme::Secp256r1);
+}
+
+/// Get the authorized signer's passkey public key
+///
+/// # Returns
+/// The uncompressed public key, or None if the signer is not a passkey
+pub fn get_secp256r1_signer(env: &Env) -> Option<BytesN<65>> {
+    env.storage().instance().get(&DataKey::Secp256r1Signer)
+}
+
+/// Get the scheme the authorized signer's signatures use
+pub fn get_signature_scheme(env: &Env) -> SignatureScheme {
+    env.storage()
+        .instance()
+        .get(&DataKey::SignatureScheme)
+        .unwrap_or(SignatureScheme::Ed25519)
+}
+
+/// Whether an authorized signer of either scheme is set
+pub fn has_authorized_signer(env: &Env) -> bool {
+    let instance = env.storage().instance();
+    instance.has(&DataKey::AuthorizedSigner)
+        || instance.has(&DataKey::Secp256k1Signer)
+        || instance.has(&DataKey::Secp256r1Signer)
+}
+
 /// Initialize the sweep nonce to 0
 ///
 /// # Arguments
@@ -126,6 +242,407 @@ pub fn get_creator(env: &Env) -> Option<Address> {
     env.storage().instance().get(&DataKey::Creator)
 }
 
+/// Set the version of the build that initialized the controller
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `version` - Version of the running build
+pub fn set_version(env: &Env, version: &ContractVersion) {
+    env.storage().instance().set(&DataKey::Version, version);
+}
+
+/// Get the version of the build that initialized the controller
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The stored version, or None for controllers initialized before versions were stored
+pub fn get_version(env: &Env) -> Option<ContractVersion> {
+    env.storage().instance().get(&DataKey::Version)
+}
+
+/// Set the enabled feature flags
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `features` - `FeatureFlags` bitfield
+pub fn set_features(env: &Env, features: u32) {
+    env.storage().instance().set(&DataKey::Features, &features);
+}
+
+/// Get the enabled feature flags
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The `FeatureFlags` bitfield, or 0 (all disabled) if never set
+pub fn get_features(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Features)
+        .unwrap_or(0)
+}
+
+/// Set the submission margin used by `sweepable_until`
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `ledgers` - Ledgers a submitted sweep may take to land
+pub fn set_submission_margin(env: &Env, ledgers: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SubmissionMargin, &ledgers);
+}
+
+/// Get the submission margin
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The margin in ledgers, or 0 if never set
+pub fn get_submission_margin(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SubmissionMargin)
+        .unwrap_or(0)
+}
+
+/// Set the account registry notified after settlements
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `registry` - Registry contract address (e.g. the account factory)
+pub fn set_registry(env: &Env, registry: &Address) {
+    env.storage().instance().set(&DataKey::Registry, registry);
+}
+
+/// Get the account registry
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The registry address, or None if no registry is configured
+pub fn get_registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Registry)
+}
+
+/// Set or clear the trace id stamped into events of the sweep being handled
+///
+/// Kept in temporary storage: it only needs to live for the transaction
+/// that sets it.
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `trace_id` - Trace id, or None to stop stamping
+pub fn set_trace_id(env: &Env, trace_id: Option<&BytesN<16>>) {
+    match trace_id {
+        Some(trace_id) => env.storage().temporary().set(&DataKey::TraceId, trace_id),
+        None => env.storage().temporary().remove(&DataKey::TraceId),
+    }
+}
+
+/// Get the trace id of the sweep being handled
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The trace id, or None outside a traced sweep
+pub fn get_trace_id(env: &Env) -> Option<BytesN<16>> {
+    env.storage().temporary().get(&DataKey::TraceId)
+}
+
+/// Set or clear the batch size above which batch sweeps are compacted
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `threshold` - Largest batch still emitting per-account events, or None to never compact
+pub fn set_batch_compaction_threshold(env: &Env, threshold: Option<u32>) {
+    match threshold {
+        Some(threshold) => env
+            .storage()
+            .instance()
+            .set(&DataKey::BatchCompactionThreshold, &threshold),
+        None => env
+            .storage()
+            .instance()
+            .remove(&DataKey::BatchCompactionThreshold),
+    }
+}
+
+/// Get the batch compaction threshold
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// The threshold, or None if batches are never compacted
+pub fn get_batch_compaction_threshold(env: &Env) -> Option<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::BatchCompactionThreshold)
+}
+
+/// Suppress or restore per-account sweep events for the rest of the
+/// transaction
+///
+/// Kept in temporary storage, like the trace id: it only needs to live for
+/// the batch that sets it.
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `compact` - Whether per-account events are suppressed
+pub fn set_events_compacted(env: &Env, compact: bool) {
+    if compact {
+        env.storage()
+            .temporary()
+            .set(&DataKey::CompactEvents, &true);
+    } else {
+        env.storage().temporary().remove(&DataKey::CompactEvents);
+    }
+}
+
+/// Whether per-account sweep events are suppressed
+///
+/// # Arguments
+/// * `env` - Soroban environment
+///
+/// # Returns
+/// True while a compacted batch sweep is running
+pub fn are_events_compacted(env: &Env) -> bool {
+    env.storage().temporary().has(&DataKey::CompactEvents)
+}
+
+/// Store the receipts of a compacted batch sweep
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `batch_digest` - Digest of the batch requests
+/// * `records` - Receipt of each account the batch swept
+pub fn set_batch_receipts(env: &Env, batch_digest: &BytesN<32>, records: &Vec<SweepRecord>) {
+    let key = DataKey::BatchReceipts(batch_digest.clone());
+    env.storage().persistent().set(&key, records);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get the receipts of a compacted batch sweep
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `batch_digest` - Digest of the batch requests
+///
+/// # Returns
+/// The receipts, or None if no compacted batch has this digest
+pub fn get_batch_receipts(env: &Env, batch_digest: &BytesN<32>) -> Option<Vec<SweepRecord>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BatchReceipts(batch_digest.clone()))
+}
+
+/// Store the undelivered assets of a partially settled account
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+/// * `pending` - Destination and payments still owed
+pub fn set_pending_settlement(env: &Env, account: &Address, pending: &PendingSettlement) {
+    let key = DataKey::PartialSettlement(account.clone());
+    env.storage().persistent().set(&key, pending);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get the undelivered assets of a partially settled account
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+///
+/// # Returns
+/// The pending settlement, or None if the account has nothing outstanding
+pub fn get_pending_settlement(env: &Env, account: &Address) -> Option<PendingSettlement> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PartialSettlement(account.clone()))
+}
+
+/// Remove the pending settlement once every asset has been delivered
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+pub fn remove_pending_settlement(env: &Env, account: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PartialSettlement(account.clone()));
+}
+
+/// Revoke a signed sweep authorization by its message digest
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `digest` - Digest returned by `construct_sweep_message`
+pub fn revoke_digest(env: &Env, digest: &BytesN<32>) {
+    let key = DataKey::RevokedDigest(digest.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Check whether a sweep authorization digest has been revoked
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `digest` - Message digest to check
+pub fn is_digest_revoked(env: &Env, digest: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::RevokedDigest(digest.clone()))
+}
+
+/// Store the outcome of a sweep made with an idempotency key
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `key` - Idempotency key supplied by the caller
+/// * `record` - Swept account and its receipt
+pub fn set_sweep_record(env: &Env, key: &BytesN<32>, record: &SweepRecord) {
+    let key = DataKey::SweepRecord(key.clone());
+    env.storage().persistent().set(&key, record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get the outcome of a sweep made with an idempotency key
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `key` - Idempotency key supplied by the caller
+///
+/// # Returns
+/// The stored record, or None if no sweep succeeded under this key
+pub fn get_sweep_record(env: &Env, key: &BytesN<32>) -> Option<SweepRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SweepRecord(key.clone()))
+}
+
+/// Store a tenant's configuration
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `tenant_id` - Tenant identifier
+/// * `config` - Signers, threshold, destination lock and fee of the tenant
+pub fn set_tenant(env: &Env, tenant_id: u32, config: &TenantConfig) {
+    let key = DataKey::Tenant(tenant_id);
+    env.storage().persistent().set(&key, config);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get a tenant's configuration
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `tenant_id` - Tenant identifier
+///
+/// # Returns
+/// The tenant configuration, or None if the tenant is not registered
+pub fn get_tenant(env: &Env, tenant_id: u32) -> Option<TenantConfig> {
+    env.storage().persistent().get(&DataKey::Tenant(tenant_id))
+}
+
+/// Get a tenant's sweep nonce
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `tenant_id` - Tenant identifier
+///
+/// # Returns
+/// The tenant's nonce, 0 before its first sweep
+pub fn get_tenant_nonce(env: &Env, tenant_id: u32) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TenantNonce(tenant_id))
+        .unwrap_or(0)
+}
+
+/// Increment a tenant's sweep nonce
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `tenant_id` - Tenant identifier
+pub fn increment_tenant_nonce(env: &Env, tenant_id: u32) {
+    let key = DataKey::TenantNonce(tenant_id);
+    let nonce = get_tenant_nonce(env, tenant_id);
+    env.storage().persistent().set(&key, &(nonce + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Assign an ephemeral account to a tenant
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+/// * `tenant_id` - Tenant whose signers may sweep the account
+pub fn set_account_tenant(env: &Env, account: &Address, tenant_id: u32) {
+    let key = DataKey::AccountTenant(account.clone());
+    env.storage().persistent().set(&key, &tenant_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get the tenant an ephemeral account was assigned to
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+///
+/// # Returns
+/// The tenant id, or None if the account is not assigned
+pub fn get_account_tenant(env: &Env, account: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccountTenant(account.clone()))
+}
+
+/// Set the signer an ephemeral account's sweeps are verified against
+///
+/// # Arguments
+/// * `env` - Soroban environment
+/// * `account` - Ephemeral account address
+/// * `signer` - Scheme and public key of the account's signer
+pub fn set_account_signer(env: &Env, account: &Address, signer: &AccountSigner) {
+    let key = DataKey::AccountSigner(account.clone());
+    env.storage().persistent().set(&key, signer);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
+}
+
+/// Get the signer an ephemeral account's sweeps are verified against
+///
+/// # Returns
+/// The account's signer, or None if it uses the authorized signer
+pub fn get_account_signer(env: &Env, account: &Address) -> Option<AccountSigner> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AccountSigner(account.clone()))
+}
+
 const INSTANCE_TTL_THRESHOLD: u32 = 100;
 const INSTANCE_TTL_EXTEND_TO: u32 = 518_400;
 
@@ -134,3 +651,23 @@ pub fn extend_instance_ttl(env: &Env) {
         .instance()
         .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_EXTEND_TO);
 }
+
+/// An account whose instance lives for fewer ledgers than this is extended
+/// before it is swept (about one day).
+const ACCOUNT_TTL_THRESHOLD: u32 = 17_280;
+
+/// Extend an ephemeral account's instance and code before settling it, so
+/// a settlement cannot leave the account archived with partially settled
+/// state. The account's own per-call extension only kicks in a few ledgers
+/// before archival, too late for a settlement that spans transactions.
+///
+/// # Arguments
+/// * `env` - The contract environment
+/// * `account` - Ephemeral account address
+pub fn extend_account_ttl(env: &Env, account: &Address) {
+    env.deployer().extend_ttl(
+        account.clone(),
+        ACCOUNT_TTL_THRESHOLD,
+        INSTANCE_TTL_EXTEND_TO,
+    );
+}
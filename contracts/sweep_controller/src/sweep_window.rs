@@ -0,0 +1,43 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, Env};
+
+/// Sweep deadlines that leave room for submission.
+#[contractimpl]
+impl SweepController {
+    /// Last ledger at which a sweep of `ephemeral_account` can be submitted
+    /// and still land before the account expires: the ledger before expiry,
+    /// minus the submission margin set with `set_submission_margin`.
+    ///
+    /// # Returns
+    /// The deadline, or None if the account holds no payment to sweep or
+    /// expires too soon for any sweep to land
+    pub fn sweepable_until(env: Env, ephemeral_account: Address) -> Option<u32> {
+        storage::extend_instance_ttl(&env);
+
+        Self::sweep_deadline(&env, &ephemeral_account)
+    }
+
+    /// Set how many ledgers a submitted sweep may take to land. Bots stop
+    /// treating an account as sweepable that many ledgers before expiry.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    pub fn set_submission_margin(env: Env, ledgers: u32) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        storage::set_submission_margin(&env, ledgers);
+        Ok(())
+    }
+
+    /// Ledgers reserved for a sweep to land before expiry. 0 until set.
+    pub fn get_submission_margin(env: Env) -> u32 {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_submission_margin(&env)
+    }
+}
@@ -0,0 +1,323 @@
+use crate::adapter::{account_info, SweepAuthorization};
+use crate::errors::Error;
+use crate::storage;
+use crate::transfers::Fee;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use bridgelet_shared::Payment;
+use soroban_sdk::{contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, Vec};
+
+/// Largest number of signers a tenant may register.
+pub const MAX_TENANT_SIGNERS: u32 = 10;
+
+/// Fees are expressed in basis points of each swept amount. A fee stays
+/// below the whole amount, so a sweep always delivers something.
+pub const FULL_BPS: u32 = 10_000;
+
+/// Configuration of one tenant sharing a controller.
+///
+/// A platform operator registers one tenant per customer instead of
+/// deploying a controller per customer. Each tenant brings its own signers,
+/// destination lock and fee, and is administered by its own `admin`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TenantConfig {
+    /// Address allowed to update this configuration
+    pub admin: Address,
+    /// Ed25519 public keys allowed to authorize the tenant's sweeps
+    pub signers: Vec<BytesN<32>>,
+    /// Number of distinct signers that must sign each sweep
+    pub threshold: u32,
+    /// If set, the tenant's sweeps can only go to this address
+    pub authorized_destination: Option<Address>,
+    /// Fee deducted from each swept asset, in basis points
+    pub fee_bps: u32,
+    /// Receives the fee; required when `fee_bps` is non-zero
+    pub fee_recipient: Option<Address>,
+}
+
+impl TenantConfig {
+    /// Check the configuration is usable.
+    ///
+    /// # Errors
+    /// Returns Error::InvalidTenantConfig if the signer set is empty or too
+    /// large, the threshold is out of range, or the fee is invalid
+    pub fn validate(&self) -> Result<(), Error> {
+        let signer_count = self.signers.len();
+        if signer_count == 0 || signer_count > MAX_TENANT_SIGNERS {
+            return Err(Error::InvalidTenantConfig);
+        }
+        if self.threshold == 0 || self.threshold > signer_count {
+            return Err(Error::InvalidTenantConfig);
+        }
+        if self.fee_bps >= FULL_BPS || (self.fee_bps > 0 && self.fee_recipient.is_none()) {
+            return Err(Error::InvalidTenantConfig);
+        }
+        Ok(())
+    }
+
+    /// The fee to deduct from the tenant's sweeps, if any.
+    pub fn fee(&self) -> Option<Fee> {
+        match &self.fee_recipient {
+            Some(recipient) if self.fee_bps > 0 => Some(Fee {
+                recipient: recipient.clone(),
+                bps: self.fee_bps,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Verify a tenant sweep authorization.
+///
+/// `signatures` maps an index into `config.signers` to that signer's
+/// Ed25519 signature over the tenant sweep message, so each signer counts
+/// once. At least `config.threshold` signatures are required.
+///
+/// # Errors
+/// Returns Error::InsufficientSignatures if fewer than `threshold` signatures are given
+/// Returns Error::AuthorizationFailed if a signature names an unknown signer
+/// Returns Error::AuthorizationRevoked if the message digest was revoked
+pub fn verify_tenant_sweep_auth(
+    env: &Env,
+    tenant_id: u32,
+    config: &TenantConfig,
+    account: &Address,
+    destination: &Address,
+    signatures: &Map<u32, BytesN<64>>,
+    destination_memo: &Option<BytesN<32>>,
+) -> Result<(), Error> {
+    if signatures.len() < config.threshold {
+        return Err(Error::InsufficientSignatures);
+    }
+
+    let message = bridgelet_shared::bytes::tenant_sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        tenant_id,
+        account,
+        destination,
+        storage::get_tenant_nonce(env, tenant_id),
+        &env.current_contract_address(),
+        destination_memo.as_ref(),
+    );
+    if storage::is_digest_revoked(env, &message) {
+        return Err(Error::AuthorizationRevoked);
+    }
+
+    for (index, signature) in signatures.iter() {
+        let signer = config
+            .signers
+            .get(index)
+            .ok_or(Error::AuthorizationFailed)?;
+        env.crypto()
+            .ed25519_verify(&signer, &message.clone().into(), &signature);
+    }
+    Ok(())
+}
+
+/// Split each payment into the fee share and the share left for the
+/// destination. Fees round down, so dust always goes to the destination.
+///
+/// # Returns
+/// `(net, fees)`; assets whose fee rounds to zero are omitted from `fees`
+///
+/// # Errors
+/// Returns Error::Overflow if a fee does not fit an `i128`
+pub fn split_fee(
+    env: &Env,
+    payments: &Vec<Payment>,
+    bps: u32,
+) -> Result<(Vec<Payment>, Vec<Payment>), Error> {
+    let mut net = Vec::new(env);
+    let mut fees = Vec::new(env);
+    for payment in payments.iter() {
+        let fee = payment
+            .amount
+            .checked_mul(bps as i128)
+            .ok_or(Error::Overflow)?
+            / FULL_BPS as i128;
+        if fee > 0 {
+            fees.push_back(Payment {
+                amount: fee,
+                ..payment.clone()
+            });
+        }
+        net.push_back(Payment {
+            amount: payment.amount.checked_sub(fee).ok_or(Error::Overflow)?,
+            ..payment
+        });
+    }
+    Ok((net, fees))
+}
+
+/// Tenant configuration event (emitted by `register_tenant` and `update_tenant`)
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct TenantUpdated {
+    pub tenant_id: u32,
+    pub config: TenantConfig,
+}
+
+fn emit_tenant_updated(env: &Env, tenant_id: u32, config: TenantConfig) {
+    let event = TenantUpdated { tenant_id, config };
+    env.events().publish((symbol_short!("tenant"),), event);
+}
+
+/// Tenants sharing this controller.
+#[contractimpl]
+impl SweepController {
+    /// Register a tenant sharing this controller, with its own signers,
+    /// destination lock and fee. Lets a platform operator serve many
+    /// customers from one deployment.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizationFailed if caller is not the creator
+    /// Returns Error::TenantAlreadyExists if `tenant_id` is taken
+    /// Returns Error::InvalidTenantConfig if the configuration is unusable
+    pub fn register_tenant(env: Env, tenant_id: u32, config: TenantConfig) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let creator = storage::get_creator(&env).ok_or(Error::AuthorizationFailed)?;
+        creator.require_auth();
+
+        if storage::get_tenant(&env, tenant_id).is_some() {
+            return Err(Error::TenantAlreadyExists);
+        }
+        config.validate()?;
+
+        storage::set_tenant(&env, tenant_id, &config);
+        emit_tenant_updated(&env, tenant_id, config);
+        Ok(())
+    }
+
+    /// Replace a tenant's configuration. Only the tenant's current admin may
+    /// call it; the new configuration may name a new admin.
+    ///
+    /// # Errors
+    /// Returns Error::TenantNotFound if the tenant is not registered
+    /// Returns Error::InvalidTenantConfig if the configuration is unusable
+    pub fn update_tenant(env: Env, tenant_id: u32, config: TenantConfig) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let current = storage::get_tenant(&env, tenant_id).ok_or(Error::TenantNotFound)?;
+        current.admin.require_auth();
+        config.validate()?;
+
+        storage::set_tenant(&env, tenant_id, &config);
+        emit_tenant_updated(&env, tenant_id, config);
+        Ok(())
+    }
+
+    /// Get a tenant's configuration
+    pub fn get_tenant(env: Env, tenant_id: u32) -> Option<TenantConfig> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_tenant(&env, tenant_id)
+    }
+
+    /// Get the nonce a tenant's next sweep message must carry
+    pub fn get_tenant_nonce(env: Env, tenant_id: u32) -> u64 {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_tenant_nonce(&env, tenant_id)
+    }
+
+    /// Place an ephemeral account under a tenant, so the tenant's signers
+    /// may sweep it with `execute_tenant_sweep`. The account's creator must
+    /// authorize the assignment, which cannot be changed afterwards.
+    ///
+    /// # Errors
+    /// Returns Error::TenantNotFound if the tenant is not registered
+    /// Returns Error::AuthorizationFailed if the account is already assigned
+    pub fn assign_account(
+        env: Env,
+        tenant_id: u32,
+        ephemeral_account: Address,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        if storage::get_tenant(&env, tenant_id).is_none() {
+            return Err(Error::TenantNotFound);
+        }
+        if storage::get_account_tenant(&env, &ephemeral_account).is_some() {
+            return Err(Error::AuthorizationFailed);
+        }
+
+        let creator = account_info(&env, &ephemeral_account).creator;
+        creator.require_auth();
+
+        storage::set_account_tenant(&env, &ephemeral_account, tenant_id);
+        Ok(())
+    }
+
+    /// Get the tenant an ephemeral account is assigned to
+    pub fn get_account_tenant(env: Env, ephemeral_account: Address) -> Option<u32> {
+        storage::extend_instance_ttl(&env);
+
+        storage::get_account_tenant(&env, &ephemeral_account)
+    }
+
+    /// Execute a sweep authorized by a tenant's signers.
+    ///
+    /// Each signer signs `tenant_sweep_message_digest(network_id, tenant_id,
+    /// ephemeral_account, destination, tenant_nonce, contract_id,
+    /// destination_memo)`;
+    /// `signatures` maps signer indexes to their signatures and must reach
+    /// the tenant's threshold. The tenant's fee, if any, is taken out of
+    /// each asset before delivery.
+    ///
+    /// # Errors
+    /// Returns Error::TenantNotFound if the tenant is not registered
+    /// Returns Error::InvalidAccount if the account is not assigned to the tenant, or
+    /// vests its funds while the tenant charges a fee
+    /// Returns Error::UnauthorizedDestination if destination doesn't match the tenant's destination (when set)
+    /// Returns Error::InsufficientSignatures if fewer than `threshold` signers signed
+    /// Returns Error::AccountNotReady if the account holds no payments
+    /// Returns Error::TransferFailed if token transfer fails
+    pub fn execute_tenant_sweep(
+        env: Env,
+        tenant_id: u32,
+        ephemeral_account: Address,
+        destination: Address,
+        signatures: Map<u32, BytesN<64>>,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        let config = storage::get_tenant(&env, tenant_id).ok_or(Error::TenantNotFound)?;
+        if storage::get_account_tenant(&env, &ephemeral_account) != Some(tenant_id) {
+            return Err(Error::InvalidAccount);
+        }
+        if let Some(authorized) = &config.authorized_destination {
+            if *authorized != destination {
+                return Err(Error::UnauthorizedDestination);
+            }
+        }
+
+        verify_tenant_sweep_auth(
+            &env,
+            tenant_id,
+            &config,
+            &ephemeral_account,
+            &destination,
+            &signatures,
+            &destination_memo,
+        )?;
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
+
+        storage::increment_tenant_nonce(&env, tenant_id);
+        Self::settle_account(
+            &env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signature(BytesN::from_array(&env, &[0u8; 64])),
+            destination_memo,
+            false,
+            config.fee(),
+        )
+        .map(|_| ())
+    }
+}
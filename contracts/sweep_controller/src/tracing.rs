@@ -0,0 +1,52 @@
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, Address, BytesN, Env, IntoVal, Symbol};
+
+/// Trace ids stamped into sweep events.
+#[contractimpl]
+impl SweepController {
+    /// Same as `execute_sweep`, tagged with an off-chain trace id.
+    ///
+    /// `trace_id` is appended as the last topic of every event the
+    /// controller and the ephemeral account emit while handling the sweep,
+    /// and carried in the `SweepReceipt` delivered to a creator contract, so
+    /// the SDK's logs can be joined with both contracts' events. It is not
+    /// part of the signed message.
+    ///
+    /// # Errors
+    /// Same as `execute_sweep`
+    pub fn execute_sweep_traced(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        auth_signature: BytesN<64>,
+        destination_memo: Option<BytesN<32>>,
+        trace_id: BytesN<16>,
+    ) -> Result<(), Error> {
+        storage::set_trace_id(&env, Some(&trace_id));
+        Self::set_account_trace_id(&env, &ephemeral_account, Some(trace_id));
+
+        let result = Self::execute_sweep(
+            env.clone(),
+            ephemeral_account.clone(),
+            destination,
+            auth_signature,
+            destination_memo,
+        );
+
+        Self::set_account_trace_id(&env, &ephemeral_account, None);
+        storage::set_trace_id(&env, None);
+        result
+    }
+
+    /// Best-effort `set_trace_id` on the account. Accounts deployed before
+    /// trace propagation emit their events untraced.
+    fn set_account_trace_id(env: &Env, ephemeral_account: &Address, trace_id: Option<BytesN<16>>) {
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            ephemeral_account,
+            &Symbol::new(env, "set_trace_id"),
+            (trace_id,).into_val(env),
+        );
+    }
+}
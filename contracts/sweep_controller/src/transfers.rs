@@ -1,34 +1,239 @@
+use crate::adapter::AccountAdapter;
 use crate::errors::Error;
-use bridgelet_shared::Payment;
+use crate::storage;
+use crate::tenants;
+use bridgelet_shared::{trace, EphemeralAccountClient, Payment};
 use soroban_sdk::token::TokenClient;
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Vec};
 
-/// Execute token transfers for all payments from the ephemeral account to the destination.
+/// Per-asset transfer event emitted by the controller, carrying the
+/// destination memo the SAC `transfer` event has no room for.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct SweepTransfer {
+    pub ephemeral_account: Address,
+    pub destination: Address,
+    pub amount: i128,
+    pub destination_memo: Option<BytesN<32>>,
+}
+
+/// Per-asset event emitted when partial settlement skips a transfer the
+/// token contract rejected (e.g. the destination has no trustline, or the
+/// token panicked). The asset is the second topic.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct TransferSkipped {
+    pub ephemeral_account: Address,
+    pub destination: Address,
+    pub amount: i128,
+}
+
+/// Per-asset event emitted when a tenant fee is taken out of a sweep.
+#[contracttype(export = false)]
+#[derive(Clone, Debug)]
+pub struct FeeCollected {
+    pub ephemeral_account: Address,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+/// Sum of the amounts of `payments`.
+///
+/// # Errors
+/// Returns `Error::Overflow` if the sum does not fit an `i128`
+pub fn total_amount(payments: &Vec<Payment>) -> Result<i128, Error> {
+    payments.iter().try_fold(0i128, |total, payment| {
+        total.checked_add(payment.amount).ok_or(Error::Overflow)
+    })
+}
+
+/// Fee taken out of each swept asset before delivery. Only tenants charge
+/// one.
+pub struct Fee {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// Transfer `fee` out of each payment to the fee recipient.
+///
+/// # Returns
+/// The payments left for the destination
 ///
-/// Iterates over each recorded payment and calls the SEP-41 token contract's
-/// `transfer()` function, moving funds from `from` to `destination`.
+/// # Errors
+/// Returns `Error::TransferFailed` if a fee transfer fails
+/// Returns `Error::Overflow` if a fee does not fit an `i128`
+pub fn collect_fee(
+    env: &Env,
+    adapter: AccountAdapter,
+    from: &Address,
+    fee: &Fee,
+    payments: &Vec<Payment>,
+) -> Result<Vec<Payment>, Error> {
+    let (net, fees) = tenants::split_fee(env, payments, fee.bps)?;
+    for payment in fees.iter() {
+        transfer(
+            env,
+            adapter,
+            &payment.asset,
+            from,
+            &fee.recipient,
+            payment.amount,
+        )?;
+
+        let event = FeeCollected {
+            ephemeral_account: from.clone(),
+            recipient: fee.recipient.clone(),
+            amount: payment.amount,
+        };
+        trace::publish(
+            env,
+            (symbol_short!("fee"), payment.asset.clone()),
+            event,
+            storage::get_trace_id(env),
+        );
+    }
+    Ok(net)
+}
+
+/// Execute token transfers for all payments from the ephemeral account to the destination.
 ///
-/// The ephemeral account must have already authorized this contract to transfer
-/// on its behalf — this is enforced by the Soroban auth model when `from.require_auth()`
-/// is satisfied by the ephemeral account's invocation context.
+/// Iterates over each recorded payment and moves it from `from` to
+/// `destination`. An account from interface version 5 makes the SEP-41
+/// `transfer()` itself, through its controller-only `deliver`; an older
+/// one is debited directly, which needs the account's own authorization.
 ///
 /// # Arguments
 /// * `env` - Soroban environment
+/// * `adapter` - Call shapes of the ephemeral account
 /// * `from` - Ephemeral account address (source of funds)
 /// * `destination` - Recipient wallet address
 /// * `payments` - All recorded payments to transfer
+/// * `destination_memo` - Optional memo echoed in each `SweepTransfer` event
 ///
 /// # Errors
 /// Returns `Error::TransferFailed` if any individual transfer fails
 pub fn execute_transfers(
     env: &Env,
+    adapter: AccountAdapter,
     from: &Address,
     destination: &Address,
     payments: &Vec<Payment>,
+    destination_memo: &Option<BytesN<32>>,
 ) -> Result<(), Error> {
     for payment in payments.iter() {
-        let token = TokenClient::new(env, &payment.asset);
-        token.transfer(from, destination, &payment.amount);
+        transfer(
+            env,
+            adapter,
+            &payment.asset,
+            from,
+            destination,
+            payment.amount,
+        )?;
+        emit_sweep_transfer(env, from, destination, &payment, destination_memo);
     }
     Ok(())
 }
+
+/// Attempt every transfer, keeping the ones the destination can receive.
+///
+/// A failed transfer (e.g. the destination has no trustline for the asset)
+/// is rolled back on its own, reported with a `TransferSkipped` event, and
+/// does not abort the others.
+///
+/// # Returns
+/// The payments that could not be delivered
+pub fn try_execute_transfers(
+    env: &Env,
+    adapter: AccountAdapter,
+    from: &Address,
+    destination: &Address,
+    payments: &Vec<Payment>,
+    destination_memo: &Option<BytesN<32>>,
+) -> Vec<Payment> {
+    let mut undelivered = Vec::new(env);
+    for payment in payments.iter() {
+        match transfer(
+            env,
+            adapter,
+            &payment.asset,
+            from,
+            destination,
+            payment.amount,
+        ) {
+            Ok(()) => emit_sweep_transfer(env, from, destination, &payment, destination_memo),
+            Err(_) => {
+                emit_transfer_skipped(env, from, destination, &payment);
+                undelivered.push_back(payment);
+            }
+        }
+    }
+    undelivered
+}
+
+/// Transfer `amount` of `asset` out of the account `from`, through the
+/// account's `try_deliver` or the token's `try_transfer`, so a token
+/// contract that errors or panics yields `Error::TransferFailed` instead of
+/// aborting the transaction with a host error.
+fn transfer(
+    env: &Env,
+    adapter: AccountAdapter,
+    asset: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    let delivered = if adapter.moves_own_funds() {
+        matches!(
+            EphemeralAccountClient::new(env, from).try_deliver(asset, to, &amount),
+            Ok(Ok(()))
+        )
+    } else {
+        matches!(
+            TokenClient::new(env, asset).try_transfer(from, to, &amount),
+            Ok(Ok(()))
+        )
+    };
+    if delivered {
+        Ok(())
+    } else {
+        Err(Error::TransferFailed)
+    }
+}
+
+fn emit_transfer_skipped(env: &Env, from: &Address, destination: &Address, payment: &Payment) {
+    let event = TransferSkipped {
+        ephemeral_account: from.clone(),
+        destination: destination.clone(),
+        amount: payment.amount,
+    };
+    trace::publish(
+        env,
+        (symbol_short!("xfer_skip"), payment.asset.clone()),
+        event,
+        storage::get_trace_id(env),
+    );
+}
+
+fn emit_sweep_transfer(
+    env: &Env,
+    from: &Address,
+    destination: &Address,
+    payment: &Payment,
+    destination_memo: &Option<BytesN<32>>,
+) {
+    if storage::are_events_compacted(env) {
+        return;
+    }
+    let event = SweepTransfer {
+        ephemeral_account: from.clone(),
+        destination: destination.clone(),
+        amount: payment.amount,
+        destination_memo: destination_memo.clone(),
+    };
+    trace::publish(
+        env,
+        (symbol_short!("swp_xfer"), payment.asset.clone()),
+        event,
+        storage::get_trace_id(env),
+    );
+}
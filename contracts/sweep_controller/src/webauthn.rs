@@ -0,0 +1,193 @@
+use crate::adapter::SweepAuthorization;
+use crate::authorization::{self, SignatureScheme};
+use crate::errors::Error;
+use crate::storage;
+use crate::{SweepController, SweepControllerArgs, SweepControllerClient};
+use soroban_sdk::{contractimpl, contracttype, Address, Bytes, BytesN, Env};
+
+/// Longest `client_data_json` a passkey assertion may carry.
+pub const MAX_CLIENT_DATA_LEN: u32 = 1024;
+
+/// Authenticator data holds a 32-byte RP id hash, a flags byte and a
+/// 4-byte signature counter before any extensions.
+const MIN_AUTHENTICATOR_DATA_LEN: u32 = 37;
+
+/// "User present" bit of the authenticator data flags.
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Length of a 32-byte challenge in unpadded base64url.
+const CHALLENGE_LEN: usize = 43;
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A WebAuthn assertion (`navigator.credentials.get()` response) by the
+/// controller's passkey signer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WebAuthnAssertion {
+    /// `response.authenticatorData`, as returned by the authenticator
+    pub authenticator_data: Bytes,
+    /// `response.clientDataJSON`, exactly as returned by the browser
+    pub client_data_json: Bytes,
+    /// `response.signature`, converted from DER to 64-byte `r || s`
+    /// with a low `s`
+    pub signature: BytesN<64>,
+}
+
+/// Verify a passkey assertion authorizing a sweep at the controller nonce.
+///
+/// The assertion's challenge must be the unpadded base64url encoding of
+/// the usual sweep digest, and the authenticator must report the user as
+/// present. The signature is checked with `secp256r1_verify` over
+/// `sha256(authenticator_data || sha256(client_data_json))`, as WebAuthn
+/// signs it.
+///
+/// # Errors
+/// Returns Error::AuthorizedSignerNotSet if `account`'s signer (its own,
+/// or else the controller's) is not a passkey
+/// Returns Error::AuthorizationRevoked if the sweep digest was revoked
+/// Returns Error::AuthorizationFailed if the assertion is malformed or
+/// signs another challenge
+pub fn verify_passkey_sweep_auth(
+    env: &Env,
+    account: &Address,
+    destination: &Address,
+    assertion: &WebAuthnAssertion,
+    destination_memo: &Option<BytesN<32>>,
+) -> Result<(), Error> {
+    let passkey = match authorization::signer_for(env, account)? {
+        (SignatureScheme::Secp256r1, key) => {
+            BytesN::<65>::try_from(key).map_err(|_| Error::InvalidSignerKey)?
+        }
+        _ => return Err(Error::AuthorizedSignerNotSet),
+    };
+
+    let message = bridgelet_shared::bytes::sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        destination,
+        storage::get_sweep_nonce(env),
+        &env.current_contract_address(),
+        destination_memo.as_ref(),
+        None,
+    );
+    if storage::is_digest_revoked(env, &message) {
+        return Err(Error::AuthorizationRevoked);
+    }
+
+    let authenticator_data = &assertion.authenticator_data;
+    if authenticator_data.len() < MIN_AUTHENTICATOR_DATA_LEN
+        || authenticator_data.get(32).unwrap_or(0) & FLAG_USER_PRESENT == 0
+    {
+        return Err(Error::AuthorizationFailed);
+    }
+    check_client_data(&assertion.client_data_json, &message)?;
+
+    let mut signed = authenticator_data.clone();
+    signed.append(&env.crypto().sha256(&assertion.client_data_json).into());
+    env.crypto().secp256r1_verify(
+        &passkey,
+        &env.crypto().sha256(&signed),
+        &assertion.signature,
+    );
+    Ok(())
+}
+
+/// Check `client_data_json` is a `webauthn.get` assertion over `challenge`.
+///
+/// The JSON is matched rather than parsed: browsers serialize `type` and
+/// `challenge` as plain string members, so both must appear verbatim.
+fn check_client_data(client_data_json: &Bytes, challenge: &BytesN<32>) -> Result<(), Error> {
+    let len = client_data_json.len();
+    if len > MAX_CLIENT_DATA_LEN {
+        return Err(Error::AuthorizationFailed);
+    }
+    let mut buffer = [0u8; MAX_CLIENT_DATA_LEN as usize];
+    let json = &mut buffer[..len as usize];
+    client_data_json.copy_into_slice(json);
+
+    let mut expected_challenge = [0u8; 13 + CHALLENGE_LEN + 1];
+    expected_challenge[..13].copy_from_slice(b"\"challenge\":\"");
+    expected_challenge[13..13 + CHALLENGE_LEN].copy_from_slice(&base64url(&challenge.to_array()));
+    expected_challenge[13 + CHALLENGE_LEN] = b'"';
+
+    if !contains(json, b"\"type\":\"webauthn.get\"") || !contains(json, &expected_challenge) {
+        return Err(Error::AuthorizationFailed);
+    }
+    Ok(())
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Unpadded base64url encoding of a 32-byte challenge.
+fn base64url(bytes: &[u8; 32]) -> [u8; CHALLENGE_LEN] {
+    let mut encoded = [0u8; CHALLENGE_LEN];
+    let mut out = 0;
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = (group[0] as u32) << 16 | (group[1] as u32) << 8 | group[2] as u32;
+        for i in 0..=chunk.len() {
+            encoded[out] = BASE64URL[(bits >> (18 - 6 * i) & 0x3f) as usize];
+            out += 1;
+        }
+    }
+    encoded
+}
+
+/// Sweeps authorized by a passkey.
+#[contractimpl]
+impl SweepController {
+    /// Same as `execute_sweep`, authorized by a WebAuthn assertion from
+    /// the account's passkey signer (`SignatureScheme::Secp256r1`, set with
+    /// `set_account_signer` or else the controller's own), so
+    /// an end user can approve the sweep on their device instead of a
+    /// server holding an Ed25519 key.
+    ///
+    /// The assertion's challenge is the unpadded base64url encoding of the
+    /// sweep message `execute_sweep` signs, at the current nonce. The nonce
+    /// is advanced as for `execute_sweep`.
+    ///
+    /// # Errors
+    /// Returns Error::AuthorizedSignerNotSet if the account's signer is not a passkey
+    /// Returns Error::AuthorizationFailed if the assertion is malformed or signs another challenge
+    /// Otherwise the same as `execute_sweep`
+    pub fn execute_passkey_sweep(
+        env: Env,
+        ephemeral_account: Address,
+        destination: Address,
+        assertion: WebAuthnAssertion,
+        destination_memo: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        storage::extend_instance_ttl(&env);
+
+        Self::validate_destination(&env, &destination)?;
+
+        verify_passkey_sweep_auth(
+            &env,
+            &ephemeral_account,
+            &destination,
+            &assertion,
+            &destination_memo,
+        )?;
+
+        if Self::report_lost_to_expiry(&env, &ephemeral_account) {
+            return Ok(());
+        }
+
+        Self::sweep_account(
+            &env,
+            ephemeral_account,
+            destination,
+            SweepAuthorization::Signature(BytesN::from_array(&env, &[0u8; 64])),
+            destination_memo,
+            true,
+        )
+        .map(|_| ())
+    }
+}
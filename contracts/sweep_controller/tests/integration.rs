@@ -2,12 +2,16 @@
 
 extern crate std;
 
-use ephemeral_account::{AccountStatus, EphemeralAccountContract, EphemeralAccountContractClient};
+use ephemeral_account::{
+    AccountStatus, ArbitrationResult, EphemeralAccountContract, EphemeralAccountContractClient,
+    SettlementPath,
+};
 use soroban_sdk::{
     testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
     Address, BytesN, Env, IntoVal, Symbol, TryFromVal,
 };
-use sweep_controller::{SweepController, SweepControllerClient};
+use sweep_controller::{Error, FeatureFlags, SweepController, SweepControllerClient};
 
 fn generate_test_keypair(env: &Env) -> (BytesN<32>, BytesN<64>) {
     let public_key = BytesN::from_array(
@@ -31,10 +35,10 @@ fn setup_ready_account(
     Address,
 ) {
     let controller_id = env.register(SweepController, ());
-    let controller_client = SweepControllerClient::new(&env, &controller_id);
+    let controller_client = SweepControllerClient::new(env, &controller_id);
 
-    let creator = Address::generate(&env);
-    let (authorized_signer, _) = generate_test_keypair(&env);
+    let creator = Address::generate(env);
+    let (authorized_signer, _) = generate_test_keypair(env);
 
     // Initialize controller with authorized signer (flexible mode - no destination)
     controller_client
@@ -50,10 +54,10 @@ fn setup_ready_account(
         .initialize(&creator, &authorized_signer, &authorized_destination);
 
     let ephemeral_id = env.register(EphemeralAccountContract, ());
-    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
 
-    let account_creator = Address::generate(&env);
-    let recovery = Address::generate(&env);
+    let account_creator = Address::generate(env);
+    let recovery = Address::generate(env);
     let expiry = env.ledger().sequence() + 1_000;
     ephemeral_client
         .mock_auths(&[soroban_sdk::testutils::MockAuth {
@@ -80,7 +84,7 @@ fn setup_ready_account(
             &account_creator,
         );
 
-    let asset_id = Address::generate(&env);
+    let asset_id = fund_account(env, &ephemeral_id, 100);
     env.mock_all_auths_allowing_non_root_auth();
     ephemeral_client.record_payment(&100, &asset_id);
     env.set_auths(&[]);
@@ -88,6 +92,51 @@ fn setup_ready_account(
     (controller_client, ephemeral_client, ephemeral_id)
 }
 
+/// Mint `amount` of a fresh SAC token to `account` and return the token.
+/// Leaves every authorization mocked.
+fn fund_account(env: &Env, account: &Address, amount: i128) -> Address {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    env.mock_all_auths();
+    StellarAssetClient::new(env, &token.address()).mint(account, &amount);
+    token.address()
+}
+
+/// Claim `ephemeral_id` for `recipient` with the recipient's signature on
+/// the claim as the only authorization, and check nothing else was needed.
+fn claim_as(
+    env: &Env,
+    controller_client: &SweepControllerClient<'_>,
+    recipient: &Address,
+    ephemeral_id: &Address,
+) {
+    controller_client
+        .mock_auths(&[soroban_sdk::testutils::MockAuth {
+            address: recipient,
+            invoke: &soroban_sdk::testutils::MockAuthInvoke {
+                contract: &controller_client.address,
+                fn_name: "claim",
+                args: (recipient, ephemeral_id).into_val(env),
+                sub_invokes: &[],
+            },
+        }])
+        .claim(recipient, ephemeral_id);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            recipient.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    controller_client.address.clone(),
+                    soroban_sdk::symbol_short!("claim"),
+                    (recipient, ephemeral_id).into_val(env),
+                )),
+                sub_invocations: std::vec![],
+            },
+        )]
+    );
+    env.set_auths(&[]);
+}
+
 /// Test that re-initialization is prevented
 #[test]
 fn test_initialize_prevents_double_init() {
@@ -146,7 +195,7 @@ fn test_execute_sweep_with_valid_signature() {
     // Execute sweep with invalid signature - should fail verification
     // In tests, client methods panic on error, so we catch it
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        controller_client.execute_sweep(&ephemeral_id, &destination, &invalid_sig);
+        controller_client.execute_sweep(&ephemeral_id, &destination, &invalid_sig, &None);
     }));
 
     // We expect this to fail due to signature verification
@@ -183,7 +232,7 @@ fn test_sweep_without_payment() {
     ephemeral_client.record_payment(&100, &asset_id);
 
     let auth_sig = BytesN::from_array(&env, &[0u8; 64]);
-    controller_client.execute_sweep(&ephemeral_id, &account_creator, &auth_sig);
+    controller_client.execute_sweep(&ephemeral_id, &account_creator, &auth_sig, &None);
 }
 
 #[test]
@@ -194,29 +243,47 @@ fn test_claim_succeeds_with_recipient_auth_and_relayable_flow() {
     let (controller_client, ephemeral_client, ephemeral_id) =
         setup_ready_account(&env, Some(recipient.clone()));
 
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
 
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
     let info = ephemeral_client.get_info();
     assert_eq!(info.swept_to, Some(recipient));
 }
 
+#[test]
+fn test_claim_transfers_payments_to_recipient() {
+    let env = Env::default();
+
+    let recipient = Address::generate(&env);
+    let (controller_client, ephemeral_client, ephemeral_id) =
+        setup_ready_account(&env, Some(recipient.clone()));
+    let asset = ephemeral_client
+        .get_payments_page(&0, &1)
+        .payments
+        .get(0)
+        .unwrap()
+        .asset;
+
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
+
+    let token = TokenClient::new(&env, &asset);
+    assert_eq!(token.balance(&recipient), 100);
+    assert_eq!(token.balance(&ephemeral_id), 0);
+}
+
 #[test]
 fn test_claim_records_recipient_authorization_context() {
     let env = Env::default();
 
     let recipient = Address::generate(&env);
-    let (controller_client, _, ephemeral_id) = setup_ready_account(&env, Some(recipient.clone()));
+    let (controller_client, ephemeral_client, ephemeral_id) =
+        setup_ready_account(&env, Some(recipient.clone()));
+    let asset = ephemeral_client
+        .get_payments_page(&0, &1)
+        .payments
+        .get(0)
+        .unwrap()
+        .asset;
 
     controller_client
         .mock_auths(&[soroban_sdk::testutils::MockAuth {
@@ -230,6 +297,8 @@ fn test_claim_records_recipient_authorization_context() {
         }])
         .claim(&recipient, &ephemeral_id);
 
+    // The recipient signs only the claim; the account transfers its own
+    // funds, which needs no authorization entry of its own.
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -244,6 +313,7 @@ fn test_claim_records_recipient_authorization_context() {
             },
         )]
     );
+    assert_eq!(TokenClient::new(&env, &asset).balance(&recipient), 100);
 }
 
 #[test]
@@ -301,7 +371,7 @@ fn test_unauthorized_signer_not_set() {
 
     // Execute sweep without initializing controller - should fail
     let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        controller_client.execute_sweep(&ephemeral_id, &destination, &auth_sig);
+        controller_client.execute_sweep(&ephemeral_id, &destination, &auth_sig, &None);
     }));
 
     // Should fail because authorized_signer is not set
@@ -466,7 +536,7 @@ fn setup_full_lifecycle(
             &account_creator,
         );
 
-    let asset = Address::generate(env);
+    let asset = fund_account(env, &ephemeral_id, 500);
     env.mock_all_auths_allowing_non_root_auth();
     ephemeral_client.record_payment(&500, &asset);
     env.set_auths(&[]);
@@ -497,19 +567,17 @@ fn test_full_lifecycle_deploy_init_record_claim_verify_state() {
     let info_before = ephemeral_client.get_info();
     assert!(info_before.payment_received);
     assert_eq!(info_before.payment_count, 1);
-    assert_eq!(info_before.payments.get(0).unwrap().amount, 500);
+    assert_eq!(
+        ephemeral_client
+            .get_payments_page(&0, &10)
+            .payments
+            .get(0)
+            .unwrap()
+            .amount,
+        500
+    );
 
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
 
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
     let info_after = ephemeral_client.get_info();
@@ -521,7 +589,7 @@ fn test_full_lifecycle_deploy_init_record_claim_verify_state() {
 
     let reserve_event = ephemeral_client.get_last_reserve_event().unwrap();
     assert_eq!(reserve_event.destination, recipient);
-    assert_eq!(reserve_event.amount, 1_000_000_000);
+    assert_eq!(reserve_event.amount, 550_000_000);
     assert!(reserve_event.fully_reclaimed);
     assert_eq!(ephemeral_client.get_reserve_reclaim_event_count(), 1);
 }
@@ -587,9 +655,9 @@ fn test_full_lifecycle_multi_asset_claim() {
             &account_creator,
         );
 
-    let asset1 = Address::generate(&env);
-    let asset2 = Address::generate(&env);
-    let asset3 = Address::generate(&env);
+    let asset1 = fund_account(&env, &ephemeral_id, 100);
+    let asset2 = fund_account(&env, &ephemeral_id, 200);
+    let asset3 = fund_account(&env, &ephemeral_id, 300);
     env.mock_all_auths_allowing_non_root_auth();
     ephemeral_client.record_payment(&100, &asset1);
     ephemeral_client.record_payment(&200, &asset2);
@@ -600,17 +668,7 @@ fn test_full_lifecycle_multi_asset_claim() {
     assert_eq!(info.payment_count, 3);
     assert_eq!(info.status, AccountStatus::PaymentReceived);
 
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
 
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
     assert_eq!(
@@ -618,8 +676,8 @@ fn test_full_lifecycle_multi_asset_claim() {
         Some(recipient.clone())
     );
 
-    let final_info = ephemeral_client.get_info();
-    let total: i128 = final_info.payments.iter().map(|p| p.amount).sum();
+    let final_page = ephemeral_client.get_payments_page(&0, &10);
+    let total: i128 = final_page.payments.iter().map(|p| p.amount).sum();
     assert_eq!(total, 600);
 }
 
@@ -643,7 +701,7 @@ fn test_full_expire_flow_funds_to_recovery() {
         &account_creator,
     );
 
-    let asset = Address::generate(&env);
+    let asset = fund_account(&env, &ephemeral_client.address, 1_000);
     ephemeral_client.record_payment(&1_000, &asset);
 
     env.ledger().set_sequence_number(expiry);
@@ -652,7 +710,8 @@ fn test_full_expire_flow_funds_to_recovery() {
 
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Expired);
     let info = ephemeral_client.get_info();
-    assert_eq!(info.swept_to, Some(recovery));
+    assert_eq!(info.swept_to, Some(recovery.clone()));
+    assert_eq!(TokenClient::new(&env, &asset).balance(&recovery), 1_000);
     assert_eq!(ephemeral_client.get_reserve_remaining(), 0);
     assert!(ephemeral_client.is_reserve_reclaimed());
 }
@@ -677,7 +736,7 @@ fn test_full_recover_flow_creator_after_expiry() {
         &account_creator,
     );
 
-    let asset = Address::generate(&env);
+    let asset = fund_account(&env, &ephemeral_client.address, 2_000);
     ephemeral_client.record_payment(&2_000, &asset);
 
     env.ledger().set_sequence_number(expiry);
@@ -778,17 +837,7 @@ fn test_double_claim_rejected() {
     let (controller_client, ephemeral_client, ephemeral_id, recipient, _) =
         setup_full_lifecycle(&env);
 
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
 
     let result = controller_client.try_claim(&recipient, &ephemeral_id);
@@ -871,25 +920,56 @@ fn test_can_sweep_reflects_account_state() {
         &controller_id2,
         &account_creator2,
     );
-    ephemeral_client2.record_payment(&100, &Address::generate(&env));
+    let asset2 = fund_account(&env, &ephemeral_id2, 100);
+    ephemeral_client2.record_payment(&100, &asset2);
 
     assert!(controller_client2.can_sweep(&ephemeral_id2));
 
-    controller_client2
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client2.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id2).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id2);
+    claim_as(&env, &controller_client2, &recipient, &ephemeral_id2);
 
     assert!(!controller_client2.can_sweep(&ephemeral_id2));
 }
 
+/// The sweep deadline leaves the configured margin before expiry.
+#[test]
+fn test_sweepable_until_applies_submission_margin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, controller_id, ephemeral_client, ephemeral_id) = deploy_contracts(&env);
+    let (authorized_signer, _) = generate_test_keypair(&env);
+    controller_client.initialize(&Address::generate(&env), &authorized_signer, &None);
+
+    let account_creator = Address::generate(&env);
+    let expiry = env.ledger().sequence() + 100;
+    ephemeral_client.initialize(
+        &account_creator,
+        &expiry,
+        &Address::generate(&env),
+        &controller_id,
+        &account_creator,
+    );
+    assert_eq!(controller_client.sweepable_until(&ephemeral_id), None);
+
+    ephemeral_client.record_payment(&100, &Address::generate(&env));
+    assert_eq!(
+        controller_client.sweepable_until(&ephemeral_id),
+        Some(expiry - 1)
+    );
+
+    controller_client.set_submission_margin(&5);
+    assert_eq!(controller_client.get_submission_margin(), 5);
+    assert_eq!(
+        controller_client.sweepable_until(&ephemeral_id),
+        Some(expiry - 6)
+    );
+
+    env.ledger().set_sequence_number(expiry - 6);
+    assert!(controller_client.can_sweep(&ephemeral_id));
+    env.ledger().set_sequence_number(expiry - 5);
+    assert!(!controller_client.can_sweep(&ephemeral_id));
+}
+
 /// SweepCompleted event emitted during claim.
 #[test]
 fn test_claim_emits_sweep_completed_event() {
@@ -898,17 +978,7 @@ fn test_claim_emits_sweep_completed_event() {
     let (controller_client, _ephemeral_client, ephemeral_id, recipient, _asset) =
         setup_full_lifecycle(&env);
 
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
 
     let events = env.events().all();
     let mut found_sweep_event = false;
@@ -1003,7 +1073,7 @@ fn test_single_controller_manages_multiple_accounts() {
                 &account_creator,
             );
 
-        let asset = Address::generate(&env);
+        let asset = fund_account(&env, &ephemeral_id, 100);
         env.mock_all_auths_allowing_non_root_auth();
         ephemeral_client.record_payment(&100, &asset);
         env.set_auths(&[]);
@@ -1013,18 +1083,8 @@ fn test_single_controller_manages_multiple_accounts() {
     }
 
     // Claim the first account
-    let first_id = ephemeral_ids.get(0).unwrap();
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&recipient, first_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&recipient, first_id);
+    let first_id = ephemeral_ids.first().unwrap();
+    claim_as(&env, &controller_client, &recipient, first_id);
 
     // First is claimed, second is still sweepable
     assert!(!controller_client.can_sweep(first_id));
@@ -1052,7 +1112,7 @@ fn test_recovery_address_can_expire_account() {
         &account_creator,
     );
 
-    let asset = Address::generate(&env);
+    let asset = fund_account(&env, &ephemeral_client.address, 500);
     ephemeral_client.record_payment(&500, &asset);
 
     env.ledger().set_sequence_number(expiry);
@@ -1103,6 +1163,30 @@ fn test_get_info_reflects_lifecycle_stages() {
     assert_eq!(info.recovery_address, recovery);
 }
 
+/// The shared interface client decodes the account's `get_info` as the same
+/// `AccountInfo` the account's own client returns.
+#[test]
+fn test_shared_client_decodes_get_info() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_, _, ephemeral_client, _) = deploy_contracts(&env);
+    let account_creator = Address::generate(&env);
+    ephemeral_client.initialize(
+        &account_creator,
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &account_creator,
+    );
+    ephemeral_client.record_payment(&100, &Address::generate(&env));
+
+    let shared = bridgelet_shared::EphemeralAccountClient::new(&env, &ephemeral_client.address);
+    let info = shared.get_info();
+    assert_eq!(info.creator, account_creator);
+    assert_eq!(info.status, AccountStatus::PaymentReceived);
+    assert_eq!(info.payment_count, 1);
+}
+
 /// Claim with flexible controller (no locked destination) succeeds for any recipient.
 #[test]
 fn test_claim_with_flexible_destination() {
@@ -1155,24 +1239,2693 @@ fn test_claim_with_flexible_destination() {
             &account_creator,
         );
 
-    let asset = Address::generate(&env);
+    let asset = fund_account(&env, &ephemeral_id, 100);
     env.mock_all_auths_allowing_non_root_auth();
     ephemeral_client.record_payment(&100, &asset);
     env.set_auths(&[]);
 
     let any_recipient = Address::generate(&env);
-    controller_client
-        .mock_auths(&[soroban_sdk::testutils::MockAuth {
-            address: &any_recipient,
-            invoke: &soroban_sdk::testutils::MockAuthInvoke {
-                contract: &controller_client.address,
-                fn_name: "claim",
-                args: (&any_recipient, &ephemeral_id).into_val(&env),
-                sub_invokes: &[],
-            },
-        }])
-        .claim(&any_recipient, &ephemeral_id);
+    claim_as(&env, &controller_client, &any_recipient, &ephemeral_id);
 
     assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
     assert_eq!(ephemeral_client.get_info().swept_to, Some(any_recipient));
 }
+
+/// A claim that lands after expire() settled the account is a no-op that
+/// reports the lost race instead of failing.
+#[test]
+fn test_claim_after_expire_emits_arbitration_result() {
+    let env = Env::default();
+
+    let (controller_client, ephemeral_client, ephemeral_id, recipient, _asset) =
+        setup_full_lifecycle(&env);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 2_000);
+    ephemeral_client.expire();
+
+    claim_as(&env, &controller_client, &recipient, &ephemeral_id);
+
+    let events = env.events().all();
+    let mut arbitration = None;
+    for i in 0..events.len() {
+        let (_contract, topics, data) = events.get_unchecked(i);
+        if let Ok(sym) = Symbol::try_from_val(&env, &topics.get(0).unwrap()) {
+            if sym == soroban_sdk::symbol_short!("arbitrate") {
+                let account = Address::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+                assert_eq!(account, ephemeral_id);
+                arbitration = Some(ArbitrationResult::try_from_val(&env, &data).unwrap());
+            }
+        }
+    }
+    let arbitration = arbitration.expect("ArbitrationResult event should be emitted");
+    assert_eq!(arbitration.winner, SettlementPath::Expiry);
+    assert_eq!(arbitration.loser_reason, 11);
+
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Expired);
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+// ── Account adapters: mixed-version fleets ──────────────────────────────────
+
+/// Minimal stand-in for an ephemeral account deployed before
+/// `interface_version()` / `sweep_and_report()` existed.
+mod legacy_account {
+    use bridgelet_shared::AccountStatus;
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+    /// `Payment` as the first release shipped it, before `payer`.
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct LegacyPayment {
+        pub asset: Address,
+        pub amount: i128,
+        pub timestamp: u64,
+    }
+
+    /// `AccountInfo` exactly as the first release shipped it. Frozen: never
+    /// add fields here, newer accounts report the shared struct.
+    #[contracttype]
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct LegacyAccountInfo {
+        pub creator: Address,
+        pub status: AccountStatus,
+        pub expiry_ledger: u32,
+        pub recovery_address: Address,
+        pub payment_received: bool,
+        pub payment_count: u32,
+        pub payments: Vec<LegacyPayment>,
+        pub swept_to: Option<Address>,
+    }
+
+    #[contracttype]
+    enum Key {
+        Asset,
+        Amount,
+        Creator,
+        Swept,
+    }
+
+    #[contract]
+    pub struct LegacyAccount;
+
+    #[contractimpl]
+    impl LegacyAccount {
+        pub fn setup(env: Env, creator: Address, asset: Address, amount: i128) {
+            env.storage().instance().set(&Key::Creator, &creator);
+            env.storage().instance().set(&Key::Asset, &asset);
+            env.storage().instance().set(&Key::Amount, &amount);
+        }
+
+        pub fn sweep(env: Env, destination: Address, _auth_signature: BytesN<64>) {
+            env.storage().instance().set(&Key::Swept, &destination);
+        }
+
+        pub fn get_status(env: Env) -> AccountStatus {
+            if env.storage().instance().has(&Key::Swept) {
+                AccountStatus::Swept
+            } else {
+                AccountStatus::PaymentReceived
+            }
+        }
+
+        pub fn get_info(env: Env) -> LegacyAccountInfo {
+            let payment = LegacyPayment {
+                asset: env.storage().instance().get(&Key::Asset).unwrap(),
+                amount: env.storage().instance().get(&Key::Amount).unwrap(),
+                timestamp: 0,
+            };
+            LegacyAccountInfo {
+                creator: env.storage().instance().get(&Key::Creator).unwrap(),
+                status: Self::get_status(env.clone()),
+                expiry_ledger: env.ledger().sequence() + 1_000,
+                recovery_address: env.storage().instance().get(&Key::Creator).unwrap(),
+                payment_received: true,
+                payment_count: 1,
+                payments: Vec::from_array(&env, [payment]),
+                swept_to: env.storage().instance().get(&Key::Swept),
+            }
+        }
+    }
+}
+
+/// Deterministic signing key whose public key is registered as the
+/// controller's authorized signer.
+fn test_signing_key() -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])
+}
+
+/// Sign `hash(network_id + account + destination + nonce + contract_id)`
+/// exactly as `authorization::construct_sweep_message` builds it.
+fn sign_sweep(
+    env: &Env,
+    controller_id: &Address,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+) -> BytesN<64> {
+    sign_sweep_with_memo(env, controller_id, account, destination, nonce, None)
+}
+
+/// Sign `hash(network_id + account + destination + nonce + contract_id [+ memo])`.
+fn sign_sweep_with_memo(
+    env: &Env,
+    controller_id: &Address,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    memo: Option<&BytesN<32>>,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    use soroban_sdk::{xdr::ToXdr, Bytes};
+
+    let mut message = Bytes::from(env.ledger().network_id());
+    message.append(&account.clone().to_xdr(env));
+    message.append(&destination.clone().to_xdr(env));
+    message.extend_from_array(&nonce.to_be_bytes());
+    message.append(&controller_id.clone().to_xdr(env));
+    if let Some(memo) = memo {
+        message.extend_from_array(&memo.to_array());
+    }
+    let digest: BytesN<32> = env.crypto().sha256(&message).into();
+
+    let signature = test_signing_key().sign(&digest.to_array());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+/// Register a controller whose authorized signer is `test_signing_key()`.
+fn setup_signing_controller(env: &Env) -> (SweepControllerClient<'_>, Address) {
+    let controller_id = env.register(SweepController, ());
+    let controller_client = SweepControllerClient::new(env, &controller_id);
+    let signer = BytesN::from_array(env, &test_signing_key().verifying_key().to_bytes());
+    controller_client.initialize(&Address::generate(env), &signer, &None);
+    (controller_client, controller_id)
+}
+
+#[test]
+fn test_execute_sweep_dispatches_current_account_through_v5_adapter() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    assert_eq!(ephemeral_client.interface_version(), 5);
+
+    let creator = Address::generate(&env);
+    let expiry = env.ledger().sequence() + 1_000;
+    ephemeral_client.initialize(
+        &creator,
+        &expiry,
+        &Address::generate(&env),
+        &controller_id,
+        &creator,
+    );
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&ephemeral_id, &300);
+    ephemeral_client.record_payment(&300, &token.address());
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    // The sweep signature is the only credential: the account delivers
+    // its funds itself, so no address has to authorize anything.
+    env.set_auths(&[]);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+    assert!(env.auths().is_empty());
+
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&destination),
+        300
+    );
+    assert_eq!(controller_client.get_nonce(), 1);
+}
+
+#[test]
+fn test_execute_sweep_extends_account_ttl_before_settling() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    let start = env.ledger().sequence();
+    ephemeral_client.initialize(
+        &Address::generate(&env),
+        &(start + 10_000),
+        &Address::generate(&env),
+        &controller_id,
+        &Address::generate(&env),
+    );
+
+    // Close to archival, but above the account's own extension threshold,
+    // so only the controller extends it.
+    env.ledger().set_sequence_number(start + 3_000);
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&ephemeral_id, &100);
+    ephemeral_client.record_payment(&100, &token.address());
+    let ttl = || {
+        use soroban_sdk::testutils::storage::Instance as _;
+        env.as_contract(&ephemeral_id, || env.storage().instance().get_ttl())
+    };
+    assert!(ttl() < 17_280);
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert_eq!(ttl(), 518_400);
+}
+
+#[test]
+fn test_execute_sweep_dispatches_legacy_account_through_v1_adapter() {
+    use legacy_account::{LegacyAccount, LegacyAccountClient};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let legacy_id = env.register(LegacyAccount, ());
+    let legacy_client = LegacyAccountClient::new(&env, &legacy_id);
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&legacy_id, &125);
+    legacy_client.setup(&Address::generate(&env), &token.address(), &125);
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &legacy_id, &destination, 0);
+    controller_client.execute_sweep(&legacy_id, &destination, &signature, &None);
+
+    assert_eq!(legacy_client.get_info().swept_to, Some(destination.clone()));
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&destination),
+        125
+    );
+}
+
+// ── Creator contract callback ───────────────────────────────────────────────
+
+/// Creator contracts: an escrow that records the receipt it is notified
+/// with, and one whose hook always fails.
+mod creator_contracts {
+    use bridgelet_shared::{CreatorCallbackInterface, SweepReceipt};
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum Key {
+        Settled(Address),
+    }
+
+    #[contract]
+    pub struct EscrowCreator;
+
+    #[contractimpl]
+    impl EscrowCreator {
+        pub fn on_sweep_completed(env: Env, account: Address, receipt: SweepReceipt) {
+            env.storage()
+                .instance()
+                .set(&Key::Settled(account), &receipt);
+        }
+
+        pub fn settled(env: Env, account: Address) -> Option<SweepReceipt> {
+            env.storage().instance().get(&Key::Settled(account))
+        }
+    }
+
+    impl CreatorCallbackInterface for EscrowCreator {
+        fn on_sweep_completed(env: Env, account: Address, receipt: SweepReceipt) {
+            Self::on_sweep_completed(env, account, receipt)
+        }
+    }
+
+    pub mod failing {
+        use bridgelet_shared::SweepReceipt;
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        #[contract]
+        pub struct FailingCreator;
+
+        #[contractimpl]
+        impl FailingCreator {
+            pub fn on_sweep_completed(_env: Env, _account: Address, _receipt: SweepReceipt) {
+                panic!("escrow rejected settlement");
+            }
+        }
+    }
+}
+
+/// Deploy an account created by `creator`, fund it with a real token and
+/// sweep it through a signing controller. Returns the `notified` event
+/// payload, if any.
+fn sweep_account_created_by(
+    env: &Env,
+    creator: &Address,
+) -> (Address, Address, Option<sweep_controller::CreatorNotified>) {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let (controller_client, controller_id) = setup_signing_controller(env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        creator,
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        &controller_id,
+        &Address::generate(env),
+    );
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&ephemeral_id, &400);
+    ephemeral_client.record_payment(&400, &token.address());
+
+    let destination = Address::generate(env);
+    let signature = sign_sweep(env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    let notified = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == controller_id)
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get_unchecked(0))
+                .map(|topic| topic == Symbol::new(env, "notified"))
+                .unwrap_or(false)
+        })
+        .map(|(_, _, data)| sweep_controller::CreatorNotified::try_from_val(env, &data).unwrap());
+
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    (ephemeral_id, destination, notified)
+}
+
+#[test]
+fn test_sweep_notifies_creator_contract_with_receipt() {
+    use creator_contracts::{EscrowCreator, EscrowCreatorClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let escrow_id = env.register(EscrowCreator, ());
+    let (ephemeral_id, destination, notified) = sweep_account_created_by(&env, &escrow_id);
+
+    let notified = notified.expect("creator contract was not notified");
+    assert_eq!(notified.creator, escrow_id);
+    assert!(notified.delivered);
+
+    let receipt = EscrowCreatorClient::new(&env, &escrow_id)
+        .settled(&ephemeral_id)
+        .expect("escrow did not record the receipt");
+    assert_eq!(receipt.destination, destination);
+    assert_eq!(receipt.total_amount, 400);
+    assert_eq!(receipt.payments.len(), 1);
+    assert_eq!(receipt.preferred_settlement_asset, None);
+}
+
+#[test]
+fn test_failing_creator_callback_does_not_fail_sweep() {
+    use creator_contracts::failing::FailingCreator;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let creator_id = env.register(FailingCreator, ());
+    let (_, _, notified) = sweep_account_created_by(&env, &creator_id);
+
+    assert!(
+        !notified
+            .expect("callback attempt was not reported")
+            .delivered
+    );
+}
+
+#[test]
+fn test_account_creator_is_not_notified() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    // `Address::generate` yields contract addresses; use a real `G...` account.
+    let creator = Address::from_str(
+        &env,
+        "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ",
+    );
+    let (_, _, notified) = sweep_account_created_by(&env, &creator);
+
+    assert!(notified.is_none());
+}
+
+// ── Destination memo ────────────────────────────────────────────────────────
+
+/// Deploy an account owned by a signing controller and fund it with `amount`
+/// of a fresh SAC token.
+fn setup_funded_account(
+    env: &Env,
+    amount: i128,
+) -> (SweepControllerClient<'_>, Address, Address, Address) {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let (controller_client, controller_id) = setup_signing_controller(env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        &Address::generate(env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        &controller_id,
+        &Address::generate(env),
+    );
+
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&ephemeral_id, &amount);
+    ephemeral_client.record_payment(&amount, &token.address());
+
+    (
+        controller_client,
+        controller_id,
+        ephemeral_id,
+        token.address(),
+    )
+}
+
+#[test]
+fn test_execute_sweep_carries_destination_memo_in_events() {
+    use sweep_controller::SweepCompleted;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let memo = BytesN::from_array(&env, &[42u8; 32]);
+    let signature = sign_sweep_with_memo(
+        &env,
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+        Some(&memo),
+    );
+
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &Some(memo.clone()));
+
+    let controller_events: std::vec::Vec<_> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == controller_id)
+        .collect();
+
+    let (_, transfer_topics, transfer_data) = controller_events
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                == Some(Symbol::new(&env, "swp_xfer"))
+        })
+        .expect("SweepTransfer event should be emitted");
+    assert_eq!(
+        Address::try_from_val(&env, &transfer_topics.get_unchecked(1)).unwrap(),
+        token
+    );
+    let transfer = sweep_controller::SweepTransfer::try_from_val(&env, transfer_data).unwrap();
+    assert_eq!(transfer.amount, 250);
+    assert_eq!(transfer.destination_memo, Some(memo.clone()));
+
+    let (_, _, completed_data) = controller_events
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                == Some(Symbol::new(&env, "sweep"))
+        })
+        .expect("SweepCompleted event should be emitted");
+    let completed = SweepCompleted::try_from_val(&env, completed_data).unwrap();
+    assert_eq!(completed.destination, destination);
+    assert_eq!(completed.destination_memo, Some(memo));
+}
+
+#[test]
+fn test_execute_sweep_rejects_memo_not_covered_by_signature() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    // Signed without a memo; a relayer then attaches one.
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let memo = BytesN::from_array(&env, &[9u8; 32]);
+
+    let result =
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &Some(memo));
+
+    assert!(result.is_err());
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_execute_sweep_rejects_signature_from_another_network() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+
+    // Same contract addresses, different network.
+    env.ledger().set_network_id([1u8; 32]);
+    let result =
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert!(result.is_err());
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_execute_sweep_rejects_signature_for_another_account() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let other_account = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &other_account, &destination, 0);
+
+    let result =
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert!(result.is_err());
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+// ── Feature flags ───────────────────────────────────────────────────────────
+
+#[test]
+fn test_features_default_to_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+
+    assert_eq!(controller_client.get_features(), 0);
+    assert!(!controller_client.is_feature_enabled(&FeatureFlags::FEES));
+}
+
+#[test]
+fn test_creator_can_toggle_features() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let controller_id = env.register(SweepController, ());
+    let controller_client = SweepControllerClient::new(&env, &controller_id);
+    let creator = Address::generate(&env);
+    let (authorized_signer, _) = generate_test_keypair(&env);
+    controller_client.initialize(&creator, &authorized_signer, &None);
+
+    let enabled = FeatureFlags::FEES | FeatureFlags::DESTINATION_BOOK;
+    controller_client.set_features(&enabled);
+
+    assert_eq!(
+        env.auths()[0],
+        (
+            creator.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    controller_id.clone(),
+                    Symbol::new(&env, "set_features"),
+                    (enabled,).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )
+    );
+    assert_eq!(controller_client.get_features(), enabled);
+    assert!(controller_client.is_feature_enabled(&FeatureFlags::FEES));
+    assert!(!controller_client.is_feature_enabled(&FeatureFlags::DUST_SKIPPING));
+    assert!(
+        !controller_client.is_feature_enabled(&(FeatureFlags::FEES | FeatureFlags::RATE_LIMITS))
+    );
+
+    controller_client.set_features(&0);
+    assert_eq!(controller_client.get_features(), 0);
+}
+
+#[test]
+fn test_set_features_rejects_unknown_bits() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+
+    let result = controller_client.try_set_features(&(FeatureFlags::ALL + 1));
+    assert_eq!(result, Err(Ok(Error::InvalidFeatureFlags)));
+    assert_eq!(controller_client.get_features(), 0);
+}
+
+// ── Contract versions ───────────────────────────────────────────────────────
+
+#[test]
+fn test_controller_reports_its_build_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+    assert_eq!(
+        controller_client.get_version(),
+        bridgelet_shared::ContractVersion::parse(env!("CARGO_PKG_VERSION"))
+    );
+}
+
+// ── Partial settlement ──────────────────────────────────────────────────────
+
+/// SEP-41-shaped token whose transfers to a blocked recipient fail, standing
+/// in for a destination without a trustline for the asset.
+mod picky_token {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum Key {
+        Balance(Address),
+        Blocked(Address),
+    }
+
+    #[contract]
+    pub struct PickyToken;
+
+    #[contractimpl]
+    impl PickyToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .instance()
+                .set(&Key::Balance(to), &(balance + amount));
+        }
+
+        pub fn set_blocked(env: Env, recipient: Address, blocked: bool) {
+            env.storage()
+                .instance()
+                .set(&Key::Blocked(recipient), &blocked);
+        }
+
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let blocked: bool = env
+                .storage()
+                .instance()
+                .get(&Key::Blocked(to.clone()))
+                .unwrap_or(false);
+            if blocked {
+                panic!("recipient has no trustline");
+            }
+            let from_balance = Self::balance(env.clone(), from.clone());
+            env.storage()
+                .instance()
+                .set(&Key::Balance(from), &(from_balance - amount));
+            Self::mint(env, to, amount);
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().instance().get(&Key::Balance(id)).unwrap_or(0)
+        }
+    }
+}
+
+/// Account holding 300 of a SAC token and 200 of a `PickyToken` that
+/// refuses transfers to the returned destination.
+fn setup_partially_receivable_account(
+    env: &Env,
+) -> (
+    SweepControllerClient<'_>,
+    Address,
+    Address,
+    Address,
+    Address,
+    Address,
+) {
+    use picky_token::{PickyToken, PickyTokenClient};
+    use soroban_sdk::token::StellarAssetClient;
+
+    let (controller_client, controller_id) = setup_signing_controller(env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        &Address::generate(env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        &controller_id,
+        &Address::generate(env),
+    );
+
+    let destination = Address::generate(env);
+
+    let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &sac.address()).mint(&ephemeral_id, &300);
+    ephemeral_client.record_payment(&300, &sac.address());
+
+    let picky_id = env.register(PickyToken, ());
+    let picky = PickyTokenClient::new(env, &picky_id);
+    picky.mint(&ephemeral_id, &200);
+    picky.set_blocked(&destination, &true);
+    ephemeral_client.record_payment(&200, &picky_id);
+
+    (
+        controller_client,
+        controller_id,
+        ephemeral_id,
+        destination,
+        sac.address(),
+        picky_id,
+    )
+}
+
+#[test]
+fn test_partial_settlement_then_sweep_remaining() {
+    use picky_token::PickyTokenClient;
+    use soroban_sdk::token::TokenClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, destination, sac, picky_id) =
+        setup_partially_receivable_account(&env);
+    controller_client.set_features(&FeatureFlags::PARTIAL_SETTLEMENT);
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    let picky = PickyTokenClient::new(&env, &picky_id);
+
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    let (_, skip_topics, skip_data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                == Some(Symbol::new(&env, "xfer_skip"))
+        })
+        .expect("TransferSkipped should be emitted");
+    assert_eq!(
+        Address::try_from_val(&env, &skip_topics.get_unchecked(1)).unwrap(),
+        picky_id
+    );
+    let skipped = sweep_controller::TransferSkipped::try_from_val(&env, &skip_data).unwrap();
+    assert_eq!(skipped.amount, 200);
+    assert_eq!(skipped.destination, destination);
+
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::PartiallySwept);
+    assert_eq!(TokenClient::new(&env, &sac).balance(&destination), 300);
+    assert_eq!(picky.balance(&destination), 0);
+    let pending = controller_client
+        .get_pending_settlement(&ephemeral_id)
+        .expect("undelivered asset should be pending");
+    assert_eq!(pending.destination, destination);
+    assert_eq!(pending.remaining.len(), 1);
+    assert_eq!(pending.remaining.get_unchecked(0).asset, picky_id);
+
+    // The account keeps its recorded amounts but reports the undelivered
+    // asset as unsettled.
+    let settled_amount = |asset: &Address| {
+        ephemeral_client
+            .get_payments_page(&0, &10)
+            .settled
+            .iter()
+            .find(|s| s.asset == *asset)
+            .map(|s| s.amount)
+    };
+    assert_eq!(settled_amount(&sac), Some(300));
+    assert_eq!(settled_amount(&picky_id), Some(0));
+    assert_eq!(ephemeral_client.get_payment(&picky_id).unwrap().amount, 200);
+
+    // The destination adds the missing trustline.
+    picky.set_blocked(&destination, &false);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 1);
+    controller_client.sweep_remaining(&ephemeral_id, &signature);
+
+    assert_eq!(picky.balance(&destination), 200);
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    assert_eq!(settled_amount(&picky_id), Some(200));
+    assert_eq!(
+        controller_client.get_pending_settlement(&ephemeral_id),
+        None
+    );
+    assert_eq!(controller_client.get_nonce(), 2);
+}
+
+#[test]
+fn test_undeliverable_asset_fails_sweep_without_partial_settlement() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, destination, _, _) =
+        setup_partially_receivable_account(&env);
+
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let result =
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert_eq!(result, Err(Ok(Error::TransferFailed)));
+    assert_eq!(
+        EphemeralAccountContractClient::new(&env, &ephemeral_id).get_status(),
+        AccountStatus::PaymentReceived
+    );
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_sweep_remaining_errors() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, destination, _, _) =
+        setup_partially_receivable_account(&env);
+    controller_client.set_features(&FeatureFlags::PARTIAL_SETTLEMENT);
+
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    assert_eq!(
+        controller_client.try_sweep_remaining(&ephemeral_id, &signature),
+        Err(Ok(Error::NothingToSweep))
+    );
+
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    // Still no trustline: nothing can be delivered and the nonce is kept.
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 1);
+    assert_eq!(
+        controller_client.try_sweep_remaining(&ephemeral_id, &signature),
+        Err(Ok(Error::TransferFailed))
+    );
+    assert_eq!(controller_client.get_nonce(), 1);
+    assert!(controller_client
+        .get_pending_settlement(&ephemeral_id)
+        .is_some());
+}
+
+// ── Registry hook ───────────────────────────────────────────────────────────
+
+mod registry_contracts {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+    #[contracttype]
+    enum Key {
+        Synced(Address),
+    }
+
+    /// Stands in for the account factory's `sync_account` hook.
+    #[contract]
+    pub struct RecordingRegistry;
+
+    #[contractimpl]
+    impl RecordingRegistry {
+        pub fn sync_account(env: Env, account: Address) -> bool {
+            env.storage().instance().set(&Key::Synced(account), &true);
+            true
+        }
+
+        pub fn synced(env: Env, account: Address) -> bool {
+            env.storage().instance().has(&Key::Synced(account))
+        }
+    }
+
+    pub mod failing {
+        use soroban_sdk::{contract, contractimpl, Address, Env};
+
+        #[contract]
+        pub struct FailingRegistry;
+
+        #[contractimpl]
+        impl FailingRegistry {
+            pub fn sync_account(_env: Env, _account: Address) -> bool {
+                panic!("registry unavailable");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sweep_syncs_account_with_registry() {
+    use registry_contracts::{RecordingRegistry, RecordingRegistryClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let registry_id = env.register(RecordingRegistry, ());
+    controller_client.set_registry(&registry_id);
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert!(RecordingRegistryClient::new(&env, &registry_id).synced(&ephemeral_id));
+}
+
+#[test]
+fn test_failing_registry_does_not_fail_sweep() {
+    use registry_contracts::failing::FailingRegistry;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    controller_client.set_registry(&env.register(FailingRegistry, ()));
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert_eq!(
+        EphemeralAccountContractClient::new(&env, &ephemeral_id).get_status(),
+        AccountStatus::Swept
+    );
+}
+
+// ── Authorization revocation ────────────────────────────────────────────────
+
+#[test]
+fn test_revoked_authorization_digest_is_rejected_without_bumping_nonce() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let revoked_destination = Address::generate(&env);
+    let digest = bridgelet_shared::bytes::sweep_message_digest(
+        &env,
+        &env.ledger().network_id(),
+        &ephemeral_id,
+        &revoked_destination,
+        0,
+        &controller_id,
+        None,
+        None,
+    );
+
+    controller_client.revoke_authorization_digest(&digest);
+    assert!(controller_client.is_authorization_revoked(&digest));
+
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &revoked_destination, 0);
+    assert_eq!(
+        controller_client.try_execute_sweep(&ephemeral_id, &revoked_destination, &signature, &None),
+        Err(Ok(Error::AuthorizationRevoked))
+    );
+    assert_eq!(controller_client.get_nonce(), 0);
+
+    // Another authorization at the same nonce is unaffected.
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+    assert_eq!(controller_client.get_nonce(), 1);
+}
+
+#[test]
+fn test_revoke_authorization_digest_requires_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _controller_id) = setup_signing_controller(&env);
+    let digest = BytesN::from_array(&env, &[1u8; 32]);
+    env.set_auths(&[]);
+
+    assert!(controller_client
+        .try_revoke_authorization_digest(&digest)
+        .is_err());
+    assert!(!controller_client.is_authorization_revoked(&digest));
+}
+
+// ── Lifecycle ledgers ───────────────────────────────────────────────────────
+
+/// The `sweep` event of the last invocation made by `controller_id`.
+fn last_sweep_completed(env: &Env, controller_id: &Address) -> sweep_controller::SweepCompleted {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| contract == controller_id)
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(env, &topics.get_unchecked(0)).ok()
+                == Some(Symbol::new(env, "sweep"))
+        })
+        .map(|(_, _, data)| sweep_controller::SweepCompleted::try_from_val(env, &data).unwrap())
+        .expect("SweepCompleted event should be emitted")
+}
+
+#[test]
+fn test_sweep_completed_carries_lifecycle_ledgers() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+    env.ledger().set_sequence_number(200);
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    env.ledger().set_sequence_number(260);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    let completed = last_sweep_completed(&env, &controller_id);
+    assert_eq!(completed.created_ledger, Some(200));
+    assert_eq!(completed.first_payment_ledger, Some(200));
+    assert_eq!(completed.settled_ledger, 260);
+}
+
+#[test]
+fn test_sweep_completed_for_legacy_account_has_no_lifecycle() {
+    use legacy_account::{LegacyAccount, LegacyAccountClient};
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let legacy_id = env.register(LegacyAccount, ());
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&legacy_id, &125);
+    LegacyAccountClient::new(&env, &legacy_id).setup(
+        &Address::generate(&env),
+        &token.address(),
+        &125,
+    );
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &legacy_id, &destination, 0);
+    controller_client.execute_sweep(&legacy_id, &destination, &signature, &None);
+
+    let completed = last_sweep_completed(&env, &controller_id);
+    assert_eq!(completed.created_ledger, None);
+    assert_eq!(completed.first_payment_ledger, None);
+    assert_eq!(completed.settled_ledger, env.ledger().sequence());
+}
+
+#[test]
+fn test_controller_reads_frozen_legacy_account_info() {
+    use bridgelet_shared::AccountInfo;
+    use legacy_account::{LegacyAccount, LegacyAccountClient};
+    use soroban_sdk::xdr::{FromXdr, ToXdr};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+    let legacy_id = env.register(LegacyAccount, ());
+    let legacy_client = LegacyAccountClient::new(&env, &legacy_id);
+    let creator = Address::generate(&env);
+    legacy_client.setup(&creator, &Address::generate(&env), &125);
+
+    // The old struct's XDR no longer decodes as the current AccountInfo.
+    let frozen = legacy_client.get_info().to_xdr(&env);
+    let strict = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        AccountInfo::from_xdr(&env, &frozen)
+    }));
+    assert!(!matches!(strict, Ok(Ok(_))));
+
+    // The controller still reads it, defaulting the fields it lacks.
+    let info = legacy_client.get_info();
+    assert_eq!(
+        controller_client.sweepable_until(&legacy_id),
+        Some(info.expiry_ledger - 1)
+    );
+    assert!(controller_client.can_sweep(&legacy_id));
+}
+
+// ── Account-authorized sweeps ───────────────────────────────────────────────
+
+#[test]
+fn test_execute_sweep_as_account_requires_account_authorization() {
+    use soroban_sdk::token::TokenClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 100);
+    let destination = Address::generate(&env);
+    controller_client.execute_sweep_as_account(&ephemeral_id, &destination, &None);
+
+    let (_, invocation) = env
+        .auths()
+        .into_iter()
+        .find(|(address, _)| *address == ephemeral_id)
+        .expect("the account did not authorize the sweep");
+    assert_eq!(
+        invocation.function,
+        AuthorizedFunction::Contract((
+            controller_id,
+            Symbol::new(&env, "execute_sweep_as_account"),
+            (&destination, None::<BytesN<32>>).into_val(&env),
+        ))
+    );
+
+    assert_eq!(TokenClient::new(&env, &token).balance(&destination), 100);
+    // Replay protection comes from the auth entry, not the signer nonce.
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_execute_sweep_as_account_rejects_without_account_authorization() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, _controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    env.set_auths(&[]);
+
+    assert!(controller_client
+        .try_execute_sweep_as_account(&ephemeral_id, &Address::generate(&env), &None)
+        .is_err());
+    assert_eq!(
+        EphemeralAccountContractClient::new(&env, &ephemeral_id).get_status(),
+        AccountStatus::PaymentReceived
+    );
+}
+
+// ── Trace correlation ───────────────────────────────────────────────────────
+
+#[test]
+fn test_execute_sweep_traced_stamps_events_of_both_contracts() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let trace_id = BytesN::from_array(&env, &[9u8; 16]);
+
+    controller_client.execute_sweep_traced(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &trace_id,
+    );
+
+    let mut stamped = 0;
+    for (contract, topics, _) in env.events().all().iter() {
+        if contract != controller_id && contract != ephemeral_id {
+            continue;
+        }
+        let last = topics.get_unchecked(topics.len() - 1);
+        assert_eq!(BytesN::<16>::try_from_val(&env, &last).unwrap(), trace_id);
+        stamped += 1;
+    }
+    // swp_xfer + sweep from the controller, swept_mul from the account.
+    assert!(stamped >= 3);
+}
+
+#[test]
+fn test_trace_id_does_not_leak_into_later_sweeps() {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let trace_id = BytesN::from_array(&env, &[9u8; 16]);
+    controller_client.execute_sweep_traced(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &trace_id,
+    );
+
+    let second_id = env.register(EphemeralAccountContract, ());
+    let second_client = EphemeralAccountContractClient::new(&env, &second_id);
+    second_client.initialize(
+        &Address::generate(&env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(&env),
+        &controller_id,
+        &Address::generate(&env),
+    );
+    StellarAssetClient::new(&env, &token).mint(&second_id, &100);
+    second_client.record_payment(&100, &token);
+
+    let signature = sign_sweep(&env, &controller_id, &second_id, &destination, 1);
+    controller_client.execute_sweep(&second_id, &destination, &signature, &None);
+
+    let (_, sweep_topics, _) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(contract, topics, _)| {
+            *contract == controller_id
+                && Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                    == Some(Symbol::new(&env, "sweep"))
+        })
+        .expect("SweepCompleted event should be emitted");
+    assert_eq!(sweep_topics.len(), 1);
+}
+
+#[test]
+fn test_traced_sweep_receipt_carries_trace_id() {
+    use creator_contracts::{EscrowCreator, EscrowCreatorClient};
+    use soroban_sdk::token::StellarAssetClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let escrow_id = env.register(EscrowCreator, ());
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    ephemeral_client.initialize(
+        &escrow_id,
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(&env),
+        &controller_id,
+        &Address::generate(&env),
+    );
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&ephemeral_id, &400);
+    ephemeral_client.record_payment(&400, &token.address());
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let trace_id = BytesN::from_array(&env, &[7u8; 16]);
+    controller_client.execute_sweep_traced(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &trace_id,
+    );
+
+    let receipt = EscrowCreatorClient::new(&env, &escrow_id)
+        .settled(&ephemeral_id)
+        .expect("escrow did not record the receipt");
+    assert_eq!(receipt.trace_id, Some(trace_id));
+}
+
+// ── Multi-tenant controller ─────────────────────────────────────────────────
+
+fn tenant_key(seed: u8) -> ed25519_dalek::SigningKey {
+    ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+}
+
+/// Two-of-two tenant taking a 1% fee.
+fn tenant_config(env: &Env, fee_recipient: &Address) -> sweep_controller::TenantConfig {
+    sweep_controller::TenantConfig {
+        admin: Address::generate(env),
+        signers: soroban_sdk::vec![
+            env,
+            BytesN::from_array(env, &tenant_key(1).verifying_key().to_bytes()),
+            BytesN::from_array(env, &tenant_key(2).verifying_key().to_bytes()),
+        ],
+        threshold: 2,
+        authorized_destination: None,
+        fee_bps: 100,
+        fee_recipient: Some(fee_recipient.clone()),
+    }
+}
+
+/// Sign `hash("bridgelet:tenant" + tenant_id + account + destination + nonce + contract_id)`
+/// with the tenant keys named by `seeds`, keyed by signer index.
+fn sign_tenant_sweep(
+    env: &Env,
+    controller_id: &Address,
+    tenant_id: u32,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    seeds: &[(u32, u8)],
+) -> soroban_sdk::Map<u32, BytesN<64>> {
+    use ed25519_dalek::Signer;
+    use soroban_sdk::{xdr::ToXdr, Bytes};
+
+    let mut message = Bytes::from_slice(env, b"bridgelet:tenant");
+    message.append(&env.ledger().network_id().into());
+    message.extend_from_array(&tenant_id.to_be_bytes());
+    message.append(&account.clone().to_xdr(env));
+    message.append(&destination.clone().to_xdr(env));
+    message.extend_from_array(&nonce.to_be_bytes());
+    message.append(&controller_id.clone().to_xdr(env));
+    let digest: BytesN<32> = env.crypto().sha256(&message).into();
+
+    let mut signatures = soroban_sdk::Map::new(env);
+    for (index, seed) in seeds {
+        let signature = tenant_key(*seed).sign(&digest.to_array());
+        signatures.set(*index, BytesN::from_array(env, &signature.to_bytes()));
+    }
+    signatures
+}
+
+#[test]
+fn test_tenant_sweep_applies_tenant_signers_and_fee() {
+    use soroban_sdk::token::TokenClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 1_000);
+    let fee_recipient = Address::generate(&env);
+    controller_client.register_tenant(&7, &tenant_config(&env, &fee_recipient));
+    controller_client.assign_account(&7, &ephemeral_id);
+    assert_eq!(controller_client.get_account_tenant(&ephemeral_id), Some(7));
+
+    let destination = Address::generate(&env);
+    let signatures = sign_tenant_sweep(
+        &env,
+        &controller_id,
+        7,
+        &ephemeral_id,
+        &destination,
+        0,
+        &[(0, 1), (1, 2)],
+    );
+    controller_client.execute_tenant_sweep(&7, &ephemeral_id, &destination, &signatures, &None);
+
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&destination), 990);
+    assert_eq!(token_client.balance(&fee_recipient), 10);
+    assert_eq!(controller_client.get_tenant_nonce(&7), 1);
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_tenant_sweep_requires_threshold_signatures() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 1_000);
+    controller_client.register_tenant(&7, &tenant_config(&env, &Address::generate(&env)));
+    controller_client.assign_account(&7, &ephemeral_id);
+
+    let destination = Address::generate(&env);
+    let signatures = sign_tenant_sweep(
+        &env,
+        &controller_id,
+        7,
+        &ephemeral_id,
+        &destination,
+        0,
+        &[(0, 1)],
+    );
+    assert_eq!(
+        controller_client.try_execute_tenant_sweep(
+            &7,
+            &ephemeral_id,
+            &destination,
+            &signatures,
+            &None
+        ),
+        Err(Ok(Error::InsufficientSignatures))
+    );
+}
+
+#[test]
+fn test_tenant_cannot_sweep_accounts_of_other_tenants() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 1_000);
+    let fee_recipient = Address::generate(&env);
+    controller_client.register_tenant(&7, &tenant_config(&env, &fee_recipient));
+    controller_client.register_tenant(&8, &tenant_config(&env, &fee_recipient));
+    controller_client.assign_account(&7, &ephemeral_id);
+
+    let destination = Address::generate(&env);
+    let signatures = sign_tenant_sweep(
+        &env,
+        &controller_id,
+        8,
+        &ephemeral_id,
+        &destination,
+        0,
+        &[(0, 1), (1, 2)],
+    );
+    assert_eq!(
+        controller_client.try_execute_tenant_sweep(
+            &8,
+            &ephemeral_id,
+            &destination,
+            &signatures,
+            &None
+        ),
+        Err(Ok(Error::InvalidAccount))
+    );
+    assert_eq!(
+        controller_client.try_assign_account(&8, &ephemeral_id),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+}
+
+#[test]
+fn test_tenant_signatures_do_not_sweep_another_account() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 1_000);
+    controller_client.register_tenant(&7, &tenant_config(&env, &Address::generate(&env)));
+    controller_client.assign_account(&7, &ephemeral_id);
+
+    let destination = Address::generate(&env);
+    let other_account = Address::generate(&env);
+    let signatures = sign_tenant_sweep(
+        &env,
+        &controller_id,
+        7,
+        &other_account,
+        &destination,
+        0,
+        &[(0, 1), (1, 2)],
+    );
+    let result = controller_client.try_execute_tenant_sweep(
+        &7,
+        &ephemeral_id,
+        &destination,
+        &signatures,
+        &None,
+    );
+    assert!(result.is_err());
+    assert_eq!(controller_client.get_tenant_nonce(&7), 0);
+}
+
+#[test]
+fn test_tenant_accounts_only_settle_through_their_tenant() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 1_000);
+    controller_client.register_tenant(&7, &tenant_config(&env, &Address::generate(&env)));
+    controller_client.assign_account(&7, &ephemeral_id);
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    assert_eq!(
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &None),
+        Err(Ok(Error::InvalidAccount))
+    );
+    assert_eq!(
+        controller_client.try_claim(&destination, &ephemeral_id),
+        Err(Ok(Error::InvalidAccount))
+    );
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_tenant_configuration_is_validated_and_admin_gated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+    let fee_recipient = Address::generate(&env);
+
+    let mut invalid = tenant_config(&env, &fee_recipient);
+    invalid.threshold = 3;
+    assert_eq!(
+        controller_client.try_register_tenant(&7, &invalid),
+        Err(Ok(Error::InvalidTenantConfig))
+    );
+
+    // A fee of the whole amount would leave nothing to deliver.
+    let mut invalid = tenant_config(&env, &fee_recipient);
+    invalid.fee_bps = 10_000;
+    assert_eq!(
+        controller_client.try_register_tenant(&7, &invalid),
+        Err(Ok(Error::InvalidTenantConfig))
+    );
+
+    let config = tenant_config(&env, &fee_recipient);
+    controller_client.register_tenant(&7, &config);
+    assert_eq!(
+        controller_client.try_register_tenant(&7, &config),
+        Err(Ok(Error::TenantAlreadyExists))
+    );
+
+    let mut updated = config.clone();
+    updated.fee_bps = 0;
+    controller_client.update_tenant(&7, &updated);
+    assert_eq!(
+        env.auths()[0].0,
+        config.admin,
+        "update_tenant must be authorized by the tenant admin"
+    );
+    assert_eq!(controller_client.get_tenant(&7), Some(updated));
+}
+
+// ── secp256k1 signers ───────────────────────────────────────────────────────
+
+fn secp256k1_key() -> k256::ecdsa::SigningKey {
+    k256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap()
+}
+
+/// Register a controller whose authorized signer is `secp256k1_key()`,
+/// and an account it sweeps holding `amount` of a fresh token.
+fn setup_secp256k1_account(
+    env: &Env,
+    amount: i128,
+) -> (SweepControllerClient<'_>, Address, Address, Address) {
+    use soroban_sdk::token::StellarAssetClient;
+    use sweep_controller::SignatureScheme;
+
+    let controller_id = env.register(SweepController, ());
+    let controller_client = SweepControllerClient::new(env, &controller_id);
+    let public_key = secp256k1_key().verifying_key().to_sec1_bytes();
+    controller_client.initialize_with_scheme(
+        &Address::generate(env),
+        &SignatureScheme::Secp256k1,
+        &soroban_sdk::Bytes::from_slice(env, &public_key),
+        &None,
+    );
+
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    EphemeralAccountContractClient::new(env, &ephemeral_id).initialize(
+        &Address::generate(env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        &controller_id,
+        &Address::generate(env),
+    );
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&ephemeral_id, &amount);
+    EphemeralAccountContractClient::new(env, &ephemeral_id)
+        .record_payment(&amount, &token.address());
+
+    (
+        controller_client,
+        controller_id,
+        ephemeral_id,
+        token.address(),
+    )
+}
+
+/// Sign the controller-nonce sweep message with `key`, EVM style: ECDSA
+/// over `keccak256(digest)`.
+fn sign_sweep_secp256k1(
+    env: &Env,
+    key: &k256::ecdsa::SigningKey,
+    controller_id: &Address,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+) -> BytesN<64> {
+    let digest = bridgelet_shared::bytes::sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        destination,
+        nonce,
+        controller_id,
+        None,
+        None,
+    );
+    let hash = env.crypto().keccak256(&digest.into());
+    let (signature, _) = key.sign_prehash_recoverable(&hash.to_array()).unwrap();
+    BytesN::from_array(env, &signature.to_bytes().into())
+}
+
+#[test]
+fn test_secp256k1_signer_authorizes_sweeps() {
+    use soroban_sdk::token::TokenClient;
+    use sweep_controller::SignatureScheme;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) =
+        setup_secp256k1_account(&env, 100);
+    assert_eq!(
+        controller_client.get_signature_scheme(),
+        SignatureScheme::Secp256k1
+    );
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep_secp256k1(
+        &env,
+        &secp256k1_key(),
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+    );
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    assert_eq!(TokenClient::new(&env, &token).balance(&destination), 100);
+    assert_eq!(controller_client.get_nonce(), 1);
+}
+
+#[test]
+fn test_secp256k1_signature_from_another_key_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_secp256k1_account(&env, 100);
+    let destination = Address::generate(&env);
+    let other_key = k256::ecdsa::SigningKey::from_slice(&[8u8; 32]).unwrap();
+    let signature = sign_sweep_secp256k1(
+        &env,
+        &other_key,
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+    );
+
+    assert_eq!(
+        controller_client.try_execute_sweep(&ephemeral_id, &destination, &signature, &None),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+}
+
+#[test]
+fn test_initialize_with_scheme_validates_the_signer_key() {
+    use sweep_controller::SignatureScheme;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let ed25519_key = soroban_sdk::Bytes::from_array(&env, &[1u8; 32]);
+    let mut uncompressed = [4u8; 65];
+    uncompressed[1..].copy_from_slice(&[1u8; 64]);
+    for (scheme, key) in [
+        (
+            SignatureScheme::Ed25519,
+            soroban_sdk::Bytes::from_array(&env, &[1u8; 33]),
+        ),
+        (SignatureScheme::Secp256k1, ed25519_key.clone()),
+        (
+            SignatureScheme::Secp256k1,
+            soroban_sdk::Bytes::from_array(&env, &uncompressed),
+        ),
+        (
+            SignatureScheme::Secp256k1,
+            soroban_sdk::Bytes::from_array(&env, &[4u8; 33]),
+        ),
+    ] {
+        let client = SweepControllerClient::new(&env, &env.register(SweepController, ()));
+        assert_eq!(
+            client.try_initialize_with_scheme(&Address::generate(&env), &scheme, &key, &None),
+            Err(Ok(Error::InvalidSignerKey))
+        );
+    }
+
+    let client = SweepControllerClient::new(&env, &env.register(SweepController, ()));
+    client.initialize_with_scheme(
+        &Address::generate(&env),
+        &SignatureScheme::Ed25519,
+        &ed25519_key,
+        &None,
+    );
+    assert_eq!(client.get_signature_scheme(), SignatureScheme::Ed25519);
+}
+
+// ── Per-account signers ─────────────────────────────────────────────────────
+
+/// Deploy an account swept by `controller_id`, created by `creator` and
+/// funded with `amount` of a fresh SAC token.
+fn deploy_funded_account(
+    env: &Env,
+    controller_id: &Address,
+    creator: &Address,
+    amount: i128,
+) -> (Address, Address) {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        creator,
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        controller_id,
+        &Address::generate(env),
+    );
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&ephemeral_id, &amount);
+    ephemeral_client.record_payment(&amount, &token.address());
+    (ephemeral_id, token.address())
+}
+
+#[test]
+fn test_account_signer_overrides_the_controller_scheme_for_that_account() {
+    use soroban_sdk::token::TokenClient;
+    use sweep_controller::{AccountSigner, SignatureScheme};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let creator = Address::generate(&env);
+    let (evm_account, evm_token) = deploy_funded_account(&env, &controller_id, &creator, 100);
+    let (ed25519_account, ed25519_token) =
+        deploy_funded_account(&env, &controller_id, &creator, 50);
+
+    let public_key =
+        soroban_sdk::Bytes::from_slice(&env, &secp256k1_key().verifying_key().to_sec1_bytes());
+    controller_client.set_account_signer(&evm_account, &SignatureScheme::Secp256k1, &public_key);
+    assert_eq!(
+        controller_client.get_account_signer(&evm_account),
+        Some(AccountSigner {
+            scheme: SignatureScheme::Secp256k1,
+            key: public_key,
+        })
+    );
+    assert_eq!(controller_client.get_account_signer(&ed25519_account), None);
+    assert_eq!(
+        controller_client.get_signature_scheme(),
+        SignatureScheme::Ed25519
+    );
+
+    // The controller's Ed25519 signer no longer sweeps the EVM account.
+    let destination = Address::generate(&env);
+    let ed25519_signature = sign_sweep(&env, &controller_id, &evm_account, &destination, 0);
+    assert!(controller_client
+        .try_execute_sweep(&evm_account, &destination, &ed25519_signature, &None)
+        .is_err());
+
+    let signature = sign_sweep_secp256k1(
+        &env,
+        &secp256k1_key(),
+        &controller_id,
+        &evm_account,
+        &destination,
+        0,
+    );
+    controller_client.execute_sweep(&evm_account, &destination, &signature, &None);
+    assert_eq!(
+        TokenClient::new(&env, &evm_token).balance(&destination),
+        100
+    );
+
+    // Other accounts keep the controller's scheme.
+    let other_destination = Address::generate(&env);
+    let signature = sign_sweep(
+        &env,
+        &controller_id,
+        &ed25519_account,
+        &other_destination,
+        1,
+    );
+    controller_client.execute_sweep(&ed25519_account, &other_destination, &signature, &None);
+    assert_eq!(
+        TokenClient::new(&env, &ed25519_token).balance(&other_destination),
+        50
+    );
+}
+
+#[test]
+fn test_set_account_signer_validates_the_key_and_is_set_once() {
+    use sweep_controller::SignatureScheme;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let (account, _) = deploy_funded_account(&env, &controller_id, &Address::generate(&env), 100);
+
+    assert_eq!(
+        controller_client.try_set_account_signer(
+            &account,
+            &SignatureScheme::Secp256k1,
+            &soroban_sdk::Bytes::from_array(&env, &[1u8; 32])
+        ),
+        Err(Ok(Error::InvalidSignerKey))
+    );
+
+    let key = soroban_sdk::Bytes::from_array(&env, &[1u8; 32]);
+    controller_client.set_account_signer(&account, &SignatureScheme::Ed25519, &key);
+    assert_eq!(
+        controller_client.try_set_account_signer(&account, &SignatureScheme::Ed25519, &key),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+}
+
+// ── Passkey signers ─────────────────────────────────────────────────────────
+
+fn passkey() -> p256::ecdsa::SigningKey {
+    p256::ecdsa::SigningKey::from_slice(&[7u8; 32]).unwrap()
+}
+
+/// Register a controller whose authorized signer is the passkey
+/// `passkey()`, and an account it sweeps holding 100 of a fresh token.
+fn setup_passkey_account(env: &Env) -> (SweepControllerClient<'_>, Address, Address, Address) {
+    use soroban_sdk::token::StellarAssetClient;
+    use sweep_controller::SignatureScheme;
+
+    let controller_id = env.register(SweepController, ());
+    let controller_client = SweepControllerClient::new(env, &controller_id);
+    let public_key = passkey().verifying_key().to_encoded_point(false);
+    controller_client.initialize_with_scheme(
+        &Address::generate(env),
+        &SignatureScheme::Secp256r1,
+        &soroban_sdk::Bytes::from_slice(env, public_key.as_bytes()),
+        &None,
+    );
+
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        &Address::generate(env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        &controller_id,
+        &Address::generate(env),
+    );
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    StellarAssetClient::new(env, &token.address()).mint(&ephemeral_id, &100);
+    ephemeral_client.record_payment(&100, &token.address());
+
+    (
+        controller_client,
+        controller_id,
+        ephemeral_id,
+        token.address(),
+    )
+}
+
+/// Assert the sweep of `destination` at `nonce` the way a browser would,
+/// with `challenge` overriding the sweep digest when given.
+fn passkey_assertion(
+    env: &Env,
+    controller_id: &Address,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    challenge: Option<[u8; 32]>,
+) -> sweep_controller::WebAuthnAssertion {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use p256::ecdsa::{signature::Signer, Signature};
+
+    let digest = bridgelet_shared::bytes::sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        destination,
+        nonce,
+        controller_id,
+        None,
+        None,
+    );
+    let challenge = URL_SAFE_NO_PAD.encode(challenge.unwrap_or(digest.to_array()));
+    let client_data_json = std::format!(
+        r#"{{"type":"webauthn.get","challenge":"{challenge}","origin":"https://wallet.example","crossOrigin":false}}"#
+    );
+    let mut authenticator_data = std::vec![0x11u8; 32];
+    authenticator_data.extend_from_slice(&[0x05, 0, 0, 0, 1]);
+
+    let client_data_hash = env.crypto().sha256(&soroban_sdk::Bytes::from_slice(
+        env,
+        client_data_json.as_bytes(),
+    ));
+    let mut signed = authenticator_data.clone();
+    signed.extend_from_slice(&client_data_hash.to_array());
+    let signature: Signature = passkey().sign(&signed);
+    let signature = signature.normalize_s().unwrap_or(signature);
+
+    sweep_controller::WebAuthnAssertion {
+        authenticator_data: soroban_sdk::Bytes::from_slice(env, &authenticator_data),
+        client_data_json: soroban_sdk::Bytes::from_slice(env, client_data_json.as_bytes()),
+        signature: BytesN::from_array(env, &signature.to_bytes().into()),
+    }
+}
+
+#[test]
+fn test_passkey_assertion_authorizes_sweep() {
+    use soroban_sdk::token::TokenClient;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_passkey_account(&env);
+    let destination = Address::generate(&env);
+    let assertion = passkey_assertion(&env, &controller_id, &ephemeral_id, &destination, 0, None);
+    controller_client.execute_passkey_sweep(&ephemeral_id, &destination, &assertion, &None);
+
+    assert_eq!(TokenClient::new(&env, &token).balance(&destination), 100);
+    assert_eq!(controller_client.get_nonce(), 1);
+}
+
+#[test]
+fn test_passkey_assertion_over_another_challenge_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_passkey_account(&env);
+    let destination = Address::generate(&env);
+    let assertion = passkey_assertion(
+        &env,
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+        Some([9u8; 32]),
+    );
+
+    assert_eq!(
+        controller_client.try_execute_passkey_sweep(&ephemeral_id, &destination, &assertion, &None),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+    // A bare signature cannot authorize a passkey controller's sweeps.
+    assert_eq!(
+        controller_client.try_execute_sweep(
+            &ephemeral_id,
+            &destination,
+            &assertion.signature,
+            &None
+        ),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+}
+
+#[test]
+fn test_passkey_assertion_requires_user_presence() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_passkey_account(&env);
+    let destination = Address::generate(&env);
+    let mut assertion =
+        passkey_assertion(&env, &controller_id, &ephemeral_id, &destination, 0, None);
+    assertion.authenticator_data.set(32, 0x04);
+
+    assert_eq!(
+        controller_client.try_execute_passkey_sweep(&ephemeral_id, &destination, &assertion, &None),
+        Err(Ok(Error::AuthorizationFailed))
+    );
+}
+
+// ── Signature deadlines ─────────────────────────────────────────────────────
+
+/// Sign the sweep message with `valid_until_ledger` appended.
+fn sign_sweep_until(
+    env: &Env,
+    controller_id: &Address,
+    account: &Address,
+    destination: &Address,
+    nonce: u64,
+    valid_until_ledger: u32,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+
+    let digest = bridgelet_shared::bytes::sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        destination,
+        nonce,
+        controller_id,
+        None,
+        Some(valid_until_ledger),
+    );
+    let signature = test_signing_key().sign(&digest.to_array());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+#[test]
+fn test_sweep_with_deadline_is_rejected_after_the_deadline() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let destination = Address::generate(&env);
+    let deadline = env.ledger().sequence() + 10;
+    let signature = sign_sweep_until(
+        &env,
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+        deadline,
+    );
+
+    env.ledger().set_sequence_number(deadline + 1);
+    assert_eq!(
+        controller_client.try_execute_sweep_with_deadline(
+            &ephemeral_id,
+            &destination,
+            &signature,
+            &None,
+            &deadline
+        ),
+        Err(Ok(Error::SignatureExpired))
+    );
+    assert_eq!(controller_client.get_nonce(), 0);
+
+    env.ledger().set_sequence_number(deadline);
+    controller_client.execute_sweep_with_deadline(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &deadline,
+    );
+    assert_eq!(
+        EphemeralAccountContractClient::new(&env, &ephemeral_id).get_status(),
+        AccountStatus::Swept
+    );
+    assert_eq!(controller_client.get_nonce(), 1);
+}
+
+#[test]
+fn test_deadline_signature_does_not_verify_without_its_deadline() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let destination = Address::generate(&env);
+    let deadline = env.ledger().sequence() + 10;
+    let signature = sign_sweep_until(
+        &env,
+        &controller_id,
+        &ephemeral_id,
+        &destination,
+        0,
+        deadline,
+    );
+
+    assert!(controller_client
+        .try_execute_sweep(&ephemeral_id, &destination, &signature, &None)
+        .is_err());
+    assert!(controller_client
+        .try_execute_sweep_with_deadline(
+            &ephemeral_id,
+            &destination,
+            &signature,
+            &None,
+            &(deadline + 1)
+        )
+        .is_err());
+}
+
+// ── Account-nonce sweeps ────────────────────────────────────────────────────
+
+/// Sign the account-nonce sweep message for `account`.
+fn sign_account_sweep(
+    env: &Env,
+    controller_id: &Address,
+    account: &Address,
+    account_nonce: u64,
+    destination: &Address,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+
+    let digest = bridgelet_shared::bytes::account_sweep_message_digest(
+        env,
+        &env.ledger().network_id(),
+        account,
+        account_nonce,
+        destination,
+        controller_id,
+        None,
+    );
+    let signature = test_signing_key().sign(&digest.to_array());
+    BytesN::from_array(env, &signature.to_bytes())
+}
+
+#[test]
+fn test_account_sweep_is_authorized_at_the_account_nonce() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    let destination = Address::generate(&env);
+    let signature = sign_account_sweep(&env, &controller_id, &ephemeral_id, 0, &destination);
+
+    controller_client.execute_account_sweep(&ephemeral_id, &destination, &signature, &None);
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    assert_eq!(ephemeral_client.get_sweep_nonce(), 1);
+    // The controller nonce is not involved.
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_account_sweep_signature_is_bound_to_its_account() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _token) = setup_funded_account(&env, 100);
+    let destination = Address::generate(&env);
+    let other_account = Address::generate(&env);
+    let signature = sign_account_sweep(&env, &controller_id, &other_account, 0, &destination);
+
+    assert!(controller_client
+        .try_execute_account_sweep(&ephemeral_id, &destination, &signature, &None)
+        .is_err());
+    assert_eq!(
+        EphemeralAccountContractClient::new(&env, &ephemeral_id).get_status(),
+        AccountStatus::PaymentReceived
+    );
+}
+
+// ── Multisig account sweeps ─────────────────────────────────────────────────
+
+#[test]
+fn test_multisig_sweep_delivers_funds_with_threshold_signatures() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let keys = [1u8, 2, 3].map(|seed| SigningKey::from_bytes(&[seed; 32]));
+    let public_key = |key: &SigningKey| BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    ephemeral_client.initialize_with_options(
+        &Address::generate(&env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(&env),
+        &controller_id,
+        &Address::generate(&env),
+        &ephemeral_account::InitOptions {
+            sweep_signers: Some(soroban_sdk::Vec::from_array(
+                &env,
+                [
+                    public_key(&keys[0]),
+                    public_key(&keys[1]),
+                    public_key(&keys[2]),
+                ],
+            )),
+            sweep_threshold: 2,
+            ..Default::default()
+        },
+    );
+    let token = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    StellarAssetClient::new(&env, &token.address()).mint(&ephemeral_id, &100);
+    ephemeral_client.record_payment(&100, &token.address());
+
+    // The controller's own signer cannot sweep the account alone.
+    let destination = Address::generate(&env);
+    let signature = sign_account_sweep(&env, &controller_id, &ephemeral_id, 0, &destination);
+    assert!(controller_client
+        .try_execute_account_sweep(&ephemeral_id, &destination, &signature, &None)
+        .is_err());
+
+    let digest = bridgelet_shared::bytes::account_sweep_message_digest(
+        &env,
+        &env.ledger().network_id(),
+        &ephemeral_id,
+        0,
+        &destination,
+        &controller_id,
+        None,
+    );
+    let mut signatures = soroban_sdk::Vec::new(&env);
+    for key in [&keys[0], &keys[2]] {
+        let signature = key.sign(&digest.to_array());
+        signatures.push_back((
+            public_key(key),
+            BytesN::from_array(&env, &signature.to_bytes()),
+        ));
+    }
+    controller_client.execute_multisig_sweep(&ephemeral_id, &destination, &signatures, &None);
+
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    assert_eq!(
+        TokenClient::new(&env, &token.address()).balance(&destination),
+        100
+    );
+    assert_eq!(controller_client.get_nonce(), 0);
+}
+
+#[test]
+fn test_multisig_sweep_rejects_accounts_without_multisig_support() {
+    use legacy_account::{LegacyAccount, LegacyAccountClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+    let legacy_id = env.register(LegacyAccount, ());
+    let token = Address::generate(&env);
+    LegacyAccountClient::new(&env, &legacy_id).setup(&Address::generate(&env), &token, &125);
+
+    assert_eq!(
+        controller_client.try_execute_multisig_sweep(
+            &legacy_id,
+            &Address::generate(&env),
+            &soroban_sdk::Vec::new(&env),
+            &None,
+        ),
+        Err(Ok(Error::InvalidAccount))
+    );
+}
+
+// ── Footprint hints ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_sweep_footprint_lists_touched_contracts() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+
+    let creator = EphemeralAccountContractClient::new(&env, &ephemeral_id)
+        .get_info()
+        .creator;
+
+    // Generated test addresses are contract addresses, so the creator is
+    // notified (and listed) too.
+    let hint = controller_client.get_sweep_footprint(&ephemeral_id, &destination);
+    assert_eq!(
+        hint.instances,
+        soroban_sdk::vec![
+            &env,
+            controller_id.clone(),
+            ephemeral_id.clone(),
+            token,
+            creator
+        ]
+    );
+    assert_eq!(hint.persistent_keys, 4);
+
+    let registry = Address::generate(&env);
+    controller_client.set_registry(&registry);
+    controller_client.set_features(&FeatureFlags::PARTIAL_SETTLEMENT);
+    let hint = controller_client.get_sweep_footprint(&ephemeral_id, &destination);
+    assert_eq!(hint.instances.last(), Some(registry));
+    assert_eq!(hint.persistent_keys, 5);
+}
+
+#[test]
+fn test_sweep_footprint_rejects_unauthorized_destination() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let controller_id = env.register(SweepController, ());
+    let controller_client = SweepControllerClient::new(&env, &controller_id);
+    let signer = BytesN::from_array(&env, &test_signing_key().verifying_key().to_bytes());
+    controller_client.initialize(
+        &Address::generate(&env),
+        &signer,
+        &Some(Address::generate(&env)),
+    );
+
+    assert_eq!(
+        controller_client
+            .try_get_sweep_footprint(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(Error::UnauthorizedDestination))
+    );
+}
+
+// ── Diagnostics ─────────────────────────────────────────────────────────────
+
+#[test]
+fn test_diagnose_healthy_and_unreadable_accounts() {
+    use sweep_controller::Diagnostic;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, _, ephemeral_id, _) = setup_funded_account(&env, 250);
+    assert!(controller_client.diagnose(&ephemeral_id).is_empty());
+
+    let uninitialized = env.register(EphemeralAccountContract, ());
+    assert_eq!(
+        controller_client.diagnose(&uninitialized),
+        soroban_sdk::vec![&env, Diagnostic::Unreadable]
+    );
+}
+
+#[test]
+fn test_diagnose_reports_shortfall_trustline_and_closed_window() {
+    use soroban_sdk::token::TokenClient;
+    use sweep_controller::Diagnostic;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, _, ephemeral_id, token) = setup_funded_account(&env, 250);
+    // Funds leave the account behind its back.
+    TokenClient::new(&env, &token).transfer(&ephemeral_id, &Address::generate(&env), &100);
+    // A classic account that never opened a trustline for the token.
+    let destination = Address::from_str(
+        &env,
+        "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ",
+    );
+    controller_client.update_authorized_destination(&destination);
+
+    let expiry = EphemeralAccountContractClient::new(&env, &ephemeral_id)
+        .get_info()
+        .expiry_ledger;
+    env.ledger().set_sequence_number(expiry);
+
+    assert_eq!(
+        controller_client.diagnose(&ephemeral_id),
+        soroban_sdk::vec![
+            &env,
+            Diagnostic::BalanceMismatch(token.clone(), 250, 150),
+            Diagnostic::MissingTrustline(token, destination),
+            Diagnostic::SweepWindowClosed(expiry - 1),
+        ]
+    );
+}
+
+// ── Idempotent sweeps ───────────────────────────────────────────────────────
+
+#[test]
+fn test_idempotent_sweep_retry_returns_stored_receipt() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, token) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let key = BytesN::from_array(&env, &[9u8; 32]);
+
+    let receipt = controller_client
+        .execute_sweep_idempotent(&ephemeral_id, &destination, &signature, &None, &key)
+        .expect("the sweep should settle the account");
+    assert_eq!(receipt.destination, destination);
+    assert_eq!(receipt.total_amount, 250);
+    assert_eq!(receipt.payments.get_unchecked(0).asset, token);
+
+    // A retry with the same key succeeds without sweeping again.
+    let retried = controller_client.execute_sweep_idempotent(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &key,
+    );
+    assert_eq!(retried, Some(receipt.clone()));
+    assert_eq!(controller_client.get_nonce(), 1);
+    assert_eq!(
+        controller_client.get_sweep_receipt(&key),
+        Some(sweep_controller::SweepRecord {
+            ephemeral_account: ephemeral_id.clone(),
+            receipt,
+        })
+    );
+
+    // Without the key, the retry fails on the swept account.
+    assert!(controller_client
+        .try_execute_sweep(&ephemeral_id, &destination, &signature, &None)
+        .is_err());
+}
+
+#[test]
+fn test_idempotency_key_cannot_be_reused_for_another_account() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, ephemeral_id, _) = setup_funded_account(&env, 250);
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    let key = BytesN::from_array(&env, &[9u8; 32]);
+    controller_client.execute_sweep_idempotent(
+        &ephemeral_id,
+        &destination,
+        &signature,
+        &None,
+        &key,
+    );
+
+    assert_eq!(
+        controller_client.try_execute_sweep_idempotent(
+            &Address::generate(&env),
+            &destination,
+            &signature,
+            &None,
+            &key,
+        ),
+        Err(Ok(Error::IdempotencyKeyConflict))
+    );
+    assert_eq!(
+        controller_client.get_sweep_receipt(&BytesN::from_array(&env, &[1u8; 32])),
+        None
+    );
+}
+
+// ── Batch sweeps ────────────────────────────────────────────────────────────
+
+/// Deploy an account for `controller_id` holding `amount` of `asset`.
+fn add_funded_account(
+    env: &Env,
+    controller_id: &Address,
+    asset: &Address,
+    amount: i128,
+) -> Address {
+    use soroban_sdk::token::StellarAssetClient;
+
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(env, &ephemeral_id);
+    ephemeral_client.initialize(
+        &Address::generate(env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(env),
+        controller_id,
+        &Address::generate(env),
+    );
+    StellarAssetClient::new(env, asset).mint(&ephemeral_id, &amount);
+    ephemeral_client.record_payment(&amount, asset);
+    ephemeral_id
+}
+
+/// Batch requests sweeping `accounts` to `destination`, signed at
+/// consecutive nonces from 0.
+fn batch_requests(
+    env: &Env,
+    controller_id: &Address,
+    accounts: &[Address],
+    destination: &Address,
+) -> soroban_sdk::Vec<sweep_controller::BatchSweepRequest> {
+    let mut requests = soroban_sdk::Vec::new(env);
+    for (nonce, account) in accounts.iter().enumerate() {
+        requests.push_back(sweep_controller::BatchSweepRequest {
+            ephemeral_account: account.clone(),
+            destination: destination.clone(),
+            auth_signature: sign_sweep(env, controller_id, account, destination, nonce as u64),
+            destination_memo: None,
+        });
+    }
+    requests
+}
+
+fn count_controller_events(env: &Env, controller_id: &Address, topic: &str) -> usize {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            contract == controller_id
+                && Symbol::try_from_val(env, &topics.get_unchecked(0)).ok()
+                    == Some(Symbol::new(env, topic))
+        })
+        .count()
+}
+
+#[test]
+fn test_batch_sweep_within_threshold_emits_per_account_events() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, first, token) = setup_funded_account(&env, 100);
+    let second = add_funded_account(&env, &controller_id, &token, 200);
+    controller_client.set_batch_compaction_threshold(&Some(2));
+    let destination = Address::generate(&env);
+
+    let requests = batch_requests(&env, &controller_id, &[first, second], &destination);
+    let records = controller_client.execute_sweep_batch(&requests);
+
+    assert_eq!(count_controller_events(&env, &controller_id, "sweep"), 2);
+    assert_eq!(
+        count_controller_events(&env, &controller_id, "batch_swp"),
+        0
+    );
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get_unchecked(1).receipt.total_amount, 200);
+    assert_eq!(controller_client.get_nonce(), 2);
+    assert_eq!(
+        controller_client.get_batch_receipts(&BytesN::from_array(&env, &[0; 32])),
+        None
+    );
+}
+
+#[test]
+fn test_batch_sweep_above_threshold_is_compacted() {
+    use soroban_sdk::xdr::ToXdr;
+    use sweep_controller::BatchSweepCompleted;
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id, first, token) = setup_funded_account(&env, 100);
+    let second = add_funded_account(&env, &controller_id, &token, 200);
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let third = add_funded_account(&env, &controller_id, &other_token, 50);
+    controller_client.set_batch_compaction_threshold(&Some(2));
+    let destination = Address::generate(&env);
+
+    let requests = batch_requests(&env, &controller_id, &[first, second, third], &destination);
+    let records = controller_client.execute_sweep_batch(&requests);
+
+    assert_eq!(count_controller_events(&env, &controller_id, "sweep"), 0);
+    assert_eq!(count_controller_events(&env, &controller_id, "swp_xfer"), 0);
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get_unchecked(0)).ok()
+                == Some(Symbol::new(&env, "batch_swp"))
+        })
+        .expect("BatchSweepCompleted should be emitted");
+    let summary = BatchSweepCompleted::try_from_val(&env, &data).unwrap();
+
+    let batch_digest: BytesN<32> = env.crypto().sha256(&requests.to_xdr(&env)).into();
+    assert_eq!(summary.count, 3);
+    assert_eq!(summary.batch_digest, batch_digest);
+    assert_eq!(
+        summary.total_per_asset,
+        soroban_sdk::vec![
+            &env,
+            bridgelet_shared::AssetAmount {
+                asset: token,
+                amount: 300
+            },
+            bridgelet_shared::AssetAmount {
+                asset: other_token,
+                amount: 50
+            },
+        ]
+    );
+    assert_eq!(
+        controller_client.get_batch_receipts(&batch_digest),
+        Some(records)
+    );
+}
+
+#[test]
+fn test_batch_sweep_rejects_empty_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, _) = setup_signing_controller(&env);
+
+    assert_eq!(
+        controller_client.try_execute_sweep_batch(&soroban_sdk::Vec::new(&env)),
+        Err(Ok(Error::InvalidBatchSize))
+    );
+}
+
+// ── Native XLM held by the controller ───────────────────────────────────────
+
+/// Balance entry of the native SAC, written directly because native XLM
+/// cannot be minted in the test environment.
+#[soroban_sdk::contracttype]
+enum NativeBalanceKey {
+    Balance(Address),
+}
+
+#[soroban_sdk::contracttype]
+struct NativeBalanceValue {
+    amount: i128,
+    authorized: bool,
+    clawback: bool,
+}
+
+fn fund_with_native(env: &Env, holder: &Address, amount: i128) -> Address {
+    let native = env
+        .deployer()
+        .with_stellar_asset(soroban_sdk::Bytes::from_array(env, &[0u8; 4]))
+        .deploy();
+    env.as_contract(&native, || {
+        env.storage().persistent().set(
+            &NativeBalanceKey::Balance(holder.clone()),
+            &NativeBalanceValue {
+                amount,
+                authorized: true,
+                clawback: false,
+            },
+        )
+    });
+    native
+}
+
+#[test]
+fn test_creator_withdraws_native_balance() {
+    use soroban_sdk::token::TokenClient;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let native = fund_with_native(&env, &controller_id, 1_000);
+    assert_eq!(controller_client.get_native_balance(), 1_000);
+
+    let treasury = Address::generate(&env);
+    controller_client.withdraw_native(&treasury, &400);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &topics.get_unchecked(0)).unwrap(),
+        Symbol::new(&env, "native_wd")
+    );
+    let event = sweep_controller::NativeWithdrawn::try_from_val(&env, &data).unwrap();
+    assert_eq!((event.to, event.amount), (treasury.clone(), 400));
+
+    assert_eq!(controller_client.get_native_balance(), 600);
+    assert_eq!(TokenClient::new(&env, &native).balance(&treasury), 400);
+
+    assert_eq!(
+        controller_client.try_withdraw_native(&treasury, &601),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        controller_client.try_withdraw_native(&treasury, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_withdraw_native_requires_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    fund_with_native(&env, &controller_id, 1_000);
+    env.set_auths(&[]);
+
+    assert!(controller_client
+        .try_withdraw_native(&Address::generate(&env), &100)
+        .is_err());
+    assert_eq!(controller_client.get_native_balance(), 1_000);
+}
+
+// ── Vesting accounts ────────────────────────────────────────────────────────
+
+#[test]
+fn test_sweep_of_vesting_account_leaves_funds_to_vest() {
+    use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let (controller_client, controller_id) = setup_signing_controller(&env);
+    let ephemeral_id = env.register(EphemeralAccountContract, ());
+    let ephemeral_client = EphemeralAccountContractClient::new(&env, &ephemeral_id);
+    ephemeral_client.initialize_with_options(
+        &Address::generate(&env),
+        &(env.ledger().sequence() + 1_000),
+        &Address::generate(&env),
+        &controller_id,
+        &Address::generate(&env),
+        &ephemeral_account::InitOptions {
+            vesting_cliff_ledger: Some(env.ledger().sequence()),
+            vesting_duration_ledgers: 10,
+            ..Default::default()
+        },
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    StellarAssetClient::new(&env, &token).mint(&ephemeral_id, &100);
+    ephemeral_client.record_payment(&100, &token);
+
+    let destination = Address::generate(&env);
+    let signature = sign_sweep(&env, &controller_id, &ephemeral_id, &destination, 0);
+    controller_client.execute_sweep(&ephemeral_id, &destination, &signature, &None);
+
+    let balance = TokenClient::new(&env, &token);
+    assert_eq!(ephemeral_client.get_status(), AccountStatus::Swept);
+    assert_eq!(balance.balance(&destination), 0);
+    assert_eq!(balance.balance(&ephemeral_id), 100);
+
+    env.ledger().with_mut(|li| li.sequence_number += 5);
+    assert_eq!(ephemeral_client.claim_vested(&token), 50);
+    assert_eq!(balance.balance(&destination), 50);
+}
@@ -26,6 +26,16 @@ fi
 echo "Building all workspace contracts (ephemeral_account, sweep_controller, reserve_contract, account_factory)..."
 stellar contract build
 
+# Soroban refuses contract wasm over `contract_max_size_bytes` (128 KiB),
+# measured on what is uploaded.
+# Deploy the `.optimized.wasm` files written here, not the raw builds;
+# scripts/check-sizes.sh checks they fit.
+echo "Optimizing contracts..."
+for wasm in target/wasm32v1-none/release/*.wasm; do
+    case "$wasm" in *.optimized.wasm) continue ;; esac
+    stellar contract optimize --wasm "$wasm"
+done
+
 echo "✅ Build complete!"
 echo "Contracts location: target/wasm32v1-none/release/"
-ls -lh target/wasm32v1-none/release/*.wasm 2>/dev/null || echo "⚠️  No .wasm files found — check the build output above for errors."
\ No newline at end of file
+ls -lh target/wasm32v1-none/release/*.optimized.wasm 2>/dev/null || echo "⚠️  No .wasm files found — check the build output above for errors."
\ No newline at end of file
@@ -0,0 +1,58 @@
+#!/bin/bash
+set -euo pipefail
+
+# Fails if a contract build would be rejected by the network for its size.
+#
+# Soroban refuses to upload contract wasm larger than the network's
+# `contract_max_size_bytes` setting (CONFIG_SETTING_CONTRACT_MAX_SIZE_BYTES),
+# checked here as 128 KiB. What gets uploaded is the optimized wasm from
+# `stellar contract optimize` (see build.sh), so that is what is measured
+# here. Set SIZE_LIMIT to check against another network setting.
+#
+# With no arguments, checks every contract; otherwise only the packages named:
+#
+#   ./scripts/check-sizes.sh ephemeral_account sweep_controller
+
+LIMIT=${SIZE_LIMIT:-131072}
+WASM_DIR="target/wasm32v1-none/release"
+OUT_DIR="target/size-check"
+
+if ! command -v stellar &> /dev/null; then
+    echo "❌ stellar-cli not found. Install it first:"
+    echo "   cargo install --locked stellar-cli --version 23.4.1"
+    exit 1
+fi
+
+mkdir -p "$OUT_DIR"
+failed=0
+
+# check <package>
+check() {
+    local package=$1
+    stellar contract build --package "$package" > /dev/null
+    stellar contract optimize \
+        --wasm "$WASM_DIR/$package.wasm" \
+        --wasm-out "$OUT_DIR/$package.wasm" > /dev/null
+    local size
+    size=$(stat -c %s "$OUT_DIR/$package.wasm")
+    if [ "$size" -gt "$LIMIT" ]; then
+        echo "❌ $package: $size bytes, over the $LIMIT byte limit"
+        failed=1
+    else
+        echo "✅ $package: $size bytes"
+    fi
+}
+
+if [ $# -gt 0 ]; then
+    packages=("$@")
+else
+    packages=(ephemeral_account sweep_controller reserve_contract account_factory)
+fi
+for package in "${packages[@]}"; do
+    check "$package"
+done
+
+# Leave the full builds in place for the tests that embed them.
+stellar contract build > /dev/null
+
+exit $failed
@@ -47,7 +47,7 @@ echo "==> Building contracts..."
 # ---------------------------------------------------------------------------
 echo "==> Deploying EphemeralAccount contract..."
 EPHEMERAL_CONTRACT_ID=$(stellar contract deploy \
-  --wasm "$WASM_DIR/ephemeral_account.wasm" \
+  --wasm "$WASM_DIR/ephemeral_account.optimized.wasm" \
   --source "$SIGNER_SECRET_KEY" \
   --network "$NETWORK" \
   --rpc-url "$SOROBAN_RPC_URL" \
@@ -61,7 +61,7 @@ echo "    EphemeralAccount deployed: $EPHEMERAL_CONTRACT_ID"
 # the hash directly without redeploying.
 echo "==> Installing EphemeralAccount WASM (for AccountFactory)..."
 EPHEMERAL_WASM_HASH=$(stellar contract install \
-  --wasm "$WASM_DIR/ephemeral_account.wasm" \
+  --wasm "$WASM_DIR/ephemeral_account.optimized.wasm" \
   --source "$SIGNER_SECRET_KEY" \
   --network "$NETWORK" \
   --rpc-url "$SOROBAN_RPC_URL" \
@@ -74,7 +74,7 @@ echo "    EphemeralAccount WASM hash: $EPHEMERAL_WASM_HASH"
 # ---------------------------------------------------------------------------
 echo "==> Deploying SweepController contract..."
 SWEEP_CONTRACT_ID=$(stellar contract deploy \
-  --wasm "$WASM_DIR/sweep_controller.wasm" \
+  --wasm "$WASM_DIR/sweep_controller.optimized.wasm" \
   --source "$SIGNER_SECRET_KEY" \
   --network "$NETWORK" \
   --rpc-url "$SOROBAN_RPC_URL" \
@@ -100,7 +100,7 @@ stellar contract invoke \
 # ---------------------------------------------------------------------------
 echo "==> Deploying ReserveContract contract..."
 RESERVE_CONTRACT_ID=$(stellar contract deploy \
-  --wasm "$WASM_DIR/reserve_contract.wasm" \
+  --wasm "$WASM_DIR/reserve_contract.optimized.wasm" \
   --source "$SIGNER_SECRET_KEY" \
   --network "$NETWORK" \
   --rpc-url "$SOROBAN_RPC_URL" \
@@ -124,7 +124,7 @@ stellar contract invoke \
 # ---------------------------------------------------------------------------
 echo "==> Deploying AccountFactory contract..."
 FACTORY_CONTRACT_ID=$(stellar contract deploy \
-  --wasm "$WASM_DIR/account_factory.wasm" \
+  --wasm "$WASM_DIR/account_factory.optimized.wasm" \
   --source "$SIGNER_SECRET_KEY" \
   --network "$NETWORK" \
   --rpc-url "$SOROBAN_RPC_URL" \
@@ -7,13 +7,17 @@
 //!         sweep-signer pubkey --signer-seed-hex <64 hex chars>
 //!
 //!   2. Per sweep, once deployed: produce the signature for execute_sweep().
-//!         sweep-signer sign --contract-id ... --destination ... --nonce ... --signer-seed-hex ...
+//!         sweep-signer sign --network-passphrase ... --contract-id ... --account ... --destination ... --nonce ... --signer-seed-hex ...
 //!
 //! Message format (matches contracts/sweep_controller/src/authorization.rs
 //! exactly - NOT the timestamp-including format that was in the old
 //! docs/SIGNATURE_FORMAT.md before it was corrected):
 //!
-//!   message = SHA256( destination.to_xdr() || nonce_be_u64(8 bytes) || contract_id.to_xdr() )
+//!   message = SHA256( network_id(32 bytes) || account.to_xdr() || destination.to_xdr() || nonce_be_u64(8 bytes)
+//!                     || contract_id.to_xdr()
+//!                     [|| destination_memo(32 bytes), only with --memo-hex]
+//!                     [|| valid_until_ledger_be_u32(4 bytes), only with --valid-until-ledger] )
+//!   network_id = SHA256(network passphrase)
 //!   signature = Ed25519_sign(message, signer_private_key)
 //!
 //! Accepts the signing key as EITHER:
@@ -23,14 +27,14 @@
 //! This key is signing-only - it never needs to be a funded Stellar account,
 //! so the raw hex form is the simpler option and is recommended.
 //!
-//! Address XDR encoding uses soroban_sdk::Address::to_xdr() itself (via a
-//! throwaway local Env, no network involved), so serialization is
-//! guaranteed to match what the deployed contract computes on-chain rather
-//! than relying on a hand-rolled XDR encoder.
+//! The digest comes from bridgelet_shared::bytes::sweep_message_digest, the
+//! same function the deployed contract calls on-chain (evaluated in a
+//! throwaway local Env, no network involved), so the message is guaranteed
+//! to match rather than relying on a hand-rolled encoder.
 
 use clap::{Args, Parser, Subcommand};
 use ed25519_dalek::{Signer, SigningKey};
-use soroban_sdk::{xdr::ToXdr, Address, Bytes, Env};
+use soroban_sdk::{Address, Env};
 
 #[derive(Parser)]
 #[command(about = "Key derivation and signing for bridgelet-core's SweepController")]
@@ -58,10 +62,21 @@ struct KeyArgs {
 
 #[derive(Args)]
 struct SignArgs {
+    /// Passphrase of the network the SweepController is deployed on, e.g.
+    /// "Test SDF Network ; September 2015". Signatures are bound to it and
+    /// will not verify on any other network.
+    #[arg(long, env = "STELLAR_NETWORK_PASSPHRASE")]
+    network_passphrase: String,
+
     /// SweepController contract ID (C... address)
     #[arg(long)]
     contract_id: String,
 
+    /// Ephemeral account the signature sweeps (C... address). The
+    /// signature does not verify for any other account.
+    #[arg(long)]
+    account: String,
+
     /// Destination wallet address funds will be swept to (G... address)
     #[arg(long)]
     destination: String,
@@ -72,6 +87,16 @@ struct SignArgs {
     #[arg(long)]
     nonce: u64,
 
+    /// Optional 32-byte destination memo, hex-encoded (64 hex chars). Pass
+    /// the same value as `destination_memo` to execute_sweep().
+    #[arg(long)]
+    memo_hex: Option<String>,
+
+    /// Optional last ledger the signature is valid in. Submit the
+    /// signature with execute_sweep_with_deadline(), passing the same value.
+    #[arg(long)]
+    valid_until_ledger: Option<u32>,
+
     #[command(flatten)]
     key: SignerKey,
 }
@@ -136,30 +161,49 @@ fn main() {
         Command::Sign(args) => {
             let signing_key = args.key.to_signing_key();
 
-            // Local, network-free Env - used only to get soroban_sdk's own
-            // Address::to_xdr() encoding, guaranteed to match on-chain.
+            // Local, network-free Env - used only to evaluate the shared
+            // message builder, guaranteed to match on-chain.
             let env = Env::default();
 
+            let account = Address::from_str(&env, &args.account);
             let destination = Address::from_str(&env, &args.destination);
             let contract_id = Address::from_str(&env, &args.contract_id);
 
-            let mut message = Bytes::new(&env);
-            message.append(&destination.to_xdr(&env));
-
-            for shift in (0..8).rev() {
-                message.push_back(((args.nonce >> (shift * 8)) & 0xFF) as u8);
-            }
-
-            message.append(&contract_id.to_xdr(&env));
+            let memo = args.memo_hex.as_ref().map(|memo_hex| {
+                let memo: [u8; 32] = hex::decode(memo_hex)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .unwrap_or_else(|| {
+                        eprintln!("--memo-hex must be exactly 32 bytes (64 hex chars)");
+                        std::process::exit(1);
+                    });
+                soroban_sdk::BytesN::from_array(&env, &memo)
+            });
 
-            let digest: soroban_sdk::BytesN<32> = env.crypto().sha256(&message).into();
+            let network_id =
+                bridgelet_shared::bytes::network_id(&env, args.network_passphrase.as_bytes());
+            let digest = bridgelet_shared::bytes::sweep_message_digest(
+                &env,
+                &network_id,
+                &account,
+                &destination,
+                args.nonce,
+                &contract_id,
+                memo.as_ref(),
+                args.valid_until_ledger,
+            );
             let mut digest_bytes = [0u8; 32];
             digest.copy_into_slice(&mut digest_bytes);
 
             let signature = signing_key.sign(&digest_bytes);
 
+            let entry_point = if args.valid_until_ledger.is_some() {
+                "execute_sweep_with_deadline"
+            } else {
+                "execute_sweep"
+            };
             println!(
-                "auth_signature (hex, pass to execute_sweep): {}",
+                "auth_signature (hex, pass to {entry_point}): {}",
                 hex::encode(signature.to_bytes())
             );
             println!(